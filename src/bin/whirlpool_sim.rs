@@ -0,0 +1,239 @@
+//! Simulates a swap against a live Whirlpool using the program's own swap math.
+//!
+//! Usage: whirlpool-sim <rpc_url> <whirlpool> <amount> <a-to-b|b-to-a> [--exact-out|--fee-on-top]
+//!
+//! The pool and the tick arrays the swap would traverse are loaded over RPC, and the quote is
+//! computed with the same swap manager the program runs on chain, so the result matches what a
+//! swap instruction would do against the same state.
+
+use std::{
+    cell::RefCell,
+    process,
+    str::FromStr,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use anchor_lang::{prelude::Pubkey, AccountDeserialize, Discriminator};
+use solana_client::rpc_client::RpcClient;
+use whirlpool::{
+    manager::swap_manager::{swap, swap_fee_on_top},
+    state::{TickArray, Whirlpool, TICK_ARRAY_SIZE},
+    util::{default_sqrt_price_limit, SwapTickSequence},
+};
+
+struct SimArgs {
+    rpc_url: String,
+    whirlpool: Pubkey,
+    amount: u64,
+    a_to_b: bool,
+    amount_specified_is_input: bool,
+    fee_on_top: bool,
+}
+
+fn parse_args() -> Result<SimArgs, String> {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if args.len() < 4 || args.len() > 5 {
+        return Err(
+            "usage: whirlpool-sim <rpc_url> <whirlpool> <amount> <a-to-b|b-to-a> \
+             [--exact-out|--fee-on-top]"
+                .to_string(),
+        );
+    }
+
+    let whirlpool = Pubkey::from_str(&args[1]).map_err(|e| format!("invalid whirlpool: {}", e))?;
+    let amount = args[2]
+        .parse::<u64>()
+        .map_err(|e| format!("invalid amount: {}", e))?;
+    let a_to_b = match args[3].as_str() {
+        "a-to-b" => true,
+        "b-to-a" => false,
+        other => return Err(format!("invalid direction: {}", other)),
+    };
+    let (amount_specified_is_input, fee_on_top) = match args.get(4).map(String::as_str) {
+        None => (true, false),
+        Some("--exact-out") => (false, false),
+        Some("--fee-on-top") => (true, true),
+        Some(other) => return Err(format!("unknown flag: {}", other)),
+    };
+
+    Ok(SimArgs {
+        rpc_url: args[0].clone(),
+        whirlpool,
+        amount,
+        a_to_b,
+        amount_specified_is_input,
+        fee_on_top,
+    })
+}
+
+fn load_whirlpool(client: &RpcClient, address: &Pubkey) -> Result<Whirlpool, String> {
+    let account = client
+        .get_account(address)
+        .map_err(|e| format!("failed to fetch whirlpool {}: {}", address, e))?;
+    Whirlpool::try_deserialize(&mut account.data.as_slice())
+        .map_err(|e| format!("failed to deserialize whirlpool {}: {}", address, e))
+}
+
+fn load_tick_array(client: &RpcClient, address: &Pubkey) -> Result<Option<TickArray>, String> {
+    let account = match client.get_account(address) {
+        Ok(account) => account,
+        // Tick arrays that were never initialized end the sequence.
+        Err(_) => return Ok(None),
+    };
+    let data = &account.data;
+    if data.len() < TickArray::LEN || data[..8] != TickArray::discriminator() {
+        return Err(format!("account {} is not a tick array", address));
+    }
+    Ok(Some(bytemuck::pod_read_unaligned(
+        &data[8..8 + std::mem::size_of::<TickArray>()],
+    )))
+}
+
+/// Load the up to three tick arrays a swap starting at the current tick would traverse.
+fn load_tick_arrays(
+    client: &RpcClient,
+    whirlpool_address: &Pubkey,
+    whirlpool: &Whirlpool,
+    a_to_b: bool,
+) -> Result<Vec<TickArray>, String> {
+    let tick_spacing = whirlpool.tick_spacing;
+    let ticks_in_array = TICK_ARRAY_SIZE * tick_spacing as i32;
+    // Swaps from b to a start in the next array when the current tick is its last tick.
+    let shift = if a_to_b { 0 } else { tick_spacing as i32 };
+    let mut start_tick_index =
+        TickArray::start_tick_index_for(whirlpool.tick_current_index + shift, tick_spacing);
+
+    let mut tick_arrays = Vec::with_capacity(3);
+    for _ in 0..3 {
+        let address = TickArray::find_address(whirlpool_address, start_tick_index);
+        match load_tick_array(client, &address)? {
+            Some(tick_array) => tick_arrays.push(tick_array),
+            None => break,
+        }
+        start_tick_index = if a_to_b {
+            start_tick_index - ticks_in_array
+        } else {
+            start_tick_index + ticks_in_array
+        };
+    }
+
+    if tick_arrays.is_empty() {
+        return Err("the tick array containing the current tick is not initialized".to_string());
+    }
+    Ok(tick_arrays)
+}
+
+fn sqrt_price_to_price(sqrt_price: u128) -> f64 {
+    let sqrt_price = sqrt_price as f64 / (1u128 << 64) as f64;
+    sqrt_price * sqrt_price
+}
+
+fn run() -> Result<(), String> {
+    let args = parse_args()?;
+    let client = RpcClient::new(args.rpc_url.clone());
+
+    let whirlpool = load_whirlpool(&client, &args.whirlpool)?;
+    let tick_arrays = load_tick_arrays(&client, &args.whirlpool, &whirlpool, args.a_to_b)?
+        .into_iter()
+        .map(RefCell::new)
+        .collect::<Vec<_>>();
+    let mut tick_array_refs = tick_arrays.iter().map(|ta| ta.borrow_mut());
+    let mut swap_tick_sequence = SwapTickSequence::new(
+        tick_array_refs.next().unwrap(),
+        tick_array_refs.next(),
+        tick_array_refs.next(),
+    );
+
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_err(|e| e.to_string())?
+        .as_secs();
+    let sqrt_price_limit = default_sqrt_price_limit(args.a_to_b);
+
+    // Rebasing-aware pools account the specified amount in underlying units.
+    let amount = whirlpool
+        .to_pool_amount(
+            args.amount,
+            args.a_to_b == args.amount_specified_is_input,
+            !args.amount_specified_is_input,
+        )
+        .map_err(|e| e.to_string())?;
+
+    let update = if args.fee_on_top {
+        swap_fee_on_top(
+            &whirlpool,
+            whirlpool.effective_fee_rate(),
+            &mut swap_tick_sequence,
+            amount,
+            sqrt_price_limit,
+            args.a_to_b,
+            timestamp,
+        )
+    } else {
+        swap(
+            &whirlpool,
+            &mut swap_tick_sequence,
+            amount,
+            sqrt_price_limit,
+            args.amount_specified_is_input,
+            args.a_to_b,
+            timestamp,
+        )
+    }
+    .map_err(|e| format!("swap failed: {}", e))?;
+
+    let amount_a = whirlpool
+        .to_token_amount(update.amount_a, true, args.a_to_b)
+        .map_err(|e| e.to_string())?;
+    let amount_b = whirlpool
+        .to_token_amount(update.amount_b, false, !args.a_to_b)
+        .map_err(|e| e.to_string())?;
+    let total_fee = whirlpool
+        .to_token_amount(update.total_fee, update.is_fee_in_token_a, false)
+        .map_err(|e| e.to_string())?;
+    let protocol_fee = whirlpool
+        .to_token_amount(update.next_protocol_fee, update.is_fee_in_token_a, false)
+        .map_err(|e| e.to_string())?;
+    let (amount_in, amount_out) = if args.a_to_b {
+        (amount_a, amount_b)
+    } else {
+        (amount_b, amount_a)
+    };
+
+    let start_price = sqrt_price_to_price(whirlpool.sqrt_price);
+    let end_price = sqrt_price_to_price(update.next_sqrt_price);
+    let price_impact = (end_price - start_price).abs() / start_price * 100.0;
+
+    println!("whirlpool:       {}", args.whirlpool);
+    println!("tick arrays:     {}", tick_arrays.len());
+    println!("amount in:       {}", amount_in);
+    println!("amount out:      {}", amount_out);
+    println!("total fee:       {}", total_fee);
+    println!("protocol fee:    {}", protocol_fee);
+    println!("start tick:      {}", whirlpool.tick_current_index);
+    println!("end tick:        {}", update.next_tick_index);
+    println!("start price:     {}", start_price);
+    println!("end price:       {}", end_price);
+    println!("price impact:    {:.4}%", price_impact);
+    // The input of a fee on top swap includes the fee charged in addition to the amount.
+    let amount_traded = if args.fee_on_top {
+        amount_in.saturating_sub(total_fee)
+    } else {
+        amount_in
+    };
+    if args.amount_specified_is_input && amount_traded < args.amount {
+        println!(
+            "warning: only {} of {} could be swapped with the loaded tick arrays",
+            amount_traded, args.amount
+        );
+    }
+
+    Ok(())
+}
+
+fn main() {
+    if let Err(err) = run() {
+        eprintln!("error: {}", err);
+        process::exit(1);
+    }
+}
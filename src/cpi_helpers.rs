@@ -0,0 +1,154 @@
+//! Composed CPI helpers for programs that manage Whirlpool positions on behalf of their users,
+//! such as vaults. Each helper chains the instructions of a common multi-step flow so callers
+//! only have to provide the accounts once.
+//!
+//! All helpers take the signer seeds of the position authority. Pass an empty slice if the
+//! position authority signed the outer transaction.
+use anchor_lang::prelude::*;
+
+use crate::cpi;
+use crate::state::Position;
+
+/// Accounts of a position and its Whirlpool used by the position helpers.
+#[derive(Clone)]
+pub struct PositionCpiAccounts<'info> {
+    pub whirlpool_program: AccountInfo<'info>,
+    pub whirlpool: AccountInfo<'info>,
+    pub position_authority: AccountInfo<'info>,
+    pub position: AccountInfo<'info>,
+    pub position_token_account: AccountInfo<'info>,
+    pub token_owner_account_a: AccountInfo<'info>,
+    pub token_owner_account_b: AccountInfo<'info>,
+    pub token_vault_a: AccountInfo<'info>,
+    pub token_vault_b: AccountInfo<'info>,
+    pub tick_array_lower: AccountInfo<'info>,
+    pub tick_array_upper: AccountInfo<'info>,
+    pub token_program: AccountInfo<'info>,
+}
+
+/// Accounts of a single reward of the Whirlpool used by `collect_rewards`.
+#[derive(Clone)]
+pub struct RewardCpiAccounts<'info> {
+    pub reward_index: u8,
+    pub reward_owner_account: AccountInfo<'info>,
+    pub reward_vault: AccountInfo<'info>,
+}
+
+fn position_liquidity(position: &AccountInfo) -> Result<u128> {
+    let data = position.try_borrow_data()?;
+    Ok(Position::try_deserialize(&mut data.as_ref())?.liquidity)
+}
+
+fn collect_fees_cpi<'info>(
+    accounts: &PositionCpiAccounts<'info>,
+    signer_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    cpi::collect_fees(CpiContext::new_with_signer(
+        accounts.whirlpool_program.clone(),
+        cpi::accounts::CollectFees {
+            whirlpool: accounts.whirlpool.clone(),
+            position_authority: accounts.position_authority.clone(),
+            position: accounts.position.clone(),
+            position_token_account: accounts.position_token_account.clone(),
+            token_owner_account_a: accounts.token_owner_account_a.clone(),
+            token_vault_a: accounts.token_vault_a.clone(),
+            token_owner_account_b: accounts.token_owner_account_b.clone(),
+            token_vault_b: accounts.token_vault_b.clone(),
+            token_program: accounts.token_program.clone(),
+        },
+        signer_seeds,
+    ))
+}
+
+/// Bring the fees and rewards owed to a position up to date and collect its fees.
+///
+/// Positions without liquidity already have up to date fees, so only the collection is performed.
+pub fn update_and_collect_fees<'info>(
+    accounts: &PositionCpiAccounts<'info>,
+    signer_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    if position_liquidity(&accounts.position)? > 0 {
+        cpi::update_fees_and_rewards(CpiContext::new(
+            accounts.whirlpool_program.clone(),
+            cpi::accounts::UpdateFeesAndRewards {
+                whirlpool: accounts.whirlpool.clone(),
+                position: accounts.position.clone(),
+                tick_array_lower: accounts.tick_array_lower.clone(),
+                tick_array_upper: accounts.tick_array_upper.clone(),
+            },
+        ))?;
+    }
+
+    collect_fees_cpi(accounts, signer_seeds)
+}
+
+/// Withdraw all liquidity of a position and collect its fees, leaving the position empty of
+/// liquidity and fees. Rewards are brought up to date but have to be collected separately with
+/// `collect_rewards`.
+///
+/// # Parameters
+/// - `token_min_a` - The minimum amount of token A the withdrawal must return
+/// - `token_min_b` - The minimum amount of token B the withdrawal must return
+pub fn withdraw_all_and_collect<'info>(
+    accounts: &PositionCpiAccounts<'info>,
+    token_min_a: u64,
+    token_min_b: u64,
+    signer_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    let liquidity = position_liquidity(&accounts.position)?;
+    if liquidity > 0 {
+        cpi::decrease_liquidity(
+            CpiContext::new_with_signer(
+                accounts.whirlpool_program.clone(),
+                cpi::accounts::ModifyLiquidity {
+                    whirlpool: accounts.whirlpool.clone(),
+                    token_program: accounts.token_program.clone(),
+                    position_authority: accounts.position_authority.clone(),
+                    position: accounts.position.clone(),
+                    position_token_account: accounts.position_token_account.clone(),
+                    token_owner_account_a: accounts.token_owner_account_a.clone(),
+                    token_owner_account_b: accounts.token_owner_account_b.clone(),
+                    token_vault_a: accounts.token_vault_a.clone(),
+                    token_vault_b: accounts.token_vault_b.clone(),
+                    tick_array_lower: accounts.tick_array_lower.clone(),
+                    tick_array_upper: accounts.tick_array_upper.clone(),
+                },
+                signer_seeds,
+            ),
+            liquidity,
+            token_min_a,
+            token_min_b,
+        )?;
+    }
+
+    collect_fees_cpi(accounts, signer_seeds)
+}
+
+/// Collect the owed amount of each of the given rewards of a position. Rewards owed are only as
+/// recent as the last update of the position, see `update_and_collect_fees`.
+pub fn collect_rewards<'info>(
+    accounts: &PositionCpiAccounts<'info>,
+    rewards: &[RewardCpiAccounts<'info>],
+    signer_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    for reward in rewards {
+        cpi::collect_reward(
+            CpiContext::new_with_signer(
+                accounts.whirlpool_program.clone(),
+                cpi::accounts::CollectReward {
+                    whirlpool: accounts.whirlpool.clone(),
+                    position_authority: accounts.position_authority.clone(),
+                    position: accounts.position.clone(),
+                    position_token_account: accounts.position_token_account.clone(),
+                    reward_owner_account: reward.reward_owner_account.clone(),
+                    reward_vault: reward.reward_vault.clone(),
+                    token_program: accounts.token_program.clone(),
+                },
+                signer_seeds,
+            ),
+            reward.reward_index,
+        )?;
+    }
+
+    Ok(())
+}
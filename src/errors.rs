@@ -1,106 +1,283 @@
 use std::num::TryFromIntError;
 
-use anchor_lang::{error, error_code};
+use anchor_lang::error_code;
 
-#[error_code]
+/// Errors of the program. Every variant has an explicit code, the number reported in transaction
+/// errors. Codes are stable: variants are only appended and codes are never reused, so clients
+/// can map them with `error_from_code`.
+#[error_code(offset = 0)]
 #[derive(PartialEq)]
 pub enum ErrorCode {
     #[msg("Enum value could not be converted")]
-    InvalidEnum, // 0x1770
+    InvalidEnum = 6000, // 0x1770
     #[msg("Invalid start tick index provided.")]
-    InvalidStartTick, // 0x1771
+    InvalidStartTick = 6001, // 0x1771
     #[msg("Tick-array already exists in this whirlpool")]
-    TickArrayExistInPool, // 0x1772
+    TickArrayExistInPool = 6002, // 0x1772
     #[msg("Attempt to search for a tick-array failed")]
-    TickArrayIndexOutofBounds, // 0x1773
+    TickArrayIndexOutofBounds = 6003, // 0x1773
     #[msg("Tick-spacing is not supported")]
-    InvalidTickSpacing, // 0x1774
+    InvalidTickSpacing = 6004, // 0x1774
     #[msg("Position is not empty It cannot be closed")]
-    ClosePositionNotEmpty, // 0x1775
-
+    ClosePositionNotEmpty = 6005, // 0x1775
     #[msg("Unable to divide by zero")]
-    DivideByZero, // 0x1776
+    DivideByZero = 6006, // 0x1776
     #[msg("Unable to cast number into BigInt")]
-    NumberCastError, //  0x1777
+    NumberCastError = 6007, // 0x1777
     #[msg("Unable to down cast number")]
-    NumberDownCastError, //  0x1778
-
+    NumberDownCastError = 6008, // 0x1778
     #[msg("Tick not found within tick array")]
-    TickNotFound, // 0x1779
+    TickNotFound = 6009, // 0x1779
     #[msg("Provided tick index is either out of bounds or uninitializable")]
-    InvalidTickIndex, // 0x177a
+    InvalidTickIndex = 6010, // 0x177a
     #[msg("Provided sqrt price out of bounds")]
-    SqrtPriceOutOfBounds, // 0x177b
-
+    SqrtPriceOutOfBounds = 6011, // 0x177b
     #[msg("Liquidity amount must be greater than zero")]
-    LiquidityZero, // 0x177c
+    LiquidityZero = 6012, // 0x177c
     #[msg("Liquidity amount must be less than i64::MAX")]
-    LiquidityTooHigh, // 0x177d
+    LiquidityTooHigh = 6013, // 0x177d
     #[msg("Liquidity overflow")]
-    LiquidityOverflow, // 0x177e
+    LiquidityOverflow = 6014, // 0x177e
     #[msg("Liquidity underflow")]
-    LiquidityUnderflow, // 0x177f
+    LiquidityUnderflow = 6015, // 0x177f
     #[msg("Tick liquidity net underflowed or overflowed")]
-    LiquidityNetError, // 0x1780
-
+    LiquidityNetError = 6016, // 0x1780
     #[msg("Exceeded token max")]
-    TokenMaxExceeded, // 0x1781
+    TokenMaxExceeded = 6017, // 0x1781
     #[msg("Did not meet token min")]
-    TokenMinSubceeded, // 0x1782
-
+    TokenMinSubceeded = 6018, // 0x1782
     #[msg("Position token account has a missing or invalid delegate")]
-    MissingOrInvalidDelegate, // 0x1783
+    MissingOrInvalidDelegate = 6019, // 0x1783
     #[msg("Position token amount must be 1")]
-    InvalidPositionTokenAmount, // 0x1784
-
+    InvalidPositionTokenAmount = 6020, // 0x1784
     #[msg("Timestamp should be convertible from i64 to u64")]
-    InvalidTimestampConversion, // 0x1785
+    InvalidTimestampConversion = 6021, // 0x1785
     #[msg("Timestamp should be greater than the last updated timestamp")]
-    InvalidTimestamp, // 0x1786
-
+    InvalidTimestamp = 6022, // 0x1786
     #[msg("Invalid tick array sequence provided for instruction.")]
-    InvalidTickArraySequence, // 0x1787
+    InvalidTickArraySequence = 6023, // 0x1787
     #[msg("Token Mint in wrong order")]
-    InvalidTokenMintOrder, // 0x1788
-
+    InvalidTokenMintOrder = 6024, // 0x1788
     #[msg("Reward not initialized")]
-    RewardNotInitialized, // 0x1789
+    RewardNotInitialized = 6025, // 0x1789
     #[msg("Invalid reward index")]
-    InvalidRewardIndex, // 0x178a
-
+    InvalidRewardIndex = 6026, // 0x178a
     #[msg("Reward vault requires amount to support emissions for at least one day")]
-    RewardVaultAmountInsufficient, // 0x178b
+    RewardVaultAmountInsufficient = 6027, // 0x178b
     #[msg("Exceeded max fee rate")]
-    FeeRateMaxExceeded, // 0x178c
+    FeeRateMaxExceeded = 6028, // 0x178c
     #[msg("Exceeded max protocol fee rate")]
-    ProtocolFeeRateMaxExceeded, // 0x178d
-
+    ProtocolFeeRateMaxExceeded = 6029, // 0x178d
     #[msg("Multiplication with shift right overflow")]
-    MultiplicationShiftRightOverflow, // 0x178e
+    MultiplicationShiftRightOverflow = 6030, // 0x178e
     #[msg("Muldiv overflow")]
-    MulDivOverflow, // 0x178f
+    MulDivOverflow = 6031, // 0x178f
     #[msg("Invalid div_u256 input")]
-    MulDivInvalidInput, //0x1790
+    MulDivInvalidInput = 6032, // 0x1790
     #[msg("Multiplication overflow")]
-    MultiplicationOverflow, //0x1791
-
+    MultiplicationOverflow = 6033, // 0x1791
     #[msg("Provided SqrtPriceLimit not in the same direction as the swap.")]
-    InvalidSqrtPriceLimitDirection, //0x1792
+    InvalidSqrtPriceLimitDirection = 6034, // 0x1792
     #[msg("There are no tradable amount to swap.")]
-    ZeroTradableAmount, //0x1793
-
+    ZeroTradableAmount = 6035, // 0x1793
     #[msg("Amount out below minimum threshold")]
-    AmountOutBelowMinimum, //0x1794
+    AmountOutBelowMinimum = 6036, // 0x1794
     #[msg("Amount in above maximum threshold")]
-    AmountInAboveMaximum, //0x1795
-
+    AmountInAboveMaximum = 6037, // 0x1795
     #[msg("Invalid index for tick array sequence")]
-    TickArraySequenceInvalidIndex, //0x1796
-
+    TickArraySequenceInvalidIndex = 6038, // 0x1796
     #[msg("Amount calculated overflows")]
-    AmountCalcOverflow, //0x1797
+    AmountCalcOverflow = 6039, // 0x1797
     #[msg("Amount remaining overflows")]
-    AmountRemainingOverflow, //0x1798
+    AmountRemainingOverflow = 6040, // 0x1798
+    #[msg("After swap hook cannot be this program")]
+    InvalidAfterSwapHook = 6041, // 0x1799
+    #[msg("After swap hook program account is missing or does not match the whirlpool")]
+    AfterSwapHookMissing = 6042, // 0x179a
+    #[msg("Too many accounts forwarded to the after swap hook")]
+    TooManyAfterSwapHookAccounts = 6043, // 0x179b
+    #[msg("Calculated token amount exceeds u64::MAX")]
+    AmountExceedsU64 = 6044, // 0x179c
+    #[msg("Calculated sqrt price is below the minimum sqrt price")]
+    PriceBelowMinimum = 6045, // 0x179d
+    #[msg("Calculated sqrt price is above the maximum sqrt price")]
+    PriceAboveMaximum = 6046, // 0x179e
+    #[msg("Position token must be burned before it can be re-minted")]
+    PositionTokenNotBurned = 6047, // 0x179f
+    #[msg("Exchange rate oracle account is invalid")]
+    InvalidExchangeRateOracle = 6048, // 0x17a0
+    #[msg("Exchange rate mode cannot be changed while the pool has liquidity")]
+    ExchangeRateModeLocked = 6049, // 0x17a1
+    #[msg("Unlock timestamp must be in the future and later than the current unlock timestamp")]
+    InvalidUnlockTimestamp = 6050, // 0x17a2
+    #[msg("Position liquidity is locked")]
+    PositionLiquidityLocked = 6051, // 0x17a3
+    #[msg("Not enough remaining accounts provided for this instruction")]
+    RemainingAccountsMissing = 6052, // 0x17a4
+    #[msg("Tick array account is not writable or does not belong to the whirlpool")]
+    InvalidTickArrayAccount = 6053, // 0x17a5
+    #[msg("Oracle account does not match the whirlpool oracle address")]
+    InvalidOracleAccount = 6054, // 0x17a6
+    #[msg("Session expiry must be in the future and within the max session duration")]
+    InvalidSessionExpiry = 6055, // 0x17a7
+    #[msg("Session scope is empty or contains unknown scopes")]
+    InvalidSessionScope = 6056, // 0x17a8
+    #[msg("Session authority is expired, out of scope or not granted by the position owner")]
+    InvalidSessionAuthority = 6057, // 0x17a9
+    #[msg("Token accounts must be owned by the position owner when acting through a session")]
+    InvalidSessionDestination = 6058, // 0x17aa
+    #[msg("Tick array account does not match the expected tick array address for this swap")]
+    TickArrayAddressMismatch = 6059, // 0x17ab
+    #[msg("Tick array cannot be linked to the provided neighbouring tick array")]
+    InvalidTickArrayLink = 6060, // 0x17ac
+    #[msg("Reward accrual mode cannot be changed after the reward has accrued growth")]
+    RewardAccrualModeLocked = 6061, // 0x17ad
+    #[msg("There is no pending authority to accept")]
+    NoPendingAuthority = 6062, // 0x17ae
+    #[msg("Destination token account is frozen")]
+    DestinationFrozen = 6063, // 0x17af
+    #[msg("Trade log account does not match the trade log of the whirlpool")]
+    InvalidTradeLogAccount = 6064, // 0x17b0
+    #[msg("Collection mint does not match the position collection of the whirlpool")]
+    InvalidPositionCollection = 6065, // 0x17b1
+    #[msg("Swaps in this direction are disabled for the whirlpool")]
+    SwapDirectionDisabled = 6066, // 0x17b2
+    #[msg("Pool price can only be reset while the pool has no liquidity")]
+    PoolHasLiquidity = 6067, // 0x17b3
+    #[msg("Tick cache must be refreshed before swapping against it")]
+    TickCacheStale = 6068, // 0x17b4
+    #[msg("Tick cache holds tick updates that must be written back to the tick arrays first")]
+    TickCacheNotWrittenBack = 6069, // 0x17b5
+    #[msg("Tick cache account does not match the tick cache of the whirlpool")]
+    InvalidTickCacheAccount = 6070, // 0x17b6
+    #[msg("A tick array covering the tick cache window was not provided")]
+    TickCacheTickArrayMissing = 6071, // 0x17b7
+    #[msg("Liquidity time accumulator does not belong to the position owner in this whirlpool")]
+    InvalidLiquidityTimeAccumulator = 6072, // 0x17b8
+    #[msg("Exceeded max referral fee rate")]
+    ReferralFeeRateMaxExceeded = 6073, // 0x17b9
+    #[msg("Whirlpool has reached its maximum number of open positions")]
+    PositionLimitReached = 6074, // 0x17ba
+    #[msg("Reward escrow program cannot be the Whirlpool program")]
+    InvalidRewardEscrowProgram = 6075, // 0x17bb
+    #[msg("Reward escrow program account is missing or does not match the whirlpool")]
+    RewardEscrowMissing = 6076, // 0x17bc
+    #[msg("Too many accounts provided for the reward escrow program")]
+    TooManyRewardEscrowAccounts = 6077, // 0x17bd
+    #[msg("Tick cross hook cannot be this program")]
+    InvalidTickCrossHook = 6078, // 0x17be
+    #[msg("Tick cross hook program account is missing or does not match the whirlpool")]
+    TickCrossHookMissing = 6079, // 0x17bf
+    #[msg("Too many accounts forwarded to the tick cross hook")]
+    TooManyTickCrossHookAccounts = 6080, // 0x17c0
+    #[msg("Swap crossed more initialized ticks than the tick cross hook can be notified of")]
+    TooManyTickCrossings = 6081, // 0x17c1
+    #[msg("Reward vault is not a token account of the reward mint owned by the whirlpool")]
+    InvalidRewardVault = 6082, // 0x17c2
+    #[msg("Withdraw authority must withdraw to token accounts of the withdraw destination")]
+    InvalidWithdrawDestination = 6083, // 0x17c3
+    #[msg("Logarithm of zero is undefined")]
+    LogOfZero = 6084, // 0x17c4
+    #[msg("Zap provides less liquidity than the minimum")]
+    LiquidityUnderMinimum = 6085, // 0x17c5
+    #[msg("Swap deadline has passed")]
+    SwapDeadlineExceeded = 6086, // 0x17c6
+    #[msg("Position has not opted in to auto compounding")]
+    AutoCompoundDisabled = 6087, // 0x17c7
+    #[msg("Exceeded max auto compound tip rate")]
+    AutoCompoundTipRateMaxExceeded = 6088, // 0x17c8
+    #[msg("Account is not a position of the upstream Whirlpool program held by the owner")]
+    InvalidUpstreamPosition = 6089, // 0x17c9
+    #[msg("Upstream Whirlpool does not pair the same mints as the Whirlpool")]
+    UpstreamWhirlpoolMismatch = 6090, // 0x17ca
+    #[msg("Rent sponsor does not sponsor this tick array")]
+    InvalidRentSponsor = 6091, // 0x17cb
+    #[msg("Rent sponsor has insufficient lamports")]
+    RentSponsorInsufficientFunds = 6092, // 0x17cc
+    #[msg("Whirlpool token vault is frozen")]
+    VaultFrozen = 6093, // 0x17cd
+    #[msg("User token account is frozen")]
+    UserAccountFrozen = 6094, // 0x17ce
+    #[msg("Fee holiday is invalid")]
+    InvalidFeeHoliday = 6095, // 0x17cf
+    #[msg("Account is not the fee schedule of the whirlpool")]
+    InvalidFeeScheduleAccount = 6096, // 0x17d0
+    #[msg("Merkle proof does not match the merkle root")]
+    InvalidMerkleProof = 6097, // 0x17d1
+    #[msg("Retroactive reward already claimed")]
+    RetroRewardAlreadyClaimed = 6098, // 0x17d2
+    #[msg("Swap truncated before exhausting the compute budget, progress is in the return data")]
+    SwapTruncated = 6099, // 0x17d3
+    #[msg("Whirlpool has not been swapped against recently enough")]
+    StalePool = 6100, // 0x17d4
+    #[msg("Existing position does not match the position to open")]
+    PositionMismatch = 6101, // 0x17d5
+    #[msg("Token mint has a freeze authority and no token badge allowing it")]
+    FreezeAuthorityNotAllowed = 6102, // 0x17d6
+    #[msg("Router fee discount rate exceeds MAX_ROUTER_FEE_DISCOUNT_RATE")]
+    RouterFeeDiscountRateMaxExceeded = 6103, // 0x17d7
+    #[msg("Approved router index is out of bounds")]
+    InvalidApprovedRouterIndex = 6104, // 0x17d8
+    #[msg("Arbitrage pools must be distinct whirlpools of the same pair and token accounts")]
+    InvalidArbitragePools = 6105, // 0x17d9
+    #[msg("Arbitrage profit is below the minimum")]
+    ArbitrageProfitBelowMinimum = 6106, // 0x17da
+    #[msg("Liquidity cannot be removed before the cooldown since the last increase has passed")]
+    LiquidityRemovalCooldown = 6107, // 0x17db
+    #[msg("JIT surcharge rate exceeds MAX_JIT_SURCHARGE_RATE")]
+    JitSurchargeRateMaxExceeded = 6108, // 0x17dc
+    #[msg("Position metadata URI template is too long or has more than one mint placeholder")]
+    InvalidPositionMetadataUriTemplate = 6109, // 0x17dd
+    #[msg("Account is owned by the upstream Whirlpool program instead of this program")]
+    ForeignProgramAccount = 6110, // 0x17de
+    #[msg("Keeper tip escrow does not belong to the whirlpool")]
+    InvalidKeeperTipEscrow = 6111, // 0x17df
+    #[msg("Keeper tip escrow holds fewer lamports above its rent exemption")]
+    KeeperTipEscrowInsufficientFunds = 6112, // 0x17e0
+    #[msg("Whirlpool is paused by the emergency authority")]
+    PoolPaused = 6113, // 0x17e1
+    #[msg("Admin parameter changes are frozen by the emergency authority")]
+    AdminParametersFrozen = 6114, // 0x17e2
+    #[msg("Signer is not allowed to set the emergency authority")]
+    InvalidEmergencyAuthority = 6115, // 0x17e3
+    #[msg("Fee on top swaps need an exact input amount and a pool charging the fee on the input")]
+    FeeOnTopUnsupported = 6116, // 0x17e4
+    #[msg("Position is frozen by a bridge attestation")]
+    PositionFrozen = 6117, // 0x17e5
+    #[msg("Pro rata rate must be between 1 and 10000 basis points")]
+    InvalidProRataRate = 6118, // 0x17e6
+    #[msg("Ladder band must split into equal usable tick ranges for 1 to 8 positions")]
+    InvalidLadder = 6119, // 0x17e7
+    #[msg("Boundary tick of a position with liquidity is not initialized")]
+    BoundaryTickUninitialized = 6120, // 0x17e8
+    #[msg("Paranoid check failed: account state disagrees with the computed amounts")]
+    ShadowAccountingMismatch = 6121, // 0x17e9
+    #[msg("Reward tenure boost base rate exceeds REWARD_TENURE_BOOST_RATE_MUL_VALUE")]
+    InvalidRewardTenureBoost = 6122, // 0x17ea
+    #[msg("Invalid collect route")]
+    InvalidCollectRoute = 6123, // 0x17eb
+    #[msg("Price oracle account is not a fully verified Pyth price update account")]
+    InvalidPriceOracle = 6124, // 0x17ec
+    #[msg("Oracle price is older than the maximum age")]
+    StaleOraclePrice = 6125, // 0x17ed
+    #[msg("Oracle price confidence interval exceeds the maximum confidence rate")]
+    OracleConfidenceTooWide = 6126, // 0x17ee
+    #[msg("Signer is not the recovery authority of the position")]
+    InvalidRecoveryAuthority = 6127, // 0x17ef
+    #[msg("After swap hook consumed more than MAX_AFTER_SWAP_HOOK_COMPUTE_UNITS")]
+    AfterSwapHookComputeExceeded = 6128, // 0x17f0
+    #[msg("Oracle price update is not of the price feed configured for the mint")]
+    OraclePriceFeedMismatch = 6129, // 0x17f1
+    #[msg("Collect destination is not the token account routed for the mint")]
+    CollectRouteMismatch = 6130, // 0x17f2
+    #[msg("Output mint of the first swap of a two-hop route is not the input mint of the second")]
+    InvalidIntermediaryMint = 6131, // 0x17f3
+    #[msg("Intermediate token amount of a two-hop route differs between its swaps")]
+    IntermediateTokenAmountMismatch = 6132, // 0x17f4
+    #[msg("Total liquidity of the pool does not count every position yet")]
+    TotalLiquidityIncomplete = 6133, // 0x17f5
+    #[msg("Tick cross hook consumed more than MAX_TICK_CROSS_HOOK_COMPUTE_UNITS")]
+    TickCrossHookComputeExceeded = 6134, // 0x17f6
 }
 
 impl From<TryFromIntError> for ErrorCode {
@@ -108,3 +285,164 @@ impl From<TryFromIntError> for ErrorCode {
         ErrorCode::NumberCastError
     }
 }
+
+/// The error with the code reported in a transaction error, or None if the code is not an error
+/// of this program.
+pub fn error_from_code(code: u32) -> Option<ErrorCode> {
+    let error = match code {
+        6000 => ErrorCode::InvalidEnum,
+        6001 => ErrorCode::InvalidStartTick,
+        6002 => ErrorCode::TickArrayExistInPool,
+        6003 => ErrorCode::TickArrayIndexOutofBounds,
+        6004 => ErrorCode::InvalidTickSpacing,
+        6005 => ErrorCode::ClosePositionNotEmpty,
+        6006 => ErrorCode::DivideByZero,
+        6007 => ErrorCode::NumberCastError,
+        6008 => ErrorCode::NumberDownCastError,
+        6009 => ErrorCode::TickNotFound,
+        6010 => ErrorCode::InvalidTickIndex,
+        6011 => ErrorCode::SqrtPriceOutOfBounds,
+        6012 => ErrorCode::LiquidityZero,
+        6013 => ErrorCode::LiquidityTooHigh,
+        6014 => ErrorCode::LiquidityOverflow,
+        6015 => ErrorCode::LiquidityUnderflow,
+        6016 => ErrorCode::LiquidityNetError,
+        6017 => ErrorCode::TokenMaxExceeded,
+        6018 => ErrorCode::TokenMinSubceeded,
+        6019 => ErrorCode::MissingOrInvalidDelegate,
+        6020 => ErrorCode::InvalidPositionTokenAmount,
+        6021 => ErrorCode::InvalidTimestampConversion,
+        6022 => ErrorCode::InvalidTimestamp,
+        6023 => ErrorCode::InvalidTickArraySequence,
+        6024 => ErrorCode::InvalidTokenMintOrder,
+        6025 => ErrorCode::RewardNotInitialized,
+        6026 => ErrorCode::InvalidRewardIndex,
+        6027 => ErrorCode::RewardVaultAmountInsufficient,
+        6028 => ErrorCode::FeeRateMaxExceeded,
+        6029 => ErrorCode::ProtocolFeeRateMaxExceeded,
+        6030 => ErrorCode::MultiplicationShiftRightOverflow,
+        6031 => ErrorCode::MulDivOverflow,
+        6032 => ErrorCode::MulDivInvalidInput,
+        6033 => ErrorCode::MultiplicationOverflow,
+        6034 => ErrorCode::InvalidSqrtPriceLimitDirection,
+        6035 => ErrorCode::ZeroTradableAmount,
+        6036 => ErrorCode::AmountOutBelowMinimum,
+        6037 => ErrorCode::AmountInAboveMaximum,
+        6038 => ErrorCode::TickArraySequenceInvalidIndex,
+        6039 => ErrorCode::AmountCalcOverflow,
+        6040 => ErrorCode::AmountRemainingOverflow,
+        6041 => ErrorCode::InvalidAfterSwapHook,
+        6042 => ErrorCode::AfterSwapHookMissing,
+        6043 => ErrorCode::TooManyAfterSwapHookAccounts,
+        6044 => ErrorCode::AmountExceedsU64,
+        6045 => ErrorCode::PriceBelowMinimum,
+        6046 => ErrorCode::PriceAboveMaximum,
+        6047 => ErrorCode::PositionTokenNotBurned,
+        6048 => ErrorCode::InvalidExchangeRateOracle,
+        6049 => ErrorCode::ExchangeRateModeLocked,
+        6050 => ErrorCode::InvalidUnlockTimestamp,
+        6051 => ErrorCode::PositionLiquidityLocked,
+        6052 => ErrorCode::RemainingAccountsMissing,
+        6053 => ErrorCode::InvalidTickArrayAccount,
+        6054 => ErrorCode::InvalidOracleAccount,
+        6055 => ErrorCode::InvalidSessionExpiry,
+        6056 => ErrorCode::InvalidSessionScope,
+        6057 => ErrorCode::InvalidSessionAuthority,
+        6058 => ErrorCode::InvalidSessionDestination,
+        6059 => ErrorCode::TickArrayAddressMismatch,
+        6060 => ErrorCode::InvalidTickArrayLink,
+        6061 => ErrorCode::RewardAccrualModeLocked,
+        6062 => ErrorCode::NoPendingAuthority,
+        6063 => ErrorCode::DestinationFrozen,
+        6064 => ErrorCode::InvalidTradeLogAccount,
+        6065 => ErrorCode::InvalidPositionCollection,
+        6066 => ErrorCode::SwapDirectionDisabled,
+        6067 => ErrorCode::PoolHasLiquidity,
+        6068 => ErrorCode::TickCacheStale,
+        6069 => ErrorCode::TickCacheNotWrittenBack,
+        6070 => ErrorCode::InvalidTickCacheAccount,
+        6071 => ErrorCode::TickCacheTickArrayMissing,
+        6072 => ErrorCode::InvalidLiquidityTimeAccumulator,
+        6073 => ErrorCode::ReferralFeeRateMaxExceeded,
+        6074 => ErrorCode::PositionLimitReached,
+        6075 => ErrorCode::InvalidRewardEscrowProgram,
+        6076 => ErrorCode::RewardEscrowMissing,
+        6077 => ErrorCode::TooManyRewardEscrowAccounts,
+        6078 => ErrorCode::InvalidTickCrossHook,
+        6079 => ErrorCode::TickCrossHookMissing,
+        6080 => ErrorCode::TooManyTickCrossHookAccounts,
+        6081 => ErrorCode::TooManyTickCrossings,
+        6082 => ErrorCode::InvalidRewardVault,
+        6083 => ErrorCode::InvalidWithdrawDestination,
+        6084 => ErrorCode::LogOfZero,
+        6085 => ErrorCode::LiquidityUnderMinimum,
+        6086 => ErrorCode::SwapDeadlineExceeded,
+        6087 => ErrorCode::AutoCompoundDisabled,
+        6088 => ErrorCode::AutoCompoundTipRateMaxExceeded,
+        6089 => ErrorCode::InvalidUpstreamPosition,
+        6090 => ErrorCode::UpstreamWhirlpoolMismatch,
+        6091 => ErrorCode::InvalidRentSponsor,
+        6092 => ErrorCode::RentSponsorInsufficientFunds,
+        6093 => ErrorCode::VaultFrozen,
+        6094 => ErrorCode::UserAccountFrozen,
+        6095 => ErrorCode::InvalidFeeHoliday,
+        6096 => ErrorCode::InvalidFeeScheduleAccount,
+        6097 => ErrorCode::InvalidMerkleProof,
+        6098 => ErrorCode::RetroRewardAlreadyClaimed,
+        6099 => ErrorCode::SwapTruncated,
+        6100 => ErrorCode::StalePool,
+        6101 => ErrorCode::PositionMismatch,
+        6102 => ErrorCode::FreezeAuthorityNotAllowed,
+        6103 => ErrorCode::RouterFeeDiscountRateMaxExceeded,
+        6104 => ErrorCode::InvalidApprovedRouterIndex,
+        6105 => ErrorCode::InvalidArbitragePools,
+        6106 => ErrorCode::ArbitrageProfitBelowMinimum,
+        6107 => ErrorCode::LiquidityRemovalCooldown,
+        6108 => ErrorCode::JitSurchargeRateMaxExceeded,
+        6109 => ErrorCode::InvalidPositionMetadataUriTemplate,
+        6110 => ErrorCode::ForeignProgramAccount,
+        6111 => ErrorCode::InvalidKeeperTipEscrow,
+        6112 => ErrorCode::KeeperTipEscrowInsufficientFunds,
+        6113 => ErrorCode::PoolPaused,
+        6114 => ErrorCode::AdminParametersFrozen,
+        6115 => ErrorCode::InvalidEmergencyAuthority,
+        6116 => ErrorCode::FeeOnTopUnsupported,
+        6117 => ErrorCode::PositionFrozen,
+        6118 => ErrorCode::InvalidProRataRate,
+        6119 => ErrorCode::InvalidLadder,
+        6120 => ErrorCode::BoundaryTickUninitialized,
+        6121 => ErrorCode::ShadowAccountingMismatch,
+        6122 => ErrorCode::InvalidRewardTenureBoost,
+        6123 => ErrorCode::InvalidCollectRoute,
+        6124 => ErrorCode::InvalidPriceOracle,
+        6125 => ErrorCode::StaleOraclePrice,
+        6126 => ErrorCode::OracleConfidenceTooWide,
+        6127 => ErrorCode::InvalidRecoveryAuthority,
+        6128 => ErrorCode::AfterSwapHookComputeExceeded,
+        6129 => ErrorCode::OraclePriceFeedMismatch,
+        6130 => ErrorCode::CollectRouteMismatch,
+        6131 => ErrorCode::InvalidIntermediaryMint,
+        6132 => ErrorCode::IntermediateTokenAmountMismatch,
+        6133 => ErrorCode::TotalLiquidityIncomplete,
+        6134 => ErrorCode::TickCrossHookComputeExceeded,
+        _ => return None,
+    };
+    Some(error)
+}
+
+#[cfg(test)]
+mod error_code_tests {
+    use super::*;
+
+    #[test]
+    fn test_error_from_code_round_trip() {
+        assert_eq!(error_from_code(6000), Some(ErrorCode::InvalidEnum));
+        assert_eq!(u32::from(ErrorCode::InvalidEnum), 0x1770);
+        for code in 6000..6135 {
+            let error = error_from_code(code).unwrap();
+            assert_eq!(u32::from(error), code);
+        }
+        assert_eq!(error_from_code(5999), None);
+        assert_eq!(error_from_code(6135), None);
+    }
+}
@@ -0,0 +1,102 @@
+use anchor_lang::prelude::*;
+
+use crate::state::NUM_REWARDS;
+
+#[event]
+pub struct PositionLocked {
+    pub whirlpool: Pubkey,
+    pub position: Pubkey,
+    pub locker: Pubkey,
+    pub locked_liquidity: u128,
+    pub unlock_timestamp: u64,
+}
+
+#[event]
+pub struct PositionLockExtended {
+    pub whirlpool: Pubkey,
+    pub position: Pubkey,
+    pub locker: Pubkey,
+    pub locked_liquidity: u128,
+    pub previous_unlock_timestamp: u64,
+    pub unlock_timestamp: u64,
+}
+
+#[event]
+pub struct SwapFees {
+    pub whirlpool: Pubkey,
+    pub a_to_b: bool,
+    pub fee_in_token_a: bool,
+    pub lp_fee: u64,
+    pub protocol_fee: u64,
+    pub referral_fee: u64,
+}
+
+#[event]
+pub struct RewardShortfall {
+    pub whirlpool: Pubkey,
+    pub position: Pubkey,
+    pub reward_index: u8,
+    pub amount_collected: u64,
+    pub amount_still_owed: u64,
+}
+
+#[event]
+pub struct PoolIntegrityVerified {
+    pub whirlpool: Pubkey,
+    pub vault_a_balance: u64,
+    pub vault_b_balance: u64,
+    pub protocol_fee_owed_a: u64,
+    pub protocol_fee_owed_b: u64,
+    pub pool_liquidity: u128,
+    pub tick_liquidity: i128,
+    pub tick_arrays_checked: u16,
+    pub discrepancy: bool,
+}
+
+#[event]
+pub struct RewardGrowthCheckpointed {
+    pub whirlpool: Pubkey,
+    pub timestamp: u64,
+    pub reward_growth_globals_x64: [u128; NUM_REWARDS],
+}
+
+#[event]
+pub struct LiquidityShifted {
+    pub whirlpool: Pubkey,
+    pub slot: u64,
+    pub slot_start_liquidity: u128,
+    pub liquidity: u128,
+}
+
+#[event]
+pub struct PositionTransferred {
+    pub whirlpool: Pubkey,
+    pub position: Pubkey,
+    pub position_mint: Pubkey,
+    pub from_owner: Pubkey,
+    pub to_owner: Pubkey,
+}
+
+#[event]
+pub struct KeeperTipPaid {
+    pub whirlpool: Pubkey,
+    pub keeper_tip_escrow: Pubkey,
+    pub keeper: Pubkey,
+    pub tip: u64,
+}
+
+#[event]
+pub struct PositionAttested {
+    pub whirlpool: Pubkey,
+    pub position: Pubkey,
+    pub attestation: Pubkey,
+    pub bridge_authority: Pubkey,
+    pub liquidity: u128,
+}
+
+#[event]
+pub struct PositionAttestationReleased {
+    pub whirlpool: Pubkey,
+    pub position: Pubkey,
+    pub bridge_authority: Pubkey,
+}
@@ -0,0 +1,16 @@
+use anchor_lang::prelude::*;
+
+use crate::state::WhirlpoolsConfig;
+
+#[derive(Accounts)]
+pub struct AcceptEmergencyAuthority<'info> {
+    #[account(mut)]
+    pub whirlpools_config: Account<'info, WhirlpoolsConfig>,
+
+    #[account(address = whirlpools_config.pending_emergency_authority)]
+    pub pending_emergency_authority: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<AcceptEmergencyAuthority>) -> Result<()> {
+    ctx.accounts.whirlpools_config.accept_emergency_authority()
+}
@@ -0,0 +1,20 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::ErrorCode;
+use crate::state::WhirlpoolsConfig;
+
+#[derive(Accounts)]
+pub struct AcceptFeeAuthority<'info> {
+    #[account(mut)]
+    pub whirlpools_config: Account<'info, WhirlpoolsConfig>,
+
+    #[account(
+        address = whirlpools_config.pending_fee_authority,
+        constraint = !whirlpools_config.admin_frozen @ ErrorCode::AdminParametersFrozen,
+    )]
+    pub pending_fee_authority: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<AcceptFeeAuthority>) -> Result<()> {
+    ctx.accounts.whirlpools_config.accept_fee_authority()
+}
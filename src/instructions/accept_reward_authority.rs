@@ -0,0 +1,19 @@
+use anchor_lang::prelude::*;
+
+use crate::state::Whirlpool;
+
+#[derive(Accounts)]
+#[instruction(reward_index: u8)]
+pub struct AcceptRewardAuthority<'info> {
+    #[account(mut)]
+    pub whirlpool: Account<'info, Whirlpool>,
+
+    #[account(address = whirlpool.pending_reward_authorities[reward_index as usize])]
+    pub pending_reward_authority: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<AcceptRewardAuthority>, reward_index: u8) -> Result<()> {
+    ctx.accounts
+        .whirlpool
+        .accept_reward_authority(reward_index as usize)
+}
@@ -0,0 +1,98 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::ErrorCode;
+use crate::manager::swap_manager::SwapParams;
+
+use super::swap::*;
+
+/// `Swap` accounts of two whirlpools of the same pair, swapped in opposite directions with the
+/// same token accounts.
+#[derive(Accounts)]
+pub struct ArbTwoPools<'info> {
+    pub swap_one: Swap<'info>,
+
+    #[account(
+        constraint = swap_two.whirlpool.key() != swap_one.whirlpool.key() @ ErrorCode::InvalidArbitragePools,
+        constraint = swap_two.whirlpool.token_mint_a == swap_one.whirlpool.token_mint_a @ ErrorCode::InvalidArbitragePools,
+        constraint = swap_two.whirlpool.token_mint_b == swap_one.whirlpool.token_mint_b @ ErrorCode::InvalidArbitragePools,
+        constraint = swap_two.token_owner_account_a.key() == swap_one.token_owner_account_a.key() @ ErrorCode::InvalidArbitragePools,
+        constraint = swap_two.token_owner_account_b.key() == swap_one.token_owner_account_b.key() @ ErrorCode::InvalidArbitragePools,
+        constraint = swap_two.token_authority.key() == swap_one.token_authority.key() @ ErrorCode::InvalidArbitragePools,
+    )]
+    pub swap_two: Swap<'info>,
+}
+
+/*
+  Swaps `amount` of the profit token into the other token in the first whirlpool, then
+  everything received back into the profit token in the second whirlpool. Neither swap has a
+  price limit or threshold, the only bound is the net profit in the profit token.
+*/
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, ArbTwoPools<'info>>,
+    amount: u64,
+    profit_in_a: bool,
+    min_profit: u64,
+    remaining_accounts_split: u8,
+) -> Result<()> {
+    let remaining_accounts_split = remaining_accounts_split as usize;
+    if remaining_accounts_split > ctx.remaining_accounts.len() {
+        return Err(ErrorCode::RemainingAccountsMissing.into());
+    }
+    let (remaining_accounts_one, remaining_accounts_two) =
+        ctx.remaining_accounts.split_at(remaining_accounts_split);
+
+    let balance_profit_token = owner_balance(&ctx.accounts.swap_one, profit_in_a);
+    let balance_other_token = owner_balance(&ctx.accounts.swap_one, !profit_in_a);
+
+    let swap_one = &mut ctx.accounts.swap_one;
+    swap_with_tick_arrays(
+        swap_one,
+        remaining_accounts_one,
+        SwapParams {
+            amount,
+            sqrt_price_limit: 0,
+            amount_specified_is_input: true,
+            a_to_b: profit_in_a,
+        },
+        0,
+        SwapOptions::default(),
+    )?;
+    swap_one.token_owner_account_a.reload()?;
+    swap_one.token_owner_account_b.reload()?;
+    let amount_other_token = owner_balance(swap_one, !profit_in_a) - balance_other_token;
+
+    let swap_two = &mut ctx.accounts.swap_two;
+    swap_two.token_owner_account_a.reload()?;
+    swap_two.token_owner_account_b.reload()?;
+    swap_with_tick_arrays(
+        swap_two,
+        remaining_accounts_two,
+        SwapParams {
+            amount: amount_other_token,
+            sqrt_price_limit: 0,
+            amount_specified_is_input: true,
+            a_to_b: !profit_in_a,
+        },
+        0,
+        SwapOptions::default(),
+    )?;
+    swap_two.token_owner_account_a.reload()?;
+    swap_two.token_owner_account_b.reload()?;
+
+    let min_balance_profit_token = balance_profit_token
+        .checked_add(min_profit)
+        .ok_or(ErrorCode::ArbitrageProfitBelowMinimum)?;
+    if owner_balance(swap_two, profit_in_a) < min_balance_profit_token {
+        return Err(ErrorCode::ArbitrageProfitBelowMinimum.into());
+    }
+
+    Ok(())
+}
+
+fn owner_balance(swap: &Swap, token_a: bool) -> u64 {
+    if token_a {
+        swap.token_owner_account_a.amount
+    } else {
+        swap.token_owner_account_b.amount
+    }
+}
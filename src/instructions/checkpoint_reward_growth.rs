@@ -0,0 +1,43 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    events::RewardGrowthCheckpointed,
+    manager::whirlpool_manager::next_whirlpool_reward_infos,
+    state::{Whirlpool, NUM_REWARDS},
+    util::to_timestamp_u64,
+};
+
+#[derive(Accounts)]
+pub struct CheckpointRewardGrowth<'info> {
+    #[account(mut)]
+    pub whirlpool: Account<'info, Whirlpool>,
+}
+
+/*
+  Permissionless: brings the reward growth globals of the Whirlpool up to date and emits them,
+  giving emission accounting regular on-chain checkpoints while no liquidity events occur.
+*/
+pub fn handler(ctx: Context<CheckpointRewardGrowth>) -> Result<()> {
+    let clock = Clock::get()?;
+    let timestamp = to_timestamp_u64(clock.unix_timestamp)?;
+
+    let whirlpool = &mut ctx.accounts.whirlpool;
+    let reward_infos = next_whirlpool_reward_infos(whirlpool, timestamp)?;
+    whirlpool.update_rewards(reward_infos, timestamp);
+
+    let mut reward_growth_globals_x64 = [0; NUM_REWARDS];
+    for (growth, reward_info) in reward_growth_globals_x64
+        .iter_mut()
+        .zip(reward_infos.iter())
+    {
+        *growth = reward_info.growth_global_x64;
+    }
+
+    emit!(RewardGrowthCheckpointed {
+        whirlpool: whirlpool.key(),
+        timestamp,
+        reward_growth_globals_x64,
+    });
+
+    Ok(())
+}
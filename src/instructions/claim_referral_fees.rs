@@ -0,0 +1,62 @@
+use crate::{
+    state::*,
+    util::{transfer_from_vault_to_owner, verify_destination_not_frozen},
+};
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount};
+
+#[derive(Accounts)]
+pub struct ClaimReferralFees<'info> {
+    #[account(mut)]
+    pub whirlpool: Box<Account<'info, Whirlpool>>,
+
+    pub referrer: Signer<'info>,
+
+    #[account(mut, has_one = whirlpool, has_one = referrer)]
+    pub referral_earnings: Box<Account<'info, ReferralEarnings>>,
+
+    #[account(mut, address = whirlpool.token_vault_a)]
+    pub token_vault_a: Account<'info, TokenAccount>,
+
+    #[account(mut, address = whirlpool.token_vault_b)]
+    pub token_vault_b: Account<'info, TokenAccount>,
+
+    #[account(mut, constraint = token_destination_a.mint == whirlpool.token_mint_a)]
+    pub token_destination_a: Account<'info, TokenAccount>,
+
+    #[account(mut, constraint = token_destination_b.mint == whirlpool.token_mint_b)]
+    pub token_destination_b: Account<'info, TokenAccount>,
+
+    #[account(address = token::ID)]
+    pub token_program: Program<'info, Token>,
+}
+
+pub fn handler(ctx: Context<ClaimReferralFees>) -> Result<()> {
+    verify_destination_not_frozen(&ctx.accounts.token_destination_a)?;
+    verify_destination_not_frozen(&ctx.accounts.token_destination_b)?;
+
+    let whirlpool = &mut ctx.accounts.whirlpool;
+    let referral_earnings = &mut ctx.accounts.referral_earnings;
+    let fee_owed_a = whirlpool.to_token_amount(referral_earnings.fee_owed_a, true, false)?;
+    let fee_owed_b = whirlpool.to_token_amount(referral_earnings.fee_owed_b, false, false)?;
+
+    transfer_from_vault_to_owner(
+        whirlpool,
+        &ctx.accounts.token_vault_a,
+        &ctx.accounts.token_destination_a,
+        &ctx.accounts.token_program,
+        fee_owed_a,
+    )?;
+
+    transfer_from_vault_to_owner(
+        whirlpool,
+        &ctx.accounts.token_vault_b,
+        &ctx.accounts.token_destination_b,
+        &ctx.accounts.token_program,
+        fee_owed_b,
+    )?;
+
+    whirlpool.record_vault_withdrawals(fee_owed_a, fee_owed_b);
+    referral_earnings.reset_fees_owed();
+    Ok(())
+}
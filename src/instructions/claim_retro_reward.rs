@@ -0,0 +1,74 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount};
+
+use crate::{
+    errors::ErrorCode,
+    state::{RetroRewardClaim, RetroRewardDistributor, Whirlpool},
+    util::{retro_reward_leaf, transfer_from_vault_to_owner, verify_merkle_proof},
+};
+
+#[derive(Accounts)]
+pub struct ClaimRetroReward<'info> {
+    pub whirlpool: Box<Account<'info, Whirlpool>>,
+
+    #[account(mut, has_one = whirlpool, has_one = vault)]
+    pub retro_reward: Box<Account<'info, RetroRewardDistributor>>,
+
+    #[account(mut)]
+    pub vault: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub claimant: Signer<'info>,
+
+    #[account(mut, constraint = claimant_token_account.mint == retro_reward.mint)]
+    pub claimant_token_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+      init_if_needed,
+      payer = claimant,
+      seeds = [
+        b"retro_reward_claim".as_ref(),
+        retro_reward.key().as_ref(),
+        claimant.key().as_ref()
+      ],
+      bump,
+      space = RetroRewardClaim::LEN)]
+    pub claim: Box<Account<'info, RetroRewardClaim>>,
+
+    #[account(address = token::ID)]
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+/*
+  Pays a claimant the part of their cumulative retroactive reward not claimed yet. The claim
+  account is created on the first claim and tracks the cumulative amount claimed, so the same
+  leaf can never be claimed twice, even across merkle root updates.
+*/
+pub fn handler(
+    ctx: Context<ClaimRetroReward>,
+    cumulative_amount: u64,
+    proof: Vec<[u8; 32]>,
+) -> Result<()> {
+    let leaf = retro_reward_leaf(
+        &ctx.accounts.retro_reward.key(),
+        &ctx.accounts.claimant.key(),
+        cumulative_amount,
+    );
+    if !verify_merkle_proof(&proof, &ctx.accounts.retro_reward.merkle_root, leaf) {
+        return Err(ErrorCode::InvalidMerkleProof.into());
+    }
+
+    let claim = &mut ctx.accounts.claim;
+    claim.distributor = ctx.accounts.retro_reward.key();
+    claim.claimant = ctx.accounts.claimant.key();
+    let amount = ctx.accounts.retro_reward.claim(claim, cumulative_amount)?;
+
+    transfer_from_vault_to_owner(
+        &ctx.accounts.whirlpool,
+        &ctx.accounts.vault,
+        &ctx.accounts.claimant_token_account,
+        &ctx.accounts.token_program,
+        amount,
+    )
+}
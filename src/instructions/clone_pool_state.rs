@@ -0,0 +1,115 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Mint, Token, TokenAccount};
+
+use crate::errors::ErrorCode;
+use crate::state::*;
+use crate::util::{to_timestamp_u64, verify_freeze_authority_allowed};
+
+#[derive(Accounts)]
+pub struct ClonePoolState<'info> {
+    pub whirlpools_config: Box<Account<'info, WhirlpoolsConfig>>,
+
+    pub source_whirlpool: Box<Account<'info, Whirlpool>>,
+
+    pub token_mint_a: Account<'info, Mint>,
+    pub token_mint_b: Account<'info, Mint>,
+
+    #[account(mut)]
+    pub funder: Signer<'info>,
+
+    #[account(
+        address = whirlpools_config.fee_authority,
+        constraint = !whirlpools_config.admin_frozen @ ErrorCode::AdminParametersFrozen,
+    )]
+    pub fee_authority: Signer<'info>,
+
+    #[account(init,
+      seeds = [
+        b"whirlpool".as_ref(),
+        whirlpools_config.key().as_ref(),
+        token_mint_a.key().as_ref(),
+        token_mint_b.key().as_ref(),
+        source_whirlpool.tick_spacing.to_le_bytes().as_ref()
+      ],
+      bump,
+      payer = funder,
+      space = Whirlpool::LEN)]
+    pub whirlpool: Box<Account<'info, Whirlpool>>,
+
+    #[account(init,
+      payer = funder,
+      token::mint = token_mint_a,
+      token::authority = whirlpool)]
+    pub token_vault_a: Box<Account<'info, TokenAccount>>,
+
+    #[account(init,
+      payer = funder,
+      token::mint = token_mint_b,
+      token::authority = whirlpool)]
+    pub token_vault_b: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut, seeds = [b"protocol_stats".as_ref()], bump)]
+    pub protocol_stats: Box<Account<'info, ProtocolStats>>,
+
+    #[account(address = token::ID)]
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+/*
+  Creates a Whirlpool with the price, fee growth and rewards of an existing Whirlpool, so staging
+  environments can mirror live pools without replaying their history. The clone starts without
+  liquidity, and its reward vaults are passed as remaining accounts since the source's vaults
+  belong to the source. The token mints are validated like for any new pool, so the token badges
+  of mints with a freeze authority follow the reward vaults in the remaining accounts.
+*/
+pub fn handler<'info>(ctx: Context<'_, '_, 'info, 'info, ClonePoolState<'info>>) -> Result<()> {
+    let whirlpools_config = ctx.accounts.whirlpools_config.key();
+    verify_freeze_authority_allowed(
+        &whirlpools_config,
+        &ctx.accounts.token_mint_a,
+        ctx.remaining_accounts,
+    )?;
+    verify_freeze_authority_allowed(
+        &whirlpools_config,
+        &ctx.accounts.token_mint_b,
+        ctx.remaining_accounts,
+    )?;
+
+    let source = &ctx.accounts.source_whirlpool;
+    let whirlpool_key = ctx.accounts.whirlpool.key();
+
+    let mut reward_vaults = Vec::with_capacity(NUM_REWARDS);
+    for (reward_info, vault_info) in source
+        .reward_infos
+        .iter()
+        .filter(|reward_info| reward_info.initialized())
+        .zip(ctx.remaining_accounts.iter())
+    {
+        let vault = Account::<TokenAccount>::try_from(vault_info)?;
+        if vault.mint != reward_info.mint || vault.owner != whirlpool_key {
+            return Err(ErrorCode::InvalidRewardVault.into());
+        }
+        reward_vaults.push(vault.key());
+    }
+
+    ctx.accounts.whirlpool.initialize(
+        &ctx.accounts.whirlpools_config,
+        ctx.bumps.whirlpool,
+        source.tick_spacing,
+        source.sqrt_price,
+        source.effective_fee_rate(),
+        ctx.accounts.token_mint_a.key(),
+        ctx.accounts.token_vault_a.key(),
+        ctx.accounts.token_mint_b.key(),
+        ctx.accounts.token_vault_b.key(),
+    )?;
+
+    ctx.accounts.protocol_stats.record_pool();
+
+    let timestamp = to_timestamp_u64(Clock::get()?.unix_timestamp)?;
+    ctx.accounts
+        .whirlpool
+        .clone_state_from(source, &reward_vaults, timestamp)
+}
@@ -12,7 +12,10 @@ pub struct ClosePosition<'info> {
     #[account(mut)]
     pub receiver: UncheckedAccount<'info>,
 
-    #[account(mut, close = receiver)]
+    #[account(mut,
+        close = receiver,
+        constraint = !position.frozen @ ErrorCode::PositionFrozen
+    )]
     pub position: Account<'info, Position>,
 
     #[account(mut, address = position.position_mint)]
@@ -25,6 +28,9 @@ pub struct ClosePosition<'info> {
 
     #[account(address = token::ID)]
     pub token_program: Program<'info, Token>,
+
+    #[account(mut, address = position.whirlpool)]
+    pub whirlpool: Box<Account<'info, Whirlpool>>,
 }
 
 pub fn handler(ctx: Context<ClosePosition>) -> Result<()> {
@@ -36,6 +42,7 @@ pub fn handler(ctx: Context<ClosePosition>) -> Result<()> {
     if !Position::is_position_empty(&ctx.accounts.position) {
         return Err(ErrorCode::ClosePositionNotEmpty.into());
     }
+    ctx.accounts.whirlpool.remove_open_position();
 
     burn_and_close_user_position_token(
         &ctx.accounts.position_authority,
@@ -2,17 +2,25 @@ use anchor_lang::prelude::*;
 use anchor_spl::token::{self, Token, TokenAccount};
 
 use crate::{
+    errors::ErrorCode,
     state::*,
-    util::{transfer_from_vault_to_owner, verify_position_authority},
+    util::{
+        load_collect_routing, transfer_from_vault_to_owner, verify_collect_destination,
+        verify_destination_not_frozen, verify_position_or_session_authority,
+    },
 };
 
 #[derive(Accounts)]
 pub struct CollectFees<'info> {
+    #[account(mut)]
     pub whirlpool: Box<Account<'info, Whirlpool>>,
 
     pub position_authority: Signer<'info>,
 
-    #[account(mut, has_one = whirlpool)]
+    #[account(mut,
+        has_one = whirlpool,
+        constraint = !position.frozen @ ErrorCode::PositionFrozen
+    )]
     pub position: Box<Account<'info, Position>>,
     #[account(
         constraint = position_token_account.mint == position.position_mint,
@@ -34,17 +42,53 @@ pub struct CollectFees<'info> {
     pub token_program: Program<'info, Token>,
 }
 
-pub fn handler(ctx: Context<CollectFees>) -> Result<()> {
-    verify_position_authority(
+pub fn handler<'info>(ctx: Context<'_, '_, 'info, 'info, CollectFees<'info>>) -> Result<()> {
+    let is_session = verify_position_or_session_authority(
         &ctx.accounts.position_token_account,
         &ctx.accounts.position_authority,
+        ctx.remaining_accounts,
+        SESSION_SCOPE_COLLECT_FEES,
+    )?;
+
+    // A session key may only harvest into the token accounts of the owed destination, which is the
+    // position owner unless redirected.
+    let owner = ctx
+        .accounts
+        .position
+        .owed_destination_or(ctx.accounts.position_token_account.owner);
+    // The owner's collect routing may follow the session authority. Routed mints must be harvested
+    // into their routed destination, for sessions and the position authority alike.
+    let collect_routing = load_collect_routing(
+        &ctx.accounts.position_token_account.owner,
+        ctx.remaining_accounts.get(is_session as usize),
+    )?;
+    verify_collect_destination(
+        &ctx.accounts.token_owner_account_a,
+        &owner,
+        collect_routing.as_ref(),
+        is_session,
+    )?;
+    verify_collect_destination(
+        &ctx.accounts.token_owner_account_b,
+        &owner,
+        collect_routing.as_ref(),
+        is_session,
     )?;
 
+    verify_destination_not_frozen(&ctx.accounts.token_owner_account_a)?;
+    verify_destination_not_frozen(&ctx.accounts.token_owner_account_b)?;
+
     let position = &mut ctx.accounts.position;
 
     // Store the fees owed to use as transfer amounts.
-    let fee_owed_a = position.fee_owed_a;
-    let fee_owed_b = position.fee_owed_b;
+    let fee_owed_a = ctx
+        .accounts
+        .whirlpool
+        .to_token_amount(position.fee_owed_a, true, false)?;
+    let fee_owed_b = ctx
+        .accounts
+        .whirlpool
+        .to_token_amount(position.fee_owed_b, false, false)?;
 
     position.reset_fees_owed();
 
@@ -64,5 +108,116 @@ pub fn handler(ctx: Context<CollectFees>) -> Result<()> {
         fee_owed_b,
     )?;
 
+    ctx.accounts
+        .whirlpool
+        .record_vault_withdrawals(fee_owed_a, fee_owed_b);
     Ok(())
 }
+
+#[cfg(test)]
+mod collect_fees_tests {
+    use super::*;
+    use crate::testing::*;
+    use crate::util::test_utils::*;
+
+    /// Collect the fees of a position as its owner, with the collect routing of the owner routing
+    /// token B, passed to the collect if `pass_collect_routing`. Returns the account infos of the
+    /// instruction and its result.
+    fn collect_as_owner(
+        routed_destination_b: Option<Pubkey>,
+        destination_b: Pubkey,
+        pass_collect_routing: bool,
+    ) -> (&'static [AccountInfo<'static>], Result<()>) {
+        let fixture = instruction_test_whirlpool(64, 3_000, 0);
+        let whirlpool_key = fixture.find_address().0;
+        let whirlpool = fixture.build();
+
+        let owner = Pubkey::new_unique();
+        let position_mint = Pubkey::new_unique();
+        let (position_key, mut position) = position(whirlpool_key, position_mint, owner, 0, 128);
+        position.fee_owed_a = 1_000;
+        position.fee_owed_b = 2_000;
+
+        let mut collect_routing = CollectRouting::default();
+        collect_routing.initialize(owner);
+        if let Some(destination) = routed_destination_b {
+            collect_routing
+                .update_route(0, fixture.token_mint_b, destination)
+                .unwrap();
+        }
+
+        let account_infos = leak_account_infos([
+            fixture_account_info(FixtureAccount::from_account(whirlpool_key, &whirlpool)),
+            signer_account_info(owner),
+            fixture_account_info(FixtureAccount::from_account(position_key, &position)),
+            token_account_info(Pubkey::new_unique(), position_mint, owner, 1),
+            token_account_info(Pubkey::new_unique(), fixture.token_mint_a, owner, 0),
+            token_account_info(
+                fixture.token_vault_a,
+                fixture.token_mint_a,
+                whirlpool_key,
+                1_000_000,
+            ),
+            token_account_info(destination_b, fixture.token_mint_b, owner, 0),
+            token_account_info(
+                fixture.token_vault_b,
+                fixture.token_mint_b,
+                whirlpool_key,
+                1_000_000,
+            ),
+            program_account_info(token::ID),
+        ]);
+        let remaining_accounts = if pass_collect_routing {
+            leak_account_infos([fixture_account_info(FixtureAccount::from_account(
+                CollectRouting::address(&owner),
+                &collect_routing,
+            ))])
+        } else {
+            &[]
+        };
+
+        let mut accounts = instruction_accounts::<CollectFees>(account_infos);
+        let result = handler(instruction_context(&mut accounts, remaining_accounts));
+        (account_infos, result)
+    }
+
+    #[test]
+    fn test_owner_collects_into_routed_destination() {
+        let destination_b = Pubkey::new_unique();
+        let (account_infos, result) = collect_as_owner(Some(destination_b), destination_b, true);
+
+        assert!(result.is_ok());
+        assert_eq!(token_amount(&account_infos[4]), 1_000);
+        assert_eq!(token_amount(&account_infos[6]), 2_000);
+    }
+
+    #[test]
+    fn test_owner_collect_rejects_unrouted_destination() {
+        let (account_infos, result) =
+            collect_as_owner(Some(Pubkey::new_unique()), Pubkey::new_unique(), true);
+
+        assert_eq!(
+            result.unwrap_err(),
+            anchor_lang::error::Error::from(ErrorCode::CollectRouteMismatch)
+        );
+        assert_eq!(token_amount(&account_infos[6]), 0);
+    }
+
+    #[test]
+    fn test_owner_collects_unrouted_mint_anywhere() {
+        let (account_infos, result) = collect_as_owner(None, Pubkey::new_unique(), true);
+
+        assert!(result.is_ok());
+        assert_eq!(token_amount(&account_infos[6]), 2_000);
+    }
+
+    #[test]
+    fn test_owner_collect_without_routing_is_not_routed() {
+        // The routing is opt-in per collect, a collect without it is not bound by it.
+        let (account_infos, result) =
+            collect_as_owner(Some(Pubkey::new_unique()), Pubkey::new_unique(), false);
+
+        assert!(result.is_ok());
+        assert_eq!(token_amount(&account_infos[6]), 2_000);
+    }
+}
@@ -1,4 +1,8 @@
-use crate::{state::*, util::transfer_from_vault_to_owner};
+use crate::{
+    errors::ErrorCode,
+    state::*,
+    util::{transfer_from_vault_to_owner, verify_destination_not_frozen},
+};
 use anchor_lang::prelude::*;
 use anchor_spl::token::{self, Token, TokenAccount};
 
@@ -9,7 +13,10 @@ pub struct CollectProtocolFees<'info> {
     #[account(mut, has_one = whirlpools_config)]
     pub whirlpool: Box<Account<'info, Whirlpool>>,
 
-    #[account(address = whirlpools_config.collect_protocol_fees_authority)]
+    #[account(
+        address = whirlpools_config.collect_protocol_fees_authority,
+        constraint = !whirlpools_config.admin_frozen @ ErrorCode::AdminParametersFrozen,
+    )]
     pub collect_protocol_fees_authority: Signer<'info>,
 
     #[account(mut, address = whirlpool.token_vault_a)]
@@ -29,14 +36,21 @@ pub struct CollectProtocolFees<'info> {
 }
 
 pub fn handler(ctx: Context<CollectProtocolFees>) -> Result<()> {
-    let whirlpool = &ctx.accounts.whirlpool;
+    verify_destination_not_frozen(&ctx.accounts.token_destination_a)?;
+    verify_destination_not_frozen(&ctx.accounts.token_destination_b)?;
+
+    let whirlpool = &mut ctx.accounts.whirlpool;
+    let protocol_fee_owed_a =
+        whirlpool.to_token_amount(whirlpool.protocol_fee_owed_a, true, false)?;
+    let protocol_fee_owed_b =
+        whirlpool.to_token_amount(whirlpool.protocol_fee_owed_b, false, false)?;
 
     transfer_from_vault_to_owner(
         whirlpool,
         &ctx.accounts.token_vault_a,
         &ctx.accounts.token_destination_a,
         &ctx.accounts.token_program,
-        whirlpool.protocol_fee_owed_a,
+        protocol_fee_owed_a,
     )?;
 
     transfer_from_vault_to_owner(
@@ -44,8 +58,9 @@ pub fn handler(ctx: Context<CollectProtocolFees>) -> Result<()> {
         &ctx.accounts.token_vault_b,
         &ctx.accounts.token_destination_b,
         &ctx.accounts.token_program,
-        whirlpool.protocol_fee_owed_b,
+        protocol_fee_owed_b,
     )?;
 
-    Ok(ctx.accounts.whirlpool.reset_protocol_fees_owed())
+    whirlpool.record_vault_withdrawals(protocol_fee_owed_a, protocol_fee_owed_b);
+    Ok(whirlpool.reset_protocol_fees_owed())
 }
@@ -2,8 +2,14 @@ use anchor_lang::prelude::*;
 use anchor_spl::token::{self, Token, TokenAccount};
 
 use crate::{
+    errors::ErrorCode,
+    events::RewardShortfall,
     state::*,
-    util::{transfer_from_vault_to_owner, verify_position_authority},
+    util::{
+        invoke_reward_escrow, load_collect_routing, transfer_from_vault_to_owner,
+        verify_collect_destination, verify_destination_not_frozen,
+        verify_position_or_session_authority,
+    },
 };
 
 #[derive(Accounts)]
@@ -13,7 +19,10 @@ pub struct CollectReward<'info> {
 
     pub position_authority: Signer<'info>,
 
-    #[account(mut, has_one = whirlpool)]
+    #[account(mut,
+        has_one = whirlpool,
+        constraint = !position.frozen @ ErrorCode::PositionFrozen
+    )]
     pub position: Box<Account<'info, Position>>,
     #[account(
         constraint = position_token_account.mint == position.position_mint,
@@ -37,7 +46,11 @@ pub struct CollectReward<'info> {
 ///
 /// If the Whirlpool reward vault does not have enough tokens, the maximum number of available
 /// tokens will be debited to the user. The unharvested amount remains tracked, and it can be
-/// harvested in the future.
+/// harvested in the future. A `RewardShortfall` event is emitted so harvesters can tell an
+/// under-funded vault from a completed harvest.
+///
+/// If the reward has an escrow program, the escrow is first asked to release the amount the
+/// vault is short of into the vault.
 ///
 /// # Parameters
 /// - `reward_index` - The reward to harvest. Acceptable values are 0, 1, and 2.
@@ -46,14 +59,57 @@ pub struct CollectReward<'info> {
 /// - `Ok`: Reward tokens at the specified reward index have been successfully harvested
 /// - `Err`: `RewardNotInitialized` if the specified reward has not been initialized
 ///          `InvalidRewardIndex` if the reward index is not 0, 1, or 2
-pub fn handler(ctx: Context<CollectReward>, reward_index: u8) -> Result<()> {
-    verify_position_authority(
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, CollectReward<'info>>,
+    reward_index: u8,
+) -> Result<()> {
+    let is_session = verify_position_or_session_authority(
         &ctx.accounts.position_token_account,
         &ctx.accounts.position_authority,
+        ctx.remaining_accounts,
+        SESSION_SCOPE_COLLECT_REWARD,
     )?;
 
+    // A session key may only harvest into the token account of the owed destination, which is the
+    // position owner unless redirected.
+    let owner = ctx
+        .accounts
+        .position
+        .owed_destination_or(ctx.accounts.position_token_account.owner);
+    // The owner's collect routing may follow the session authority. A routed reward mint must be
+    // harvested into its routed destination, for sessions and the position authority alike.
+    let collect_routing = load_collect_routing(
+        &ctx.accounts.position_token_account.owner,
+        ctx.remaining_accounts.get(is_session as usize),
+    )?;
+    verify_collect_destination(
+        &ctx.accounts.reward_owner_account,
+        &owner,
+        collect_routing.as_ref(),
+        is_session,
+    )?;
+
+    verify_destination_not_frozen(&ctx.accounts.reward_owner_account)?;
+
     let index = reward_index as usize;
 
+    let amount_owed = ctx.accounts.position.reward_infos[index].amount_owed;
+    let vault_amount = ctx.accounts.reward_vault.amount;
+    if ctx.accounts.whirlpool.has_reward_escrow_program(index) && amount_owed > vault_amount {
+        // The escrow program and its accounts follow the session authority and collect routing,
+        // if used.
+        let skipped = is_session as usize + collect_routing.is_some() as usize;
+        let escrow_accounts = &ctx.remaining_accounts[skipped..];
+        invoke_reward_escrow(
+            &ctx.accounts.whirlpool,
+            index,
+            &ctx.accounts.reward_vault.to_account_info(),
+            escrow_accounts,
+            amount_owed - vault_amount,
+        )?;
+        ctx.accounts.reward_vault.reload()?;
+    }
+
     let position = &mut ctx.accounts.position;
     let (transfer_amount, updated_amount_owed) = calculate_collect_reward(
         position.reward_infos[index],
@@ -61,6 +117,15 @@ pub fn handler(ctx: Context<CollectReward>, reward_index: u8) -> Result<()> {
     );
 
     position.update_reward_owed(index, updated_amount_owed);
+    if updated_amount_owed > 0 {
+        emit!(RewardShortfall {
+            whirlpool: ctx.accounts.whirlpool.key(),
+            position: position.key(),
+            reward_index,
+            amount_collected: transfer_amount,
+            amount_still_owed: updated_amount_owed,
+        });
+    }
 
     Ok(transfer_from_vault_to_owner(
         &ctx.accounts.whirlpool,
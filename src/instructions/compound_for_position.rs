@@ -0,0 +1,154 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount};
+
+use crate::errors::ErrorCode;
+use crate::manager::liquidity_manager::{
+    calculate_fee_and_reward_growths, calculate_liquidity_from_amounts,
+    calculate_liquidity_token_deltas, calculate_modify_liquidity, record_liquidity_rounding_dust,
+    sync_modify_liquidity_values,
+};
+use crate::math::convert_to_liquidity_delta;
+use crate::state::*;
+use crate::util::{
+    accrue_liquidity_time, record_liquidity_shift, to_timestamp_u64, transfer_from_vault_to_owner,
+};
+
+#[derive(Accounts)]
+pub struct CompoundForPosition<'info> {
+    #[account(mut)]
+    pub whirlpool: Box<Account<'info, Whirlpool>>,
+
+    pub keeper: Signer<'info>,
+
+    #[account(mut,
+        has_one = whirlpool,
+        constraint = !position.frozen @ ErrorCode::PositionFrozen
+    )]
+    pub position: Box<Account<'info, Position>>,
+    #[account(
+        constraint = position_token_account.mint == position.position_mint,
+        constraint = position_token_account.amount == 1
+    )]
+    pub position_token_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut, constraint = keeper_token_account_a.mint == whirlpool.token_mint_a)]
+    pub keeper_token_account_a: Box<Account<'info, TokenAccount>>,
+    #[account(mut, address = whirlpool.token_vault_a)]
+    pub token_vault_a: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut, constraint = keeper_token_account_b.mint == whirlpool.token_mint_b)]
+    pub keeper_token_account_b: Box<Account<'info, TokenAccount>>,
+    #[account(mut, address = whirlpool.token_vault_b)]
+    pub token_vault_b: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut, has_one = whirlpool)]
+    pub tick_array_lower: AccountLoader<'info, TickArray>,
+    #[account(mut, has_one = whirlpool)]
+    pub tick_array_upper: AccountLoader<'info, TickArray>,
+
+    #[account(address = token::ID)]
+    pub token_program: Program<'info, Token>,
+}
+
+/*
+  Reinvests the fees owed of a position that opted in to auto compounding. The fees are already
+  held by the vaults, so the largest liquidity they provide at the current price is added without
+  any transfer from the owner and paid out of the fees owed. What does not fit stays owed. The
+  keeper is only paid its tip, taken from the fees owed before compounding.
+*/
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, CompoundForPosition<'info>>,
+) -> Result<()> {
+    if !ctx.accounts.position.auto_compound {
+        return Err(ErrorCode::AutoCompoundDisabled.into());
+    }
+    ctx.accounts.whirlpool.verify_not_paused()?;
+    let clock = Clock::get()?;
+    let timestamp = to_timestamp_u64(clock.unix_timestamp)?;
+
+    // Liquidity time is accrued with the liquidity before the change.
+    accrue_liquidity_time(
+        ctx.remaining_accounts.first(),
+        &ctx.accounts.whirlpool,
+        &mut ctx.accounts.position,
+        &ctx.accounts.position_token_account.owner,
+        timestamp,
+    )?;
+
+    let whirlpool = &mut ctx.accounts.whirlpool;
+    let position = &mut ctx.accounts.position;
+    whirlpool.verify_tick_arrays_synced()?;
+
+    let (position_update, reward_infos) = calculate_fee_and_reward_growths(
+        whirlpool,
+        position,
+        &ctx.accounts.tick_array_lower,
+        &ctx.accounts.tick_array_upper,
+        timestamp,
+    )?;
+    whirlpool.update_rewards(reward_infos, timestamp);
+    position.update(&position_update);
+
+    let (tip_a, tip_b) = position.take_auto_compound_tip();
+
+    let liquidity_amount = calculate_liquidity_from_amounts(
+        whirlpool.tick_current_index,
+        whirlpool.sqrt_price,
+        position,
+        position.fee_owed_a,
+        position.fee_owed_b,
+    )?;
+    if liquidity_amount == 0 {
+        return Err(ErrorCode::LiquidityZero.into());
+    }
+    let liquidity_delta = convert_to_liquidity_delta(liquidity_amount, true)?;
+
+    let previous_liquidity = whirlpool.liquidity;
+    let update = calculate_modify_liquidity(
+        whirlpool,
+        position,
+        &ctx.accounts.tick_array_lower.load().unwrap(),
+        &ctx.accounts.tick_array_upper.load().unwrap(),
+        liquidity_delta,
+        timestamp,
+    )?;
+    sync_modify_liquidity_values(
+        whirlpool,
+        position,
+        &mut ctx.accounts.tick_array_lower.load_mut().unwrap(),
+        Some(&mut ctx.accounts.tick_array_upper.load_mut().unwrap()),
+        &update,
+        timestamp,
+    )?;
+    whirlpool.record_tick_updates(false);
+    record_liquidity_shift(whirlpool, previous_liquidity, clock.slot);
+
+    let (delta_a, delta_b) = calculate_liquidity_token_deltas(
+        whirlpool.tick_current_index,
+        whirlpool.sqrt_price,
+        position,
+        liquidity_delta,
+    )?;
+    record_liquidity_rounding_dust(whirlpool, position, liquidity_delta);
+    position.spend_fees_owed(delta_a, delta_b)?;
+
+    let tip_a = whirlpool.to_token_amount(tip_a, true, false)?;
+    let tip_b = whirlpool.to_token_amount(tip_b, false, false)?;
+    transfer_from_vault_to_owner(
+        whirlpool,
+        &ctx.accounts.token_vault_a,
+        &ctx.accounts.keeper_token_account_a,
+        &ctx.accounts.token_program,
+        tip_a,
+    )?;
+    transfer_from_vault_to_owner(
+        whirlpool,
+        &ctx.accounts.token_vault_b,
+        &ctx.accounts.keeper_token_account_b,
+        &ctx.accounts.token_program,
+        tip_b,
+    )?;
+
+    whirlpool.record_vault_withdrawals(tip_a, tip_b);
+    Ok(())
+}
@@ -0,0 +1,59 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::TokenAccount;
+
+use crate::errors::ErrorCode;
+use crate::state::*;
+use crate::util::to_timestamp_u64;
+
+#[derive(Accounts)]
+#[instruction(session_key: Pubkey)]
+pub struct CreateSessionAuthority<'info> {
+    #[account(mut)]
+    pub funder: Signer<'info>,
+
+    pub position_owner: Signer<'info>,
+
+    #[account(
+        constraint = position_token_account.amount == 1,
+        constraint = position_token_account.owner == position_owner.key() @ ErrorCode::MissingOrInvalidDelegate
+    )]
+    pub position_token_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(init,
+      payer = funder,
+      space = SessionAuthority::LEN,
+      seeds = [
+        b"session_authority".as_ref(),
+        position_token_account.mint.as_ref(),
+        session_key.as_ref()
+      ],
+      bump,
+    )]
+    pub session_authority: Account<'info, SessionAuthority>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/*
+  Authorizes a session key to act for the position owner in a limited set of handlers until the
+  expiry. The session stops being accepted once the position token changes owner.
+*/
+pub fn handler(
+    ctx: Context<CreateSessionAuthority>,
+    session_key: Pubkey,
+    expiry_timestamp: u64,
+    scopes: u8,
+) -> Result<()> {
+    let clock = Clock::get()?;
+    let timestamp = to_timestamp_u64(clock.unix_timestamp)?;
+
+    ctx.accounts.session_authority.initialize(
+        ctx.accounts.position_token_account.mint,
+        ctx.accounts.position_owner.key(),
+        session_key,
+        expiry_timestamp,
+        scopes,
+        ctx.bumps.session_authority,
+        timestamp,
+    )
+}
@@ -0,0 +1,258 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount};
+
+use crate::errors::ErrorCode;
+use crate::state::{Position, Whirlpool};
+use crate::util::{
+    accrue_liquidity_time, fail_with_quote_hint, to_timestamp_u64,
+    verify_position_or_withdraw_authority,
+};
+
+use super::swap_v2::load_tick_array;
+use super::{decrease_position_liquidity, DecreaseLiquidityParams, LiquidityTokenAccounts};
+
+// Remaining accounts per position: the position (writable), its position token account, its
+// lower and upper tick arrays (writable) and the liquidity time accumulator of its owner
+// (writable), or the program id in place of the accumulator to forfeit the liquidity time.
+pub const PRO_RATA_ACCOUNTS_PER_POSITION: usize = 5;
+
+// Basis point denominator of the pro rata rate.
+pub const MAX_PRO_RATA_RATE: u16 = 10_000;
+
+/// Decrease liquidity accounts without a position, the positions are passed as remaining accounts
+/// in groups of `PRO_RATA_ACCOUNTS_PER_POSITION`.
+#[derive(Accounts)]
+pub struct DecreaseBundleLiquidityProRata<'info> {
+    #[account(mut)]
+    pub whirlpool: Box<Account<'info, Whirlpool>>,
+
+    #[account(address = token::ID)]
+    pub token_program: Program<'info, Token>,
+
+    pub position_authority: Signer<'info>,
+
+    #[account(mut, constraint = token_owner_account_a.mint == whirlpool.token_mint_a)]
+    pub token_owner_account_a: Box<Account<'info, TokenAccount>>,
+    #[account(mut, constraint = token_owner_account_b.mint == whirlpool.token_mint_b)]
+    pub token_owner_account_b: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut, constraint = token_vault_a.key() == whirlpool.token_vault_a)]
+    pub token_vault_a: Box<Account<'info, TokenAccount>>,
+    #[account(mut, constraint = token_vault_b.key() == whirlpool.token_vault_b)]
+    pub token_vault_b: Box<Account<'info, TokenAccount>>,
+}
+
+/*
+  Removes the same share of liquidity from every position of a bundle in one instruction, so a
+  ladder of positions can be de-risked at once. The authority has to be the position authority or
+  the withdraw authority of each position, as in decrease_liquidity. Positions whose share rounds
+  down to zero liquidity are skipped. The slippage bounds apply to the total withdrawn.
+*/
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, DecreaseBundleLiquidityProRata<'info>>,
+    bps: u16,
+    token_min_a: u64,
+    token_min_b: u64,
+) -> Result<()> {
+    if bps == 0 || bps > MAX_PRO_RATA_RATE {
+        return Err(ErrorCode::InvalidProRataRate.into());
+    }
+    let remaining_accounts = ctx.remaining_accounts;
+    if remaining_accounts.is_empty()
+        || !remaining_accounts
+            .len()
+            .is_multiple_of(PRO_RATA_ACCOUNTS_PER_POSITION)
+    {
+        return Err(ErrorCode::RemainingAccountsMissing.into());
+    }
+
+    let timestamp = to_timestamp_u64(Clock::get()?.unix_timestamp)?;
+    let whirlpool_key = ctx.accounts.whirlpool.key();
+    let balance_a = ctx.accounts.token_owner_account_a.amount;
+    let balance_b = ctx.accounts.token_owner_account_b.amount;
+
+    for position_accounts in remaining_accounts.chunks_exact(PRO_RATA_ACCOUNTS_PER_POSITION) {
+        let mut position = Account::<Position>::try_from(&position_accounts[0])?;
+        if !position.describe().belongs_to(&whirlpool_key) {
+            return Err(ErrorCode::PositionMismatch.into());
+        }
+        position.verify_not_frozen()?;
+
+        let position_token_account = Account::<TokenAccount>::try_from(&position_accounts[1])?;
+        if position_token_account.mint != position.position_mint
+            || position_token_account.amount != 1
+        {
+            return Err(ErrorCode::InvalidPositionTokenAmount.into());
+        }
+        let is_withdraw_authority = verify_position_or_withdraw_authority(
+            &position_token_account,
+            &ctx.accounts.position_authority,
+            &position,
+        )?;
+        if is_withdraw_authority
+            && (ctx.accounts.token_owner_account_a.owner != position.withdraw_destination
+                || ctx.accounts.token_owner_account_b.owner != position.withdraw_destination)
+        {
+            return Err(ErrorCode::InvalidWithdrawDestination.into());
+        }
+
+        let tick_array_lower = load_tick_array(&position_accounts[2], &whirlpool_key)?;
+        let tick_array_upper = load_tick_array(&position_accounts[3], &whirlpool_key)?;
+
+        let liquidity_amount = pro_rata_liquidity(position.liquidity, bps);
+        if liquidity_amount == 0 {
+            continue;
+        }
+
+        // Liquidity time is accrued with the liquidity before the change.
+        let accumulator_info = Some(&position_accounts[4]).filter(|info| info.key() != crate::ID);
+        accrue_liquidity_time(
+            accumulator_info,
+            &ctx.accounts.whirlpool,
+            &mut position,
+            &position_token_account.owner,
+            timestamp,
+        )?;
+
+        decrease_position_liquidity(
+            &mut ctx.accounts.whirlpool,
+            &mut position,
+            &tick_array_lower,
+            &tick_array_upper,
+            LiquidityTokenAccounts {
+                token_owner_account_a: &ctx.accounts.token_owner_account_a,
+                token_owner_account_b: &ctx.accounts.token_owner_account_b,
+                token_vault_a: &ctx.accounts.token_vault_a,
+                token_vault_b: &ctx.accounts.token_vault_b,
+                token_program: &ctx.accounts.token_program,
+            },
+            DecreaseLiquidityParams {
+                liquidity_amount,
+                token_min_a: 0,
+                token_min_b: 0,
+            },
+        )?;
+        position.exit(&crate::ID)?;
+    }
+
+    ctx.accounts.token_owner_account_a.reload()?;
+    ctx.accounts.token_owner_account_b.reload()?;
+    let amount_a = ctx.accounts.token_owner_account_a.amount - balance_a;
+    let amount_b = ctx.accounts.token_owner_account_b.amount - balance_b;
+    if amount_a < token_min_a || amount_b < token_min_b {
+        return fail_with_quote_hint(ErrorCode::TokenMinSubceeded, amount_a, amount_b);
+    }
+
+    Ok(())
+}
+
+/// The liquidity removed from a position by a pro rata decrease of `bps` basis points, rounded
+/// down. Splitting off the remainder keeps the product within u128 for any liquidity.
+pub fn pro_rata_liquidity(liquidity: u128, bps: u16) -> u128 {
+    let bps = bps as u128;
+    let denominator = MAX_PRO_RATA_RATE as u128;
+    liquidity / denominator * bps + liquidity % denominator * bps / denominator
+}
+
+#[cfg(test)]
+mod decrease_bundle_liquidity_pro_rata_tests {
+    use super::*;
+    use crate::state::LiquidityTimeAccumulator;
+    use crate::testing::*;
+    use crate::util::test_utils::*;
+
+    const TICK_SPACING: u16 = 64;
+
+    #[test]
+    fn test_withdraw_authority_accrues_liquidity_time() {
+        let fixture = instruction_test_whirlpool(TICK_SPACING, 3_000, 100);
+        let whirlpool_key = fixture.find_address().0;
+        let mut whirlpool = fixture.build();
+
+        let owner = Pubkey::new_unique();
+        let withdraw_authority = Pubkey::new_unique();
+        let withdraw_destination = Pubkey::new_unique();
+        let position_mint = Pubkey::new_unique();
+        let (position_key, mut position) = position(whirlpool_key, position_mint, owner, 0, 5696);
+        position.withdraw_authority = withdraw_authority;
+        position.withdraw_destination = withdraw_destination;
+        position.checkpoint_liquidity_time(TEST_UNIX_TIMESTAMP as u64 - 100);
+        let (lower_array_key, mut lower_array) = tick_array(whirlpool_key, 0);
+        let (upper_array_key, mut upper_array) = tick_array(whirlpool_key, 5632);
+        add_position_liquidity(&mut whirlpool, &mut position, 1_000_000).unwrap();
+        add_tick_liquidity(&mut lower_array, 0, TICK_SPACING, 1_000_000, false).unwrap();
+        add_tick_liquidity(&mut upper_array, 5696, TICK_SPACING, 1_000_000, true).unwrap();
+
+        let accumulator_key = Pubkey::new_unique();
+        let mut accumulator = LiquidityTimeAccumulator::default();
+        accumulator.initialize(whirlpool_key, owner);
+
+        let account_infos = leak_account_infos([
+            fixture_account_info(FixtureAccount::from_account(whirlpool_key, &whirlpool)),
+            program_account_info(token::ID),
+            signer_account_info(withdraw_authority),
+            token_account_info(
+                Pubkey::new_unique(),
+                fixture.token_mint_a,
+                withdraw_destination,
+                0,
+            ),
+            token_account_info(
+                Pubkey::new_unique(),
+                fixture.token_mint_b,
+                withdraw_destination,
+                0,
+            ),
+            token_account_info(
+                fixture.token_vault_a,
+                fixture.token_mint_a,
+                whirlpool_key,
+                1_000_000,
+            ),
+            token_account_info(
+                fixture.token_vault_b,
+                fixture.token_mint_b,
+                whirlpool_key,
+                1_000_000,
+            ),
+        ]);
+        let remaining_accounts = leak_account_infos([
+            fixture_account_info(FixtureAccount::from_account(position_key, &position)),
+            token_account_info(Pubkey::new_unique(), position_mint, owner, 1),
+            fixture_account_info(FixtureAccount::from_zero_copy(
+                lower_array_key,
+                &lower_array,
+            )),
+            fixture_account_info(FixtureAccount::from_zero_copy(
+                upper_array_key,
+                &upper_array,
+            )),
+            fixture_account_info(FixtureAccount::from_account(accumulator_key, &accumulator)),
+        ]);
+
+        let mut accounts = instruction_accounts::<DecreaseBundleLiquidityProRata>(account_infos);
+        handler(
+            instruction_context(&mut accounts, remaining_accounts),
+            5_000,
+            0,
+            0,
+        )
+        .unwrap();
+
+        let position: Position = read_account(&remaining_accounts[0]);
+        assert_eq!(position.liquidity, 500_000);
+        assert!(token_amount(&account_infos[3]) > 0);
+        assert!(token_amount(&account_infos[4]) > 0);
+        let accumulator: LiquidityTimeAccumulator = read_account(&remaining_accounts[4]);
+        assert_eq!(accumulator.liquidity_seconds, 100_000_000);
+    }
+
+    #[test]
+    fn test_pro_rata_liquidity() {
+        assert_eq!(pro_rata_liquidity(1_000_000, 2_500), 250_000);
+        assert_eq!(pro_rata_liquidity(1_000_000, MAX_PRO_RATA_RATE), 1_000_000);
+        // Rounds down, so a small position may not be decreased at all.
+        assert_eq!(pro_rata_liquidity(3, 3_333), 0);
+        assert_eq!(pro_rata_liquidity(u128::MAX, MAX_PRO_RATA_RATE), u128::MAX);
+    }
+}
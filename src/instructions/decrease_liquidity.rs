@@ -2,27 +2,94 @@ use anchor_lang::prelude::*;
 
 use crate::errors::ErrorCode;
 use crate::manager::liquidity_manager::{
-    calculate_liquidity_token_deltas, calculate_modify_liquidity, sync_modify_liquidity_values,
+    calculate_liquidity_token_deltas, calculate_modify_liquidity, record_liquidity_rounding_dust,
+    sync_modify_liquidity_values,
 };
 use crate::math::convert_to_liquidity_delta;
-use crate::util::{to_timestamp_u64, transfer_from_vault_to_owner, verify_position_authority};
+use crate::state::{Position, TickArray, Whirlpool};
+use crate::util::{
+    accrue_liquidity_time, fail_with_quote_hint, record_liquidity_shift, to_timestamp_u64,
+    transfer_from_vault_to_owner, verify_position_or_withdraw_authority,
+};
 
-use super::ModifyLiquidity;
+use super::{LiquidityTokenAccounts, ModifyLiquidity};
 
 /*
-  Removes liquidity from an existing Whirlpool Position.
+  Removes liquidity from an existing Whirlpool Position. The withdraw authority of the position may
+  also remove liquidity, into token accounts of the withdraw destination only.
 */
-pub fn handler(
-    ctx: Context<ModifyLiquidity>,
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, ModifyLiquidity<'info>>,
     liquidity_amount: u128,
     token_min_a: u64,
     token_min_b: u64,
 ) -> Result<()> {
-    verify_position_authority(
+    let is_withdraw_authority = verify_position_or_withdraw_authority(
         &ctx.accounts.position_token_account,
         &ctx.accounts.position_authority,
+        &ctx.accounts.position,
     )?;
+    if is_withdraw_authority {
+        let withdraw_destination = ctx.accounts.position.withdraw_destination;
+        if ctx.accounts.token_owner_account_a.owner != withdraw_destination
+            || ctx.accounts.token_owner_account_b.owner != withdraw_destination
+        {
+            return Err(ErrorCode::InvalidWithdrawDestination.into());
+        }
+    }
+
+    // Liquidity time is accrued with the liquidity before the change.
+    accrue_liquidity_time(
+        ctx.remaining_accounts.first(),
+        &ctx.accounts.whirlpool,
+        &mut ctx.accounts.position,
+        &ctx.accounts.position_token_account.owner,
+        to_timestamp_u64(Clock::get()?.unix_timestamp)?,
+    )?;
+
+    decrease_position_liquidity(
+        &mut ctx.accounts.whirlpool,
+        &mut ctx.accounts.position,
+        &ctx.accounts.tick_array_lower,
+        &ctx.accounts.tick_array_upper,
+        LiquidityTokenAccounts {
+            token_owner_account_a: &ctx.accounts.token_owner_account_a,
+            token_owner_account_b: &ctx.accounts.token_owner_account_b,
+            token_vault_a: &ctx.accounts.token_vault_a,
+            token_vault_b: &ctx.accounts.token_vault_b,
+            token_program: &ctx.accounts.token_program,
+        },
+        DecreaseLiquidityParams {
+            liquidity_amount,
+            token_min_a,
+            token_min_b,
+        },
+    )
+}
 
+/// Liquidity removed from a position and the fewest tokens the owner withdraws for it.
+#[derive(Clone, Copy)]
+pub struct DecreaseLiquidityParams {
+    pub liquidity_amount: u128,
+    pub token_min_a: u64,
+    pub token_min_b: u64,
+}
+
+/// Remove liquidity from a position and withdraw the tokens to the owner accounts. Shared by
+/// position token and protocol owned positions, the caller verifies the position authority.
+pub fn decrease_position_liquidity<'info>(
+    whirlpool: &mut Account<'info, Whirlpool>,
+    position: &mut Account<'info, Position>,
+    tick_array_lower: &AccountLoader<'info, TickArray>,
+    tick_array_upper: &AccountLoader<'info, TickArray>,
+    token_accounts: LiquidityTokenAccounts<'_, 'info>,
+    params: DecreaseLiquidityParams,
+) -> Result<()> {
+    let DecreaseLiquidityParams {
+        liquidity_amount,
+        token_min_a,
+        token_min_b,
+    } = params;
     let clock = Clock::get()?;
 
     if liquidity_amount == 0 {
@@ -31,52 +98,110 @@ pub fn handler(
     let liquidity_delta = convert_to_liquidity_delta(liquidity_amount, false)?;
     let timestamp = to_timestamp_u64(clock.unix_timestamp)?;
 
+    let next_liquidity = position.liquidity.saturating_sub(liquidity_amount);
+    if position.is_liquidity_locked(next_liquidity, timestamp) {
+        return Err(ErrorCode::PositionLiquidityLocked.into());
+    }
+    whirlpool.verify_liquidity_removal_cooldown(position, timestamp)?;
+
+    whirlpool.verify_tick_arrays_synced()?;
+
     let update = calculate_modify_liquidity(
-        &ctx.accounts.whirlpool,
-        &ctx.accounts.position,
-        &ctx.accounts.tick_array_lower.load().unwrap(),
-        &ctx.accounts.tick_array_upper.load().unwrap(),
+        whirlpool,
+        position,
+        &tick_array_lower.load().unwrap(),
+        &tick_array_upper.load().unwrap(),
         liquidity_delta,
         timestamp,
     )?;
 
+    let previous_liquidity = whirlpool.liquidity;
     sync_modify_liquidity_values(
-        &mut ctx.accounts.whirlpool,
-        &mut ctx.accounts.position,
-        &mut ctx.accounts.tick_array_lower.load_mut().unwrap(),
-        &mut ctx.accounts.tick_array_upper.load_mut().unwrap(),
-        update,
+        whirlpool,
+        position,
+        &mut tick_array_lower.load_mut().unwrap(),
+        Some(&mut tick_array_upper.load_mut().unwrap()),
+        &update,
         timestamp,
     )?;
+    whirlpool.record_tick_updates(false);
+    record_liquidity_shift(whirlpool, previous_liquidity, clock.slot);
 
+    withdraw_liquidity_delta(
+        whirlpool,
+        position,
+        token_accounts,
+        liquidity_delta,
+        token_min_a,
+        token_min_b,
+    )
+}
+
+/// Withdraw the tokens of a liquidity decrease that was applied to the position, its ticks and
+/// its Whirlpool.
+pub(crate) fn withdraw_liquidity_delta<'info>(
+    whirlpool: &mut Account<'info, Whirlpool>,
+    position: &Position,
+    token_accounts: LiquidityTokenAccounts<'_, 'info>,
+    liquidity_delta: i128,
+    token_min_a: u64,
+    token_min_b: u64,
+) -> Result<()> {
+    let LiquidityTokenAccounts {
+        token_owner_account_a,
+        token_owner_account_b,
+        token_vault_a,
+        token_vault_b,
+        token_program,
+    } = token_accounts;
     let (delta_a, delta_b) = calculate_liquidity_token_deltas(
-        ctx.accounts.whirlpool.tick_current_index,
-        ctx.accounts.whirlpool.sqrt_price,
-        &ctx.accounts.position,
+        whirlpool.tick_current_index,
+        whirlpool.sqrt_price,
+        position,
         liquidity_delta,
     )?;
+    record_liquidity_rounding_dust(whirlpool, position, liquidity_delta);
+
+    // The surcharge stays in the vaults, credited to the remaining in-range liquidity.
+    let (surcharge_a, surcharge_b) =
+        whirlpool.take_jit_surcharge(position, Clock::get()?.slot, delta_a, delta_b);
+    let delta_a = whirlpool.to_token_amount(delta_a - surcharge_a, true, false)?;
+    let delta_b = whirlpool.to_token_amount(delta_b - surcharge_b, false, false)?;
 
-    if delta_a < token_min_a {
-        return Err(ErrorCode::TokenMinSubceeded.into());
-    } else if delta_b < token_min_b {
-        return Err(ErrorCode::TokenMinSubceeded.into());
+    if delta_a < token_min_a || delta_b < token_min_b {
+        return fail_with_quote_hint(ErrorCode::TokenMinSubceeded, delta_a, delta_b);
     }
 
+    #[cfg(feature = "paranoid-checks")]
+    let vault_balances = crate::util::VaultBalances::read(
+        &token_vault_a.to_account_info(),
+        &token_vault_b.to_account_info(),
+    )?;
+
     transfer_from_vault_to_owner(
-        &ctx.accounts.whirlpool,
-        &ctx.accounts.token_vault_a,
-        &ctx.accounts.token_owner_account_a,
-        &ctx.accounts.token_program,
+        whirlpool,
+        token_vault_a,
+        token_owner_account_a,
+        token_program,
         delta_a,
     )?;
 
     transfer_from_vault_to_owner(
-        &ctx.accounts.whirlpool,
-        &ctx.accounts.token_vault_b,
-        &ctx.accounts.token_owner_account_b,
-        &ctx.accounts.token_program,
+        whirlpool,
+        token_vault_b,
+        token_owner_account_b,
+        token_program,
         delta_b,
     )?;
+    whirlpool.record_vault_withdrawals(delta_a, delta_b);
+
+    #[cfg(feature = "paranoid-checks")]
+    vault_balances.verify_deltas(
+        &token_vault_a.to_account_info(),
+        &token_vault_b.to_account_info(),
+        -i128::from(delta_a),
+        -i128::from(delta_b),
+    )?;
 
     Ok(())
 }
@@ -0,0 +1,36 @@
+use anchor_lang::prelude::*;
+
+use super::{
+    decrease_position_liquidity, DecreaseLiquidityParams, LiquidityTokenAccounts,
+    ModifyProtocolLiquidity,
+};
+
+/*
+  Removes liquidity from a protocol owned position into token accounts chosen by the fee
+  authority.
+*/
+pub fn handler(
+    ctx: Context<ModifyProtocolLiquidity>,
+    liquidity_amount: u128,
+    token_min_a: u64,
+    token_min_b: u64,
+) -> Result<()> {
+    decrease_position_liquidity(
+        &mut ctx.accounts.whirlpool,
+        &mut ctx.accounts.position,
+        &ctx.accounts.tick_array_lower,
+        &ctx.accounts.tick_array_upper,
+        LiquidityTokenAccounts {
+            token_owner_account_a: &ctx.accounts.token_owner_account_a,
+            token_owner_account_b: &ctx.accounts.token_owner_account_b,
+            token_vault_a: &ctx.accounts.token_vault_a,
+            token_vault_b: &ctx.accounts.token_vault_b,
+            token_program: &ctx.accounts.token_program,
+        },
+        DecreaseLiquidityParams {
+            liquidity_amount,
+            token_min_a,
+            token_min_b,
+        },
+    )
+}
@@ -0,0 +1,93 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::ErrorCode;
+use crate::manager::liquidity_manager::{
+    calculate_emergency_decrease_liquidity, sync_modify_liquidity_values,
+};
+use crate::math::convert_to_liquidity_delta;
+use crate::util::{
+    accrue_liquidity_time, record_liquidity_shift, to_timestamp_u64, verify_position_authority,
+};
+
+use super::{withdraw_liquidity_delta, LiquidityTokenAccounts, ModifyLiquidity};
+
+/*
+  Removes liquidity from a position without updating fee and reward growths, so principal stays
+  withdrawable if the fee or reward accounting of the pool cannot be updated. The fees, rewards
+  and liquidity time accrued by the removed liquidity since the last update are forfeited.
+*/
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, ModifyLiquidity<'info>>,
+    liquidity_amount: u128,
+    token_min_a: u64,
+    token_min_b: u64,
+) -> Result<()> {
+    verify_position_authority(
+        &ctx.accounts.position_token_account,
+        &ctx.accounts.position_authority,
+    )?;
+
+    if liquidity_amount == 0 {
+        return Err(ErrorCode::LiquidityZero.into());
+    }
+    let liquidity_delta = convert_to_liquidity_delta(liquidity_amount, false)?;
+    let clock = Clock::get()?;
+    let timestamp = to_timestamp_u64(clock.unix_timestamp)?;
+
+    let whirlpool = &mut ctx.accounts.whirlpool;
+    let position = &mut ctx.accounts.position;
+
+    let next_liquidity = position.liquidity.saturating_sub(liquidity_amount);
+    if position.is_liquidity_locked(next_liquidity, timestamp) {
+        return Err(ErrorCode::PositionLiquidityLocked.into());
+    }
+    whirlpool.verify_liquidity_removal_cooldown(position, timestamp)?;
+
+    // The liquidity time of the owner is not accrued for emergency withdrawals.
+    accrue_liquidity_time(
+        None,
+        whirlpool,
+        position,
+        &ctx.accounts.position_token_account.owner,
+        timestamp,
+    )?;
+
+    whirlpool.verify_tick_arrays_synced()?;
+
+    let update = calculate_emergency_decrease_liquidity(
+        whirlpool,
+        position,
+        &ctx.accounts.tick_array_lower.load().unwrap(),
+        &ctx.accounts.tick_array_upper.load().unwrap(),
+        liquidity_delta,
+    )?;
+
+    let previous_liquidity = whirlpool.liquidity;
+    // Reward infos are written back unchanged with their last updated timestamp.
+    let reward_last_updated_timestamp = whirlpool.reward_last_updated_timestamp;
+    sync_modify_liquidity_values(
+        whirlpool,
+        position,
+        &mut ctx.accounts.tick_array_lower.load_mut().unwrap(),
+        Some(&mut ctx.accounts.tick_array_upper.load_mut().unwrap()),
+        &update,
+        reward_last_updated_timestamp,
+    )?;
+    whirlpool.record_tick_updates(false);
+    record_liquidity_shift(whirlpool, previous_liquidity, clock.slot);
+
+    withdraw_liquidity_delta(
+        whirlpool,
+        position,
+        LiquidityTokenAccounts {
+            token_owner_account_a: &ctx.accounts.token_owner_account_a,
+            token_owner_account_b: &ctx.accounts.token_owner_account_b,
+            token_vault_a: &ctx.accounts.token_vault_a,
+            token_vault_b: &ctx.accounts.token_vault_b,
+            token_program: &ctx.accounts.token_program,
+        },
+        liquidity_delta,
+        token_min_a,
+        token_min_b,
+    )
+}
@@ -0,0 +1,67 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::TokenAccount;
+
+use crate::errors::ErrorCode;
+use crate::events::PositionLockExtended;
+use crate::state::*;
+use crate::util::{to_timestamp_u64, verify_position_authority};
+
+#[derive(Accounts)]
+pub struct ExtendPositionLock<'info> {
+    pub position_authority: Signer<'info>,
+
+    #[account(mut)]
+    pub position: Account<'info, Position>,
+
+    #[account(
+        constraint = position_token_account.mint == position.position_mint,
+        constraint = position_token_account.amount == 1
+    )]
+    pub position_token_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut,
+      seeds = [b"lock_receipt".as_ref(), position.key().as_ref()],
+      bump = lock_receipt.bump,
+    )]
+    pub lock_receipt: Account<'info, LiquidityLockReceipt>,
+}
+
+/*
+  Pushes the unlock timestamp of a position lock further out and locks any liquidity added to the
+  position since it was last locked.
+*/
+pub fn handler(ctx: Context<ExtendPositionLock>, unlock_timestamp: u64) -> Result<()> {
+    verify_position_authority(
+        &ctx.accounts.position_token_account,
+        &ctx.accounts.position_authority,
+    )?;
+
+    let clock = Clock::get()?;
+    let timestamp = to_timestamp_u64(clock.unix_timestamp)?;
+
+    let previous_unlock_timestamp = ctx.accounts.lock_receipt.unlock_timestamp;
+    if unlock_timestamp <= timestamp || unlock_timestamp <= previous_unlock_timestamp {
+        return Err(ErrorCode::InvalidUnlockTimestamp.into());
+    }
+
+    let position = &mut ctx.accounts.position;
+    if position.liquidity == 0 {
+        return Err(ErrorCode::LiquidityZero.into());
+    }
+    position.lock_liquidity(unlock_timestamp);
+
+    ctx.accounts
+        .lock_receipt
+        .update(position.liquidity, timestamp, unlock_timestamp);
+
+    emit!(PositionLockExtended {
+        whirlpool: position.whirlpool,
+        position: position.key(),
+        locker: ctx.accounts.lock_receipt.locker,
+        locked_liquidity: position.locked_liquidity,
+        previous_unlock_timestamp,
+        unlock_timestamp,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,99 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::TokenAccount;
+
+use crate::errors::ErrorCode;
+use crate::events::PositionAttested;
+use crate::manager::liquidity_manager::calculate_fee_and_reward_growths;
+use crate::state::*;
+use crate::util::{to_timestamp_u64, verify_position_authority};
+
+#[derive(Accounts)]
+pub struct FreezeAndAttestPosition<'info> {
+    #[account(mut)]
+    pub funder: Signer<'info>,
+
+    pub position_authority: Signer<'info>,
+
+    #[account(mut)]
+    pub whirlpool: Account<'info, Whirlpool>,
+
+    #[account(mut,
+        has_one = whirlpool,
+        constraint = !position.frozen @ ErrorCode::PositionFrozen,
+    )]
+    pub position: Account<'info, Position>,
+
+    #[account(
+        constraint = position_token_account.mint == position.position_mint,
+        constraint = position_token_account.amount == 1
+    )]
+    pub position_token_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(has_one = whirlpool)]
+    pub tick_array_lower: AccountLoader<'info, TickArray>,
+    #[account(has_one = whirlpool)]
+    pub tick_array_upper: AccountLoader<'info, TickArray>,
+
+    /// CHECK: only recorded as the authority allowed to release the attestation
+    pub bridge_authority: UncheckedAccount<'info>,
+
+    #[account(init,
+      payer = funder,
+      space = PositionAttestation::LEN,
+      seeds = [b"position_attestation".as_ref(), position.key().as_ref()],
+      bump,
+    )]
+    pub attestation: Account<'info, PositionAttestation>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/*
+  Freezes a position and attests its owner, range, liquidity and amounts owed for a bridge
+  program. Fees and rewards are brought up to date first, so the amounts owed are those at the
+  time of the freeze.
+*/
+pub fn handler(ctx: Context<FreezeAndAttestPosition>) -> Result<()> {
+    verify_position_authority(
+        &ctx.accounts.position_token_account,
+        &ctx.accounts.position_authority,
+    )?;
+
+    let clock = Clock::get()?;
+    let timestamp = to_timestamp_u64(clock.unix_timestamp)?;
+
+    let whirlpool = &mut ctx.accounts.whirlpool;
+    let position = &mut ctx.accounts.position;
+    // Positions without liquidity accrue nothing, their amounts owed are already current.
+    if position.liquidity > 0 {
+        whirlpool.verify_tick_arrays_synced()?;
+        let (position_update, reward_infos) = calculate_fee_and_reward_growths(
+            whirlpool,
+            position,
+            &ctx.accounts.tick_array_lower,
+            &ctx.accounts.tick_array_upper,
+            timestamp,
+        )?;
+        whirlpool.update_rewards(reward_infos, timestamp);
+        position.update(&position_update);
+    }
+    position.update_frozen(true);
+
+    ctx.accounts.attestation.initialize(
+        position,
+        ctx.accounts.position_token_account.owner,
+        ctx.accounts.bridge_authority.key(),
+        ctx.bumps.attestation,
+        timestamp,
+    );
+
+    emit!(PositionAttested {
+        whirlpool: position.whirlpool,
+        position: position.key(),
+        attestation: ctx.accounts.attestation.key(),
+        bridge_authority: ctx.accounts.bridge_authority.key(),
+        liquidity: position.liquidity,
+    });
+
+    Ok(())
+}
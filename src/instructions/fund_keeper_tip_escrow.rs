@@ -0,0 +1,28 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{self, Transfer};
+
+use crate::state::KeeperTipEscrow;
+
+#[derive(Accounts)]
+pub struct FundKeeperTipEscrow<'info> {
+    #[account(mut)]
+    pub funder: Signer<'info>,
+
+    #[account(mut)]
+    pub keeper_tip_escrow: Box<Account<'info, KeeperTipEscrow>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<FundKeeperTipEscrow>, lamports: u64) -> Result<()> {
+    system_program::transfer(
+        CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.funder.to_account_info(),
+                to: ctx.accounts.keeper_tip_escrow.to_account_info(),
+            },
+        ),
+        lamports,
+    )
+}
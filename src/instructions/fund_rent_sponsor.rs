@@ -0,0 +1,28 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{self, Transfer};
+
+use crate::state::RentSponsor;
+
+#[derive(Accounts)]
+pub struct FundRentSponsor<'info> {
+    #[account(mut)]
+    pub funder: Signer<'info>,
+
+    #[account(mut)]
+    pub rent_sponsor: Box<Account<'info, RentSponsor>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<FundRentSponsor>, lamports: u64) -> Result<()> {
+    system_program::transfer(
+        CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.funder.to_account_info(),
+                to: ctx.accounts.rent_sponsor.to_account_info(),
+            },
+        ),
+        lamports,
+    )
+}
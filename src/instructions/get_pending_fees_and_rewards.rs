@@ -0,0 +1,52 @@
+use anchor_lang::prelude::*;
+
+use crate::{state::*, util::to_timestamp_u64};
+
+#[derive(Accounts)]
+pub struct GetPendingFeesAndRewards<'info> {
+    pub whirlpool: Box<Account<'info, Whirlpool>>,
+
+    #[account(has_one = whirlpool)]
+    pub position: Box<Account<'info, Position>>,
+
+    #[account(has_one = whirlpool)]
+    pub tick_array_lower: AccountLoader<'info, TickArray>,
+    #[account(has_one = whirlpool)]
+    pub tick_array_upper: AccountLoader<'info, TickArray>,
+}
+
+/// Fees and rewards a position can collect, returned by `get_pending_fees_and_rewards`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, PartialEq)]
+#[cfg_attr(not(target_arch = "bpf"), derive(Debug))]
+pub struct PendingFeesAndRewards {
+    pub fee_owed_a: u64,
+    pub fee_owed_b: u64,
+    pub rewards_owed: [u64; NUM_REWARDS],
+}
+
+/*
+  Read-only: computes what collect_fees and collect_reward would pay for the position right now,
+  including the growth accrued since the position was last updated, without writing any account.
+*/
+pub fn handler(ctx: Context<GetPendingFeesAndRewards>) -> Result<PendingFeesAndRewards> {
+    let clock = Clock::get()?;
+    let timestamp = to_timestamp_u64(clock.unix_timestamp)?;
+
+    let whirlpool = &ctx.accounts.whirlpool;
+    let position = &ctx.accounts.position;
+    let tick_array_lower = ctx.accounts.tick_array_lower.load()?;
+    let tick_array_upper = ctx.accounts.tick_array_upper.load()?;
+    let tick_arrays = TickArrayRefs {
+        lower: &tick_array_lower,
+        upper: &tick_array_upper,
+    };
+
+    let (fee_owed_a, fee_owed_b) = position.pending_fees(whirlpool, &tick_arrays)?;
+    let rewards_owed = position.pending_rewards(whirlpool, &tick_arrays, timestamp)?;
+
+    Ok(PendingFeesAndRewards {
+        fee_owed_a,
+        fee_owed_b,
+        rewards_owed,
+    })
+}
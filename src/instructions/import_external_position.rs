@@ -0,0 +1,138 @@
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token::{self, Mint, Token, TokenAccount};
+
+use crate::errors::ErrorCode;
+use crate::state::*;
+use crate::util::{
+    mint_position_token_and_remove_authority, read_upstream_position, read_upstream_whirlpool_mints,
+};
+
+use super::{increase_position_liquidity, IncreaseLiquidityParams, LiquidityTokenAccounts};
+
+#[derive(Accounts)]
+pub struct ImportExternalPosition<'info> {
+    #[account(mut)]
+    pub funder: Signer<'info>,
+
+    pub owner: Signer<'info>,
+
+    pub external_position: UncheckedAccount<'info>,
+    #[account(constraint = external_position_token_account.owner == owner.key())]
+    pub external_position_token_account: Box<Account<'info, TokenAccount>>,
+    pub external_whirlpool: UncheckedAccount<'info>,
+
+    #[account(init,
+      payer = funder,
+      space = Position::LEN,
+      seeds = [b"imported_position".as_ref(), external_position.key().as_ref()],
+      bump,
+    )]
+    pub position: Box<Account<'info, Position>>,
+
+    #[account(init,
+        payer = funder,
+        mint::authority = whirlpool,
+        mint::decimals = 0,
+    )]
+    pub position_mint: Box<Account<'info, Mint>>,
+
+    #[account(init,
+      payer = funder,
+      associated_token::mint = position_mint,
+      associated_token::authority = owner,
+    )]
+    pub position_token_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub whirlpool: Box<Account<'info, Whirlpool>>,
+
+    #[account(mut, constraint = token_owner_account_a.mint == whirlpool.token_mint_a)]
+    pub token_owner_account_a: Box<Account<'info, TokenAccount>>,
+    #[account(mut, constraint = token_owner_account_b.mint == whirlpool.token_mint_b)]
+    pub token_owner_account_b: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut, address = whirlpool.token_vault_a)]
+    pub token_vault_a: Box<Account<'info, TokenAccount>>,
+    #[account(mut, address = whirlpool.token_vault_b)]
+    pub token_vault_b: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut, has_one = whirlpool)]
+    pub tick_array_lower: AccountLoader<'info, TickArray>,
+    #[account(mut, has_one = whirlpool)]
+    pub tick_array_upper: AccountLoader<'info, TickArray>,
+
+    #[account(address = token::ID)]
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+/*
+  Mirrors a position of the upstream Whirlpool program held by the owner into a new position with
+  the same range and liquidity in a Whirlpool of the same mints, funded from the owner's token
+  accounts. The position address is derived from the upstream position, so every upstream position
+  can be imported once. The upstream position is left untouched, its liquidity can be withdrawn
+  in the same transaction after the import.
+*/
+pub fn handler(
+    ctx: Context<ImportExternalPosition>,
+    token_max_a: u64,
+    token_max_b: u64,
+) -> Result<()> {
+    let external_position = read_upstream_position(&ctx.accounts.external_position)?;
+    let external_position_token_account = &ctx.accounts.external_position_token_account;
+    if external_position_token_account.mint != external_position.position_mint
+        || external_position_token_account.amount != 1
+        || ctx.accounts.external_whirlpool.key() != external_position.whirlpool
+    {
+        return Err(ErrorCode::InvalidUpstreamPosition.into());
+    }
+
+    let (token_mint_a, token_mint_b) =
+        read_upstream_whirlpool_mints(&ctx.accounts.external_whirlpool)?;
+    if token_mint_a != ctx.accounts.whirlpool.token_mint_a
+        || token_mint_b != ctx.accounts.whirlpool.token_mint_b
+    {
+        return Err(ErrorCode::UpstreamWhirlpoolMismatch.into());
+    }
+
+    ctx.accounts.whirlpool.add_open_position()?;
+
+    let position_mint = &ctx.accounts.position_mint;
+    ctx.accounts.position.open_position(
+        &ctx.accounts.whirlpool,
+        position_mint.key(),
+        ctx.accounts.owner.key(),
+        external_position.tick_lower_index,
+        external_position.tick_upper_index,
+    )?;
+
+    mint_position_token_and_remove_authority(
+        &ctx.accounts.whirlpool,
+        position_mint,
+        &ctx.accounts.position_token_account,
+        &ctx.accounts.token_program,
+    )?;
+
+    increase_position_liquidity(
+        &mut ctx.accounts.whirlpool,
+        &mut ctx.accounts.position,
+        &ctx.accounts.tick_array_lower,
+        &ctx.accounts.tick_array_upper,
+        &ctx.accounts.owner,
+        LiquidityTokenAccounts {
+            token_owner_account_a: &ctx.accounts.token_owner_account_a,
+            token_owner_account_b: &ctx.accounts.token_owner_account_b,
+            token_vault_a: &ctx.accounts.token_vault_a,
+            token_vault_b: &ctx.accounts.token_vault_b,
+            token_program: &ctx.accounts.token_program,
+        },
+        IncreaseLiquidityParams {
+            liquidity_amount: external_position.liquidity,
+            token_max_a,
+            token_max_b,
+        },
+    )
+}
@@ -3,24 +3,31 @@ use anchor_spl::token::{self, Token, TokenAccount};
 
 use crate::errors::ErrorCode;
 use crate::manager::liquidity_manager::{
-    calculate_liquidity_token_deltas, calculate_modify_liquidity, sync_modify_liquidity_values,
+    calculate_liquidity_token_deltas, calculate_modify_liquidity, record_liquidity_rounding_dust,
+    sync_modify_liquidity_values,
 };
 use crate::math::convert_to_liquidity_delta;
 use crate::state::*;
-use crate::util::{to_timestamp_u64, transfer_from_owner_to_vault, verify_position_authority};
+use crate::util::{
+    accrue_liquidity_time, fail_with_quote_hint, record_liquidity_shift, to_timestamp_u64,
+    transfer_from_owner_to_vault, verify_position_authority,
+};
 
 #[derive(Accounts)]
 pub struct ModifyLiquidity<'info> {
     #[account(mut)]
-    pub whirlpool: Account<'info, Whirlpool>,
+    pub whirlpool: Box<Account<'info, Whirlpool>>,
 
     #[account(address = token::ID)]
     pub token_program: Program<'info, Token>,
 
     pub position_authority: Signer<'info>,
 
-    #[account(mut, has_one = whirlpool)]
-    pub position: Account<'info, Position>,
+    #[account(mut,
+        has_one = whirlpool,
+        constraint = !position.frozen @ ErrorCode::PositionFrozen
+    )]
+    pub position: Box<Account<'info, Position>>,
     #[account(
         constraint = position_token_account.mint == position.position_mint,
         constraint = position_token_account.amount == 1
@@ -43,8 +50,25 @@ pub struct ModifyLiquidity<'info> {
     pub tick_array_upper: AccountLoader<'info, TickArray>,
 }
 
-pub fn handler(
-    ctx: Context<ModifyLiquidity>,
+/// Token accounts a liquidity change transfers between, shared by the liquidity instructions.
+pub struct LiquidityTokenAccounts<'a, 'info> {
+    pub token_owner_account_a: &'a Account<'info, TokenAccount>,
+    pub token_owner_account_b: &'a Account<'info, TokenAccount>,
+    pub token_vault_a: &'a Account<'info, TokenAccount>,
+    pub token_vault_b: &'a Account<'info, TokenAccount>,
+    pub token_program: &'a Program<'info, Token>,
+}
+
+/// Liquidity added to a position and the most tokens the owner deposits for it.
+#[derive(Clone, Copy)]
+pub struct IncreaseLiquidityParams {
+    pub liquidity_amount: u128,
+    pub token_max_a: u64,
+    pub token_max_b: u64,
+}
+
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, ModifyLiquidity<'info>>,
     liquidity_amount: u128,
     token_max_a: u64,
     token_max_b: u64,
@@ -54,6 +78,59 @@ pub fn handler(
         &ctx.accounts.position_authority,
     )?;
 
+    // Liquidity time is accrued with the liquidity before the change.
+    accrue_liquidity_time(
+        ctx.remaining_accounts.first(),
+        &ctx.accounts.whirlpool,
+        &mut ctx.accounts.position,
+        &ctx.accounts.position_token_account.owner,
+        to_timestamp_u64(Clock::get()?.unix_timestamp)?,
+    )?;
+
+    increase_position_liquidity(
+        &mut ctx.accounts.whirlpool,
+        &mut ctx.accounts.position,
+        &ctx.accounts.tick_array_lower,
+        &ctx.accounts.tick_array_upper,
+        &ctx.accounts.position_authority,
+        LiquidityTokenAccounts {
+            token_owner_account_a: &ctx.accounts.token_owner_account_a,
+            token_owner_account_b: &ctx.accounts.token_owner_account_b,
+            token_vault_a: &ctx.accounts.token_vault_a,
+            token_vault_b: &ctx.accounts.token_vault_b,
+            token_program: &ctx.accounts.token_program,
+        },
+        IncreaseLiquidityParams {
+            liquidity_amount,
+            token_max_a,
+            token_max_b,
+        },
+    )
+}
+
+/// Add liquidity to a position and deposit the required tokens from the owner accounts. Shared by
+/// position token and protocol owned positions, the caller verifies the position authority.
+pub fn increase_position_liquidity<'info>(
+    whirlpool: &mut Account<'info, Whirlpool>,
+    position: &mut Account<'info, Position>,
+    tick_array_lower: &AccountLoader<'info, TickArray>,
+    tick_array_upper: &AccountLoader<'info, TickArray>,
+    position_authority: &Signer<'info>,
+    token_accounts: LiquidityTokenAccounts<'_, 'info>,
+    params: IncreaseLiquidityParams,
+) -> Result<()> {
+    let LiquidityTokenAccounts {
+        token_owner_account_a,
+        token_owner_account_b,
+        token_vault_a,
+        token_vault_b,
+        token_program,
+    } = token_accounts;
+    let IncreaseLiquidityParams {
+        liquidity_amount,
+        token_max_a,
+        token_max_b,
+    } = params;
     let clock = Clock::get()?;
 
     if liquidity_amount == 0 {
@@ -62,52 +139,76 @@ pub fn handler(
     let liquidity_delta = convert_to_liquidity_delta(liquidity_amount, true)?;
     let timestamp = to_timestamp_u64(clock.unix_timestamp)?;
 
+    whirlpool.verify_not_paused()?;
+    whirlpool.verify_tick_arrays_synced()?;
+
     let update = calculate_modify_liquidity(
-        &ctx.accounts.whirlpool,
-        &ctx.accounts.position,
-        &ctx.accounts.tick_array_lower.load().unwrap(),
-        &ctx.accounts.tick_array_upper.load().unwrap(),
+        whirlpool,
+        position,
+        &tick_array_lower.load().unwrap(),
+        &tick_array_upper.load().unwrap(),
         liquidity_delta,
         timestamp,
     )?;
 
+    let previous_liquidity = whirlpool.liquidity;
     sync_modify_liquidity_values(
-        &mut ctx.accounts.whirlpool,
-        &mut ctx.accounts.position,
-        &mut ctx.accounts.tick_array_lower.load_mut().unwrap(),
-        &mut ctx.accounts.tick_array_upper.load_mut().unwrap(),
-        update,
+        whirlpool,
+        position,
+        &mut tick_array_lower.load_mut().unwrap(),
+        Some(&mut tick_array_upper.load_mut().unwrap()),
+        &update,
         timestamp,
     )?;
+    whirlpool.record_tick_updates(false);
+    record_liquidity_shift(whirlpool, previous_liquidity, clock.slot);
+    position.record_liquidity_increase(timestamp, clock.slot);
 
     let (delta_a, delta_b) = calculate_liquidity_token_deltas(
-        ctx.accounts.whirlpool.tick_current_index,
-        ctx.accounts.whirlpool.sqrt_price,
-        &ctx.accounts.position,
+        whirlpool.tick_current_index,
+        whirlpool.sqrt_price,
+        position,
         liquidity_delta,
     )?;
+    record_liquidity_rounding_dust(whirlpool, position, liquidity_delta);
 
-    if delta_a > token_max_a {
-        return Err(ErrorCode::TokenMaxExceeded.into());
-    } else if delta_b > token_max_b {
-        return Err(ErrorCode::TokenMaxExceeded.into());
+    let delta_a = whirlpool.to_token_amount(delta_a, true, true)?;
+    let delta_b = whirlpool.to_token_amount(delta_b, false, true)?;
+
+    if delta_a > token_max_a || delta_b > token_max_b {
+        return fail_with_quote_hint(ErrorCode::TokenMaxExceeded, delta_a, delta_b);
     }
 
+    #[cfg(feature = "paranoid-checks")]
+    let vault_balances = crate::util::VaultBalances::read(
+        &token_vault_a.to_account_info(),
+        &token_vault_b.to_account_info(),
+    )?;
+
     transfer_from_owner_to_vault(
-        &ctx.accounts.position_authority,
-        &ctx.accounts.token_owner_account_a,
-        &ctx.accounts.token_vault_a,
-        &ctx.accounts.token_program,
+        position_authority,
+        token_owner_account_a,
+        token_vault_a,
+        token_program,
         delta_a,
     )?;
 
     transfer_from_owner_to_vault(
-        &ctx.accounts.position_authority,
-        &ctx.accounts.token_owner_account_b,
-        &ctx.accounts.token_vault_b,
-        &ctx.accounts.token_program,
+        position_authority,
+        token_owner_account_b,
+        token_vault_b,
+        token_program,
         delta_b,
     )?;
+    whirlpool.record_vault_deposits(delta_a, delta_b);
+
+    #[cfg(feature = "paranoid-checks")]
+    vault_balances.verify_deltas(
+        &token_vault_a.to_account_info(),
+        &token_vault_b.to_account_info(),
+        i128::from(delta_a),
+        i128::from(delta_b),
+    )?;
 
     Ok(())
 }
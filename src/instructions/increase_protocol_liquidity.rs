@@ -0,0 +1,82 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount};
+
+use crate::errors::ErrorCode;
+use crate::state::*;
+
+use super::{increase_position_liquidity, IncreaseLiquidityParams, LiquidityTokenAccounts};
+
+#[derive(Accounts)]
+pub struct ModifyProtocolLiquidity<'info> {
+    pub whirlpools_config: Box<Account<'info, WhirlpoolsConfig>>,
+
+    #[account(mut, has_one = whirlpools_config)]
+    pub whirlpool: Account<'info, Whirlpool>,
+
+    #[account(
+        address = whirlpools_config.fee_authority,
+        constraint = !whirlpools_config.admin_frozen @ ErrorCode::AdminParametersFrozen,
+    )]
+    pub fee_authority: Signer<'info>,
+
+    #[account(mut,
+      has_one = whirlpool,
+      seeds = [
+        b"protocol_position".as_ref(),
+        whirlpool.key().as_ref(),
+        position.tick_lower_index.to_le_bytes().as_ref(),
+        position.tick_upper_index.to_le_bytes().as_ref()
+      ],
+      bump,
+    )]
+    pub position: Account<'info, Position>,
+
+    #[account(mut, constraint = token_owner_account_a.mint == whirlpool.token_mint_a)]
+    pub token_owner_account_a: Box<Account<'info, TokenAccount>>,
+    #[account(mut, constraint = token_owner_account_b.mint == whirlpool.token_mint_b)]
+    pub token_owner_account_b: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut, constraint = token_vault_a.key() == whirlpool.token_vault_a)]
+    pub token_vault_a: Box<Account<'info, TokenAccount>>,
+    #[account(mut, constraint = token_vault_b.key() == whirlpool.token_vault_b)]
+    pub token_vault_b: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut, has_one = whirlpool)]
+    pub tick_array_lower: AccountLoader<'info, TickArray>,
+    #[account(mut, has_one = whirlpool)]
+    pub tick_array_upper: AccountLoader<'info, TickArray>,
+
+    #[account(address = token::ID)]
+    pub token_program: Program<'info, Token>,
+}
+
+/*
+  Adds treasury liquidity to a protocol owned position. Tokens are deposited from accounts of the
+  fee authority.
+*/
+pub fn handler(
+    ctx: Context<ModifyProtocolLiquidity>,
+    liquidity_amount: u128,
+    token_max_a: u64,
+    token_max_b: u64,
+) -> Result<()> {
+    increase_position_liquidity(
+        &mut ctx.accounts.whirlpool,
+        &mut ctx.accounts.position,
+        &ctx.accounts.tick_array_lower,
+        &ctx.accounts.tick_array_upper,
+        &ctx.accounts.fee_authority,
+        LiquidityTokenAccounts {
+            token_owner_account_a: &ctx.accounts.token_owner_account_a,
+            token_owner_account_b: &ctx.accounts.token_owner_account_b,
+            token_vault_a: &ctx.accounts.token_vault_a,
+            token_vault_b: &ctx.accounts.token_vault_b,
+            token_program: &ctx.accounts.token_program,
+        },
+        IncreaseLiquidityParams {
+            liquidity_amount,
+            token_max_a,
+            token_max_b,
+        },
+    )
+}
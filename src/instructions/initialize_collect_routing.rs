@@ -0,0 +1,28 @@
+use anchor_lang::prelude::*;
+
+use crate::state::CollectRouting;
+
+#[derive(Accounts)]
+pub struct InitializeCollectRouting<'info> {
+    #[account(mut)]
+    pub funder: Signer<'info>,
+
+    pub owner: Signer<'info>,
+
+    #[account(init,
+      payer = funder,
+      space = CollectRouting::LEN,
+      seeds = [b"collect_routing".as_ref(), owner.key().as_ref()],
+      bump,
+    )]
+    pub collect_routing: Box<Account<'info, CollectRouting>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<InitializeCollectRouting>) -> Result<()> {
+    ctx.accounts
+        .collect_routing
+        .initialize(ctx.accounts.owner.key());
+    Ok(())
+}
@@ -0,0 +1,45 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::ErrorCode;
+use crate::state::{FeeSchedule, Whirlpool, WhirlpoolsConfig};
+
+#[derive(Accounts)]
+pub struct InitializeFeeSchedule<'info> {
+    pub whirlpools_config: Account<'info, WhirlpoolsConfig>,
+
+    #[account(mut, has_one = whirlpools_config)]
+    pub whirlpool: Account<'info, Whirlpool>,
+
+    #[account(
+        address = whirlpools_config.fee_authority,
+        constraint = !whirlpools_config.admin_frozen @ ErrorCode::AdminParametersFrozen,
+    )]
+    pub fee_authority: Signer<'info>,
+
+    #[account(mut)]
+    pub funder: Signer<'info>,
+
+    #[account(
+      init,
+      payer = funder,
+      seeds = [b"fee_schedule".as_ref(), whirlpool.key().as_ref()],
+      bump,
+      space = FeeSchedule::LEN)]
+    pub fee_schedule: Box<Account<'info, FeeSchedule>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/*
+  Creates the fee schedule of a Whirlpool. Every swap of the pool reads the schedule from then on,
+  at the cost of one extra account per swap.
+*/
+pub fn handler(ctx: Context<InitializeFeeSchedule>) -> Result<()> {
+    ctx.accounts
+        .fee_schedule
+        .initialize(ctx.accounts.whirlpool.key());
+    ctx.accounts
+        .whirlpool
+        .update_fee_schedule(ctx.accounts.fee_schedule.key());
+    Ok(())
+}
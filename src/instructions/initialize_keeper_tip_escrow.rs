@@ -0,0 +1,46 @@
+use anchor_lang::prelude::*;
+
+use crate::state::{KeeperTipEscrow, Whirlpool};
+
+#[derive(Accounts)]
+pub struct InitializeKeeperTipEscrow<'info> {
+    pub whirlpool: Box<Account<'info, Whirlpool>>,
+
+    pub authority: Signer<'info>,
+
+    #[account(mut)]
+    pub funder: Signer<'info>,
+
+    #[account(
+      init,
+      payer = funder,
+      seeds = [
+        b"keeper_tip_escrow".as_ref(),
+        whirlpool.key().as_ref(),
+        authority.key().as_ref()
+      ],
+      bump,
+      space = KeeperTipEscrow::LEN)]
+    pub keeper_tip_escrow: Box<Account<'info, KeeperTipEscrow>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/*
+  Creates a keeper tip escrow for the cranks of a Whirlpool. The escrow tips nothing until it is
+  funded with `fund_keeper_tip_escrow`.
+*/
+pub fn handler(
+    ctx: Context<InitializeKeeperTipEscrow>,
+    base_tip: u64,
+    max_priority_fee_tip: u64,
+) -> Result<()> {
+    ctx.accounts.keeper_tip_escrow.initialize(
+        ctx.accounts.whirlpool.key(),
+        ctx.accounts.authority.key(),
+        base_tip,
+        max_priority_fee_tip,
+    );
+
+    Ok(())
+}
@@ -0,0 +1,36 @@
+use anchor_lang::prelude::*;
+
+use crate::state::{LiquidityTimeAccumulator, Whirlpool};
+
+#[derive(Accounts)]
+pub struct InitializeLiquidityTimeAccumulator<'info> {
+    pub whirlpool: Account<'info, Whirlpool>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    #[account(
+      init,
+      payer = owner,
+      seeds = [
+        b"liquidity_time".as_ref(),
+        whirlpool.key().as_ref(),
+        owner.key().as_ref()
+      ],
+      bump,
+      space = LiquidityTimeAccumulator::LEN)]
+    pub liquidity_time_accumulator: Account<'info, LiquidityTimeAccumulator>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/*
+  Creates the liquidity time accumulator of an owner in a Whirlpool. Positions of the owner start
+  accruing to it on their next liquidity change or fee update that passes the accumulator.
+*/
+pub fn handler(ctx: Context<InitializeLiquidityTimeAccumulator>) -> Result<()> {
+    ctx.accounts
+        .liquidity_time_accumulator
+        .initialize(ctx.accounts.whirlpool.key(), ctx.accounts.owner.key());
+    Ok(())
+}
@@ -0,0 +1,39 @@
+use anchor_lang::prelude::*;
+
+use crate::state::{PartnerStats, Whirlpool};
+
+#[derive(Accounts)]
+pub struct InitializePartnerStats<'info> {
+    pub whirlpool: Box<Account<'info, Whirlpool>>,
+
+    /// CHECK: any account can identify a partner, stats are only attributed to it
+    pub partner: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub funder: Signer<'info>,
+
+    #[account(
+      init,
+      payer = funder,
+      seeds = [
+        b"partner_stats".as_ref(),
+        whirlpool.key().as_ref(),
+        partner.key().as_ref()
+      ],
+      bump,
+      space = PartnerStats::LEN)]
+    pub partner_stats: Box<Account<'info, PartnerStats>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/*
+  Creates the swap stats of a partner frontend in a Whirlpool. Swaps made with swap_with_partner
+  record their volume and fees to it from then on.
+*/
+pub fn handler(ctx: Context<InitializePartnerStats>) -> Result<()> {
+    ctx.accounts
+        .partner_stats
+        .initialize(ctx.accounts.whirlpool.key(), ctx.accounts.partner.key());
+    Ok(())
+}
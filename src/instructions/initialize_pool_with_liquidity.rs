@@ -0,0 +1,369 @@
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token::{self, Mint, Token, TokenAccount};
+
+use crate::errors::ErrorCode;
+use crate::manager::liquidity_manager::{
+    calculate_liquidity_token_deltas, calculate_modify_liquidity, record_liquidity_rounding_dust,
+    sync_modify_liquidity_values,
+};
+use crate::math::{convert_to_liquidity_delta, fee_rate_from_legacy};
+use crate::state::*;
+use crate::util::{
+    fail_with_quote_hint, mint_position_token_and_remove_authority, read_oracle_sqrt_price,
+    to_timestamp_u64, transfer_from_owner_to_vault, verify_freeze_authority_allowed,
+    OraclePriceCheck,
+};
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default)]
+pub struct InitializePoolWithLiquidityParams {
+    pub tick_spacing: u16,
+    pub initial_sqrt_price: u128,
+    pub tick_lower_index: i32,
+    pub tick_upper_index: i32,
+    pub liquidity_amount: u128,
+    pub token_max_a: u64,
+    pub token_max_b: u64,
+    pub liquidity_removal_cooldown: u32,
+    pub min_tick_index: i32,
+    pub max_tick_index: i32,
+    // Oracle prices to read the initial sqrt price from instead of `initial_sqrt_price`
+    pub oracle_price_check: Option<OraclePriceCheck>,
+}
+
+#[derive(Accounts)]
+#[instruction(params: InitializePoolWithLiquidityParams)]
+pub struct InitializePoolWithLiquidity<'info> {
+    pub whirlpools_config: Box<Account<'info, WhirlpoolsConfig>>,
+
+    pub token_mint_a: Account<'info, Mint>,
+    pub token_mint_b: Account<'info, Mint>,
+
+    #[account(mut)]
+    pub funder: Signer<'info>,
+
+    #[account(init,
+      seeds = [
+        b"whirlpool".as_ref(),
+        whirlpools_config.key().as_ref(),
+        token_mint_a.key().as_ref(),
+        token_mint_b.key().as_ref(),
+        params.tick_spacing.to_le_bytes().as_ref()
+      ],
+      bump,
+      payer = funder,
+      space = Whirlpool::LEN)]
+    pub whirlpool: Box<Account<'info, Whirlpool>>,
+
+    #[account(init,
+      payer = funder,
+      token::mint = token_mint_a,
+      token::authority = whirlpool)]
+    pub token_vault_a: Box<Account<'info, TokenAccount>>,
+
+    #[account(init,
+      payer = funder,
+      token::mint = token_mint_b,
+      token::authority = whirlpool)]
+    pub token_vault_b: Box<Account<'info, TokenAccount>>,
+
+    #[account(has_one = whirlpools_config, constraint = fee_tier.tick_spacing == params.tick_spacing)]
+    pub fee_tier: Box<Account<'info, FeeTier>>,
+
+    #[account(init,
+      payer = funder,
+      seeds = [
+        b"tick_array".as_ref(),
+        whirlpool.key().as_ref(),
+        TickArray::start_tick_index_for(params.tick_lower_index, params.tick_spacing)
+          .to_string()
+          .as_bytes()
+      ],
+      bump,
+      space = TickArray::LEN)]
+    pub tick_array_lower: AccountLoader<'info, TickArray>,
+    // None if the upper tick is in the lower tick array
+    #[account(init,
+      payer = funder,
+      seeds = [
+        b"tick_array".as_ref(),
+        whirlpool.key().as_ref(),
+        TickArray::start_tick_index_for(params.tick_upper_index, params.tick_spacing)
+          .to_string()
+          .as_bytes()
+      ],
+      bump,
+      space = TickArray::LEN)]
+    pub tick_array_upper: Option<AccountLoader<'info, TickArray>>,
+
+    #[account(init,
+      payer = funder,
+      space = Position::LEN,
+      seeds = [b"position".as_ref(), position_mint.key().as_ref()],
+      bump,
+    )]
+    pub position: Box<Account<'info, Position>>,
+
+    #[account(init,
+        payer = funder,
+        mint::authority = whirlpool,
+        mint::decimals = 0,
+    )]
+    pub position_mint: Box<Account<'info, Mint>>,
+
+    #[account(init,
+      payer = funder,
+      associated_token::mint = position_mint,
+      associated_token::authority = funder,
+    )]
+    pub position_token_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut, constraint = token_owner_account_a.mint == token_mint_a.key())]
+    pub token_owner_account_a: Box<Account<'info, TokenAccount>>,
+    #[account(mut, constraint = token_owner_account_b.mint == token_mint_b.key())]
+    pub token_owner_account_b: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut, seeds = [b"protocol_stats".as_ref()], bump)]
+    pub protocol_stats: Box<Account<'info, ProtocolStats>>,
+
+    #[account(address = token::ID)]
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+/*
+  Creates a Whirlpool and seeds it with a first position in a single instruction so the pool is
+  never observable in an empty or partially configured state. Mints with a freeze authority need
+  a token badge allowing it in remaining accounts. The initial price can be read from a Pyth price
+  account in remaining accounts instead of being passed, so a mistyped price does not invite
+  arbitrage against the seed position. A seed range within one tick array initializes only the
+  lower tick array.
+*/
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, InitializePoolWithLiquidity<'info>>,
+    params: InitializePoolWithLiquidityParams,
+) -> Result<()> {
+    let whirlpool_bump = ctx.bumps.whirlpool;
+    let tick_spacing = params.tick_spacing;
+
+    if params.liquidity_amount == 0 {
+        return Err(ErrorCode::LiquidityZero.into());
+    }
+
+    let whirlpools_config = ctx.accounts.whirlpools_config.key();
+    verify_freeze_authority_allowed(
+        &whirlpools_config,
+        &ctx.accounts.token_mint_a,
+        ctx.remaining_accounts,
+    )?;
+    verify_freeze_authority_allowed(
+        &whirlpools_config,
+        &ctx.accounts.token_mint_b,
+        ctx.remaining_accounts,
+    )?;
+
+    let clock = Clock::get()?;
+    let initial_sqrt_price = match params.oracle_price_check {
+        Some(oracle_price_check) => read_oracle_sqrt_price(
+            &oracle_price_check,
+            &whirlpools_config,
+            &ctx.accounts.token_mint_a,
+            &ctx.accounts.token_mint_b,
+            ctx.remaining_accounts,
+            clock.unix_timestamp,
+        )?,
+        None => params.initial_sqrt_price,
+    };
+
+    ctx.accounts.whirlpool.initialize(
+        &ctx.accounts.whirlpools_config,
+        whirlpool_bump,
+        tick_spacing,
+        initial_sqrt_price,
+        fee_rate_from_legacy(ctx.accounts.fee_tier.default_fee_rate),
+        ctx.accounts.token_mint_a.key(),
+        ctx.accounts.token_vault_a.key(),
+        ctx.accounts.token_mint_b.key(),
+        ctx.accounts.token_vault_b.key(),
+    )?;
+    ctx.accounts
+        .whirlpool
+        .update_liquidity_removal_cooldown(params.liquidity_removal_cooldown);
+    ctx.accounts
+        .whirlpool
+        .update_tick_bounds(params.min_tick_index, params.max_tick_index)?;
+
+    // The discriminators of the new tick arrays are only written on exit, so the arrays have to
+    // be accessed through their initial loads for the rest of the instruction.
+    let lower_start_tick_index =
+        TickArray::start_tick_index_for(params.tick_lower_index, tick_spacing);
+    let upper_start_tick_index =
+        TickArray::start_tick_index_for(params.tick_upper_index, tick_spacing);
+    let mut tick_array_lower = ctx.accounts.tick_array_lower.load_init()?;
+    tick_array_lower.initialize(&ctx.accounts.whirlpool, lower_start_tick_index)?;
+    let mut tick_array_upper = match &ctx.accounts.tick_array_upper {
+        Some(tick_array_upper) if upper_start_tick_index != lower_start_tick_index => {
+            let mut tick_array_upper = tick_array_upper.load_init()?;
+            tick_array_upper.initialize(&ctx.accounts.whirlpool, upper_start_tick_index)?;
+            tick_array_upper.link_after(&mut tick_array_lower)?;
+            Some(tick_array_upper)
+        }
+        None if upper_start_tick_index == lower_start_tick_index => None,
+        _ => return Err(ErrorCode::InvalidTickArraySequence.into()),
+    };
+
+    ctx.accounts.protocol_stats.record_pool();
+
+    ctx.accounts.whirlpool.add_open_position()?;
+    ctx.accounts.position.open_position(
+        &ctx.accounts.whirlpool,
+        ctx.accounts.position_mint.key(),
+        ctx.accounts.funder.key(),
+        params.tick_lower_index,
+        params.tick_upper_index,
+    )?;
+
+    mint_position_token_and_remove_authority(
+        &ctx.accounts.whirlpool,
+        &ctx.accounts.position_mint,
+        &ctx.accounts.position_token_account,
+        &ctx.accounts.token_program,
+    )?;
+
+    let timestamp = to_timestamp_u64(clock.unix_timestamp)?;
+    let liquidity_delta = convert_to_liquidity_delta(params.liquidity_amount, true)?;
+
+    add_seed_liquidity(
+        &mut ctx.accounts.whirlpool,
+        &mut ctx.accounts.position,
+        &mut tick_array_lower,
+        tick_array_upper.as_deref_mut(),
+        liquidity_delta,
+        timestamp,
+    )?;
+    ctx.accounts.position.record_liquidity_increase(timestamp, clock.slot);
+
+    let (delta_a, delta_b) = calculate_liquidity_token_deltas(
+        ctx.accounts.whirlpool.tick_current_index,
+        ctx.accounts.whirlpool.sqrt_price,
+        &ctx.accounts.position,
+        liquidity_delta,
+    )?;
+    record_liquidity_rounding_dust(
+        &mut ctx.accounts.whirlpool,
+        &ctx.accounts.position,
+        liquidity_delta,
+    );
+
+    let delta_a = ctx
+        .accounts
+        .whirlpool
+        .to_token_amount(delta_a, true, true)?;
+    let delta_b = ctx
+        .accounts
+        .whirlpool
+        .to_token_amount(delta_b, false, true)?;
+
+    if delta_a > params.token_max_a || delta_b > params.token_max_b {
+        return fail_with_quote_hint(ErrorCode::TokenMaxExceeded, delta_a, delta_b);
+    }
+
+    transfer_from_owner_to_vault(
+        &ctx.accounts.funder,
+        &ctx.accounts.token_owner_account_a,
+        &ctx.accounts.token_vault_a,
+        &ctx.accounts.token_program,
+        delta_a,
+    )?;
+
+    transfer_from_owner_to_vault(
+        &ctx.accounts.funder,
+        &ctx.accounts.token_owner_account_b,
+        &ctx.accounts.token_vault_b,
+        &ctx.accounts.token_program,
+        delta_b,
+    )?;
+
+    ctx.accounts
+        .whirlpool
+        .record_vault_deposits(delta_a, delta_b);
+    Ok(())
+}
+
+/// Add the seed liquidity to the position and its range ticks. `tick_array_upper` is None if the
+/// upper tick is in `tick_array_lower`.
+fn add_seed_liquidity(
+    whirlpool: &mut Whirlpool,
+    position: &mut Position,
+    tick_array_lower: &mut TickArray,
+    tick_array_upper: Option<&mut TickArray>,
+    liquidity_delta: i128,
+    timestamp: u64,
+) -> Result<()> {
+    let update = calculate_modify_liquidity(
+        whirlpool,
+        position,
+        tick_array_lower,
+        tick_array_upper.as_deref().unwrap_or(tick_array_lower),
+        liquidity_delta,
+        timestamp,
+    )?;
+
+    sync_modify_liquidity_values(
+        whirlpool,
+        position,
+        tick_array_lower,
+        tick_array_upper,
+        &update,
+        timestamp,
+    )
+}
+
+#[cfg(test)]
+mod initialize_pool_with_liquidity_tests {
+    use super::*;
+    use crate::testing::{position, tick_array};
+    use crate::util::test_utils::instruction_test_whirlpool;
+
+    const TICK_SPACING: u16 = 64;
+
+    #[test]
+    fn test_add_seed_liquidity_range_in_one_tick_array() {
+        let fixture = instruction_test_whirlpool(TICK_SPACING, 30_000, 100);
+        let whirlpool_key = fixture.find_address().0;
+        let mut whirlpool = fixture.build();
+        let (_, mut position) = position(
+            whirlpool_key,
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            0,
+            640,
+        );
+        assert_eq!(
+            TickArray::start_tick_index_for(0, TICK_SPACING),
+            TickArray::start_tick_index_for(640, TICK_SPACING)
+        );
+        let (_, mut tick_array) = tick_array(whirlpool_key, 0);
+
+        add_seed_liquidity(
+            &mut whirlpool,
+            &mut position,
+            &mut tick_array,
+            None,
+            1_000,
+            0,
+        )
+        .unwrap();
+
+        assert_eq!(whirlpool.liquidity, 1_000);
+        assert_eq!(position.liquidity, 1_000);
+        let tick_lower = tick_array.get_tick(0, TICK_SPACING).unwrap();
+        assert!(tick_lower.initialized);
+        assert_eq!({ tick_lower.liquidity_net }, 1_000);
+        let tick_upper = tick_array.get_tick(640, TICK_SPACING).unwrap();
+        assert!(tick_upper.initialized);
+        assert_eq!({ tick_upper.liquidity_net }, -1_000);
+    }
+}
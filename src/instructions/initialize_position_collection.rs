@@ -0,0 +1,142 @@
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token::{self, Mint, Token, TokenAccount};
+use mpl_token_metadata::instructions::{
+    CreateMasterEditionV3Builder, CreateMetadataAccountV3Builder,
+};
+use mpl_token_metadata::types::DataV2;
+use solana_program::program::invoke_signed;
+
+use crate::{
+    state::*,
+    util::{mint_position_token, WP_METADATA_SYMBOL, WP_METADATA_URI},
+};
+
+const WP_COLLECTION_METADATA_NAME: &str = "Orca Whirlpool Positions";
+
+#[derive(Accounts)]
+pub struct InitializePositionCollection<'info> {
+    #[account(mut)]
+    pub whirlpool: Box<Account<'info, Whirlpool>>,
+
+    #[account(mut)]
+    pub funder: Signer<'info>,
+
+    #[account(init,
+      payer = funder,
+      seeds = [b"position_collection".as_ref(), whirlpool.key().as_ref()],
+      bump,
+      mint::authority = whirlpool,
+      mint::decimals = 0,
+    )]
+    pub collection_mint: Box<Account<'info, Mint>>,
+
+    #[account(init,
+      payer = funder,
+      associated_token::mint = collection_mint,
+      associated_token::authority = whirlpool,
+    )]
+    pub collection_token_account: Box<Account<'info, TokenAccount>>,
+
+    /// CHECK: checked via the Metadata CPI call
+    #[account(mut)]
+    pub collection_metadata: UncheckedAccount<'info>,
+
+    /// CHECK: checked via the Metadata CPI call
+    #[account(mut)]
+    pub collection_master_edition: UncheckedAccount<'info>,
+
+    /// CHECK: checked via account constraints
+    #[account(address = mpl_token_metadata::ID)]
+    pub metadata_program: UncheckedAccount<'info>,
+
+    #[account(address = token::ID)]
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+/*
+  Mints the collection NFT that positions of the Whirlpool opened with metadata are verified into,
+  so marketplaces and portfolio trackers can group positions by pool. The collection is a PDA of
+  the Whirlpool and can only be created once.
+*/
+pub fn handler(ctx: Context<InitializePositionCollection>) -> Result<()> {
+    let whirlpool = &ctx.accounts.whirlpool;
+    let collection_mint = &ctx.accounts.collection_mint;
+    let collection_metadata = &ctx.accounts.collection_metadata;
+    let funder = &ctx.accounts.funder;
+    let rent = &ctx.accounts.rent;
+
+    mint_position_token(
+        whirlpool,
+        &collection_mint.to_account_info(),
+        &ctx.accounts.collection_token_account.to_account_info(),
+        &ctx.accounts.token_program,
+    )?;
+
+    // The whirlpool is the update authority of the collection so it can verify positions into it.
+    invoke_signed(
+        &CreateMetadataAccountV3Builder::new()
+            .metadata(collection_metadata.key())
+            .mint(collection_mint.key())
+            .mint_authority(whirlpool.key())
+            .payer(funder.key())
+            .update_authority(whirlpool.key(), true)
+            .data(DataV2 {
+                name: WP_COLLECTION_METADATA_NAME.to_string(),
+                symbol: WP_METADATA_SYMBOL.to_string(),
+                uri: WP_METADATA_URI.to_string(),
+                seller_fee_basis_points: 0,
+                creators: None,
+                collection: None,
+                uses: None,
+            })
+            .is_mutable(true)
+            .rent(Some(rent.key()))
+            .instruction(),
+        &[
+            collection_metadata.to_account_info(),
+            collection_mint.to_account_info(),
+            whirlpool.to_account_info(),
+            funder.to_account_info(),
+            ctx.accounts.metadata_program.to_account_info(),
+            ctx.accounts.system_program.to_account_info(),
+            rent.to_account_info(),
+        ],
+        &[&whirlpool.seeds()],
+    )?;
+
+    // Creating the master edition caps the supply of the collection at one.
+    invoke_signed(
+        &CreateMasterEditionV3Builder::new()
+            .edition(ctx.accounts.collection_master_edition.key())
+            .mint(collection_mint.key())
+            .update_authority(whirlpool.key())
+            .mint_authority(whirlpool.key())
+            .payer(funder.key())
+            .metadata(collection_metadata.key())
+            .rent(Some(rent.key()))
+            .max_supply(0)
+            .instruction(),
+        &[
+            ctx.accounts.collection_master_edition.to_account_info(),
+            collection_mint.to_account_info(),
+            whirlpool.to_account_info(),
+            funder.to_account_info(),
+            collection_metadata.to_account_info(),
+            ctx.accounts.token_program.to_account_info(),
+            ctx.accounts.system_program.to_account_info(),
+            rent.to_account_info(),
+            ctx.accounts.metadata_program.to_account_info(),
+        ],
+        &[&whirlpool.seeds()],
+    )?;
+
+    ctx.accounts
+        .whirlpool
+        .update_position_collection_mint(ctx.accounts.collection_mint.key());
+
+    Ok(())
+}
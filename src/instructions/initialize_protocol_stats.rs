@@ -0,0 +1,26 @@
+use anchor_lang::prelude::*;
+
+use crate::state::ProtocolStats;
+
+#[derive(Accounts)]
+pub struct InitializeProtocolStats<'info> {
+    #[account(mut)]
+    pub funder: Signer<'info>,
+
+    #[account(
+      init,
+      payer = funder,
+      seeds = [b"protocol_stats".as_ref()],
+      bump,
+      space = ProtocolStats::LEN)]
+    pub protocol_stats: Box<Account<'info, ProtocolStats>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/*
+  Creates the singleton protocol stats account, which pool creation requires.
+*/
+pub fn handler(_ctx: Context<InitializeProtocolStats>) -> Result<()> {
+    Ok(())
+}
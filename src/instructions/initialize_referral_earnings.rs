@@ -0,0 +1,39 @@
+use anchor_lang::prelude::*;
+
+use crate::state::{ReferralEarnings, Whirlpool};
+
+#[derive(Accounts)]
+pub struct InitializeReferralEarnings<'info> {
+    pub whirlpool: Box<Account<'info, Whirlpool>>,
+
+    /// CHECK: any account can be a referrer, it only has to sign to claim
+    pub referrer: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub funder: Signer<'info>,
+
+    #[account(
+      init,
+      payer = funder,
+      seeds = [
+        b"referral_earnings".as_ref(),
+        whirlpool.key().as_ref(),
+        referrer.key().as_ref()
+      ],
+      bump,
+      space = ReferralEarnings::LEN)]
+    pub referral_earnings: Box<Account<'info, ReferralEarnings>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/*
+  Creates the referral earnings of a referrer in a Whirlpool. Swaps attributed to the referrer
+  credit its share of the protocol fee to the account from then on.
+*/
+pub fn handler(ctx: Context<InitializeReferralEarnings>) -> Result<()> {
+    ctx.accounts
+        .referral_earnings
+        .initialize(ctx.accounts.whirlpool.key(), ctx.accounts.referrer.key());
+    Ok(())
+}
@@ -0,0 +1,44 @@
+use anchor_lang::prelude::*;
+
+use crate::state::{RentSponsor, Whirlpool};
+
+#[derive(Accounts)]
+pub struct InitializeRentSponsor<'info> {
+    pub whirlpool: Box<Account<'info, Whirlpool>>,
+
+    pub authority: Signer<'info>,
+
+    #[account(mut)]
+    pub funder: Signer<'info>,
+
+    #[account(
+      init,
+      payer = funder,
+      seeds = [
+        b"rent_sponsor".as_ref(),
+        whirlpool.key().as_ref(),
+        authority.key().as_ref()
+      ],
+      bump,
+      space = RentSponsor::LEN)]
+    pub rent_sponsor: Box<Account<'info, RentSponsor>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/*
+  Creates a rent sponsor for the tick arrays of a Whirlpool starting within the given range. The
+  sponsor pays nothing until it is funded with `fund_rent_sponsor`.
+*/
+pub fn handler(
+    ctx: Context<InitializeRentSponsor>,
+    start_tick_index_min: i32,
+    start_tick_index_max: i32,
+) -> Result<()> {
+    ctx.accounts.rent_sponsor.initialize(
+        ctx.accounts.whirlpool.key(),
+        ctx.accounts.authority.key(),
+        start_tick_index_min,
+        start_tick_index_max,
+    )
+}
@@ -0,0 +1,57 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Mint, Token, TokenAccount};
+
+use crate::state::{RetroRewardDistributor, Whirlpool};
+
+#[derive(Accounts)]
+#[instruction(reward_index: u8)]
+pub struct InitializeRetroReward<'info> {
+    pub whirlpool: Box<Account<'info, Whirlpool>>,
+
+    #[account(address = whirlpool.reward_infos[reward_index as usize].authority)]
+    pub reward_authority: Signer<'info>,
+
+    #[account(mut)]
+    pub funder: Signer<'info>,
+
+    pub reward_mint: Box<Account<'info, Mint>>,
+
+    #[account(
+      init,
+      payer = funder,
+      seeds = [
+        b"retro_reward".as_ref(),
+        whirlpool.key().as_ref(),
+        reward_mint.key().as_ref()
+      ],
+      bump,
+      space = RetroRewardDistributor::LEN)]
+    pub retro_reward: Box<Account<'info, RetroRewardDistributor>>,
+
+    #[account(init,
+      payer = funder,
+      token::mint = reward_mint,
+      token::authority = whirlpool
+    )]
+    pub reward_vault: Box<Account<'info, TokenAccount>>,
+
+    #[account(address = token::ID)]
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+/*
+  Creates a retroactive reward distributor of a Whirlpool and its vault, owned by the Whirlpool
+  like the reward vaults. The vault is funded with plain token transfers and nothing can be
+  claimed until a merkle root is set.
+*/
+pub fn handler(ctx: Context<InitializeRetroReward>, reward_index: u8) -> Result<()> {
+    ctx.accounts.retro_reward.initialize(
+        ctx.accounts.whirlpool.key(),
+        reward_index,
+        ctx.accounts.reward_mint.key(),
+        ctx.accounts.reward_vault.key(),
+    );
+    Ok(())
+}
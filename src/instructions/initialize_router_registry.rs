@@ -0,0 +1,34 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::ErrorCode;
+use crate::state::{RouterRegistry, WhirlpoolsConfig};
+
+#[derive(Accounts)]
+pub struct InitializeRouterRegistry<'info> {
+    pub whirlpools_config: Box<Account<'info, WhirlpoolsConfig>>,
+
+    #[account(
+        address = whirlpools_config.fee_authority,
+        constraint = !whirlpools_config.admin_frozen @ ErrorCode::AdminParametersFrozen,
+    )]
+    pub fee_authority: Signer<'info>,
+
+    #[account(mut)]
+    pub funder: Signer<'info>,
+
+    #[account(
+      init,
+      payer = funder,
+      seeds = [b"router_registry".as_ref(), whirlpools_config.key().as_ref()],
+      bump,
+      space = RouterRegistry::LEN)]
+    pub router_registry: Box<Account<'info, RouterRegistry>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<InitializeRouterRegistry>, fee_discount_rate: u16) -> Result<()> {
+    ctx.accounts
+        .router_registry
+        .initialize(ctx.accounts.whirlpools_config.key(), fee_discount_rate)
+}
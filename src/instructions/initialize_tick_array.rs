@@ -1,7 +1,9 @@
-
 use anchor_lang::prelude::*;
+use anchor_lang::Discriminator;
 
+use crate::errors::ErrorCode;
 use crate::state::*;
+use crate::util::{draw_rent_sponsor, link_tick_array_neighbours, verify_start_tick_index};
 
 #[derive(Accounts)]
 #[instruction(start_tick_index: i32)]
@@ -12,17 +14,58 @@ pub struct InitializeTickArray<'info> {
     pub funder: Signer<'info>,
 
     #[account(
-      mut,
-      // seeds = [b"tick_array", whirlpool.key().as_ref(), start_tick_index.to_string().as_bytes()],
-      //bump,
-      //space = TickArray::LEN)]
-    )]
+      init,
+      payer = funder,
+      seeds = [b"tick_array", whirlpool.key().as_ref(), start_tick_index.to_string().as_bytes()],
+      bump,
+      space = TickArray::LEN)]
     pub tick_array: AccountLoader<'info, TickArray>,
 
     pub system_program: Program<'info, System>,
 }
 
-pub fn handler(ctx: Context<InitializeTickArray>, start_tick_index: i32) -> Result<()> {
+/*
+  Initializes a tick array and optionally links it to the neighbouring initialized tick arrays,
+  passed as up to two writable remaining accounts, to maintain the paging hints for indexers.
+  A rent sponsor of the Whirlpool can be passed along them to refund the rent to the funder.
+*/
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, InitializeTickArray<'info>>,
+    start_tick_index: i32,
+) -> Result<()> {
+    verify_start_tick_index(start_tick_index, ctx.accounts.whirlpool.tick_spacing)?;
+
     let mut tick_array = ctx.accounts.tick_array.load_init()?;
-    Ok(tick_array.initialize(&ctx.accounts.whirlpool, start_tick_index)?)
-}
\ No newline at end of file
+    tick_array.initialize(&ctx.accounts.whirlpool, start_tick_index)?;
+
+    let mut neighbours = Vec::with_capacity(2);
+    let mut sponsored = false;
+    for account_info in ctx.remaining_accounts {
+        if is_rent_sponsor(account_info)? {
+            let rent_sponsor = Account::<RentSponsor>::try_from(account_info)?;
+            if sponsored
+                || !rent_sponsor.is_sponsored(&ctx.accounts.whirlpool.key(), start_tick_index)
+            {
+                return Err(ErrorCode::InvalidRentSponsor.into());
+            }
+            draw_rent_sponsor(
+                account_info,
+                &ctx.accounts.funder.to_account_info(),
+                Rent::get()?.minimum_balance(TickArray::LEN),
+            )?;
+            sponsored = true;
+            continue;
+        }
+
+        neighbours.push(account_info);
+    }
+
+    link_tick_array_neighbours(&mut tick_array, &neighbours)
+}
+
+fn is_rent_sponsor(account_info: &AccountInfo) -> Result<bool> {
+    Ok(account_info.owner == &crate::ID
+        && account_info
+            .try_borrow_data()?
+            .starts_with(&RentSponsor::discriminator()))
+}
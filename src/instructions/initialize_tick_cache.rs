@@ -0,0 +1,47 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::ErrorCode;
+use crate::state::{TickCache, Whirlpool, WhirlpoolsConfig};
+
+#[derive(Accounts)]
+pub struct InitializeTickCache<'info> {
+    pub whirlpools_config: Account<'info, WhirlpoolsConfig>,
+
+    #[account(mut, has_one = whirlpools_config)]
+    pub whirlpool: Account<'info, Whirlpool>,
+
+    #[account(
+        address = whirlpools_config.fee_authority,
+        constraint = !whirlpools_config.admin_frozen @ ErrorCode::AdminParametersFrozen,
+    )]
+    pub fee_authority: Signer<'info>,
+
+    #[account(mut)]
+    pub funder: Signer<'info>,
+
+    #[account(
+      init,
+      payer = funder,
+      seeds = [b"tick_cache".as_ref(), whirlpool.key().as_ref()],
+      bump,
+      space = TickCache::LEN)]
+    pub tick_cache: AccountLoader<'info, TickCache>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/*
+  Creates the tick cache of a Whirlpool. The cache starts out stale and has to be loaded with
+  refresh_tick_cache before swaps can use it.
+*/
+pub fn handler(ctx: Context<InitializeTickCache>) -> Result<()> {
+    let whirlpool_key = ctx.accounts.whirlpool.key();
+    ctx.accounts
+        .tick_cache
+        .load_init()?
+        .initialize(whirlpool_key);
+    ctx.accounts
+        .whirlpool
+        .update_tick_cache(ctx.accounts.tick_cache.key());
+    Ok(())
+}
@@ -0,0 +1,44 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::Mint;
+
+use crate::errors::ErrorCode;
+use crate::state::{TokenBadge, WhirlpoolsConfig};
+
+#[derive(Accounts)]
+pub struct InitializeTokenBadge<'info> {
+    pub whirlpools_config: Box<Account<'info, WhirlpoolsConfig>>,
+
+    pub token_mint: Account<'info, Mint>,
+
+    #[account(
+        address = whirlpools_config.fee_authority,
+        constraint = !whirlpools_config.admin_frozen @ ErrorCode::AdminParametersFrozen,
+    )]
+    pub fee_authority: Signer<'info>,
+
+    #[account(mut)]
+    pub funder: Signer<'info>,
+
+    #[account(
+      init,
+      payer = funder,
+      seeds = [
+        b"token_badge".as_ref(),
+        whirlpools_config.key().as_ref(),
+        token_mint.key().as_ref()
+      ],
+      bump,
+      space = TokenBadge::LEN)]
+    pub token_badge: Box<Account<'info, TokenBadge>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<InitializeTokenBadge>, allow_freeze_authority: bool) -> Result<()> {
+    ctx.accounts.token_badge.initialize(
+        ctx.accounts.whirlpools_config.key(),
+        ctx.accounts.token_mint.key(),
+        allow_freeze_authority,
+    );
+    Ok(())
+}
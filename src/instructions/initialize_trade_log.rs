@@ -0,0 +1,47 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::ErrorCode;
+use crate::state::{TradeLog, Whirlpool, WhirlpoolsConfig};
+
+#[derive(Accounts)]
+pub struct InitializeTradeLog<'info> {
+    pub whirlpools_config: Account<'info, WhirlpoolsConfig>,
+
+    #[account(mut, has_one = whirlpools_config)]
+    pub whirlpool: Account<'info, Whirlpool>,
+
+    #[account(
+        address = whirlpools_config.fee_authority,
+        constraint = !whirlpools_config.admin_frozen @ ErrorCode::AdminParametersFrozen,
+    )]
+    pub fee_authority: Signer<'info>,
+
+    #[account(mut)]
+    pub funder: Signer<'info>,
+
+    #[account(
+      init,
+      payer = funder,
+      seeds = [b"trade_log".as_ref(), whirlpool.key().as_ref()],
+      bump,
+      space = TradeLog::LEN)]
+    pub trade_log: AccountLoader<'info, TradeLog>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/*
+  Creates the trade log of a Whirlpool. Every swap of the pool records itself to the log from
+  then on, at the cost of one extra account per swap.
+*/
+pub fn handler(ctx: Context<InitializeTradeLog>) -> Result<()> {
+    let whirlpool_key = ctx.accounts.whirlpool.key();
+    ctx.accounts
+        .trade_log
+        .load_init()?
+        .initialize(whirlpool_key);
+    ctx.accounts
+        .whirlpool
+        .update_trade_log(ctx.accounts.trade_log.key());
+    Ok(())
+}
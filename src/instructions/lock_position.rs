@@ -0,0 +1,76 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::TokenAccount;
+
+use crate::errors::ErrorCode;
+use crate::events::PositionLocked;
+use crate::state::*;
+use crate::util::{to_timestamp_u64, verify_position_authority};
+
+#[derive(Accounts)]
+pub struct LockPosition<'info> {
+    #[account(mut)]
+    pub funder: Signer<'info>,
+
+    pub position_authority: Signer<'info>,
+
+    #[account(mut)]
+    pub position: Account<'info, Position>,
+
+    #[account(
+        constraint = position_token_account.mint == position.position_mint,
+        constraint = position_token_account.amount == 1
+    )]
+    pub position_token_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(init,
+      payer = funder,
+      space = LiquidityLockReceipt::LEN,
+      seeds = [b"lock_receipt".as_ref(), position.key().as_ref()],
+      bump,
+    )]
+    pub lock_receipt: Account<'info, LiquidityLockReceipt>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/*
+  Locks the current liquidity of a position until the unlock timestamp. The lock stays with the
+  position if the position token changes hands.
+*/
+pub fn handler(ctx: Context<LockPosition>, unlock_timestamp: u64) -> Result<()> {
+    verify_position_authority(
+        &ctx.accounts.position_token_account,
+        &ctx.accounts.position_authority,
+    )?;
+
+    let clock = Clock::get()?;
+    let timestamp = to_timestamp_u64(clock.unix_timestamp)?;
+
+    if unlock_timestamp <= timestamp {
+        return Err(ErrorCode::InvalidUnlockTimestamp.into());
+    }
+
+    let position = &mut ctx.accounts.position;
+    if position.liquidity == 0 {
+        return Err(ErrorCode::LiquidityZero.into());
+    }
+    position.lock_liquidity(unlock_timestamp);
+
+    ctx.accounts.lock_receipt.initialize(
+        position,
+        ctx.accounts.position_authority.key(),
+        ctx.bumps.lock_receipt,
+        timestamp,
+        unlock_timestamp,
+    );
+
+    emit!(PositionLocked {
+        whirlpool: position.whirlpool,
+        position: position.key(),
+        locker: ctx.accounts.position_authority.key(),
+        locked_liquidity: position.locked_liquidity,
+        unlock_timestamp,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,29 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::ErrorCode;
+use crate::state::{Whirlpool, WhirlpoolsConfig};
+
+#[derive(Accounts)]
+pub struct MarkTotalLiquidityComplete<'info> {
+    pub whirlpools_config: Account<'info, WhirlpoolsConfig>,
+
+    #[account(mut, has_one = whirlpools_config)]
+    pub whirlpool: Account<'info, Whirlpool>,
+
+    #[account(
+        address = whirlpools_config.fee_authority,
+        constraint = !whirlpools_config.admin_frozen @ ErrorCode::AdminParametersFrozen,
+    )]
+    pub fee_authority: Signer<'info>,
+}
+
+/*
+  Marks the total liquidity of a Whirlpool created before the counter as complete, once every
+  position opened before has been migrated and counted. The program cannot enumerate the positions
+  of a pool, so the fee authority confirms it off-chain, e.g. by finding no position of the pool
+  with the legacy size.
+*/
+pub fn handler(ctx: Context<MarkTotalLiquidityComplete>) -> Result<()> {
+    ctx.accounts.whirlpool.mark_total_liquidity_complete();
+    Ok(())
+}
@@ -0,0 +1,69 @@
+use anchor_lang::{prelude::*, Discriminator};
+
+use crate::errors::ErrorCode;
+use crate::state::{FeeSchedule, Whirlpool};
+use crate::util::realloc_account;
+
+#[derive(Accounts)]
+pub struct MigrateFeeRatePrecision<'info> {
+    #[account(mut)]
+    pub funder: Signer<'info>,
+
+    /// CHECK: Whirlpools created before the fee rate precision increase are shorter than
+    /// Whirlpool::LEN and cannot be deserialized until they are reallocated
+    #[account(mut, owner = crate::ID)]
+    pub whirlpool: UncheckedAccount<'info>,
+
+    /// CHECK: Checked against the fee schedule of the Whirlpool in the handler. Required if the
+    /// Whirlpool has a fee schedule
+    #[account(mut)]
+    pub fee_schedule: Option<UncheckedAccount<'info>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/*
+  Reallocates a Whirlpool created before the fee rate precision increase to the current size,
+  then stores its legacy fee rate as its precise fee rate. Fee schedules with the legacy layout
+  are reallocated as well, and the rates of their fee holidays converted to thousandths of a basis
+  point. Whirlpools and fee schedules that are already migrated are left as is.
+*/
+pub fn handler(ctx: Context<MigrateFeeRatePrecision>) -> Result<()> {
+    let whirlpool_info = ctx.accounts.whirlpool.to_account_info();
+    if whirlpool_info.try_borrow_data()?[..8] != Whirlpool::DISCRIMINATOR {
+        return Err(anchor_lang::error::ErrorCode::AccountDiscriminatorMismatch.into());
+    }
+    realloc_account(
+        &whirlpool_info,
+        Whirlpool::LEN,
+        &ctx.accounts.funder,
+        &ctx.accounts.system_program,
+    )?;
+
+    let mut whirlpool = Whirlpool::try_deserialize(&mut &whirlpool_info.try_borrow_data()?[..])?;
+    whirlpool.migrate_fee_rate_precision();
+
+    if whirlpool.has_fee_schedule() {
+        let fee_schedule_info = match &ctx.accounts.fee_schedule {
+            Some(fee_schedule) if fee_schedule.key() == whirlpool.fee_schedule => {
+                fee_schedule.to_account_info()
+            }
+            _ => return Err(ErrorCode::InvalidFeeScheduleAccount.into()),
+        };
+        if fee_schedule_info.data_len() == FeeSchedule::LEGACY_LEN {
+            let fee_schedule =
+                FeeSchedule::try_from_legacy_data(&fee_schedule_info.try_borrow_data()?)?;
+            realloc_account(
+                &fee_schedule_info,
+                FeeSchedule::LEN,
+                &ctx.accounts.funder,
+                &ctx.accounts.system_program,
+            )?;
+            fee_schedule.try_serialize(&mut &mut fee_schedule_info.try_borrow_mut_data()?[..])?;
+        }
+    }
+
+    whirlpool.try_serialize(&mut &mut whirlpool_info.try_borrow_mut_data()?[..])?;
+
+    Ok(())
+}
@@ -0,0 +1,146 @@
+use anchor_lang::{prelude::*, Discriminator};
+
+use crate::state::{Position, Whirlpool};
+use crate::util::realloc_account;
+
+#[derive(Accounts)]
+pub struct MigratePosition<'info> {
+    #[account(mut)]
+    pub funder: Signer<'info>,
+
+    /// CHECK: Positions opened before the fields following the reward infos were appended are
+    /// shorter than Position::LEN and cannot be deserialized until they are reallocated
+    #[account(mut, owner = crate::ID)]
+    pub position: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub whirlpool: Box<Account<'info, Whirlpool>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/*
+  Reallocates a position with the legacy layout to the current size. The appended fields are
+  zeroed, which reads as schema version 0 and leaves every opt-in of the position disabled. Its
+  liquidity is added to the total liquidity of its Whirlpool, which does not count positions opened
+  before it was tracked. Positions that are already migrated are left as is.
+*/
+pub fn handler(ctx: Context<MigratePosition>) -> Result<()> {
+    let position_info = ctx.accounts.position.to_account_info();
+    if !position_info
+        .try_borrow_data()?
+        .starts_with(&Position::DISCRIMINATOR)
+    {
+        return Err(anchor_lang::error::ErrorCode::AccountDiscriminatorMismatch.into());
+    }
+    if position_info.data_len() >= Position::LEN {
+        return Ok(());
+    }
+    realloc_account(
+        &position_info,
+        Position::LEN,
+        &ctx.accounts.funder,
+        &ctx.accounts.system_program,
+    )?;
+
+    let position = Position::try_deserialize(&mut &position_info.try_borrow_data()?[..])?;
+    if position.whirlpool != ctx.accounts.whirlpool.key() {
+        return Err(anchor_lang::error::ErrorCode::ConstraintHasOne.into());
+    }
+    ctx.accounts
+        .whirlpool
+        .count_migrated_position_liquidity(position.liquidity)
+}
+
+#[cfg(test)]
+mod migrate_position_tests {
+    use super::*;
+    use crate::instructions::{decrease_liquidity, ModifyLiquidity};
+    use crate::testing::*;
+    use crate::util::test_utils::*;
+    use anchor_spl::token;
+
+    const TICK_SPACING: u16 = 64;
+
+    #[test]
+    fn test_decrease_liquidity_of_migrated_legacy_position() {
+        let fixture = instruction_test_whirlpool(TICK_SPACING, 3_000, 100);
+        let whirlpool_key = fixture.find_address().0;
+        let mut whirlpool = fixture.build();
+
+        let owner = Pubkey::new_unique();
+        let position_mint = Pubkey::new_unique();
+        let (position_key, mut position) = position(whirlpool_key, position_mint, owner, 0, 5696);
+        let (lower_array_key, mut lower_array) = tick_array(whirlpool_key, 0);
+        let (upper_array_key, mut upper_array) = tick_array(whirlpool_key, 5632);
+        add_position_liquidity(&mut whirlpool, &mut position, 1_000_000).unwrap();
+        add_tick_liquidity(&mut lower_array, 0, TICK_SPACING, 1_000_000, false).unwrap();
+        add_tick_liquidity(&mut upper_array, 5696, TICK_SPACING, 1_000_000, true).unwrap();
+        // A Whirlpool created before its total liquidity was tracked, reallocated by the fee rate
+        // migration.
+        whirlpool.total_liquidity = 0;
+        whirlpool.total_liquidity_complete = false;
+
+        let whirlpool_info =
+            fixture_account_info(FixtureAccount::from_account(whirlpool_key, &whirlpool));
+        let position_info = reallocatable_account_info(
+            FixtureAccount::from_account(position_key, &position),
+            Position::LEGACY_LEN,
+        );
+        let migrate_account_infos = leak_account_infos([
+            signer_account_info(Pubkey::new_unique()),
+            position_info.clone(),
+            whirlpool_info.clone(),
+            program_account_info(System::id()),
+        ]);
+        let mut accounts = instruction_accounts::<MigratePosition>(migrate_account_infos);
+        handler(instruction_context(&mut accounts, &[])).unwrap();
+        accounts.exit(&crate::ID).unwrap();
+
+        assert_eq!(position_info.data_len(), Position::LEN);
+        let migrated_whirlpool: Whirlpool = read_account(&whirlpool_info);
+        assert_eq!(migrated_whirlpool.total_liquidity, 1_000_000);
+
+        let decrease_account_infos = leak_account_infos([
+            whirlpool_info.clone(),
+            program_account_info(token::ID),
+            signer_account_info(owner),
+            position_info.clone(),
+            token_account_info(Pubkey::new_unique(), position_mint, owner, 1),
+            token_account_info(Pubkey::new_unique(), fixture.token_mint_a, owner, 0),
+            token_account_info(Pubkey::new_unique(), fixture.token_mint_b, owner, 0),
+            token_account_info(
+                fixture.token_vault_a,
+                fixture.token_mint_a,
+                whirlpool_key,
+                1_000_000,
+            ),
+            token_account_info(
+                fixture.token_vault_b,
+                fixture.token_mint_b,
+                whirlpool_key,
+                1_000_000,
+            ),
+            fixture_account_info(FixtureAccount::from_zero_copy(
+                lower_array_key,
+                &lower_array,
+            )),
+            fixture_account_info(FixtureAccount::from_zero_copy(
+                upper_array_key,
+                &upper_array,
+            )),
+        ]);
+        let mut accounts = instruction_accounts::<ModifyLiquidity>(decrease_account_infos);
+        decrease_liquidity::handler(instruction_context(&mut accounts, &[]), 1_000_000, 0, 0)
+            .unwrap();
+        accounts.exit(&crate::ID).unwrap();
+
+        let position: Position = read_account(&position_info);
+        assert_eq!(position.liquidity, 0);
+        let whirlpool: Whirlpool = read_account(&whirlpool_info);
+        assert_eq!(whirlpool.total_liquidity, 0);
+        assert_eq!(whirlpool.liquidity, 0);
+        assert!(token_amount(&decrease_account_infos[5]) > 0);
+        assert!(token_amount(&decrease_account_infos[6]) > 0);
+    }
+}
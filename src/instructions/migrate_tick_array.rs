@@ -0,0 +1,50 @@
+use anchor_lang::prelude::*;
+use anchor_lang::Discriminator;
+
+use crate::state::TickArray;
+use crate::util::{link_tick_array_neighbours, realloc_account};
+
+#[derive(Accounts)]
+pub struct MigrateTickArray<'info> {
+    #[account(mut)]
+    pub funder: Signer<'info>,
+
+    /// CHECK: Tick arrays initialized before the paging hints, the initialized tick bitmap and
+    /// the schema version are shorter than TickArray::LEN and cannot be loaded until they are
+    /// reallocated
+    #[account(mut, owner = crate::ID)]
+    pub tick_array: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/*
+  Reallocates a tick array with the legacy layout to the current size, rebuilds its initialized
+  tick bitmap from its ticks and links it to the neighbouring initialized tick arrays, passed as
+  up to two writable remaining accounts that are already migrated. Tick arrays that are already
+  migrated are left as is.
+*/
+pub fn handler<'info>(ctx: Context<'_, '_, 'info, 'info, MigrateTickArray<'info>>) -> Result<()> {
+    let tick_array_info = ctx.accounts.tick_array.to_account_info();
+    if !tick_array_info
+        .try_borrow_data()?
+        .starts_with(&TickArray::discriminator())
+    {
+        return Err(anchor_lang::error::ErrorCode::AccountDiscriminatorMismatch.into());
+    }
+    if tick_array_info.data_len() >= TickArray::LEN {
+        return Ok(());
+    }
+    realloc_account(
+        &tick_array_info,
+        TickArray::LEN,
+        &ctx.accounts.funder,
+        &ctx.accounts.system_program,
+    )?;
+
+    let mut data = tick_array_info.try_borrow_mut_data()?;
+    let tick_array: &mut TickArray = bytemuck::from_bytes_mut(&mut data[8..TickArray::LEN]);
+    tick_array.migrate_legacy_layout();
+    let neighbours: Vec<&AccountInfo> = ctx.remaining_accounts.iter().collect();
+    link_tick_array_neighbours(tick_array, &neighbours)
+}
@@ -7,8 +7,106 @@ pub mod increase_liquidity;
 pub mod open_position;
 pub mod open_position_with_metadata;
 pub mod swap;
+pub mod swap_v2;
 pub mod update_fees_and_rewards;
 pub mod initialize_tick_array;
+pub mod initialize_pool_with_liquidity;
+pub mod remint_position_token;
+pub mod set_after_swap_hook;
+pub mod set_exchange_rate_oracle;
+pub mod refresh_exchange_rate;
+pub mod lock_position;
+pub mod extend_position_lock;
+pub mod verify_pool_integrity;
+pub mod create_session_authority;
+pub mod revoke_session_authority;
+pub mod set_reward_accrual_mode;
+pub mod propose_fee_authority;
+pub mod accept_fee_authority;
+pub mod propose_reward_authority;
+pub mod accept_reward_authority;
+pub mod redirect_owed;
+pub mod initialize_trade_log;
+pub mod initialize_position_collection;
+pub mod open_protocol_position;
+pub mod increase_protocol_liquidity;
+pub mod decrease_protocol_liquidity;
+pub mod set_swap_direction_enabled;
+pub mod reset_pool_price;
+pub mod initialize_tick_cache;
+pub mod refresh_tick_cache;
+pub mod swap_with_tick_cache;
+pub mod set_approved_liquidator;
+pub mod settle_position_for_liquidation;
+pub mod initialize_liquidity_time_accumulator;
+pub mod set_fees_in_token_b;
+pub mod initialize_referral_earnings;
+pub mod swap_with_referral;
+pub mod claim_referral_fees;
+pub mod set_referral_fee_rate;
+pub mod open_position_with_seeds;
+pub mod emergency_decrease_liquidity;
+pub mod initialize_partner_stats;
+pub mod swap_with_partner;
+pub mod set_max_positions;
+pub mod set_reward_escrow_program;
+pub mod set_tick_cross_hook;
+pub mod clone_pool_state;
+pub mod set_withdraw_authority;
+pub mod initialize_protocol_stats;
+pub mod zap_in;
+pub mod zap_out;
+pub mod swap_v3;
+pub mod set_auto_compound;
+pub mod compound_for_position;
+pub mod import_external_position;
+pub mod initialize_rent_sponsor;
+pub mod fund_rent_sponsor;
+pub mod withdraw_rent_sponsor;
+pub mod initialize_fee_schedule;
+pub mod set_fee_holiday;
+pub mod initialize_retro_reward;
+pub mod set_reward_merkle_root;
+pub mod claim_retro_reward;
+pub mod refresh_position_nft;
+pub mod get_pending_fees_and_rewards;
+pub mod verify_recent_swap;
+pub mod open_position_idempotent;
+pub mod initialize_token_badge;
+pub mod set_token_badge_allow_freeze_authority;
+pub mod set_mint_price_feed;
+pub mod checkpoint_reward_growth;
+pub mod initialize_router_registry;
+pub mod set_approved_router;
+pub mod set_router_fee_discount_rate;
+pub mod swap_with_router;
+pub mod set_liquidity_shift_alert_rate;
+pub mod arb_two_pools;
+pub mod set_jit_surcharge;
+pub mod transfer_position;
+pub mod set_fee_rate;
+pub mod migrate_fee_rate_precision;
+pub mod mark_total_liquidity_complete;
+pub mod migrate_position;
+pub mod migrate_tick_array;
+pub mod set_position_metadata_uri_template;
+pub mod initialize_keeper_tip_escrow;
+pub mod fund_keeper_tip_escrow;
+pub mod set_keeper_tips;
+pub mod withdraw_keeper_tip_escrow;
+pub mod propose_emergency_authority;
+pub mod accept_emergency_authority;
+pub mod set_pool_paused;
+pub mod set_position_recovery_authority;
+pub mod set_admin_frozen;
+pub mod freeze_and_attest_position;
+pub mod release_position_attestation;
+pub mod decrease_bundle_liquidity_pro_rata;
+pub mod open_ladder_positions;
+pub mod set_reward_tenure_boost;
+pub mod initialize_collect_routing;
+pub mod set_collect_route;
+pub mod set_reward_emission_mode;
 
 pub use close_position::*;
 pub use collect_fees::*;
@@ -19,5 +117,100 @@ pub use increase_liquidity::*;
 pub use open_position::*;
 pub use open_position_with_metadata::*;
 pub use swap::*;
+pub use swap_v2::*;
 pub use update_fees_and_rewards::*;
-pub use initialize_tick_array::*;
\ No newline at end of file
+pub use initialize_tick_array::*;
+pub use initialize_pool_with_liquidity::*;
+pub use remint_position_token::*;
+pub use set_after_swap_hook::*;
+pub use set_exchange_rate_oracle::*;
+pub use refresh_exchange_rate::*;
+pub use lock_position::*;
+pub use extend_position_lock::*;
+pub use verify_pool_integrity::*;
+pub use create_session_authority::*;
+pub use revoke_session_authority::*;
+pub use set_reward_accrual_mode::*;
+pub use propose_fee_authority::*;
+pub use accept_fee_authority::*;
+pub use propose_reward_authority::*;
+pub use accept_reward_authority::*;
+pub use redirect_owed::*;
+pub use initialize_trade_log::*;
+pub use initialize_position_collection::*;
+pub use open_protocol_position::*;
+pub use increase_protocol_liquidity::*;
+pub use set_swap_direction_enabled::*;
+pub use reset_pool_price::*;
+pub use initialize_tick_cache::*;
+pub use refresh_tick_cache::*;
+pub use swap_with_tick_cache::*;
+pub use set_approved_liquidator::*;
+pub use settle_position_for_liquidation::*;
+pub use initialize_liquidity_time_accumulator::*;
+pub use set_fees_in_token_b::*;
+pub use initialize_referral_earnings::*;
+pub use swap_with_referral::*;
+pub use claim_referral_fees::*;
+pub use set_referral_fee_rate::*;
+pub use open_position_with_seeds::*;
+pub use initialize_partner_stats::*;
+pub use swap_with_partner::*;
+pub use set_max_positions::*;
+pub use set_reward_escrow_program::*;
+pub use set_tick_cross_hook::*;
+pub use clone_pool_state::*;
+pub use set_withdraw_authority::*;
+pub use initialize_protocol_stats::*;
+pub use zap_in::*;
+pub use zap_out::*;
+pub use swap_v3::*;
+pub use set_auto_compound::*;
+pub use compound_for_position::*;
+pub use import_external_position::*;
+pub use initialize_rent_sponsor::*;
+pub use fund_rent_sponsor::*;
+pub use withdraw_rent_sponsor::*;
+pub use initialize_fee_schedule::*;
+pub use set_fee_holiday::*;
+pub use initialize_retro_reward::*;
+pub use set_reward_merkle_root::*;
+pub use claim_retro_reward::*;
+pub use refresh_position_nft::*;
+pub use get_pending_fees_and_rewards::*;
+pub use verify_recent_swap::*;
+pub use open_position_idempotent::*;
+pub use initialize_token_badge::*;
+pub use set_token_badge_allow_freeze_authority::*;
+pub use set_mint_price_feed::*;
+pub use checkpoint_reward_growth::*;
+pub use initialize_router_registry::*;
+pub use set_approved_router::*;
+pub use set_router_fee_discount_rate::*;
+pub use swap_with_router::*;
+pub use set_liquidity_shift_alert_rate::*;
+pub use arb_two_pools::*;
+pub use set_jit_surcharge::*;
+pub use transfer_position::*;
+pub use set_fee_rate::*;
+pub use migrate_fee_rate_precision::*;
+pub use mark_total_liquidity_complete::*;
+pub use migrate_position::*;
+pub use migrate_tick_array::*;
+pub use set_position_metadata_uri_template::*;
+pub use initialize_keeper_tip_escrow::*;
+pub use fund_keeper_tip_escrow::*;
+pub use set_keeper_tips::*;
+pub use withdraw_keeper_tip_escrow::*;
+pub use propose_emergency_authority::*;
+pub use accept_emergency_authority::*;
+pub use set_pool_paused::*;
+pub use set_position_recovery_authority::*;
+pub use set_admin_frozen::*;
+pub use freeze_and_attest_position::*;
+pub use release_position_attestation::*;
+pub use decrease_bundle_liquidity_pro_rata::*;
+pub use open_ladder_positions::*;
+pub use set_reward_tenure_boost::*;
+pub use initialize_collect_routing::*;
+pub use set_collect_route::*;
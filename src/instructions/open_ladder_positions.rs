@@ -0,0 +1,287 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{self, CreateAccount};
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token::{self, Token, TokenAccount};
+
+use crate::errors::ErrorCode;
+use crate::state::*;
+use crate::util::{
+    create_position_mint_and_token_account, fail_with_quote_hint,
+    mint_created_position_token_and_remove_authority, PositionTokenPrograms,
+};
+
+use super::swap_v2::load_tick_array;
+use super::{increase_position_liquidity, IncreaseLiquidityParams, LiquidityTokenAccounts};
+
+// Remaining accounts per position: the position PDA (writable), the position mint (signer,
+// writable), the associated token account of the owner for the mint (writable) and the lower and
+// upper tick arrays (writable).
+pub const LADDER_ACCOUNTS_PER_POSITION: usize = 5;
+
+pub const MAX_LADDER_POSITIONS: usize = 8;
+
+/// Open position accounts without a position, the positions are created from remaining accounts
+/// in groups of `LADDER_ACCOUNTS_PER_POSITION`. The funder pays the rent and deposits the tokens.
+#[derive(Accounts)]
+pub struct OpenLadderPositions<'info> {
+    #[account(mut)]
+    pub funder: Signer<'info>,
+
+    pub owner: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub whirlpool: Box<Account<'info, Whirlpool>>,
+
+    #[account(mut, constraint = token_owner_account_a.mint == whirlpool.token_mint_a)]
+    pub token_owner_account_a: Box<Account<'info, TokenAccount>>,
+    #[account(mut, constraint = token_owner_account_b.mint == whirlpool.token_mint_b)]
+    pub token_owner_account_b: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut, constraint = token_vault_a.key() == whirlpool.token_vault_a)]
+    pub token_vault_a: Box<Account<'info, TokenAccount>>,
+    #[account(mut, constraint = token_vault_b.key() == whirlpool.token_vault_b)]
+    pub token_vault_b: Box<Account<'info, TokenAccount>>,
+
+    #[account(address = token::ID)]
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+/*
+  Opens one position per liquidity weight, splitting the band into equally wide adjacent ranges,
+  and deposits the total liquidity split by the weights. The slippage bounds apply to the total
+  deposited.
+*/
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, OpenLadderPositions<'info>>,
+    tick_lower_index: i32,
+    tick_upper_index: i32,
+    liquidity_amount: u128,
+    liquidity_weights: Vec<u16>,
+    token_max_a: u64,
+    token_max_b: u64,
+) -> Result<()> {
+    let ranges = ladder_ranges(
+        tick_lower_index,
+        tick_upper_index,
+        ctx.accounts.whirlpool.tick_spacing,
+        liquidity_weights.len(),
+    )?;
+    let liquidity_amounts = ladder_liquidity(liquidity_amount, &liquidity_weights)?;
+    if ctx.remaining_accounts.len() != ranges.len() * LADDER_ACCOUNTS_PER_POSITION {
+        return Err(ErrorCode::RemainingAccountsMissing.into());
+    }
+
+    let whirlpool_key = ctx.accounts.whirlpool.key();
+    let balance_a = ctx.accounts.token_owner_account_a.amount;
+    let balance_b = ctx.accounts.token_owner_account_b.amount;
+
+    let rungs = ctx
+        .remaining_accounts
+        .chunks_exact(LADDER_ACCOUNTS_PER_POSITION)
+        .zip(ranges)
+        .zip(liquidity_amounts);
+    for ((position_accounts, (lower, upper)), liquidity) in rungs {
+        let position_info = &position_accounts[0];
+        let position_mint_info = &position_accounts[1];
+        let position_token_account_info = &position_accounts[2];
+
+        ctx.accounts.whirlpool.add_open_position()?;
+        create_position_account(
+            &ctx.accounts.funder,
+            position_info,
+            position_mint_info,
+            &ctx.accounts.system_program,
+        )?;
+        create_position_mint_and_token_account(
+            &ctx.accounts.funder.to_account_info(),
+            &ctx.accounts.owner.to_account_info(),
+            &ctx.accounts.whirlpool,
+            position_mint_info,
+            position_token_account_info,
+            PositionTokenPrograms {
+                token_program: &ctx.accounts.token_program,
+                system_program: &ctx.accounts.system_program,
+                associated_token_program: &ctx.accounts.associated_token_program,
+            },
+        )?;
+
+        let mut position_data = Position::default();
+        position_data.open_position(
+            &ctx.accounts.whirlpool,
+            position_mint_info.key(),
+            ctx.accounts.owner.key(),
+            lower,
+            upper,
+        )?;
+        position_data.try_serialize(&mut &mut position_info.try_borrow_mut_data()?[..])?;
+
+        mint_created_position_token_and_remove_authority(
+            &ctx.accounts.whirlpool,
+            position_mint_info,
+            position_token_account_info,
+            &ctx.accounts.token_program,
+        )?;
+
+        if liquidity == 0 {
+            continue;
+        }
+        let mut position = Account::<Position>::try_from(position_info)?;
+        let tick_array_lower = load_tick_array(&position_accounts[3], &whirlpool_key)?;
+        let tick_array_upper = load_tick_array(&position_accounts[4], &whirlpool_key)?;
+        increase_position_liquidity(
+            &mut ctx.accounts.whirlpool,
+            &mut position,
+            &tick_array_lower,
+            &tick_array_upper,
+            &ctx.accounts.funder,
+            LiquidityTokenAccounts {
+                token_owner_account_a: &ctx.accounts.token_owner_account_a,
+                token_owner_account_b: &ctx.accounts.token_owner_account_b,
+                token_vault_a: &ctx.accounts.token_vault_a,
+                token_vault_b: &ctx.accounts.token_vault_b,
+                token_program: &ctx.accounts.token_program,
+            },
+            IncreaseLiquidityParams {
+                liquidity_amount: liquidity,
+                token_max_a: u64::MAX,
+                token_max_b: u64::MAX,
+            },
+        )?;
+        position.exit(&crate::ID)?;
+    }
+
+    ctx.accounts.token_owner_account_a.reload()?;
+    ctx.accounts.token_owner_account_b.reload()?;
+    let amount_a = balance_a - ctx.accounts.token_owner_account_a.amount;
+    let amount_b = balance_b - ctx.accounts.token_owner_account_b.amount;
+    if amount_a > token_max_a || amount_b > token_max_b {
+        return fail_with_quote_hint(ErrorCode::TokenMaxExceeded, amount_a, amount_b);
+    }
+
+    Ok(())
+}
+
+// Create the position PDA of the mint, owned by this program.
+fn create_position_account<'info>(
+    funder: &Signer<'info>,
+    position_info: &AccountInfo<'info>,
+    position_mint_info: &AccountInfo<'info>,
+    system_program: &Program<'info, System>,
+) -> Result<()> {
+    let (position_address, position_bump) = Pubkey::find_program_address(
+        &[b"position".as_ref(), position_mint_info.key.as_ref()],
+        &crate::ID,
+    );
+    if position_info.key() != position_address {
+        return Err(ErrorCode::PositionMismatch.into());
+    }
+
+    system_program::create_account(
+        CpiContext::new_with_signer(
+            system_program.to_account_info(),
+            CreateAccount {
+                from: funder.to_account_info(),
+                to: position_info.clone(),
+            },
+            &[&[
+                b"position".as_ref(),
+                position_mint_info.key.as_ref(),
+                &[position_bump],
+            ]],
+        ),
+        Rent::get()?.minimum_balance(Position::LEN),
+        Position::LEN as u64,
+        &crate::ID,
+    )
+}
+
+/// Split the band between the tick indexes into `count` adjacent ranges of equal width.
+///
+/// # Errors
+/// - `InvalidLadder` - The count is 0 or above `MAX_LADDER_POSITIONS`, or the band does not split
+///   into ranges that are whole multiples of the tick spacing.
+pub fn ladder_ranges(
+    tick_lower_index: i32,
+    tick_upper_index: i32,
+    tick_spacing: u16,
+    count: usize,
+) -> Result<Vec<(i32, i32)>> {
+    if count == 0 || count > MAX_LADDER_POSITIONS || tick_lower_index >= tick_upper_index {
+        return Err(ErrorCode::InvalidLadder.into());
+    }
+    let band_width = tick_upper_index - tick_lower_index;
+    let width = band_width / count as i32;
+    if width * count as i32 != band_width || width % tick_spacing as i32 != 0 {
+        return Err(ErrorCode::InvalidLadder.into());
+    }
+
+    Ok((0..count as i32)
+        .map(|i| {
+            let lower = tick_lower_index + i * width;
+            (lower, lower + width)
+        })
+        .collect())
+}
+
+/// Split the liquidity by the weights, rounding each share down.
+///
+/// # Errors
+/// - `InvalidLadder` - The weights sum to 0.
+pub fn ladder_liquidity(liquidity_amount: u128, weights: &[u16]) -> Result<Vec<u128>> {
+    let total_weight: u128 = weights.iter().map(|weight| *weight as u128).sum();
+    if total_weight == 0 {
+        return Err(ErrorCode::InvalidLadder.into());
+    }
+    // Splitting off the remainder keeps the products within u128 for any liquidity.
+    Ok(weights
+        .iter()
+        .map(|weight| {
+            let weight = *weight as u128;
+            liquidity_amount / total_weight * weight
+                + liquidity_amount % total_weight * weight / total_weight
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod open_ladder_positions_tests {
+    use super::*;
+
+    #[test]
+    fn test_ladder_ranges() {
+        assert_eq!(
+            ladder_ranges(-128, 128, 64, 4).unwrap(),
+            vec![(-128, -64), (-64, 0), (0, 64), (64, 128)]
+        );
+        assert_eq!(ladder_ranges(0, 96, 8, 1).unwrap(), vec![(0, 96)]);
+    }
+
+    #[test]
+    fn test_ladder_ranges_invalid() {
+        let invalid = ErrorCode::InvalidLadder.into();
+        assert_eq!(ladder_ranges(0, 128, 64, 0).unwrap_err(), invalid);
+        assert_eq!(ladder_ranges(0, 640, 64, 9).unwrap_err(), invalid);
+        assert_eq!(ladder_ranges(128, 0, 64, 2).unwrap_err(), invalid);
+        // Ranges of 96 ticks are not multiples of the tick spacing.
+        assert_eq!(ladder_ranges(0, 192, 64, 2).unwrap_err(), invalid);
+        // 128 ticks do not split into 3 equal ranges.
+        assert_eq!(ladder_ranges(0, 128, 8, 3).unwrap_err(), invalid);
+    }
+
+    #[test]
+    fn test_ladder_liquidity() {
+        assert_eq!(
+            ladder_liquidity(1_000, &[1, 2, 1]).unwrap(),
+            vec![250, 500, 250]
+        );
+        assert_eq!(ladder_liquidity(10, &[1, 1, 1]).unwrap(), vec![3, 3, 3]);
+        assert_eq!(ladder_liquidity(1_000, &[0, 1]).unwrap(), vec![0, 1_000]);
+        assert_eq!(ladder_liquidity(u128::MAX, &[1]).unwrap(), vec![u128::MAX]);
+        assert_eq!(
+            ladder_liquidity(1_000, &[0, 0]).unwrap_err(),
+            ErrorCode::InvalidLadder.into()
+        );
+    }
+}
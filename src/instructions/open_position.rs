@@ -1,36 +1,42 @@
 use anchor_lang::prelude::*;
 use anchor_spl::associated_token::AssociatedToken;
-use anchor_spl::token::{self, Mint, Token, TokenAccount};
+use anchor_spl::token::{self, Token};
 
-use crate::{state::*, util::mint_position_token_and_remove_authority};
+use crate::{
+    state::*,
+    util::{
+        create_position_mint_and_token_account, mint_created_position_token_and_remove_authority,
+        PositionTokenPrograms,
+    },
+};
 
 #[derive(Accounts)]
-#[instruction(bumps: OpenPositionBumps)]
 pub struct OpenPosition<'info> {
     #[account(mut)]
     pub funder: Signer<'info>,
 
+    // Any account can own the position, including program derived addresses off the curve
     pub owner: UncheckedAccount<'info>,
 
-    #[account(mut,
+    #[account(init,
+      payer = funder,
+      space = Position::LEN,
       seeds = [b"position".as_ref(), position_mint.key().as_ref()],
-      bump = bumps.position_bump,
+      bump,
     )]
     pub position: Box<Account<'info, Position>>,
 
-    #[account(mut, signer,
-        mint::authority = whirlpool,
-        mint::decimals = 0,
-    )]
-    pub position_mint: Account<'info, Mint>,
+    // Created by the handler
+    #[account(mut)]
+    pub position_mint: Signer<'info>,
 
-    #[account(mut,
-      associated_token::mint = position_mint,
-      associated_token::authority = owner,
-    )]
-    pub position_token_account: Box<Account<'info, TokenAccount>>,
+    /// CHECK: the associated token account of the owner, created by the handler if missing. The
+    /// associated token program checks its address
+    #[account(mut)]
+    pub position_token_account: UncheckedAccount<'info>,
 
-    pub whirlpool: UncheckedAccount<'info>,
+    #[account(mut)]
+    pub whirlpool: Box<Account<'info, Whirlpool>>,
 
     #[account(address = token::ID)]
     pub token_program: Program<'info, Token>,
@@ -38,3 +44,46 @@ pub struct OpenPosition<'info> {
     pub rent: Sysvar<'info, Rent>,
     pub associated_token_program: Program<'info, AssociatedToken>,
 }
+
+/*
+  Opens a position for any owner. The associated token account of the owner is created
+  idempotently, so owners that are program derived addresses, or that already created the account
+  in an earlier instruction, need no workaround.
+*/
+pub fn handler(
+    ctx: Context<OpenPosition>,
+    tick_lower_index: i32,
+    tick_upper_index: i32,
+) -> Result<()> {
+    ctx.accounts.whirlpool.add_open_position()?;
+
+    let position_mint_info = ctx.accounts.position_mint.to_account_info();
+    create_position_mint_and_token_account(
+        &ctx.accounts.funder.to_account_info(),
+        &ctx.accounts.owner.to_account_info(),
+        &ctx.accounts.whirlpool,
+        &position_mint_info,
+        &ctx.accounts.position_token_account.to_account_info(),
+        PositionTokenPrograms {
+            token_program: &ctx.accounts.token_program,
+            system_program: &ctx.accounts.system_program,
+            associated_token_program: &ctx.accounts.associated_token_program,
+        },
+    )?;
+
+    let whirlpool = &ctx.accounts.whirlpool;
+    ctx.accounts.position.open_position(
+        whirlpool,
+        position_mint_info.key(),
+        ctx.accounts.owner.key(),
+        tick_lower_index,
+        tick_upper_index,
+    )?;
+
+    mint_created_position_token_and_remove_authority(
+        whirlpool,
+        &position_mint_info,
+        &ctx.accounts.position_token_account.to_account_info(),
+        &ctx.accounts.token_program,
+    )
+}
@@ -0,0 +1,129 @@
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token::{self, Token, TokenAccount};
+
+use crate::{
+    errors::ErrorCode,
+    state::*,
+    util::{
+        create_position_mint_and_token_account, mint_created_position_token_and_remove_authority,
+        PositionTokenPrograms,
+    },
+};
+
+#[derive(Accounts)]
+pub struct OpenPositionIdempotent<'info> {
+    #[account(mut)]
+    pub funder: Signer<'info>,
+
+    pub owner: UncheckedAccount<'info>,
+
+    #[account(init_if_needed,
+      payer = funder,
+      space = Position::LEN,
+      seeds = [b"position".as_ref(), position_mint.key().as_ref()],
+      bump,
+    )]
+    pub position: Box<Account<'info, Position>>,
+
+    // Created by the handler, an existing mint belongs to a retried open
+    #[account(mut)]
+    pub position_mint: Signer<'info>,
+
+    /// CHECK: the associated token account of the owner, created or checked by the handler
+    #[account(mut)]
+    pub position_token_account: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub whirlpool: Box<Account<'info, Whirlpool>>,
+
+    #[account(address = token::ID)]
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+/*
+  Opens a position like open_position, but succeeds without doing anything if the position was
+  already opened by an earlier attempt of the same transaction, i.e. for the same mint, Whirlpool
+  and tick range, with the position token still held by the owner. RPC retries during congestion
+  then no longer fail on the position mint already being in use.
+*/
+pub fn handler(
+    ctx: Context<OpenPositionIdempotent>,
+    tick_lower_index: i32,
+    tick_upper_index: i32,
+) -> Result<()> {
+    let (tick_lower_index, tick_upper_index) = Tick::resolve_full_range(
+        tick_lower_index,
+        tick_upper_index,
+        ctx.accounts.whirlpool.usable_tick_range(),
+    );
+
+    if ctx.accounts.position.whirlpool != Pubkey::default() {
+        return verify_opened_position(ctx.accounts, tick_lower_index, tick_upper_index);
+    }
+
+    ctx.accounts.whirlpool.add_open_position()?;
+
+    let position_mint_info = ctx.accounts.position_mint.to_account_info();
+    create_position_mint_and_token_account(
+        &ctx.accounts.funder.to_account_info(),
+        &ctx.accounts.owner.to_account_info(),
+        &ctx.accounts.whirlpool,
+        &position_mint_info,
+        &ctx.accounts.position_token_account.to_account_info(),
+        PositionTokenPrograms {
+            token_program: &ctx.accounts.token_program,
+            system_program: &ctx.accounts.system_program,
+            associated_token_program: &ctx.accounts.associated_token_program,
+        },
+    )?;
+
+    let whirlpool = &ctx.accounts.whirlpool;
+    ctx.accounts.position.open_position(
+        whirlpool,
+        position_mint_info.key(),
+        ctx.accounts.owner.key(),
+        tick_lower_index,
+        tick_upper_index,
+    )?;
+
+    mint_created_position_token_and_remove_authority(
+        whirlpool,
+        &position_mint_info,
+        &ctx.accounts.position_token_account.to_account_info(),
+        &ctx.accounts.token_program,
+    )
+}
+
+fn verify_opened_position(
+    accounts: &OpenPositionIdempotent,
+    tick_lower_index: i32,
+    tick_upper_index: i32,
+) -> Result<()> {
+    let position = &accounts.position;
+    if !position.describe().belongs_to(&accounts.whirlpool.key())
+        || position.position_mint != accounts.position_mint.key()
+        || position.tick_lower_index != tick_lower_index
+        || position.tick_upper_index != tick_upper_index
+    {
+        return Err(ErrorCode::PositionMismatch.into());
+    }
+
+    if accounts.position_token_account.owner != &token::ID {
+        return Err(ErrorCode::PositionMismatch.into());
+    }
+    let position_token_account = TokenAccount::try_deserialize(
+        &mut accounts.position_token_account.try_borrow_data()?.as_ref(),
+    )?;
+    if position_token_account.mint != position.position_mint
+        || position_token_account.owner != accounts.owner.key()
+        || position_token_account.amount != 1
+    {
+        return Err(ErrorCode::PositionMismatch.into());
+    }
+
+    Ok(())
+}
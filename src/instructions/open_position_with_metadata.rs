@@ -1,31 +1,39 @@
-use crate::state::Whirlpool;
 use anchor_lang::prelude::*;
 use anchor_spl::associated_token::AssociatedToken;
 use anchor_spl::token::{self, Mint, Token, TokenAccount};
 
-use crate::{state::*, util::mint_position_token_with_metadata_and_remove_authority};
+use crate::{
+    state::*,
+    util::{
+        mint_position_token_with_metadata_and_remove_authority, position_collection_accounts,
+        PositionMetadataAccounts,
+    },
+};
 
-use whirlpool_nft_update_auth::ID as WP_NFT_UPDATE_AUTH;
+pub(crate) use whirlpool_nft_update_auth::ID as WP_NFT_UPDATE_AUTH;
 mod whirlpool_nft_update_auth {
     use super::*;
     declare_id!("3axbTs2z5GBy6usVbNVoqEgZMng3vZvMnAoX29BFfwhr");
 }
 
 #[derive(Accounts)]
-#[instruction(bumps: OpenPositionWithMetadataBumps)]
+#[instruction(bumps: crate::state::OpenPositionWithMetadataBumps)]
 pub struct OpenPositionWithMetadata<'info> {
     #[account(mut)]
     pub funder: Signer<'info>,
 
     pub owner: UncheckedAccount<'info>,
 
-    #[account(mut,
+    #[account(init,
+      payer = funder,
+      space = Position::LEN,
       seeds = [b"position".as_ref(), position_mint.key().as_ref()],
-      bump = bumps.position_bump,
+      bump,
     )]
     pub position: Box<Account<'info, Position>>,
 
-    #[account(mut,
+    #[account(init,
+        payer = funder,
         mint::authority = whirlpool,
         mint::decimals = 0,
     )]
@@ -43,7 +51,10 @@ pub struct OpenPositionWithMetadata<'info> {
     )]
     pub position_token_account: Box<Account<'info, TokenAccount>>,
 
-    pub whirlpool: UncheckedAccount<'info>,
+    pub whirlpools_config: Box<Account<'info, WhirlpoolsConfig>>,
+
+    #[account(mut, has_one = whirlpools_config)]
+    pub whirlpool: Box<Account<'info, Whirlpool>>,
 
     #[account(address = token::ID)]
     pub token_program: Program<'info, Token>,
@@ -59,3 +70,52 @@ pub struct OpenPositionWithMetadata<'info> {
     #[account(address = WP_NFT_UPDATE_AUTH)]
     pub metadata_update_auth: UncheckedAccount<'info>,
 }
+
+/*
+  Opens a position with Metaplex metadata. If the Whirlpool has a position collection, the
+  collection mint, metadata and master edition are passed as the first three remaining accounts
+  and the position is verified into the collection.
+*/
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, OpenPositionWithMetadata<'info>>,
+    tick_lower_index: i32,
+    tick_upper_index: i32,
+) -> Result<()> {
+    let (tick_lower_index, tick_upper_index) = Tick::resolve_full_range(
+        tick_lower_index,
+        tick_upper_index,
+        ctx.accounts.whirlpool.usable_tick_range(),
+    );
+    ctx.accounts.whirlpool.add_open_position()?;
+
+    let whirlpool = &ctx.accounts.whirlpool;
+    let position_mint = &ctx.accounts.position_mint;
+    let position = &mut ctx.accounts.position;
+
+    position.open_position(
+        whirlpool,
+        position_mint.key(),
+        ctx.accounts.owner.key(),
+        tick_lower_index,
+        tick_upper_index,
+    )?;
+
+    let position_collection = position_collection_accounts(whirlpool, ctx.remaining_accounts)?;
+
+    mint_position_token_with_metadata_and_remove_authority(
+        &ctx.accounts.whirlpools_config,
+        whirlpool,
+        position_mint,
+        &ctx.accounts.position_token_account,
+        &ctx.accounts.token_program,
+        PositionMetadataAccounts {
+            position_metadata_account: &ctx.accounts.position_metadata_account,
+            metadata_update_auth: &ctx.accounts.metadata_update_auth,
+            funder: &ctx.accounts.funder,
+            metadata_program: &ctx.accounts.metadata_program,
+            system_program: &ctx.accounts.system_program,
+            rent: &ctx.accounts.rent,
+        },
+        position_collection.as_ref(),
+    )
+}
@@ -0,0 +1,88 @@
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token::{self, Mint, Token, TokenAccount};
+
+use crate::{state::*, util::mint_position_token_and_remove_authority};
+
+#[derive(Accounts)]
+#[instruction(tick_lower_index: i32, tick_upper_index: i32, nonce: u64)]
+pub struct OpenPositionWithSeeds<'info> {
+    #[account(mut)]
+    pub funder: Signer<'info>,
+
+    pub owner: Signer<'info>,
+
+    #[account(init,
+      payer = funder,
+      space = Position::LEN,
+      seeds = [
+        b"position".as_ref(),
+        owner.key().as_ref(),
+        whirlpool.key().as_ref(),
+        tick_lower_index.to_le_bytes().as_ref(),
+        tick_upper_index.to_le_bytes().as_ref(),
+        nonce.to_le_bytes().as_ref()
+      ],
+      bump,
+    )]
+    pub position: Box<Account<'info, Position>>,
+
+    #[account(init,
+        payer = funder,
+        mint::authority = whirlpool,
+        mint::decimals = 0,
+    )]
+    pub position_mint: Account<'info, Mint>,
+
+    #[account(init,
+      payer = funder,
+      associated_token::mint = position_mint,
+      associated_token::authority = owner,
+    )]
+    pub position_token_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub whirlpool: Box<Account<'info, Whirlpool>>,
+
+    #[account(address = token::ID)]
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+/*
+  Opens a position at an address derived from the owner, the Whirlpool, the tick range and a
+  nonce instead of the position mint, so integrations can find their positions without storing
+  the mint. The position token is minted as for any other position and remains its authority.
+*/
+pub fn handler(
+    ctx: Context<OpenPositionWithSeeds>,
+    tick_lower_index: i32,
+    tick_upper_index: i32,
+) -> Result<()> {
+    let (tick_lower_index, tick_upper_index) = Tick::resolve_full_range(
+        tick_lower_index,
+        tick_upper_index,
+        ctx.accounts.whirlpool.usable_tick_range(),
+    );
+    ctx.accounts.whirlpool.add_open_position()?;
+
+    let whirlpool = &ctx.accounts.whirlpool;
+    let position_mint = &ctx.accounts.position_mint;
+
+    ctx.accounts.position.open_position(
+        whirlpool,
+        position_mint.key(),
+        ctx.accounts.owner.key(),
+        tick_lower_index,
+        tick_upper_index,
+    )?;
+
+    mint_position_token_and_remove_authority(
+        whirlpool,
+        position_mint,
+        &ctx.accounts.position_token_account,
+        &ctx.accounts.token_program,
+    )
+}
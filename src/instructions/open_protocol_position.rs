@@ -0,0 +1,63 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::ErrorCode;
+use crate::state::*;
+
+#[derive(Accounts)]
+#[instruction(tick_lower_index: i32, tick_upper_index: i32)]
+pub struct OpenProtocolPosition<'info> {
+    pub whirlpools_config: Box<Account<'info, WhirlpoolsConfig>>,
+
+    #[account(mut)]
+    pub whirlpool: Box<Account<'info, Whirlpool>>,
+
+    #[account(
+        address = whirlpools_config.fee_authority,
+        constraint = !whirlpools_config.admin_frozen @ ErrorCode::AdminParametersFrozen,
+    )]
+    pub fee_authority: Signer<'info>,
+
+    #[account(mut)]
+    pub funder: Signer<'info>,
+
+    #[account(init,
+      payer = funder,
+      space = Position::LEN,
+      seeds = [
+        b"protocol_position".as_ref(),
+        whirlpool.key().as_ref(),
+        tick_lower_index.to_le_bytes().as_ref(),
+        tick_upper_index.to_le_bytes().as_ref()
+      ],
+      bump,
+      constraint = whirlpool.whirlpools_config == whirlpools_config.key(),
+    )]
+    pub position: Box<Account<'info, Position>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/*
+  Opens a protocol owned position. Protocol positions have no position token, they are a PDA of
+  the Whirlpool and their tick range and are managed by the fee authority of the config.
+*/
+pub fn handler(
+    ctx: Context<OpenProtocolPosition>,
+    tick_lower_index: i32,
+    tick_upper_index: i32,
+) -> Result<()> {
+    let (tick_lower_index, tick_upper_index) = Tick::resolve_full_range(
+        tick_lower_index,
+        tick_upper_index,
+        ctx.accounts.whirlpool.usable_tick_range(),
+    );
+    ctx.accounts.whirlpool.add_open_position()?;
+
+    ctx.accounts.position.open_position(
+        &ctx.accounts.whirlpool,
+        Pubkey::default(),
+        Pubkey::default(),
+        tick_lower_index,
+        tick_upper_index,
+    )
+}
@@ -0,0 +1,26 @@
+use anchor_lang::prelude::*;
+
+use crate::state::WhirlpoolsConfig;
+
+#[derive(Accounts)]
+pub struct ProposeEmergencyAuthority<'info> {
+    #[account(mut)]
+    pub whirlpools_config: Account<'info, WhirlpoolsConfig>,
+
+    pub authority: Signer<'info>,
+
+    /// CHECK: safe, the account that will be proposed as the new emergency authority
+    pub new_emergency_authority: UncheckedAccount<'info>,
+}
+
+/*
+  Proposes a new emergency authority of the config. The fee authority proposes the first one,
+  afterwards only the emergency authority can hand over its role, so a compromised fee authority
+  cannot replace it. The proposed authority has to accept before the role changes hands.
+*/
+pub fn handler(ctx: Context<ProposeEmergencyAuthority>) -> Result<()> {
+    ctx.accounts.whirlpools_config.propose_emergency_authority(
+        ctx.accounts.authority.key,
+        ctx.accounts.new_emergency_authority.key(),
+    )
+}
@@ -0,0 +1,26 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::ErrorCode;
+use crate::state::WhirlpoolsConfig;
+
+#[derive(Accounts)]
+pub struct ProposeFeeAuthority<'info> {
+    #[account(mut)]
+    pub whirlpools_config: Account<'info, WhirlpoolsConfig>,
+
+    #[account(
+        address = whirlpools_config.fee_authority,
+        constraint = !whirlpools_config.admin_frozen @ ErrorCode::AdminParametersFrozen,
+    )]
+    pub fee_authority: Signer<'info>,
+
+    /// CHECK: safe, the account that will be proposed as the new fee authority
+    pub new_fee_authority: UncheckedAccount<'info>,
+}
+
+pub fn handler(ctx: Context<ProposeFeeAuthority>) -> Result<()> {
+    ctx.accounts
+        .whirlpools_config
+        .propose_fee_authority(ctx.accounts.new_fee_authority.key());
+    Ok(())
+}
@@ -0,0 +1,39 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::TokenAccount;
+
+use crate::errors::ErrorCode;
+use crate::state::*;
+use crate::util::verify_position_authority;
+
+#[derive(Accounts)]
+pub struct RedirectOwed<'info> {
+    pub position_authority: Signer<'info>,
+
+    #[account(mut,
+        constraint = !position.frozen @ ErrorCode::PositionFrozen
+    )]
+    pub position: Account<'info, Position>,
+
+    #[account(
+        constraint = position_token_account.mint == position.position_mint,
+        constraint = position_token_account.amount == 1
+    )]
+    pub position_token_account: Box<Account<'info, TokenAccount>>,
+}
+
+/*
+  Changes the wallet that collections made through sessions pay owed fees and rewards to, so
+  automated collection can continue when the position owner's token accounts are frozen.
+*/
+pub fn handler(ctx: Context<RedirectOwed>, owed_destination: Pubkey) -> Result<()> {
+    verify_position_authority(
+        &ctx.accounts.position_token_account,
+        &ctx.accounts.position_authority,
+    )?;
+
+    ctx.accounts
+        .position
+        .update_owed_destination(owed_destination);
+
+    Ok(())
+}
@@ -0,0 +1,30 @@
+use anchor_lang::prelude::*;
+
+use crate::state::Whirlpool;
+use crate::util::{pay_keeper_tip, read_exchange_rate_x64, split_keeper_tip_accounts};
+
+#[derive(Accounts)]
+pub struct RefreshExchangeRate<'info> {
+    #[account(mut)]
+    pub whirlpool: Account<'info, Whirlpool>,
+
+    /// CHECK: Ownership and layout are checked when the exchange rate is read
+    #[account(address = whirlpool.exchange_rate_oracle)]
+    pub exchange_rate_oracle: UncheckedAccount<'info>,
+}
+
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, RefreshExchangeRate<'info>>,
+) -> Result<()> {
+    let exchange_rate_x64 = read_exchange_rate_x64(&ctx.accounts.exchange_rate_oracle)?;
+
+    ctx.accounts
+        .whirlpool
+        .update_exchange_rate(exchange_rate_x64)?;
+
+    if let (Some(keeper_tip_accounts), _) = split_keeper_tip_accounts(ctx.remaining_accounts)? {
+        pay_keeper_tip(&keeper_tip_accounts, &ctx.accounts.whirlpool.key())?;
+    }
+
+    Ok(())
+}
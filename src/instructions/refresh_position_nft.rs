@@ -0,0 +1,112 @@
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token::{self, Mint, Token, TokenAccount};
+
+use super::open_position_with_metadata::WP_NFT_UPDATE_AUTH;
+use crate::{
+    state::*,
+    util::{
+        burn_and_close_user_position_token, mint_position_token_with_metadata_and_remove_authority,
+        position_collection_accounts, PositionMetadataAccounts,
+    },
+};
+
+#[derive(Accounts)]
+pub struct RefreshPositionNft<'info> {
+    #[account(mut)]
+    pub funder: Signer<'info>,
+
+    pub position_authority: Signer<'info>,
+
+    #[account(mut)]
+    pub receiver: UncheckedAccount<'info>,
+
+    pub whirlpools_config: Box<Account<'info, WhirlpoolsConfig>>,
+
+    #[account(has_one = whirlpools_config)]
+    pub whirlpool: Box<Account<'info, Whirlpool>>,
+
+    #[account(mut, has_one = whirlpool)]
+    pub position: Box<Account<'info, Position>>,
+
+    #[account(mut, address = position.position_mint)]
+    pub position_mint: Account<'info, Mint>,
+
+    #[account(mut,
+        constraint = position_token_account.amount == 1,
+        constraint = position_token_account.mint == position.position_mint,
+        constraint = position_token_account.owner == position_authority.key()
+    )]
+    pub position_token_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(init,
+        payer = funder,
+        mint::authority = whirlpool,
+        mint::decimals = 0,
+    )]
+    pub new_position_mint: Account<'info, Mint>,
+
+    /// CHECK: checked via the Metadata CPI call
+    #[account(mut)]
+    pub new_position_metadata_account: UncheckedAccount<'info>,
+
+    #[account(init,
+      payer = funder,
+      associated_token::mint = new_position_mint,
+      associated_token::authority = position_authority,
+    )]
+    pub new_position_token_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(address = token::ID)]
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+
+    /// CHECK: checked via account constraints
+    #[account(address = mpl_token_metadata::ID)]
+    pub metadata_program: UncheckedAccount<'info>,
+
+    /// CHECK: checked via account constraints
+    #[account(address = WP_NFT_UPDATE_AUTH)]
+    pub metadata_update_auth: UncheckedAccount<'info>,
+}
+
+/*
+  Burns the position token of a position and mints a replacement with the current metadata, and
+  the position collection of the Whirlpool if it has one, to the same holder. The position is
+  moved onto the new mint and keeps its liquidity, fees and rewards.
+*/
+pub fn handler<'info>(ctx: Context<'_, '_, 'info, 'info, RefreshPositionNft<'info>>) -> Result<()> {
+    burn_and_close_user_position_token(
+        &ctx.accounts.position_authority,
+        &ctx.accounts.receiver,
+        &ctx.accounts.position_mint,
+        &ctx.accounts.position_token_account,
+        &ctx.accounts.token_program,
+    )?;
+
+    ctx.accounts
+        .position
+        .update_position_mint(ctx.accounts.new_position_mint.key());
+
+    let whirlpool = &ctx.accounts.whirlpool;
+    let position_collection = position_collection_accounts(whirlpool, ctx.remaining_accounts)?;
+
+    mint_position_token_with_metadata_and_remove_authority(
+        &ctx.accounts.whirlpools_config,
+        whirlpool,
+        &ctx.accounts.new_position_mint,
+        &ctx.accounts.new_position_token_account,
+        &ctx.accounts.token_program,
+        PositionMetadataAccounts {
+            position_metadata_account: &ctx.accounts.new_position_metadata_account,
+            metadata_update_auth: &ctx.accounts.metadata_update_auth,
+            funder: &ctx.accounts.funder,
+            metadata_program: &ctx.accounts.metadata_program,
+            system_program: &ctx.accounts.system_program,
+            rent: &ctx.accounts.rent,
+        },
+        position_collection.as_ref(),
+    )
+}
@@ -0,0 +1,63 @@
+use std::cell::RefMut;
+
+use anchor_lang::prelude::*;
+
+use crate::state::{TickArray, TickCache, Whirlpool};
+use crate::util::{pay_keeper_tip, split_keeper_tip_accounts};
+
+use super::load_tick_array;
+
+/// Remaining accounts must hold the tick arrays covering the current window of the cache, if it
+/// has ticks to write back, and the tick arrays covering the window around the current tick,
+/// after the keeper tip accounts if the keeper is tipped.
+#[derive(Accounts)]
+pub struct RefreshTickCache<'info> {
+    #[account(mut)]
+    pub whirlpool: Account<'info, Whirlpool>,
+
+    #[account(mut, has_one = whirlpool, address = whirlpool.tick_cache)]
+    pub tick_cache: AccountLoader<'info, TickCache>,
+}
+
+/*
+  Writes the ticks updated by swaps against the cache back to the tick arrays, then reloads the
+  cache with the window around the current tick. Anyone can refresh the cache.
+*/
+pub fn handler<'info>(ctx: Context<'_, '_, 'info, 'info, RefreshTickCache<'info>>) -> Result<()> {
+    let whirlpool = &mut ctx.accounts.whirlpool;
+    let whirlpool_key = whirlpool.key();
+    let tick_spacing = whirlpool.tick_spacing;
+
+    let (keeper_tip_accounts, tick_array_accounts) =
+        split_keeper_tip_accounts(ctx.remaining_accounts)?;
+    let tick_array_loaders = tick_array_accounts
+        .iter()
+        .map(|account_info| load_tick_array(account_info, &whirlpool_key))
+        .collect::<Result<Vec<_>>>()?;
+    let mut loaded_tick_arrays = tick_array_loaders
+        .iter()
+        .map(|tick_array| tick_array.load_mut())
+        .collect::<Result<Vec<RefMut<TickArray>>>>()?;
+    let mut tick_arrays = loaded_tick_arrays
+        .iter_mut()
+        .map(|tick_array| &mut **tick_array)
+        .collect::<Vec<&mut TickArray>>();
+
+    let mut tick_cache = ctx.accounts.tick_cache.load_mut()?;
+    if whirlpool.tick_cache_ahead {
+        tick_cache.write_back(&mut tick_arrays, tick_spacing)?;
+    }
+    tick_cache.load_window(
+        TickCache::window_start_for(whirlpool.tick_current_index, tick_spacing),
+        &tick_arrays,
+        tick_spacing,
+    )?;
+
+    whirlpool.mark_tick_cache_synced();
+
+    if let Some(keeper_tip_accounts) = keeper_tip_accounts {
+        pay_keeper_tip(&keeper_tip_accounts, &whirlpool_key)?;
+    }
+
+    Ok(())
+}
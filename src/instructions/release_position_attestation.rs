@@ -0,0 +1,39 @@
+use anchor_lang::prelude::*;
+
+use crate::events::PositionAttestationReleased;
+use crate::state::*;
+
+#[derive(Accounts)]
+pub struct ReleasePositionAttestation<'info> {
+    pub bridge_authority: Signer<'info>,
+
+    #[account(mut)]
+    pub receiver: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub position: Account<'info, Position>,
+
+    #[account(mut,
+        close = receiver,
+        has_one = position,
+        has_one = bridge_authority,
+    )]
+    pub attestation: Account<'info, PositionAttestation>,
+}
+
+/*
+  Unfreezes an attested position and closes its attestation, once the bridge program no longer
+  represents the position elsewhere.
+*/
+pub fn handler(ctx: Context<ReleasePositionAttestation>) -> Result<()> {
+    let position = &mut ctx.accounts.position;
+    position.update_frozen(false);
+
+    emit!(PositionAttestationReleased {
+        whirlpool: position.whirlpool,
+        position: position.key(),
+        bridge_authority: ctx.accounts.bridge_authority.key(),
+    });
+
+    Ok(())
+}
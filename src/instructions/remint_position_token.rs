@@ -0,0 +1,71 @@
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token::{self, Mint, Token, TokenAccount};
+
+use crate::errors::ErrorCode;
+use crate::{state::*, util::mint_position_token_and_remove_authority};
+
+#[derive(Accounts)]
+pub struct RemintPositionToken<'info> {
+    #[account(mut)]
+    pub funder: Signer<'info>,
+
+    #[account(
+        constraint = position.is_recovery_authority(recovery_authority.key)
+            @ ErrorCode::InvalidRecoveryAuthority
+    )]
+    pub recovery_authority: Signer<'info>,
+
+    pub whirlpool: Box<Account<'info, Whirlpool>>,
+
+    #[account(mut,
+        has_one = whirlpool,
+        constraint = !position.frozen @ ErrorCode::PositionFrozen
+    )]
+    pub position: Box<Account<'info, Position>>,
+
+    #[account(
+        address = position.position_mint,
+        constraint = burned_position_mint.supply == 0 @ ErrorCode::PositionTokenNotBurned
+    )]
+    pub burned_position_mint: Account<'info, Mint>,
+
+    #[account(init,
+        payer = funder,
+        mint::authority = whirlpool,
+        mint::decimals = 0,
+    )]
+    pub position_mint: Account<'info, Mint>,
+
+    #[account(init,
+      payer = funder,
+      associated_token::mint = position_mint,
+      associated_token::authority = recovery_authority,
+    )]
+    pub position_token_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(address = token::ID)]
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+/*
+  Mints a replacement position token for a position whose token was burned by its holder.
+  The original mint had its mint authority removed, so the position is moved onto a new mint.
+  A burned token leaves no holder to authorize the recovery, so it is authorized by the recovery
+  authority the holder set on the position beforehand.
+*/
+pub fn handler(ctx: Context<RemintPositionToken>) -> Result<()> {
+    ctx.accounts
+        .position
+        .update_position_mint(ctx.accounts.position_mint.key());
+
+    mint_position_token_and_remove_authority(
+        &ctx.accounts.whirlpool,
+        &ctx.accounts.position_mint,
+        &ctx.accounts.position_token_account,
+        &ctx.accounts.token_program,
+    )
+}
@@ -0,0 +1,22 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::ErrorCode;
+use crate::state::{Whirlpool, WhirlpoolsConfig};
+
+#[derive(Accounts)]
+pub struct ResetPoolPrice<'info> {
+    pub whirlpools_config: Account<'info, WhirlpoolsConfig>,
+
+    #[account(mut, has_one = whirlpools_config)]
+    pub whirlpool: Account<'info, Whirlpool>,
+
+    #[account(
+        address = whirlpools_config.fee_authority,
+        constraint = !whirlpools_config.admin_frozen @ ErrorCode::AdminParametersFrozen,
+    )]
+    pub fee_authority: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<ResetPoolPrice>, sqrt_price: u128) -> Result<()> {
+    ctx.accounts.whirlpool.reset_sqrt_price(sqrt_price)
+}
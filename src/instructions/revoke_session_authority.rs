@@ -0,0 +1,19 @@
+use anchor_lang::prelude::*;
+
+use crate::state::*;
+
+#[derive(Accounts)]
+pub struct RevokeSessionAuthority<'info> {
+    pub owner: Signer<'info>,
+
+    /// CHECK: safe, for receiving rent only
+    #[account(mut)]
+    pub receiver: UncheckedAccount<'info>,
+
+    #[account(mut, has_one = owner, close = receiver)]
+    pub session_authority: Account<'info, SessionAuthority>,
+}
+
+pub fn handler(_ctx: Context<RevokeSessionAuthority>) -> Result<()> {
+    Ok(())
+}
@@ -0,0 +1,19 @@
+use anchor_lang::prelude::*;
+
+use crate::state::WhirlpoolsConfig;
+
+#[derive(Accounts)]
+pub struct SetAdminFrozen<'info> {
+    #[account(mut)]
+    pub whirlpools_config: Account<'info, WhirlpoolsConfig>,
+
+    #[account(address = whirlpools_config.emergency_authority)]
+    pub emergency_authority: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<SetAdminFrozen>, admin_frozen: bool) -> Result<()> {
+    ctx.accounts
+        .whirlpools_config
+        .update_admin_frozen(admin_frozen);
+    Ok(())
+}
@@ -0,0 +1,23 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::ErrorCode;
+use crate::state::WhirlpoolsConfig;
+
+#[derive(Accounts)]
+pub struct SetApprovedLiquidator<'info> {
+    #[account(mut)]
+    pub whirlpools_config: Account<'info, WhirlpoolsConfig>,
+
+    #[account(
+        address = whirlpools_config.fee_authority,
+        constraint = !whirlpools_config.admin_frozen @ ErrorCode::AdminParametersFrozen,
+    )]
+    pub fee_authority: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<SetApprovedLiquidator>, approved_liquidator: Pubkey) -> Result<()> {
+    ctx.accounts
+        .whirlpools_config
+        .update_approved_liquidator(approved_liquidator);
+    Ok(())
+}
@@ -0,0 +1,24 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::ErrorCode;
+use crate::state::{RouterRegistry, WhirlpoolsConfig};
+
+#[derive(Accounts)]
+pub struct SetApprovedRouter<'info> {
+    pub whirlpools_config: Box<Account<'info, WhirlpoolsConfig>>,
+
+    #[account(mut, has_one = whirlpools_config)]
+    pub router_registry: Box<Account<'info, RouterRegistry>>,
+
+    #[account(
+        address = whirlpools_config.fee_authority,
+        constraint = !whirlpools_config.admin_frozen @ ErrorCode::AdminParametersFrozen,
+    )]
+    pub fee_authority: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<SetApprovedRouter>, router_index: u8, router: Pubkey) -> Result<()> {
+    ctx.accounts
+        .router_registry
+        .update_router(router_index as usize, router)
+}
@@ -0,0 +1,37 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::TokenAccount;
+
+use crate::errors::ErrorCode;
+use crate::state::*;
+use crate::util::verify_position_authority;
+
+#[derive(Accounts)]
+pub struct SetAutoCompound<'info> {
+    pub position_authority: Signer<'info>,
+
+    #[account(mut,
+        constraint = !position.frozen @ ErrorCode::PositionFrozen
+    )]
+    pub position: Account<'info, Position>,
+
+    #[account(
+        constraint = position_token_account.mint == position.position_mint,
+        constraint = position_token_account.amount == 1
+    )]
+    pub position_token_account: Box<Account<'info, TokenAccount>>,
+}
+
+/*
+  Lets any keeper compound the fees owed of the position back into its liquidity, for the tip rate
+  set here. Keepers never gain withdrawal rights over the position.
+*/
+pub fn handler(ctx: Context<SetAutoCompound>, auto_compound: bool, tip_rate: u16) -> Result<()> {
+    verify_position_authority(
+        &ctx.accounts.position_token_account,
+        &ctx.accounts.position_authority,
+    )?;
+
+    ctx.accounts
+        .position
+        .update_auto_compound(auto_compound, tip_rate)
+}
@@ -0,0 +1,26 @@
+use anchor_lang::prelude::*;
+
+use crate::state::CollectRouting;
+
+#[derive(Accounts)]
+pub struct SetCollectRoute<'info> {
+    #[account(mut, has_one = owner)]
+    pub collect_routing: Box<Account<'info, CollectRouting>>,
+
+    pub owner: Signer<'info>,
+}
+
+/*
+  Routes the fees and rewards of a mint to a destination token account, or clears the route slot
+  with the default mint. The destination is not required to exist yet, collects check its mint.
+*/
+pub fn handler(
+    ctx: Context<SetCollectRoute>,
+    route_index: u8,
+    mint: Pubkey,
+    destination: Pubkey,
+) -> Result<()> {
+    ctx.accounts
+        .collect_routing
+        .update_route(route_index as usize, mint, destination)
+}
@@ -0,0 +1,50 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::ErrorCode;
+use crate::state::{Whirlpool, WhirlpoolsConfig};
+use crate::util::read_exchange_rate_x64;
+
+#[derive(Accounts)]
+pub struct SetExchangeRateOracle<'info> {
+    pub whirlpools_config: Account<'info, WhirlpoolsConfig>,
+
+    #[account(mut, has_one = whirlpools_config)]
+    pub whirlpool: Account<'info, Whirlpool>,
+
+    #[account(
+        address = whirlpools_config.fee_authority,
+        constraint = !whirlpools_config.admin_frozen @ ErrorCode::AdminParametersFrozen,
+    )]
+    pub fee_authority: Signer<'info>,
+
+    /// CHECK: Ownership and layout are checked when the exchange rate is read
+    pub exchange_rate_oracle: UncheckedAccount<'info>,
+}
+
+/*
+  Maps one side of the pool to a stake pool exchange rate. Changing the units the pool accounts in
+  would misprice existing positions, so this is only allowed while the pool owes no tokens. The
+  owed amounts are tracked by the pool, tokens sent to the vaults directly do not lock the mode.
+  Only SPL stake pool accounts are supported as oracles, Marinade and Lido state accounts are
+  rejected.
+*/
+pub fn handler(ctx: Context<SetExchangeRateOracle>, exchange_rate_on_a: bool) -> Result<()> {
+    // Pools created before the owed amounts were tracked only reveal the liquidity of their
+    // positions once every position is counted in the total liquidity.
+    let whirlpool = &ctx.accounts.whirlpool;
+    if whirlpool.has_vault_owed()
+        || whirlpool.liquidity != 0
+        || whirlpool.total_liquidity != 0
+        || !whirlpool.total_liquidity_complete
+    {
+        return Err(ErrorCode::ExchangeRateModeLocked.into());
+    }
+
+    let exchange_rate_x64 = read_exchange_rate_x64(&ctx.accounts.exchange_rate_oracle)?;
+
+    ctx.accounts.whirlpool.update_exchange_rate_oracle(
+        ctx.accounts.exchange_rate_oracle.key(),
+        exchange_rate_on_a,
+        exchange_rate_x64,
+    )
+}
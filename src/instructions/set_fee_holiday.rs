@@ -0,0 +1,38 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::ErrorCode;
+use crate::state::{FeeHoliday, FeeSchedule, Whirlpool, WhirlpoolsConfig};
+
+#[derive(Accounts)]
+pub struct SetFeeHoliday<'info> {
+    pub whirlpools_config: Account<'info, WhirlpoolsConfig>,
+
+    #[account(has_one = whirlpools_config)]
+    pub whirlpool: Account<'info, Whirlpool>,
+
+    #[account(mut, has_one = whirlpool)]
+    pub fee_schedule: Box<Account<'info, FeeSchedule>>,
+
+    #[account(
+        address = whirlpools_config.fee_authority,
+        constraint = !whirlpools_config.admin_frozen @ ErrorCode::AdminParametersFrozen,
+    )]
+    pub fee_authority: Signer<'info>,
+}
+
+pub fn handler(
+    ctx: Context<SetFeeHoliday>,
+    index: u8,
+    start_timestamp: u64,
+    end_timestamp: u64,
+    fee_rate: u32,
+) -> Result<()> {
+    ctx.accounts.fee_schedule.update_holiday(
+        index as usize,
+        FeeHoliday {
+            start_timestamp,
+            end_timestamp,
+            fee_rate,
+        },
+    )
+}
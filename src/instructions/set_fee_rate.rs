@@ -0,0 +1,26 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::ErrorCode;
+use crate::state::{Whirlpool, WhirlpoolsConfig};
+
+#[derive(Accounts)]
+pub struct SetFeeRate<'info> {
+    pub whirlpools_config: Account<'info, WhirlpoolsConfig>,
+
+    #[account(mut, has_one = whirlpools_config)]
+    pub whirlpool: Account<'info, Whirlpool>,
+
+    #[account(
+        address = whirlpools_config.fee_authority,
+        constraint = !whirlpools_config.admin_frozen @ ErrorCode::AdminParametersFrozen,
+    )]
+    pub fee_authority: Signer<'info>,
+}
+
+/*
+  Sets the fee rate of the Whirlpool in thousandths of a basis point, keeping the legacy fee rate
+  in hundredths of a basis point in sync for clients that still read it.
+*/
+pub fn handler(ctx: Context<SetFeeRate>, fee_rate: u32) -> Result<()> {
+    ctx.accounts.whirlpool.update_fee_rate(fee_rate)
+}
@@ -0,0 +1,29 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::ErrorCode;
+use crate::state::{Whirlpool, WhirlpoolsConfig};
+
+#[derive(Accounts)]
+pub struct SetFeesInTokenB<'info> {
+    pub whirlpools_config: Account<'info, WhirlpoolsConfig>,
+
+    #[account(mut, has_one = whirlpools_config)]
+    pub whirlpool: Account<'info, Whirlpool>,
+
+    #[account(
+        address = whirlpools_config.fee_authority,
+        constraint = !whirlpools_config.admin_frozen @ ErrorCode::AdminParametersFrozen,
+    )]
+    pub fee_authority: Signer<'info>,
+}
+
+/*
+  Switches a Whirlpool between taking swap fees in the input token and accounting all swap fees in
+  token B. Fee growth is tracked per token, so fees accrued under either mode stay collectable.
+*/
+pub fn handler(ctx: Context<SetFeesInTokenB>, fees_in_token_b: bool) -> Result<()> {
+    ctx.accounts
+        .whirlpool
+        .update_fees_in_token_b(fees_in_token_b);
+    Ok(())
+}
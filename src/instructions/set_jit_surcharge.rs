@@ -0,0 +1,32 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::ErrorCode;
+use crate::state::{Whirlpool, WhirlpoolsConfig};
+
+#[derive(Accounts)]
+pub struct SetJitSurcharge<'info> {
+    pub whirlpools_config: Account<'info, WhirlpoolsConfig>,
+
+    #[account(mut, has_one = whirlpools_config)]
+    pub whirlpool: Account<'info, Whirlpool>,
+
+    #[account(
+        address = whirlpools_config.fee_authority,
+        constraint = !whirlpools_config.admin_frozen @ ErrorCode::AdminParametersFrozen,
+    )]
+    pub fee_authority: Signer<'info>,
+}
+
+/*
+  Sets the surcharge on liquidity removed within a number of slots of its position's last
+  liquidity increase. The surcharge is left to the liquidity remaining in range as fees.
+*/
+pub fn handler(
+    ctx: Context<SetJitSurcharge>,
+    jit_surcharge_rate: u16,
+    jit_surcharge_slots: u32,
+) -> Result<()> {
+    ctx.accounts
+        .whirlpool
+        .update_jit_surcharge(jit_surcharge_rate, jit_surcharge_slots)
+}
@@ -0,0 +1,23 @@
+use anchor_lang::prelude::*;
+
+use crate::state::KeeperTipEscrow;
+
+#[derive(Accounts)]
+pub struct SetKeeperTips<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(mut, has_one = authority)]
+    pub keeper_tip_escrow: Box<Account<'info, KeeperTipEscrow>>,
+}
+
+pub fn handler(
+    ctx: Context<SetKeeperTips>,
+    base_tip: u64,
+    max_priority_fee_tip: u64,
+) -> Result<()> {
+    ctx.accounts
+        .keeper_tip_escrow
+        .update_tips(base_tip, max_priority_fee_tip);
+
+    Ok(())
+}
@@ -0,0 +1,32 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::ErrorCode;
+use crate::state::{Whirlpool, WhirlpoolsConfig};
+
+#[derive(Accounts)]
+pub struct SetLiquidityShiftAlertRate<'info> {
+    pub whirlpools_config: Account<'info, WhirlpoolsConfig>,
+
+    #[account(mut, has_one = whirlpools_config)]
+    pub whirlpool: Account<'info, Whirlpool>,
+
+    #[account(
+        address = whirlpools_config.fee_authority,
+        constraint = !whirlpools_config.admin_frozen @ ErrorCode::AdminParametersFrozen,
+    )]
+    pub fee_authority: Signer<'info>,
+}
+
+/*
+  Sets the change of the active liquidity within a slot above which swaps and liquidity changes
+  emit a LiquidityShifted event. Changes are measured from the next slot with a liquidity change.
+*/
+pub fn handler(
+    ctx: Context<SetLiquidityShiftAlertRate>,
+    liquidity_shift_alert_rate: u16,
+) -> Result<()> {
+    ctx.accounts
+        .whirlpool
+        .update_liquidity_shift_alert_rate(liquidity_shift_alert_rate);
+    Ok(())
+}
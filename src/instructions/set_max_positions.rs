@@ -0,0 +1,27 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::ErrorCode;
+use crate::state::{Whirlpool, WhirlpoolsConfig};
+
+#[derive(Accounts)]
+pub struct SetMaxPositions<'info> {
+    pub whirlpools_config: Account<'info, WhirlpoolsConfig>,
+
+    #[account(mut, has_one = whirlpools_config)]
+    pub whirlpool: Account<'info, Whirlpool>,
+
+    #[account(
+        address = whirlpools_config.fee_authority,
+        constraint = !whirlpools_config.admin_frozen @ ErrorCode::AdminParametersFrozen,
+    )]
+    pub fee_authority: Signer<'info>,
+}
+
+/*
+  Caps the number of open positions in a Whirlpool, for gated launches that promise capped
+  participation. Lowering the cap below the current count only blocks new positions.
+*/
+pub fn handler(ctx: Context<SetMaxPositions>, max_positions: u32) -> Result<()> {
+    ctx.accounts.whirlpool.update_max_positions(max_positions);
+    Ok(())
+}
@@ -0,0 +1,48 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::Mint;
+
+use crate::errors::ErrorCode;
+use crate::state::{MintPriceFeed, WhirlpoolsConfig};
+
+#[derive(Accounts)]
+pub struct SetMintPriceFeed<'info> {
+    pub whirlpools_config: Box<Account<'info, WhirlpoolsConfig>>,
+
+    pub token_mint: Account<'info, Mint>,
+
+    #[account(
+        address = whirlpools_config.fee_authority,
+        constraint = !whirlpools_config.admin_frozen @ ErrorCode::AdminParametersFrozen,
+    )]
+    pub fee_authority: Signer<'info>,
+
+    #[account(mut)]
+    pub funder: Signer<'info>,
+
+    #[account(
+      init_if_needed,
+      payer = funder,
+      seeds = [
+        b"mint_price_feed".as_ref(),
+        whirlpools_config.key().as_ref(),
+        token_mint.key().as_ref()
+      ],
+      bump,
+      space = MintPriceFeed::LEN)]
+    pub mint_price_feed: Box<Account<'info, MintPriceFeed>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/*
+  Creates the price feed of the mint on first use and replaces its feed id afterwards. Only pools
+  initialized afterwards read the new feed.
+*/
+pub fn handler(ctx: Context<SetMintPriceFeed>, feed_id: [u8; 32]) -> Result<()> {
+    ctx.accounts.mint_price_feed.initialize(
+        ctx.accounts.whirlpools_config.key(),
+        ctx.accounts.token_mint.key(),
+        feed_id,
+    );
+    Ok(())
+}
@@ -0,0 +1,19 @@
+use anchor_lang::prelude::*;
+
+use crate::state::{Whirlpool, WhirlpoolsConfig};
+
+#[derive(Accounts)]
+pub struct SetPoolPaused<'info> {
+    pub whirlpools_config: Account<'info, WhirlpoolsConfig>,
+
+    #[account(mut, has_one = whirlpools_config)]
+    pub whirlpool: Account<'info, Whirlpool>,
+
+    #[account(address = whirlpools_config.emergency_authority)]
+    pub emergency_authority: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<SetPoolPaused>, paused: bool) -> Result<()> {
+    ctx.accounts.whirlpool.update_paused(paused);
+    Ok(())
+}
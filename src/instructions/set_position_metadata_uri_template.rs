@@ -0,0 +1,26 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::ErrorCode;
+use crate::state::WhirlpoolsConfig;
+
+#[derive(Accounts)]
+pub struct SetPositionMetadataUriTemplate<'info> {
+    #[account(mut)]
+    pub whirlpools_config: Account<'info, WhirlpoolsConfig>,
+
+    #[account(
+        address = whirlpools_config.fee_authority,
+        constraint = !whirlpools_config.admin_frozen @ ErrorCode::AdminParametersFrozen,
+    )]
+    pub fee_authority: Signer<'info>,
+}
+
+/*
+  Sets the URI template of the metadata of position NFTs minted from now on for the Whirlpools of
+  the config.
+*/
+pub fn handler(ctx: Context<SetPositionMetadataUriTemplate>, uri_template: String) -> Result<()> {
+    ctx.accounts
+        .whirlpools_config
+        .update_position_metadata_uri_template(uri_template)
+}
@@ -0,0 +1,42 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::TokenAccount;
+
+use crate::errors::ErrorCode;
+use crate::state::*;
+use crate::util::verify_position_authority;
+
+#[derive(Accounts)]
+pub struct SetPositionRecoveryAuthority<'info> {
+    pub position_authority: Signer<'info>,
+
+    #[account(mut,
+        constraint = !position.frozen @ ErrorCode::PositionFrozen
+    )]
+    pub position: Account<'info, Position>,
+
+    #[account(
+        constraint = position_token_account.mint == position.position_mint,
+        constraint = position_token_account.amount == 1
+    )]
+    pub position_token_account: Box<Account<'info, TokenAccount>>,
+}
+
+/*
+  Lets the position token holder name the key that can re-mint the position token if it is burned.
+  The holder has to be known while the token still exists, a burned token has no holder left.
+*/
+pub fn handler(
+    ctx: Context<SetPositionRecoveryAuthority>,
+    recovery_authority: Pubkey,
+) -> Result<()> {
+    verify_position_authority(
+        &ctx.accounts.position_token_account,
+        &ctx.accounts.position_authority,
+    )?;
+
+    ctx.accounts
+        .position
+        .update_recovery_authority(recovery_authority);
+
+    Ok(())
+}
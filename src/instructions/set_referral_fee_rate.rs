@@ -0,0 +1,28 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::ErrorCode;
+use crate::state::{Whirlpool, WhirlpoolsConfig};
+
+#[derive(Accounts)]
+pub struct SetReferralFeeRate<'info> {
+    pub whirlpools_config: Account<'info, WhirlpoolsConfig>,
+
+    #[account(mut, has_one = whirlpools_config)]
+    pub whirlpool: Account<'info, Whirlpool>,
+
+    #[account(
+        address = whirlpools_config.fee_authority,
+        constraint = !whirlpools_config.admin_frozen @ ErrorCode::AdminParametersFrozen,
+    )]
+    pub fee_authority: Signer<'info>,
+}
+
+/*
+  Sets the share of the protocol fee paid to the referrer of a swap made with swap_with_referral.
+  Swaps without a referrer keep the whole protocol fee.
+*/
+pub fn handler(ctx: Context<SetReferralFeeRate>, referral_fee_rate: u16) -> Result<()> {
+    ctx.accounts
+        .whirlpool
+        .update_referral_fee_rate(referral_fee_rate)
+}
@@ -0,0 +1,57 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::TokenAccount;
+
+use crate::manager::whirlpool_manager::next_whirlpool_reward_infos;
+use crate::state::Whirlpool;
+use crate::util::to_timestamp_u64;
+
+/// Accounts to change how a reward is distributed, shared by `set_reward_accrual_mode` and
+/// `set_reward_emission_mode`.
+#[derive(Accounts)]
+#[instruction(reward_index: u8)]
+pub struct SetRewardMode<'info> {
+    #[account(mut)]
+    pub whirlpool: Account<'info, Whirlpool>,
+
+    #[account(address = whirlpool.reward_infos[reward_index as usize].authority)]
+    pub reward_authority: Signer<'info>,
+
+    #[account(address = whirlpool.reward_infos[reward_index as usize].vault)]
+    pub reward_vault: Box<Account<'info, TokenAccount>>,
+}
+
+/*
+  Switches a reward between accruing to in-range liquidity and accruing to all liquidity.
+*/
+pub fn handler(
+    ctx: Context<SetRewardMode>,
+    reward_index: u8,
+    accrues_to_all_liquidity: bool,
+) -> Result<()> {
+    update_reward_mode(ctx.accounts, reward_index, |whirlpool| {
+        whirlpool
+            .update_reward_accrues_to_all_liquidity(reward_index as usize, accrues_to_all_liquidity)
+    })
+}
+
+/// Bring the rewards up to date, so that emissions since the last update are not accounted under
+/// the new mode, then apply the mode update and verify the reward vault still covers a day of the
+/// emissions of the reward.
+pub fn update_reward_mode(
+    accounts: &mut SetRewardMode,
+    reward_index: u8,
+    update: impl FnOnce(&mut Whirlpool) -> Result<()>,
+) -> Result<()> {
+    let whirlpool = &mut accounts.whirlpool;
+
+    let clock = Clock::get()?;
+    let timestamp = to_timestamp_u64(clock.unix_timestamp)?;
+    let next_reward_infos = next_whirlpool_reward_infos(whirlpool, timestamp)?;
+    whirlpool.update_rewards(next_reward_infos, timestamp);
+
+    update(whirlpool)?;
+    whirlpool.verify_reward_vault_covers_emissions(
+        reward_index as usize,
+        accounts.reward_vault.amount,
+    )
+}
@@ -0,0 +1,21 @@
+use anchor_lang::prelude::*;
+
+use super::{update_reward_mode, SetRewardMode};
+
+/*
+  Switches a reward between emissions split among the liquidity it accrues to and emissions per
+  unit of that liquidity, up to the liquidity cap. The reward vault has to cover a day of the
+  emissions of the capped liquidity.
+*/
+pub fn handler(
+    ctx: Context<SetRewardMode>,
+    reward_index: u8,
+    emissions_liquidity_cap: u128,
+) -> Result<()> {
+    update_reward_mode(ctx.accounts, reward_index, |whirlpool| {
+        whirlpool.update_reward_emissions_liquidity_cap(
+            reward_index as usize,
+            emissions_liquidity_cap,
+        )
+    })
+}
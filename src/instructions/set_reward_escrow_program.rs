@@ -0,0 +1,28 @@
+use anchor_lang::prelude::*;
+
+use crate::state::Whirlpool;
+
+#[derive(Accounts)]
+#[instruction(reward_index: u8)]
+pub struct SetRewardEscrowProgram<'info> {
+    #[account(mut)]
+    pub whirlpool: Account<'info, Whirlpool>,
+
+    #[account(address = whirlpool.reward_infos[reward_index as usize].authority)]
+    pub reward_authority: Signer<'info>,
+}
+
+/*
+  Sets the program that releases rewards into the reward vault when positions collect more than
+  the vault holds, e.g. a payment stream funding the reward. The reward vault stays the token
+  account of the Whirlpool, so rewards already in it are unaffected.
+*/
+pub fn handler(
+    ctx: Context<SetRewardEscrowProgram>,
+    reward_index: u8,
+    escrow_program: Pubkey,
+) -> Result<()> {
+    ctx.accounts
+        .whirlpool
+        .update_reward_escrow_program(reward_index as usize, escrow_program)
+}
@@ -0,0 +1,23 @@
+use anchor_lang::prelude::*;
+
+use crate::state::{RetroRewardDistributor, Whirlpool};
+
+#[derive(Accounts)]
+pub struct SetRewardMerkleRoot<'info> {
+    pub whirlpool: Box<Account<'info, Whirlpool>>,
+
+    #[account(mut, has_one = whirlpool)]
+    pub retro_reward: Box<Account<'info, RetroRewardDistributor>>,
+
+    #[account(address = whirlpool.reward_infos[retro_reward.reward_index as usize].authority)]
+    pub reward_authority: Signer<'info>,
+}
+
+/*
+  Replaces the merkle root of a retroactive reward distributor. Leaves hold cumulative amounts, so
+  a new root must keep every claimant at or above what the previous root granted.
+*/
+pub fn handler(ctx: Context<SetRewardMerkleRoot>, merkle_root: [u8; 32]) -> Result<()> {
+    ctx.accounts.retro_reward.update_merkle_root(merkle_root);
+    Ok(())
+}
@@ -0,0 +1,31 @@
+use anchor_lang::prelude::*;
+
+use crate::state::Whirlpool;
+
+#[derive(Accounts)]
+#[instruction(reward_index: u8)]
+pub struct SetRewardTenureBoost<'info> {
+    #[account(mut)]
+    pub whirlpool: Account<'info, Whirlpool>,
+
+    #[account(address = whirlpool.reward_infos[reward_index as usize].authority)]
+    pub reward_authority: Signer<'info>,
+}
+
+/*
+  Sets the tenure boost of a reward. Positions earn the base rate of the rewards they accrue, rising
+  to the full rewards over the boost period of tenure. The tenure boost applies to the rewards
+  accrued since each position's last checkpoint when it is next checkpointed.
+*/
+pub fn handler(
+    ctx: Context<SetRewardTenureBoost>,
+    reward_index: u8,
+    boost_period: u32,
+    boost_base_rate: u16,
+) -> Result<()> {
+    ctx.accounts.whirlpool.update_reward_tenure_boost(
+        reward_index as usize,
+        boost_period,
+        boost_base_rate,
+    )
+}
@@ -0,0 +1,24 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::ErrorCode;
+use crate::state::{RouterRegistry, WhirlpoolsConfig};
+
+#[derive(Accounts)]
+pub struct SetRouterFeeDiscountRate<'info> {
+    pub whirlpools_config: Box<Account<'info, WhirlpoolsConfig>>,
+
+    #[account(mut, has_one = whirlpools_config)]
+    pub router_registry: Box<Account<'info, RouterRegistry>>,
+
+    #[account(
+        address = whirlpools_config.fee_authority,
+        constraint = !whirlpools_config.admin_frozen @ ErrorCode::AdminParametersFrozen,
+    )]
+    pub fee_authority: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<SetRouterFeeDiscountRate>, fee_discount_rate: u16) -> Result<()> {
+    ctx.accounts
+        .router_registry
+        .update_fee_discount_rate(fee_discount_rate)
+}
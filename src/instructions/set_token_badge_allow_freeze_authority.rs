@@ -0,0 +1,31 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::ErrorCode;
+use crate::state::{TokenBadge, WhirlpoolsConfig};
+
+#[derive(Accounts)]
+pub struct SetTokenBadgeAllowFreezeAuthority<'info> {
+    pub whirlpools_config: Box<Account<'info, WhirlpoolsConfig>>,
+
+    #[account(mut, has_one = whirlpools_config)]
+    pub token_badge: Box<Account<'info, TokenBadge>>,
+
+    #[account(
+        address = whirlpools_config.fee_authority,
+        constraint = !whirlpools_config.admin_frozen @ ErrorCode::AdminParametersFrozen,
+    )]
+    pub fee_authority: Signer<'info>,
+}
+
+/*
+  Only affects pools created afterwards, pools already holding the token keep their vaults.
+*/
+pub fn handler(
+    ctx: Context<SetTokenBadgeAllowFreezeAuthority>,
+    allow_freeze_authority: bool,
+) -> Result<()> {
+    ctx.accounts
+        .token_badge
+        .update_allow_freeze_authority(allow_freeze_authority);
+    Ok(())
+}
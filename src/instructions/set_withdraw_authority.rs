@@ -0,0 +1,43 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::TokenAccount;
+
+use crate::errors::ErrorCode;
+use crate::state::*;
+use crate::util::verify_position_authority;
+
+#[derive(Accounts)]
+pub struct SetWithdrawAuthority<'info> {
+    pub position_authority: Signer<'info>,
+
+    #[account(mut,
+        constraint = !position.frozen @ ErrorCode::PositionFrozen
+    )]
+    pub position: Account<'info, Position>,
+
+    #[account(
+        constraint = position_token_account.mint == position.position_mint,
+        constraint = position_token_account.amount == 1
+    )]
+    pub position_token_account: Box<Account<'info, TokenAccount>>,
+}
+
+/*
+  Lets an operations key decrease the liquidity of the position while the position token stays in
+  cold storage. The withdrawn tokens can only go to the token accounts of the withdraw destination.
+*/
+pub fn handler(
+    ctx: Context<SetWithdrawAuthority>,
+    withdraw_authority: Pubkey,
+    withdraw_destination: Pubkey,
+) -> Result<()> {
+    verify_position_authority(
+        &ctx.accounts.position_token_account,
+        &ctx.accounts.position_authority,
+    )?;
+
+    ctx.accounts
+        .position
+        .update_withdraw_authority(withdraw_authority, withdraw_destination);
+
+    Ok(())
+}
@@ -0,0 +1,142 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount};
+
+use crate::{
+    errors::ErrorCode,
+    manager::liquidity_manager::calculate_fee_and_reward_growths,
+    state::*,
+    util::{
+        accrue_liquidity_time, to_timestamp_u64, transfer_from_vault_to_owner,
+        verify_destination_not_frozen, verify_position_authority,
+    },
+};
+
+use super::{decrease_position_liquidity, DecreaseLiquidityParams, LiquidityTokenAccounts};
+
+#[derive(Accounts)]
+pub struct SettlePositionForLiquidation<'info> {
+    pub whirlpools_config: Box<Account<'info, WhirlpoolsConfig>>,
+
+    #[account(mut, has_one = whirlpools_config)]
+    pub whirlpool: Account<'info, Whirlpool>,
+
+    #[account(address = whirlpools_config.approved_liquidator)]
+    pub liquidator: Signer<'info>,
+
+    #[account(mut,
+        has_one = whirlpool,
+        constraint = !position.frozen @ ErrorCode::PositionFrozen
+    )]
+    pub position: Account<'info, Position>,
+    #[account(
+        constraint = position_token_account.mint == position.position_mint,
+        constraint = position_token_account.amount == 1
+    )]
+    pub position_token_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut, constraint = token_owner_account_a.mint == whirlpool.token_mint_a)]
+    pub token_owner_account_a: Box<Account<'info, TokenAccount>>,
+    #[account(mut, constraint = token_owner_account_b.mint == whirlpool.token_mint_b)]
+    pub token_owner_account_b: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut, address = whirlpool.token_vault_a)]
+    pub token_vault_a: Box<Account<'info, TokenAccount>>,
+    #[account(mut, address = whirlpool.token_vault_b)]
+    pub token_vault_b: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut, has_one = whirlpool)]
+    pub tick_array_lower: AccountLoader<'info, TickArray>,
+    #[account(mut, has_one = whirlpool)]
+    pub tick_array_upper: AccountLoader<'info, TickArray>,
+
+    #[account(address = token::ID)]
+    pub token_program: Program<'info, Token>,
+}
+
+/*
+  Updates the fees of a collateralized position, withdraws liquidity and collects the fees into
+  the token accounts of the approved liquidator. The position token has to be held by or
+  delegated to the liquidator, so the owner does not need to sign.
+*/
+pub fn handler(
+    ctx: Context<SettlePositionForLiquidation>,
+    liquidity_amount: u128,
+    token_min_a: u64,
+    token_min_b: u64,
+) -> Result<()> {
+    verify_position_authority(
+        &ctx.accounts.position_token_account,
+        &ctx.accounts.liquidator,
+    )?;
+    verify_destination_not_frozen(&ctx.accounts.token_owner_account_a)?;
+    verify_destination_not_frozen(&ctx.accounts.token_owner_account_b)?;
+
+    let whirlpool = &mut ctx.accounts.whirlpool;
+    let position = &mut ctx.accounts.position;
+
+    // Withdrawing liquidity updates the fees and rewards of the position. Without a withdrawal
+    // they are updated here, as in update_fees_and_rewards.
+    if liquidity_amount > 0 {
+        // The liquidity time of the owner is not accrued for liquidations.
+        accrue_liquidity_time(
+            None,
+            whirlpool,
+            position,
+            &ctx.accounts.position_token_account.owner,
+            to_timestamp_u64(Clock::get()?.unix_timestamp)?,
+        )?;
+        decrease_position_liquidity(
+            whirlpool,
+            position,
+            &ctx.accounts.tick_array_lower,
+            &ctx.accounts.tick_array_upper,
+            LiquidityTokenAccounts {
+                token_owner_account_a: &ctx.accounts.token_owner_account_a,
+                token_owner_account_b: &ctx.accounts.token_owner_account_b,
+                token_vault_a: &ctx.accounts.token_vault_a,
+                token_vault_b: &ctx.accounts.token_vault_b,
+                token_program: &ctx.accounts.token_program,
+            },
+            DecreaseLiquidityParams {
+                liquidity_amount,
+                token_min_a,
+                token_min_b,
+            },
+        )?;
+    } else if position.liquidity > 0 {
+        whirlpool.verify_tick_arrays_synced()?;
+        let timestamp = to_timestamp_u64(Clock::get()?.unix_timestamp)?;
+        let (position_update, reward_infos) = calculate_fee_and_reward_growths(
+            whirlpool,
+            position,
+            &ctx.accounts.tick_array_lower,
+            &ctx.accounts.tick_array_upper,
+            timestamp,
+        )?;
+        whirlpool.update_rewards(reward_infos, timestamp);
+        position.update(&position_update);
+    }
+
+    let fee_owed_a = whirlpool.to_token_amount(position.fee_owed_a, true, false)?;
+    let fee_owed_b = whirlpool.to_token_amount(position.fee_owed_b, false, false)?;
+    position.reset_fees_owed();
+
+    transfer_from_vault_to_owner(
+        whirlpool,
+        &ctx.accounts.token_vault_a,
+        &ctx.accounts.token_owner_account_a,
+        &ctx.accounts.token_program,
+        fee_owed_a,
+    )?;
+
+    transfer_from_vault_to_owner(
+        whirlpool,
+        &ctx.accounts.token_vault_b,
+        &ctx.accounts.token_owner_account_b,
+        &ctx.accounts.token_program,
+        fee_owed_b,
+    )?;
+
+    whirlpool.record_vault_withdrawals(fee_owed_a, fee_owed_b);
+    Ok(())
+}
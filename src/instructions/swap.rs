@@ -3,11 +3,17 @@ use anchor_spl::token::{self, Token, TokenAccount};
 
 use crate::{
     errors::ErrorCode,
+    events::SwapFees,
     manager::swap_manager::*,
-    state::{TickArray, Whirlpool},
+    math::discounted_fee_rate,
+    state::{
+        FeeSchedule, PartnerStats, ReferralEarnings, TickArray, TradeLog, TradeRecord, Whirlpool,
+    },
     util::{
+        fail_with_quote_hint, invoke_after_swap_hook, invoke_tick_cross_hook,
+        record_liquidity_shift, resolve_sqrt_price_limit, split_tick_cross_hook_accounts,
         to_timestamp_u64, transfer_from_owner_to_vault, transfer_from_vault_to_owner,
-        SwapTickSequence,
+        verify_swap_tick_array_address, AfterSwapHookData, SwapTickSequence,
     },
 };
 
@@ -45,48 +51,252 @@ pub struct Swap<'info> {
     pub oracle: UncheckedAccount<'info>,
 }
 
-pub fn handler(
-    ctx: Context<Swap>,
+/// Fees charged on a swap, denominated in the input token. Returned from the swap instructions
+/// as return data.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, PartialEq)]
+#[cfg_attr(not(target_arch = "bpf"), derive(Debug))]
+pub struct SwapFeeBreakdown {
+    // Portion of the fee accrued to liquidity providers
+    pub lp_fee: u64,
+    // Portion of the fee accrued to the protocol
+    pub protocol_fee: u64,
+    // Portion of the fee paid to a referrer
+    pub referral_fee: u64,
+}
+
+/// Token accounts a swap transfers between, shared by all swap account layouts.
+pub struct SwapTokenAccounts<'a, 'info> {
+    pub token_authority: &'a Signer<'info>,
+    pub token_owner_account_a: &'a Account<'info, TokenAccount>,
+    pub token_owner_account_b: &'a Account<'info, TokenAccount>,
+    pub token_vault_a: &'a Account<'info, TokenAccount>,
+    pub token_vault_b: &'a Account<'info, TokenAccount>,
+    pub token_program: &'a Program<'info, Token>,
+}
+
+/// Options of a swap set by the instruction running it. The defaults are those of a plain swap
+/// against tick arrays.
+#[derive(Default)]
+pub struct SwapOptions<'a> {
+    // True if the tick sequence is the window of the whirlpool's tick cache instead of its tick
+    // arrays
+    pub uses_tick_cache: bool,
+    // Credited with the referral share of the protocol fee, if the swap has a referrer
+    pub referral_earnings: Option<&'a mut ReferralEarnings>,
+    // Records the volume and fees of the swap, if the swap has a partner
+    pub partner_stats: Option<&'a mut PartnerStats>,
+    // Basis point share of the fee rate waived for the swap, non-zero for swaps made by an
+    // approved router
+    pub fee_discount_rate: u16,
+    // Charges the fee of an exact input swap on top of `amount` instead of deducting it, so the
+    // whole `amount` is traded
+    pub fee_on_top: bool,
+}
+
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, Swap<'info>>,
     amount: u64,
     other_amount_threshold: u64,
     sqrt_price_limit: u128,
     amount_specified_is_input: bool,
     a_to_b: bool, // Zero for one
-) -> Result<()> {
-    let whirlpool = &mut ctx.accounts.whirlpool;
-    let clock = Clock::get()?;
-    // Update the global reward growth which increases as a function of time.
-    let timestamp = to_timestamp_u64(clock.unix_timestamp)?;
+) -> Result<SwapFeeBreakdown> {
+    swap_with_tick_arrays(
+        ctx.accounts,
+        ctx.remaining_accounts,
+        SwapParams {
+            amount,
+            sqrt_price_limit,
+            amount_specified_is_input,
+            a_to_b,
+        },
+        other_amount_threshold,
+        SwapOptions::default(),
+    )
+}
+
+/// Run a swap with the `Swap` account layout against its tick arrays. The tick cache option is
+/// ignored.
+pub fn swap_with_tick_arrays<'info>(
+    accounts: &mut Swap<'info>,
+    remaining_accounts: &'info [AccountInfo<'info>],
+    params: SwapParams,
+    other_amount_threshold: u64,
+    options: SwapOptions,
+) -> Result<SwapFeeBreakdown> {
+    verify_swap_tick_array_address(
+        &accounts.whirlpool,
+        &accounts.whirlpool.key(),
+        params.a_to_b,
+        &accounts.tick_array_0.key(),
+    )?;
+
     let mut swap_tick_sequence = SwapTickSequence::new(
-        ctx.accounts.tick_array_0.load_mut().unwrap(),
-        ctx.accounts.tick_array_1.load_mut().ok(),
-        ctx.accounts.tick_array_2.load_mut().ok(),
+        accounts.tick_array_0.load_mut().unwrap(),
+        accounts.tick_array_1.load_mut().ok(),
+        accounts.tick_array_2.load_mut().ok(),
     );
 
-    let swap_update = swap(
-        &whirlpool,
+    execute_swap(
+        &mut accounts.whirlpool,
+        SwapTokenAccounts {
+            token_authority: &accounts.token_authority,
+            token_owner_account_a: &accounts.token_owner_account_a,
+            token_owner_account_b: &accounts.token_owner_account_b,
+            token_vault_a: &accounts.token_vault_a,
+            token_vault_b: &accounts.token_vault_b,
+            token_program: &accounts.token_program,
+        },
         &mut swap_tick_sequence,
+        remaining_accounts,
+        params,
+        other_amount_threshold,
+        SwapOptions {
+            uses_tick_cache: false,
+            ..options
+        },
+    )
+}
+
+/// Run a swap against the provided tick sequence, settle the token transfers, record the trade
+/// and invoke the tick cross and after swap hooks. Shared by all swap account layouts.
+///
+/// `remaining_accounts` holds the protocol stats in builds with the `protocol-stats-swaps`
+/// feature, followed by the trade log if the whirlpool has one, followed by the fee schedule if
+/// the whirlpool has one, followed by the tick cross hook program and its accounts, followed by
+/// the after swap hook program and its accounts.
+pub fn execute_swap<'info>(
+    whirlpool: &mut Account<'info, Whirlpool>,
+    token_accounts: SwapTokenAccounts<'_, 'info>,
+    swap_tick_sequence: &mut SwapTickSequence,
+    remaining_accounts: &'info [AccountInfo<'info>],
+    params: SwapParams,
+    other_amount_threshold: u64,
+    options: SwapOptions,
+) -> Result<SwapFeeBreakdown> {
+    let SwapTokenAccounts {
+        token_authority,
+        token_owner_account_a,
+        token_owner_account_b,
+        token_vault_a,
+        token_vault_b,
+        token_program,
+    } = token_accounts;
+    let SwapParams {
         amount,
         sqrt_price_limit,
         amount_specified_is_input,
         a_to_b,
-        timestamp,
+    } = params;
+    let SwapOptions {
+        uses_tick_cache,
+        referral_earnings,
+        partner_stats,
+        fee_discount_rate,
+        fee_on_top,
+    } = options;
+
+    whirlpool.verify_not_paused()?;
+    if !whirlpool.is_swap_direction_enabled(a_to_b) {
+        return Err(ErrorCode::SwapDirectionDisabled.into());
+    }
+    if uses_tick_cache {
+        // The cache window starts half an array below the current tick, off array boundaries.
+        whirlpool.verify_tick_cache_fresh()?;
+    } else {
+        whirlpool.verify_tick_arrays_synced()?;
+        swap_tick_sequence.verify_start_tick_indexes(whirlpool.tick_spacing)?;
+    }
+
+    let clock = Clock::get()?;
+    // Update the global reward growth which increases as a function of time.
+    let timestamp = to_timestamp_u64(clock.unix_timestamp)?;
+
+    #[cfg(feature = "protocol-stats-swaps")]
+    let (protocol_stats, remaining_accounts) = match remaining_accounts.split_first() {
+        Some((protocol_stats, remaining_accounts)) => (protocol_stats, remaining_accounts),
+        None => return Err(ErrorCode::RemainingAccountsMissing.into()),
+    };
+
+    let (trade_log, hook_accounts) = if whirlpool.has_trade_log() {
+        match remaining_accounts.split_first() {
+            Some((trade_log, hook_accounts)) => (Some(trade_log), hook_accounts),
+            None => return Err(ErrorCode::RemainingAccountsMissing.into()),
+        }
+    } else {
+        (None, remaining_accounts)
+    };
+    let (fee_rate, hook_accounts) = if whirlpool.has_fee_schedule() {
+        match hook_accounts.split_first() {
+            Some((fee_schedule, hook_accounts)) => (
+                scheduled_fee_rate(whirlpool, fee_schedule, timestamp)?,
+                hook_accounts,
+            ),
+            None => return Err(ErrorCode::RemainingAccountsMissing.into()),
+        }
+    } else {
+        (whirlpool.effective_fee_rate(), hook_accounts)
+    };
+    let fee_rate = discounted_fee_rate(fee_rate, fee_discount_rate);
+    let (tick_cross_hook_accounts, hook_accounts) =
+        split_tick_cross_hook_accounts(whirlpool, hook_accounts);
+
+    // For rebasing-aware pools the specified amount is accounted in underlying units.
+    let amount = whirlpool.to_pool_amount(
+        amount,
+        a_to_b == amount_specified_is_input,
+        !amount_specified_is_input,
     )?;
 
+    // A sqrt price limit of 0 lets the swap run to the price bound in its direction. Limits
+    // beyond the tick bounds of the pool stop the swap at the bound.
+    let sqrt_price_limit = resolve_sqrt_price_limit(sqrt_price_limit, a_to_b);
+    let sqrt_price_limit = whirlpool.clamp_sqrt_price_limit(sqrt_price_limit, a_to_b);
+
+    let swap_update = if fee_on_top {
+        swap_fee_on_top(
+            whirlpool,
+            fee_rate,
+            swap_tick_sequence,
+            amount,
+            sqrt_price_limit,
+            a_to_b,
+            timestamp,
+        )?
+    } else {
+        swap_with_fee_rate(
+            whirlpool,
+            fee_rate,
+            swap_tick_sequence,
+            SwapParams {
+                amount,
+                sqrt_price_limit,
+                amount_specified_is_input,
+                a_to_b,
+            },
+            timestamp,
+        )?
+    };
+
+    // Deposits round up and withdrawals round down when converting back to token amounts.
+    let amount_a = whirlpool.to_token_amount(swap_update.amount_a, true, a_to_b)?;
+    let amount_b = whirlpool.to_token_amount(swap_update.amount_b, false, !a_to_b)?;
+
     if amount_specified_is_input {
-        if (a_to_b && other_amount_threshold > swap_update.amount_b)
-            || (!a_to_b && other_amount_threshold > swap_update.amount_a)
+        if (a_to_b && other_amount_threshold > amount_b)
+            || (!a_to_b && other_amount_threshold > amount_a)
         {
-            return Err(ErrorCode::AmountOutBelowMinimum.into());
+            return fail_with_quote_hint(ErrorCode::AmountOutBelowMinimum, amount_a, amount_b);
         }
     } else {
-        if (a_to_b && other_amount_threshold < swap_update.amount_a)
-            || (!a_to_b && other_amount_threshold < swap_update.amount_b)
+        if (a_to_b && other_amount_threshold < amount_a)
+            || (!a_to_b && other_amount_threshold < amount_b)
         {
-            return Err(ErrorCode::AmountInAboveMaximum.into());
+            return fail_with_quote_hint(ErrorCode::AmountInAboveMaximum, amount_a, amount_b);
         }
     }
 
+    let previous_liquidity = whirlpool.liquidity;
     whirlpool.update_after_swap(
         swap_update.next_liquidity,
         swap_update.next_tick_index,
@@ -94,26 +304,179 @@ pub fn handler(
         swap_update.next_fee_growth_global,
         swap_update.next_reward_infos,
         swap_update.next_protocol_fee,
-        a_to_b,
+        swap_update.is_fee_in_token_a,
         timestamp,
     );
+    whirlpool.update_last_swap_slot(clock.slot);
+    record_liquidity_shift(whirlpool, previous_liquidity, clock.slot);
+    whirlpool.add_rounding_dust(swap_update.rounding_dust_a, swap_update.rounding_dust_b);
+    let is_fee_in_token_a = swap_update.is_fee_in_token_a;
+    // The referral fee is carved out of the protocol fee, liquidity providers are unaffected.
+    let referral_fee = match referral_earnings {
+        Some(referral_earnings) => {
+            let referral_fee =
+                whirlpool.take_referral_fee(swap_update.next_protocol_fee, is_fee_in_token_a);
+            referral_earnings.add_fee(referral_fee, is_fee_in_token_a);
+            referral_fee
+        }
+        None => 0,
+    };
+    if swap_update.crossed_initialized_tick {
+        whirlpool.record_tick_updates(uses_tick_cache);
+    }
+
+    #[cfg(feature = "paranoid-checks")]
+    let vault_balances = crate::util::VaultBalances::read(
+        &token_vault_a.to_account_info(),
+        &token_vault_b.to_account_info(),
+    )?;
 
     perform_swap(
-        &ctx.accounts.whirlpool,
-        &ctx.accounts.token_authority,
-        &ctx.accounts.token_owner_account_a,
-        &ctx.accounts.token_owner_account_b,
-        &ctx.accounts.token_vault_a,
-        &ctx.accounts.token_vault_b,
-        &ctx.accounts.token_program,
-        swap_update.amount_a,
-        swap_update.amount_b,
+        whirlpool,
+        token_authority,
+        token_owner_account_a,
+        token_owner_account_b,
+        token_vault_a,
+        token_vault_b,
+        token_program,
+        amount_a,
+        amount_b,
         a_to_b,
-    )
+    )?;
+
+    #[cfg(feature = "paranoid-checks")]
+    {
+        let (delta_a, delta_b) = if a_to_b {
+            (i128::from(amount_a), -i128::from(amount_b))
+        } else {
+            (-i128::from(amount_a), i128::from(amount_b))
+        };
+        vault_balances.verify_deltas(
+            &token_vault_a.to_account_info(),
+            &token_vault_b.to_account_info(),
+            delta_a,
+            delta_b,
+        )?;
+    }
+
+    if let Some(trade_log) = trade_log {
+        let (amount_in, amount_out) = if a_to_b {
+            (amount_a, amount_b)
+        } else {
+            (amount_b, amount_a)
+        };
+        append_trade_record(
+            whirlpool,
+            trade_log,
+            TradeRecord {
+                sqrt_price: swap_update.next_sqrt_price,
+                amount_in,
+                amount_out,
+                slot: clock.slot,
+                a_to_b,
+            },
+        )?;
+    }
+
+    // Fees are taken from the input token, or in token B for pools that account fees in token B.
+    let protocol_and_referral_fee =
+        whirlpool.to_token_amount(swap_update.next_protocol_fee, is_fee_in_token_a, false)?;
+    let referral_fee = whirlpool.to_token_amount(referral_fee, is_fee_in_token_a, false)?;
+    let total_fee = whirlpool.to_token_amount(swap_update.total_fee, is_fee_in_token_a, false)?;
+    whirlpool.record_swap_volume(amount_a, amount_b, total_fee, is_fee_in_token_a);
+    if let Some(partner_stats) = partner_stats {
+        partner_stats.record_swap(amount_a, amount_b, total_fee, is_fee_in_token_a);
+    }
+    let fee_breakdown = SwapFeeBreakdown {
+        lp_fee: total_fee.saturating_sub(protocol_and_referral_fee),
+        protocol_fee: protocol_and_referral_fee.saturating_sub(referral_fee),
+        referral_fee,
+    };
+
+    // Hooks run last, once the whirlpool holds its final state for this swap. Each hook writes the
+    // whirlpool to its account before invoking the hook program.
+    if whirlpool.has_tick_cross_hook() && !swap_update.crossed_tick_indexes.is_empty() {
+        invoke_tick_cross_hook(
+            whirlpool,
+            tick_cross_hook_accounts,
+            &swap_update.crossed_tick_indexes,
+            a_to_b,
+            timestamp,
+        )?;
+    }
+
+    if whirlpool.has_after_swap_hook() {
+        invoke_after_swap_hook(
+            whirlpool,
+            &token_authority.to_account_info(),
+            hook_accounts,
+            &AfterSwapHookData {
+                whirlpool: whirlpool.key(),
+                token_authority: token_authority.key(),
+                a_to_b,
+                amount_specified_is_input,
+                amount_a,
+                amount_b,
+                sqrt_price: swap_update.next_sqrt_price,
+                tick_current_index: swap_update.next_tick_index,
+                timestamp,
+            },
+        )?;
+    }
+
+    #[cfg(feature = "protocol-stats-swaps")]
+    record_protocol_stats_swap(protocol_stats, fee_breakdown.protocol_fee)?;
+
+    emit!(SwapFees {
+        whirlpool: whirlpool.key(),
+        a_to_b,
+        fee_in_token_a: is_fee_in_token_a,
+        lp_fee: fee_breakdown.lp_fee,
+        protocol_fee: fee_breakdown.protocol_fee,
+        referral_fee: fee_breakdown.referral_fee,
+    });
+
+    Ok(fee_breakdown)
+}
+
+fn append_trade_record<'info>(
+    whirlpool: &Account<Whirlpool>,
+    trade_log: &'info AccountInfo<'info>,
+    record: TradeRecord,
+) -> Result<()> {
+    if trade_log.key() != whirlpool.trade_log {
+        return Err(ErrorCode::InvalidTradeLogAccount.into());
+    }
+    let trade_log = AccountLoader::<TradeLog>::try_from(trade_log)?;
+    trade_log.load_mut()?.append(record);
+    Ok(())
+}
+
+fn scheduled_fee_rate<'info>(
+    whirlpool: &Account<Whirlpool>,
+    fee_schedule: &'info AccountInfo<'info>,
+    timestamp: u64,
+) -> Result<u32> {
+    if fee_schedule.key() != whirlpool.fee_schedule {
+        return Err(ErrorCode::InvalidFeeScheduleAccount.into());
+    }
+    let fee_schedule = Account::<FeeSchedule>::try_from(fee_schedule)?;
+    Ok(fee_schedule.fee_rate_at(whirlpool.effective_fee_rate(), timestamp))
+}
+
+// Any ProtocolStats account of this program is the singleton, it can only be created at its PDA.
+#[cfg(feature = "protocol-stats-swaps")]
+fn record_protocol_stats_swap<'info>(
+    protocol_stats: &'info AccountInfo<'info>,
+    protocol_fee: u64,
+) -> Result<()> {
+    let mut protocol_stats = Account::<crate::state::ProtocolStats>::try_from(protocol_stats)?;
+    protocol_stats.record_swap(protocol_fee);
+    protocol_stats.exit(&crate::ID)
 }
 
 fn perform_swap<'info>(
-    whirlpool: &Account<'info, Whirlpool>,
+    whirlpool: &mut Account<'info, Whirlpool>,
     token_authority: &Signer<'info>,
     token_owner_account_a: &Account<'info, TokenAccount>,
     token_owner_account_b: &Account<'info, TokenAccount>,
@@ -168,5 +531,13 @@ fn perform_swap<'info>(
         withdrawal_amount,
     )?;
 
+    if a_to_b {
+        whirlpool.record_vault_deposits(amount_a, 0);
+        whirlpool.record_vault_withdrawals(0, amount_b);
+    } else {
+        whirlpool.record_vault_deposits(0, amount_b);
+        whirlpool.record_vault_withdrawals(amount_a, 0);
+    }
+
     Ok(())
 }
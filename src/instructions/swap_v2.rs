@@ -0,0 +1,151 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount};
+
+use crate::{
+    errors::ErrorCode,
+    manager::swap_manager::SwapParams,
+    state::*,
+    util::{verify_not_upstream_account, verify_swap_tick_array_address, SwapTickSequence},
+};
+
+use super::{execute_swap, SwapFeeBreakdown, SwapOptions, SwapTokenAccounts};
+
+// Number of remaining accounts with a fixed position in the swap_v2 layout.
+pub const SWAP_V2_FIXED_REMAINING_ACCOUNTS: usize = 4;
+
+/// Swap account layout with the tick arrays and the oracle moved into remaining accounts.
+///
+/// Remaining accounts must be passed in the following order:
+/// - `[0]` tick_array_0 (writable)
+/// - `[1]` tick_array_1 (writable)
+/// - `[2]` tick_array_2 (writable)
+/// - `[3]` oracle
+/// - `[4..]` trade log (writable), if the whirlpool has a trade log
+/// - then the fee schedule, if the whirlpool has a fee schedule
+/// - then the tick cross hook program and its accounts, if the whirlpool has one
+/// - then the after swap hook program and its accounts, if the whirlpool has a hook
+///
+/// Builds with the `protocol-stats-swaps` feature expect the protocol stats account at `[4]`,
+/// ahead of the trade log, fee schedule and hook accounts.
+#[derive(Accounts)]
+pub struct SwapV2<'info> {
+    #[account(address = token::ID)]
+    pub token_program: Program<'info, Token>,
+
+    pub token_authority: Signer<'info>,
+
+    #[account(mut)]
+    pub whirlpool: Box<Account<'info, Whirlpool>>,
+
+    #[account(mut, constraint = token_owner_account_a.mint == whirlpool.token_mint_a)]
+    pub token_owner_account_a: Box<Account<'info, TokenAccount>>,
+    #[account(mut, address = whirlpool.token_vault_a)]
+    pub token_vault_a: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut, constraint = token_owner_account_b.mint == whirlpool.token_mint_b)]
+    pub token_owner_account_b: Box<Account<'info, TokenAccount>>,
+    #[account(mut, address = whirlpool.token_vault_b)]
+    pub token_vault_b: Box<Account<'info, TokenAccount>>,
+}
+
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, SwapV2<'info>>,
+    amount: u64,
+    other_amount_threshold: u64,
+    sqrt_price_limit: u128,
+    amount_specified_is_input: bool,
+    a_to_b: bool, // Zero for one
+) -> Result<SwapFeeBreakdown> {
+    swap_v2_with_fee_mode(
+        ctx,
+        amount,
+        other_amount_threshold,
+        sqrt_price_limit,
+        amount_specified_is_input,
+        a_to_b,
+        false,
+    )
+}
+
+/// Run a `swap_v2`, charging the fee on top of `amount` if `fee_on_top` is set.
+pub fn swap_v2_with_fee_mode<'info>(
+    ctx: Context<'_, '_, 'info, 'info, SwapV2<'info>>,
+    amount: u64,
+    other_amount_threshold: u64,
+    sqrt_price_limit: u128,
+    amount_specified_is_input: bool,
+    a_to_b: bool,
+    fee_on_top: bool,
+) -> Result<SwapFeeBreakdown> {
+    if ctx.remaining_accounts.len() < SWAP_V2_FIXED_REMAINING_ACCOUNTS {
+        return Err(ErrorCode::RemainingAccountsMissing.into());
+    }
+    let (fixed_accounts, optional_accounts) = ctx
+        .remaining_accounts
+        .split_at(SWAP_V2_FIXED_REMAINING_ACCOUNTS);
+
+    let whirlpool_key = ctx.accounts.whirlpool.key();
+    verify_swap_tick_array_address(
+        &ctx.accounts.whirlpool,
+        &whirlpool_key,
+        a_to_b,
+        &fixed_accounts[0].key(),
+    )?;
+
+    let tick_array_0 = load_tick_array(&fixed_accounts[0], &whirlpool_key)?;
+    let tick_array_1 = load_tick_array(&fixed_accounts[1], &whirlpool_key)?;
+    let tick_array_2 = load_tick_array(&fixed_accounts[2], &whirlpool_key)?;
+
+    let (oracle, _) =
+        Pubkey::find_program_address(&[b"oracle", whirlpool_key.as_ref()], &crate::ID);
+    if fixed_accounts[3].key() != oracle {
+        return Err(ErrorCode::InvalidOracleAccount.into());
+    }
+
+    let mut swap_tick_sequence = SwapTickSequence::new(
+        tick_array_0.load_mut().unwrap(),
+        tick_array_1.load_mut().ok(),
+        tick_array_2.load_mut().ok(),
+    );
+
+    execute_swap(
+        &mut ctx.accounts.whirlpool,
+        SwapTokenAccounts {
+            token_authority: &ctx.accounts.token_authority,
+            token_owner_account_a: &ctx.accounts.token_owner_account_a,
+            token_owner_account_b: &ctx.accounts.token_owner_account_b,
+            token_vault_a: &ctx.accounts.token_vault_a,
+            token_vault_b: &ctx.accounts.token_vault_b,
+            token_program: &ctx.accounts.token_program,
+        },
+        &mut swap_tick_sequence,
+        optional_accounts,
+        SwapParams {
+            amount,
+            sqrt_price_limit,
+            amount_specified_is_input,
+            a_to_b,
+        },
+        other_amount_threshold,
+        SwapOptions {
+            fee_on_top,
+            ..Default::default()
+        },
+    )
+}
+
+// Equivalent of the `mut, has_one = whirlpool` constraints on the tick arrays of `Swap`.
+pub(crate) fn load_tick_array<'info>(
+    account_info: &'info AccountInfo<'info>,
+    whirlpool: &Pubkey,
+) -> Result<AccountLoader<'info, TickArray>> {
+    if !account_info.is_writable {
+        return Err(ErrorCode::InvalidTickArrayAccount.into());
+    }
+    verify_not_upstream_account(account_info)?;
+    let tick_array = AccountLoader::<TickArray>::try_from(account_info)?;
+    if !tick_array.load()?.describe().belongs_to(whirlpool) {
+        return Err(ErrorCode::InvalidTickArrayAccount.into());
+    }
+    Ok(tick_array)
+}
@@ -0,0 +1,112 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::ErrorCode;
+
+use super::{swap_v2, SwapFeeBreakdown, SwapV2};
+
+/// Optional swap parameter. New parameters are added as new variants, so the serialized
+/// arguments of existing integrators keep their layout and meaning.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq)]
+#[cfg_attr(not(target_arch = "bpf"), derive(Debug))]
+pub enum SwapExtension {
+    // Unix timestamp after which the swap fails
+    Deadline(i64),
+    // Charge the fee on top of the exact input amount instead of deducting it, so the output only
+    // depends on the specified amount
+    FeeOnTop,
+}
+
+/// Arguments of `swap_v3`. The fixed fields match the parameters of `swap`, anything added later
+/// goes into `extensions`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Default, PartialEq)]
+#[cfg_attr(not(target_arch = "bpf"), derive(Debug))]
+pub struct SwapV3Args {
+    pub amount: u64,
+    pub other_amount_threshold: u64,
+    pub sqrt_price_limit: u128,
+    pub amount_specified_is_input: bool,
+    pub a_to_b: bool,
+    pub extensions: Vec<SwapExtension>,
+}
+
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, SwapV2<'info>>,
+    args: SwapV3Args,
+) -> Result<SwapFeeBreakdown> {
+    verify_swap_extensions(&args.extensions, Clock::get()?.unix_timestamp)?;
+
+    swap_v2::swap_v2_with_fee_mode(
+        ctx,
+        args.amount,
+        args.other_amount_threshold,
+        args.sqrt_price_limit,
+        args.amount_specified_is_input,
+        args.a_to_b,
+        is_fee_on_top(&args.extensions),
+    )
+}
+
+/// Check the extensions that constrain whether the swap may run at `timestamp`.
+pub fn verify_swap_extensions(extensions: &[SwapExtension], timestamp: i64) -> Result<()> {
+    for extension in extensions {
+        match extension {
+            SwapExtension::Deadline(deadline) => {
+                if timestamp > *deadline {
+                    return Err(ErrorCode::SwapDeadlineExceeded.into());
+                }
+            }
+            SwapExtension::FeeOnTop => {}
+        }
+    }
+    Ok(())
+}
+
+/// Returns true if the extensions select charging the fee on top of the input.
+pub fn is_fee_on_top(extensions: &[SwapExtension]) -> bool {
+    extensions.contains(&SwapExtension::FeeOnTop)
+}
+
+#[cfg(test)]
+mod swap_v3_tests {
+    use super::*;
+
+    #[test]
+    fn test_args_without_extensions_extend_swap_parameters() {
+        let args = SwapV3Args {
+            amount: 100,
+            other_amount_threshold: 90,
+            sqrt_price_limit: 1 << 64,
+            amount_specified_is_input: true,
+            a_to_b: false,
+            extensions: vec![],
+        };
+        let data = args.try_to_vec().unwrap();
+
+        let mut expected = (100u64, 90u64, 1u128 << 64, true, false)
+            .try_to_vec()
+            .unwrap();
+        expected.extend_from_slice(&0u32.to_le_bytes());
+        assert_eq!(data, expected);
+        assert_eq!(SwapV3Args::try_from_slice(&data).unwrap(), args);
+    }
+
+    #[test]
+    fn test_verify_swap_extensions_deadline() {
+        assert!(verify_swap_extensions(&[], 1_000).is_ok());
+        assert!(verify_swap_extensions(&[SwapExtension::Deadline(1_000)], 1_000).is_ok());
+        assert_eq!(
+            verify_swap_extensions(&[SwapExtension::Deadline(999)], 1_000).unwrap_err(),
+            ErrorCode::SwapDeadlineExceeded.into()
+        );
+    }
+    #[test]
+    fn test_fee_on_top_extension() {
+        assert!(!is_fee_on_top(&[]));
+        assert!(!is_fee_on_top(&[SwapExtension::Deadline(1_000)]));
+        assert!(is_fee_on_top(&[
+            SwapExtension::Deadline(1_000),
+            SwapExtension::FeeOnTop
+        ]));
+        assert!(verify_swap_extensions(&[SwapExtension::FeeOnTop], 1_000).is_ok());
+    }
+}
@@ -0,0 +1,40 @@
+use anchor_lang::prelude::*;
+
+use crate::manager::swap_manager::SwapParams;
+use crate::state::PartnerStats;
+
+use super::swap::*;
+
+/// `Swap` accounts with the stats of the partner frontend the swap is attributed to.
+#[derive(Accounts)]
+pub struct SwapWithPartner<'info> {
+    pub swap: Swap<'info>,
+
+    #[account(mut, constraint = partner_stats.whirlpool == swap.whirlpool.key())]
+    pub partner_stats: Box<Account<'info, PartnerStats>>,
+}
+
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, SwapWithPartner<'info>>,
+    amount: u64,
+    other_amount_threshold: u64,
+    sqrt_price_limit: u128,
+    amount_specified_is_input: bool,
+    a_to_b: bool, // Zero for one
+) -> Result<SwapFeeBreakdown> {
+    swap_with_tick_arrays(
+        &mut ctx.accounts.swap,
+        ctx.remaining_accounts,
+        SwapParams {
+            amount,
+            sqrt_price_limit,
+            amount_specified_is_input,
+            a_to_b,
+        },
+        other_amount_threshold,
+        SwapOptions {
+            partner_stats: Some(&mut ctx.accounts.partner_stats),
+            ..Default::default()
+        },
+    )
+}
@@ -0,0 +1,40 @@
+use anchor_lang::prelude::*;
+
+use crate::manager::swap_manager::SwapParams;
+use crate::state::ReferralEarnings;
+
+use super::swap::*;
+
+/// `Swap` accounts with the referral earnings of the referrer the swap is attributed to.
+#[derive(Accounts)]
+pub struct SwapWithReferral<'info> {
+    pub swap: Swap<'info>,
+
+    #[account(mut, constraint = referral_earnings.whirlpool == swap.whirlpool.key())]
+    pub referral_earnings: Box<Account<'info, ReferralEarnings>>,
+}
+
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, SwapWithReferral<'info>>,
+    amount: u64,
+    other_amount_threshold: u64,
+    sqrt_price_limit: u128,
+    amount_specified_is_input: bool,
+    a_to_b: bool, // Zero for one
+) -> Result<SwapFeeBreakdown> {
+    swap_with_tick_arrays(
+        &mut ctx.accounts.swap,
+        ctx.remaining_accounts,
+        SwapParams {
+            amount,
+            sqrt_price_limit,
+            amount_specified_is_input,
+            a_to_b,
+        },
+        other_amount_threshold,
+        SwapOptions {
+            referral_earnings: Some(&mut ctx.accounts.referral_earnings),
+            ..Default::default()
+        },
+    )
+}
@@ -0,0 +1,96 @@
+use anchor_lang::prelude::*;
+use solana_program::instruction::{get_stack_height, TRANSACTION_LEVEL_STACK_HEIGHT};
+use solana_program::sysvar::instructions::{
+    load_current_index_checked, load_instruction_at_checked,
+};
+
+use crate::manager::swap_manager::SwapParams;
+use crate::state::RouterRegistry;
+
+use super::swap::*;
+
+/// `Swap` accounts with the router registry of the whirlpool's config and the instructions
+/// sysvar used to identify the router invoking the swap.
+#[derive(Accounts)]
+pub struct SwapWithRouter<'info> {
+    pub swap: Swap<'info>,
+
+    #[account(
+        seeds = [b"router_registry", swap.whirlpool.whirlpools_config.as_ref()],
+        bump,
+    )]
+    pub router_registry: Box<Account<'info, RouterRegistry>>,
+
+    /// CHECK: checked by the address constraint, read through the sysvar helpers
+    #[account(address = solana_program::sysvar::instructions::ID)]
+    pub instructions_sysvar: UncheckedAccount<'info>,
+}
+
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, SwapWithRouter<'info>>,
+    amount: u64,
+    other_amount_threshold: u64,
+    sqrt_price_limit: u128,
+    amount_specified_is_input: bool,
+    a_to_b: bool, // Zero for one
+) -> Result<SwapFeeBreakdown> {
+    let fee_discount_rate = match invoking_program(&ctx.accounts.instructions_sysvar)? {
+        Some(program_id) => ctx
+            .accounts
+            .router_registry
+            .fee_discount_rate_for(&program_id),
+        None => 0,
+    };
+
+    swap_with_tick_arrays(
+        &mut ctx.accounts.swap,
+        ctx.remaining_accounts,
+        SwapParams {
+            amount,
+            sqrt_price_limit,
+            amount_specified_is_input,
+            a_to_b,
+        },
+        other_amount_threshold,
+        SwapOptions {
+            fee_discount_rate,
+            ..Default::default()
+        },
+    )
+}
+
+/*
+  The router is the program of the transaction level instruction currently executing, if it
+  invoked the swap directly. Swaps invoked directly by the transaction have no router, and swaps
+  nested deeper are not attributed to any router: only the top level program can be introspected,
+  and it is not the direct caller of a swap below the first CPI level.
+*/
+fn invoking_program(instructions_sysvar: &AccountInfo) -> Result<Option<Pubkey>> {
+    if !is_direct_cpi(get_stack_height()) {
+        return Ok(None);
+    }
+    let current_index = load_current_index_checked(instructions_sysvar)?;
+    let instruction = load_instruction_at_checked(current_index as usize, instructions_sysvar)?;
+    if instruction.program_id == crate::ID {
+        return Ok(None);
+    }
+    Ok(Some(instruction.program_id))
+}
+
+fn is_direct_cpi(stack_height: usize) -> bool {
+    stack_height == TRANSACTION_LEVEL_STACK_HEIGHT + 1
+}
+
+#[cfg(test)]
+mod swap_with_router_tests {
+    use super::*;
+
+    #[test]
+    fn test_only_direct_cpis_have_a_router() {
+        assert!(!is_direct_cpi(TRANSACTION_LEVEL_STACK_HEIGHT));
+        assert!(is_direct_cpi(TRANSACTION_LEVEL_STACK_HEIGHT + 1));
+        // A program called by the top level router could be any program
+        assert!(!is_direct_cpi(TRANSACTION_LEVEL_STACK_HEIGHT + 2));
+        assert!(!is_direct_cpi(TRANSACTION_LEVEL_STACK_HEIGHT + 3));
+    }
+}
@@ -0,0 +1,158 @@
+use std::cell::RefMut;
+
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount};
+
+use crate::{
+    manager::swap_manager::SwapParams,
+    state::{TickCache, Whirlpool},
+    util::SwapTickSequence,
+};
+
+use super::{execute_swap, SwapFeeBreakdown, SwapOptions, SwapTokenAccounts};
+
+/// Swap account layout using the tick cache of the whirlpool instead of tick arrays. The swap can
+/// only move the price within the cached window.
+///
+/// Remaining accounts hold the trade log, fee schedule and hook accounts, as for `swap`.
+#[derive(Accounts)]
+pub struct SwapWithTickCache<'info> {
+    #[account(address = token::ID)]
+    pub token_program: Program<'info, Token>,
+
+    pub token_authority: Signer<'info>,
+
+    #[account(mut)]
+    pub whirlpool: Box<Account<'info, Whirlpool>>,
+
+    #[account(mut, constraint = token_owner_account_a.mint == whirlpool.token_mint_a)]
+    pub token_owner_account_a: Box<Account<'info, TokenAccount>>,
+    #[account(mut, address = whirlpool.token_vault_a)]
+    pub token_vault_a: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut, constraint = token_owner_account_b.mint == whirlpool.token_mint_b)]
+    pub token_owner_account_b: Box<Account<'info, TokenAccount>>,
+    #[account(mut, address = whirlpool.token_vault_b)]
+    pub token_vault_b: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut, has_one = whirlpool, address = whirlpool.tick_cache)]
+    pub tick_cache: AccountLoader<'info, TickCache>,
+}
+
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, SwapWithTickCache<'info>>,
+    amount: u64,
+    other_amount_threshold: u64,
+    sqrt_price_limit: u128,
+    amount_specified_is_input: bool,
+    a_to_b: bool, // Zero for one
+) -> Result<SwapFeeBreakdown> {
+    let window = RefMut::map(ctx.accounts.tick_cache.load_mut()?, |tick_cache| {
+        &mut tick_cache.window
+    });
+    let mut swap_tick_sequence = SwapTickSequence::new(window, None, None);
+
+    execute_swap(
+        &mut ctx.accounts.whirlpool,
+        SwapTokenAccounts {
+            token_authority: &ctx.accounts.token_authority,
+            token_owner_account_a: &ctx.accounts.token_owner_account_a,
+            token_owner_account_b: &ctx.accounts.token_owner_account_b,
+            token_vault_a: &ctx.accounts.token_vault_a,
+            token_vault_b: &ctx.accounts.token_vault_b,
+            token_program: &ctx.accounts.token_program,
+        },
+        &mut swap_tick_sequence,
+        ctx.remaining_accounts,
+        SwapParams {
+            amount,
+            sqrt_price_limit,
+            amount_specified_is_input,
+            a_to_b,
+        },
+        other_amount_threshold,
+        SwapOptions {
+            uses_tick_cache: true,
+            ..Default::default()
+        },
+    )
+}
+
+#[cfg(test)]
+mod swap_with_tick_cache_tests {
+    use super::*;
+    use crate::testing::FixtureAccount;
+    use crate::util::test_utils::*;
+
+    const TICK_SPACING: u16 = 64;
+
+    #[test]
+    fn test_swap_against_window_off_array_boundaries() {
+        let fixture = instruction_test_whirlpool(TICK_SPACING, 30_000, 100);
+        let whirlpool_key = fixture.find_address().0;
+        let tick_cache_key = Pubkey::new_unique();
+        let mut whirlpool = fixture.build();
+        whirlpool.liquidity = 1_000_000_000;
+        whirlpool.update_tick_cache(tick_cache_key);
+        whirlpool.mark_tick_cache_synced();
+
+        let start_tick_index = TickCache::window_start_for(100, TICK_SPACING);
+        assert_ne!(
+            start_tick_index,
+            crate::state::TickArray::start_tick_index_for(100, TICK_SPACING)
+        );
+        let mut tick_cache = TickCache::default();
+        tick_cache.initialize(whirlpool_key);
+        tick_cache.window.start_tick_index = start_tick_index;
+
+        let authority = Pubkey::new_unique();
+        let account_infos = leak_account_infos([
+            program_account_info(token::ID),
+            signer_account_info(authority),
+            fixture_account_info(FixtureAccount::from_account(whirlpool_key, &whirlpool)),
+            token_account_info(
+                Pubkey::new_unique(),
+                fixture.token_mint_a,
+                authority,
+                10_000,
+            ),
+            token_account_info(
+                fixture.token_vault_a,
+                fixture.token_mint_a,
+                whirlpool_key,
+                10_000,
+            ),
+            token_account_info(Pubkey::new_unique(), fixture.token_mint_b, authority, 0),
+            token_account_info(
+                fixture.token_vault_b,
+                fixture.token_mint_b,
+                whirlpool_key,
+                10_000,
+            ),
+            fixture_account_info(FixtureAccount::from_zero_copy(tick_cache_key, &tick_cache)),
+        ]);
+
+        let mut accounts = instruction_accounts::<SwapWithTickCache>(account_infos);
+        let fees = handler(
+            instruction_context(&mut accounts, &[]),
+            1_000,
+            0,
+            0,
+            true,
+            true,
+        )
+        .unwrap();
+        accounts.exit(&crate::ID).unwrap();
+
+        assert_eq!(fees.lp_fee, 3);
+        assert_eq!(token_amount(&account_infos[3]), 9_000);
+        assert_eq!(token_amount(&account_infos[4]), 11_000);
+        // 997 A after fees, at a price of 1.0001^100 B per A.
+        assert_eq!(token_amount(&account_infos[5]), 1_007);
+        assert_eq!(token_amount(&account_infos[6]), 8_993);
+
+        let whirlpool: Whirlpool = read_account(&account_infos[2]);
+        assert!(whirlpool.sqrt_price < fixture.sqrt_price);
+        assert_eq!({ whirlpool.last_swap_slot }, TEST_SLOT);
+    }
+}
@@ -0,0 +1,64 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount};
+
+use crate::errors::ErrorCode;
+use crate::events::PositionTransferred;
+use crate::state::Position;
+use crate::util::{transfer_position_token, verify_position_authority};
+
+#[derive(Accounts)]
+pub struct TransferPosition<'info> {
+    pub position_authority: Signer<'info>,
+
+    #[account(mut,
+        constraint = !position.frozen @ ErrorCode::PositionFrozen
+    )]
+    pub position: Account<'info, Position>,
+
+    #[account(mut,
+        constraint = position_token_account.mint == position.position_mint,
+        constraint = position_token_account.amount == 1
+    )]
+    pub position_token_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut, constraint = destination_token_account.mint == position.position_mint)]
+    pub destination_token_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(address = token::ID)]
+    pub token_program: Program<'info, Token>,
+}
+
+/*
+  Moves the position token and records the new owner on the position, so indexers can follow
+  ownership from the position account and the event alone. Clears the recovery authority set by
+  the previous holder.
+*/
+pub fn handler(ctx: Context<TransferPosition>) -> Result<()> {
+    verify_position_authority(
+        &ctx.accounts.position_token_account,
+        &ctx.accounts.position_authority,
+    )?;
+
+    transfer_position_token(
+        &ctx.accounts.position_authority,
+        &ctx.accounts.position_token_account,
+        &ctx.accounts.destination_token_account,
+        &ctx.accounts.token_program,
+    )?;
+
+    let position = &mut ctx.accounts.position;
+    let to_owner = ctx.accounts.destination_token_account.owner;
+    position.update_owner_hint(to_owner);
+    // The recovery authority was chosen by the previous holder.
+    position.update_recovery_authority(Pubkey::default());
+
+    emit!(PositionTransferred {
+        whirlpool: position.whirlpool,
+        position: position.key(),
+        position_mint: position.position_mint,
+        from_owner: ctx.accounts.position_token_account.owner,
+        to_owner,
+    });
+
+    Ok(())
+}
@@ -1,7 +1,11 @@
 use anchor_lang::prelude::*;
+use anchor_spl::token::TokenAccount;
 
 use crate::{
-    manager::liquidity_manager::calculate_fee_and_reward_growths, state::*, util::to_timestamp_u64,
+    errors::ErrorCode,
+    manager::liquidity_manager::calculate_fee_and_reward_growths,
+    state::*,
+    util::{accrue_liquidity_time, to_timestamp_u64},
 };
 
 #[derive(Accounts)]
@@ -18,12 +22,34 @@ pub struct UpdateFeesAndRewards<'info> {
     pub tick_array_upper: AccountLoader<'info, TickArray>,
 }
 
-pub fn handler(ctx: Context<UpdateFeesAndRewards>) -> Result<()> {
-    let whirlpool = &mut ctx.accounts.whirlpool;
-    let position = &mut ctx.accounts.position;
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, UpdateFeesAndRewards<'info>>,
+) -> Result<()> {
     let clock = Clock::get()?;
     let timestamp = to_timestamp_u64(clock.unix_timestamp)?;
 
+    // The accumulator is optional here, the position keeps its checkpoint when it is not passed.
+    if let [accumulator_info, position_token_account_info, ..] = ctx.remaining_accounts {
+        let position_token_account =
+            Account::<TokenAccount>::try_from(position_token_account_info)?;
+        if position_token_account.mint != ctx.accounts.position.position_mint
+            || position_token_account.amount != 1
+        {
+            return Err(ErrorCode::InvalidPositionTokenAmount.into());
+        }
+        accrue_liquidity_time(
+            Some(accumulator_info),
+            &ctx.accounts.whirlpool,
+            &mut ctx.accounts.position,
+            &position_token_account.owner,
+            timestamp,
+        )?;
+    }
+
+    let whirlpool = &mut ctx.accounts.whirlpool;
+    let position = &mut ctx.accounts.position;
+    whirlpool.verify_tick_arrays_synced()?;
+
     let (position_update, reward_infos) = calculate_fee_and_reward_growths(
         whirlpool,
         position,
@@ -36,4 +62,4 @@ pub fn handler(ctx: Context<UpdateFeesAndRewards>) -> Result<()> {
     position.update(&position_update);
 
     Ok(())
-}
\ No newline at end of file
+}
@@ -0,0 +1,72 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::TokenAccount;
+
+use crate::errors::ErrorCode;
+use crate::events::PoolIntegrityVerified;
+use crate::state::*;
+use crate::util::verify_not_upstream_account;
+
+#[derive(Accounts)]
+pub struct VerifyPoolIntegrity<'info> {
+    pub whirlpool: Box<Account<'info, Whirlpool>>,
+
+    #[account(address = whirlpool.token_vault_a)]
+    pub token_vault_a: Box<Account<'info, TokenAccount>>,
+    #[account(address = whirlpool.token_vault_b)]
+    pub token_vault_b: Box<Account<'info, TokenAccount>>,
+}
+
+/*
+  Recomputes the pool invariants and emits the result, never failing on a discrepancy so that it
+  can be used as an on-chain canary. The tick liquidity check is only meaningful when every
+  initialized tick array of the pool is passed in remaining accounts, in ascending order.
+*/
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, VerifyPoolIntegrity<'info>>,
+) -> Result<()> {
+    let whirlpool = &ctx.accounts.whirlpool;
+
+    let protocol_fee_owed_a =
+        whirlpool.to_token_amount(whirlpool.protocol_fee_owed_a, true, true)?;
+    let protocol_fee_owed_b =
+        whirlpool.to_token_amount(whirlpool.protocol_fee_owed_b, false, true)?;
+
+    let mut tick_liquidity = 0i128;
+    let mut last_start_tick_index = None;
+    for account_info in ctx.remaining_accounts {
+        verify_not_upstream_account(account_info)?;
+        let tick_array_loader = AccountLoader::<TickArray>::try_from(account_info)?;
+        let tick_array = tick_array_loader.load()?;
+        if !tick_array.describe().belongs_to(&whirlpool.key()) {
+            return Err(ErrorCode::InvalidTickArrayAccount.into());
+        }
+        // Strictly ascending start indices rule out counting an array twice.
+        if last_start_tick_index.is_some_and(|last| tick_array.start_tick_index <= last) {
+            return Err(ErrorCode::InvalidTickArraySequence.into());
+        }
+        last_start_tick_index = Some(tick_array.start_tick_index);
+
+        tick_liquidity += tick_array
+            .liquidity_net_at_or_below(whirlpool.tick_current_index, whirlpool.tick_spacing);
+    }
+
+    let vault_a_balance = ctx.accounts.token_vault_a.amount;
+    let vault_b_balance = ctx.accounts.token_vault_b.amount;
+    let discrepancy = vault_a_balance < protocol_fee_owed_a
+        || vault_b_balance < protocol_fee_owed_b
+        || tick_liquidity != whirlpool.liquidity as i128;
+
+    emit!(PoolIntegrityVerified {
+        whirlpool: whirlpool.key(),
+        vault_a_balance,
+        vault_b_balance,
+        protocol_fee_owed_a,
+        protocol_fee_owed_b,
+        pool_liquidity: whirlpool.liquidity,
+        tick_liquidity,
+        tick_arrays_checked: ctx.remaining_accounts.len() as u16,
+        discrepancy,
+    });
+
+    Ok(())
+}
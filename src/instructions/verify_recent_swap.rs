@@ -0,0 +1,22 @@
+use anchor_lang::prelude::*;
+
+use crate::state::Whirlpool;
+
+#[derive(Accounts)]
+pub struct VerifyRecentSwap<'info> {
+    pub whirlpool: Box<Account<'info, Whirlpool>>,
+}
+
+/*
+  Read-only guard failing if the Whirlpool was not swapped against recently, so consumers of its
+  price can reject stale pools by adding it to their transaction or invoking it through CPI.
+*/
+pub fn handler(
+    ctx: Context<VerifyRecentSwap>,
+    require_recent_swap_within_slots: u64,
+) -> Result<()> {
+    let clock = Clock::get()?;
+    ctx.accounts
+        .whirlpool
+        .verify_recent_swap(clock.slot, require_recent_swap_within_slots)
+}
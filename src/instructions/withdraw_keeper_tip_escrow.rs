@@ -0,0 +1,32 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::ErrorCode;
+use crate::state::KeeperTipEscrow;
+
+#[derive(Accounts)]
+pub struct WithdrawKeeperTipEscrow<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(mut, has_one = authority)]
+    pub keeper_tip_escrow: Box<Account<'info, KeeperTipEscrow>>,
+
+    #[account(mut)]
+    pub destination: UncheckedAccount<'info>,
+}
+
+/*
+  Returns unused tip lamports. The escrow keeps its own rent exemption.
+*/
+pub fn handler(ctx: Context<WithdrawKeeperTipEscrow>, lamports: u64) -> Result<()> {
+    let keeper_tip_escrow = ctx.accounts.keeper_tip_escrow.to_account_info();
+    let available = keeper_tip_escrow
+        .lamports()
+        .saturating_sub(Rent::get()?.minimum_balance(KeeperTipEscrow::LEN));
+    if lamports > available {
+        return Err(ErrorCode::KeeperTipEscrowInsufficientFunds.into());
+    }
+
+    **keeper_tip_escrow.try_borrow_mut_lamports()? -= lamports;
+    **ctx.accounts.destination.try_borrow_mut_lamports()? += lamports;
+    Ok(())
+}
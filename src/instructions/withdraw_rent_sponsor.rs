@@ -0,0 +1,26 @@
+use anchor_lang::prelude::*;
+
+use crate::state::RentSponsor;
+use crate::util::draw_rent_sponsor;
+
+#[derive(Accounts)]
+pub struct WithdrawRentSponsor<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(mut, has_one = authority)]
+    pub rent_sponsor: Box<Account<'info, RentSponsor>>,
+
+    #[account(mut)]
+    pub destination: UncheckedAccount<'info>,
+}
+
+/*
+  Returns unused sponsorship lamports. The sponsor keeps its own rent exemption.
+*/
+pub fn handler(ctx: Context<WithdrawRentSponsor>, lamports: u64) -> Result<()> {
+    draw_rent_sponsor(
+        &ctx.accounts.rent_sponsor.to_account_info(),
+        &ctx.accounts.destination.to_account_info(),
+        lamports,
+    )
+}
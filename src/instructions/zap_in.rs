@@ -0,0 +1,133 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::TokenAccount;
+
+use crate::errors::ErrorCode;
+use crate::manager::liquidity_manager::{
+    calculate_liquidity_from_amounts, calculate_zap_swap_amount,
+};
+use crate::manager::swap_manager::SwapParams;
+use crate::state::{Position, TickArray};
+use crate::util::{accrue_liquidity_time, to_timestamp_u64, verify_position_authority};
+
+use super::swap::*;
+use super::{increase_position_liquidity, IncreaseLiquidityParams, LiquidityTokenAccounts};
+
+/// `Swap` accounts with the position to deposit into. The token authority of the swap must be
+/// the position authority.
+#[derive(Accounts)]
+pub struct ZapIn<'info> {
+    pub swap: Swap<'info>,
+
+    #[account(mut,
+        constraint = position.whirlpool == swap.whirlpool.key(),
+        constraint = !position.frozen @ ErrorCode::PositionFrozen
+    )]
+    pub position: Box<Account<'info, Position>>,
+    #[account(
+        constraint = position_token_account.mint == position.position_mint,
+        constraint = position_token_account.amount == 1
+    )]
+    pub position_token_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut, constraint = tick_array_lower.load()?.whirlpool == swap.whirlpool.key())]
+    pub tick_array_lower: AccountLoader<'info, TickArray>,
+    #[account(mut, constraint = tick_array_upper.load()?.whirlpool == swap.whirlpool.key())]
+    pub tick_array_upper: AccountLoader<'info, TickArray>,
+}
+
+/*
+  Deposits a single token into a position. The share of the input that the position needs in the
+  other token is swapped in the same pool first, then the largest liquidity the two amounts can
+  provide at the new price is deposited. What does not fit stays in the owner's token accounts.
+*/
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, ZapIn<'info>>,
+    amount: u64,
+    input_is_a: bool,
+    liquidity_min: u128,
+) -> Result<()> {
+    verify_position_authority(
+        &ctx.accounts.position_token_account,
+        &ctx.accounts.swap.token_authority,
+    )?;
+
+    // Remaining accounts are forwarded to the swap, so liquidity time is forfeited.
+    accrue_liquidity_time(
+        None,
+        &ctx.accounts.swap.whirlpool,
+        &mut ctx.accounts.position,
+        &ctx.accounts.position_token_account.owner,
+        to_timestamp_u64(Clock::get()?.unix_timestamp)?,
+    )?;
+
+    let swap_amount = calculate_zap_swap_amount(
+        ctx.accounts.swap.whirlpool.tick_current_index,
+        ctx.accounts.swap.whirlpool.sqrt_price,
+        &ctx.accounts.position,
+        amount,
+        input_is_a,
+    )?;
+
+    let (mut amount_a, mut amount_b) = if input_is_a { (amount, 0) } else { (0, amount) };
+    if swap_amount > 0 {
+        let balance_a = ctx.accounts.swap.token_owner_account_a.amount;
+        let balance_b = ctx.accounts.swap.token_owner_account_b.amount;
+        swap_with_tick_arrays(
+            &mut ctx.accounts.swap,
+            ctx.remaining_accounts,
+            SwapParams {
+                amount: swap_amount,
+                sqrt_price_limit: 0,
+                amount_specified_is_input: true,
+                a_to_b: input_is_a,
+            },
+            0,
+            SwapOptions::default(),
+        )?;
+        ctx.accounts.swap.token_owner_account_a.reload()?;
+        ctx.accounts.swap.token_owner_account_b.reload()?;
+
+        let balance_a_after = ctx.accounts.swap.token_owner_account_a.amount;
+        let balance_b_after = ctx.accounts.swap.token_owner_account_b.amount;
+        if input_is_a {
+            amount_a -= balance_a - balance_a_after;
+            amount_b = balance_b_after - balance_b;
+        } else {
+            amount_a = balance_a_after - balance_a;
+            amount_b -= balance_b - balance_b_after;
+        }
+    }
+
+    let whirlpool = &ctx.accounts.swap.whirlpool;
+    let liquidity = calculate_liquidity_from_amounts(
+        whirlpool.tick_current_index,
+        whirlpool.sqrt_price,
+        &ctx.accounts.position,
+        whirlpool.to_pool_amount(amount_a, true, false)?,
+        whirlpool.to_pool_amount(amount_b, false, false)?,
+    )?;
+    if liquidity < liquidity_min {
+        return Err(ErrorCode::LiquidityUnderMinimum.into());
+    }
+
+    let swap = &mut ctx.accounts.swap;
+    increase_position_liquidity(
+        &mut swap.whirlpool,
+        &mut ctx.accounts.position,
+        &ctx.accounts.tick_array_lower,
+        &ctx.accounts.tick_array_upper,
+        &swap.token_authority,
+        LiquidityTokenAccounts {
+            token_owner_account_a: &swap.token_owner_account_a,
+            token_owner_account_b: &swap.token_owner_account_b,
+            token_vault_a: &swap.token_vault_a,
+            token_vault_b: &swap.token_vault_b,
+            token_program: &swap.token_program,
+        },
+        IncreaseLiquidityParams {
+            liquidity_amount: liquidity,
+            token_max_a: amount_a,
+            token_max_b: amount_b,
+        },
+    )
+}
@@ -0,0 +1,148 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::TokenAccount;
+
+use crate::errors::ErrorCode;
+use crate::manager::swap_manager::SwapParams;
+use crate::state::{Position, TickArray};
+use crate::util::{
+    accrue_liquidity_time, to_timestamp_u64, transfer_from_vault_to_owner,
+    verify_position_authority,
+};
+
+use super::swap::*;
+use super::{decrease_position_liquidity, DecreaseLiquidityParams, LiquidityTokenAccounts};
+
+/// `Swap` accounts with the position to withdraw from. The token authority of the swap must be
+/// the position authority.
+#[derive(Accounts)]
+pub struct ZapOut<'info> {
+    pub swap: Swap<'info>,
+
+    #[account(mut,
+        constraint = position.whirlpool == swap.whirlpool.key(),
+        constraint = !position.frozen @ ErrorCode::PositionFrozen
+    )]
+    pub position: Box<Account<'info, Position>>,
+    #[account(
+        constraint = position_token_account.mint == position.position_mint,
+        constraint = position_token_account.amount == 1
+    )]
+    pub position_token_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut, constraint = tick_array_lower.load()?.whirlpool == swap.whirlpool.key())]
+    pub tick_array_lower: AccountLoader<'info, TickArray>,
+    #[account(mut, constraint = tick_array_upper.load()?.whirlpool == swap.whirlpool.key())]
+    pub tick_array_upper: AccountLoader<'info, TickArray>,
+}
+
+/*
+  Withdraws a position into a single token. The liquidity is removed and the fees owed are
+  collected, then everything received in the other token is swapped in the same pool. The total
+  received in the chosen token has to reach `min_out`.
+*/
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, ZapOut<'info>>,
+    liquidity_amount: u128,
+    output_is_a: bool,
+    min_out: u64,
+) -> Result<()> {
+    verify_position_authority(
+        &ctx.accounts.position_token_account,
+        &ctx.accounts.swap.token_authority,
+    )?;
+
+    // Remaining accounts are forwarded to the swap, so liquidity time is forfeited.
+    accrue_liquidity_time(
+        None,
+        &ctx.accounts.swap.whirlpool,
+        &mut ctx.accounts.position,
+        &ctx.accounts.position_token_account.owner,
+        to_timestamp_u64(Clock::get()?.unix_timestamp)?,
+    )?;
+
+    let balance_a = ctx.accounts.swap.token_owner_account_a.amount;
+    let balance_b = ctx.accounts.swap.token_owner_account_b.amount;
+
+    let swap = &mut ctx.accounts.swap;
+    let position = &mut ctx.accounts.position;
+    decrease_position_liquidity(
+        &mut swap.whirlpool,
+        position,
+        &ctx.accounts.tick_array_lower,
+        &ctx.accounts.tick_array_upper,
+        LiquidityTokenAccounts {
+            token_owner_account_a: &swap.token_owner_account_a,
+            token_owner_account_b: &swap.token_owner_account_b,
+            token_vault_a: &swap.token_vault_a,
+            token_vault_b: &swap.token_vault_b,
+            token_program: &swap.token_program,
+        },
+        DecreaseLiquidityParams {
+            liquidity_amount,
+            token_min_a: 0,
+            token_min_b: 0,
+        },
+    )?;
+
+    // Fees were brought up to date by the liquidity decrease.
+    let fee_owed_a = swap
+        .whirlpool
+        .to_token_amount(position.fee_owed_a, true, false)?;
+    let fee_owed_b = swap
+        .whirlpool
+        .to_token_amount(position.fee_owed_b, false, false)?;
+    position.reset_fees_owed();
+
+    transfer_from_vault_to_owner(
+        &swap.whirlpool,
+        &swap.token_vault_a,
+        &swap.token_owner_account_a,
+        &swap.token_program,
+        fee_owed_a,
+    )?;
+    transfer_from_vault_to_owner(
+        &swap.whirlpool,
+        &swap.token_vault_b,
+        &swap.token_owner_account_b,
+        &swap.token_program,
+        fee_owed_b,
+    )?;
+    swap.whirlpool
+        .record_vault_withdrawals(fee_owed_a, fee_owed_b);
+
+    swap.token_owner_account_a.reload()?;
+    swap.token_owner_account_b.reload()?;
+    let swap_amount = if output_is_a {
+        swap.token_owner_account_b.amount - balance_b
+    } else {
+        swap.token_owner_account_a.amount - balance_a
+    };
+
+    if swap_amount > 0 {
+        swap_with_tick_arrays(
+            swap,
+            ctx.remaining_accounts,
+            SwapParams {
+                amount: swap_amount,
+                sqrt_price_limit: 0,
+                amount_specified_is_input: true,
+                a_to_b: !output_is_a,
+            },
+            0,
+            SwapOptions::default(),
+        )?;
+        swap.token_owner_account_a.reload()?;
+        swap.token_owner_account_b.reload()?;
+    }
+
+    let amount_out = if output_is_a {
+        swap.token_owner_account_a.amount - balance_a
+    } else {
+        swap.token_owner_account_b.amount - balance_b
+    };
+    if amount_out < min_out {
+        return Err(ErrorCode::AmountOutBelowMinimum.into());
+    }
+
+    Ok(())
+}
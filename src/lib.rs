@@ -4,8 +4,11 @@ declare_id!("whirLbMiicVdio4qvUfM5KAg6Ct8VwpYzGff3uctyCc");
 
 #[doc(hidden)]
 pub mod constants;
+#[cfg(feature = "cpi")]
+pub mod cpi_helpers;
 #[doc(hidden)]
 pub mod errors;
+pub mod events;
 #[doc(hidden)]
 pub mod instructions;
 #[doc(hidden)]
@@ -13,10 +16,12 @@ pub mod manager;
 #[doc(hidden)]
 pub mod math;
 pub mod state;
+#[cfg(any(test, feature = "testing"))]
+pub mod testing;
 #[doc(hidden)]
 pub mod util;
 
-use crate::state::{OpenPositionBumps, OpenPositionWithMetadataBumps, WhirlpoolBumps};
+use crate::state::{OpenPositionWithMetadataBumps, WhirlpoolBumps};
 use instructions::*;
 
 #[program]
@@ -26,6 +31,9 @@ pub mod whirlpool {
     /// Open a position in a Whirlpool. A unique token will be minted to represent the position
     /// in the users wallet. The position will start off with 0 liquidity.
     ///
+    /// The owner can be any account, including a program derived address. Its associated token
+    /// account for the position mint is created if it does not exist yet.
+    ///
     /// ### Parameters
     /// - `tick_lower_index` - The tick specifying the lower end of the position range.
     /// - `tick_upper_index` - The tick specifying the upper end of the position range.
@@ -33,33 +41,47 @@ pub mod whirlpool {
     /// #### Special Errors
     /// - `InvalidTickIndex` - If a provided tick is out of bounds, out of order or not a multiple of
     ///                        the tick-spacing in this pool.
+    /// - `PositionLimitReached` - The Whirlpool has its maximum number of open positions.
     pub fn open_position(
         ctx: Context<OpenPosition>,
-        bumps: OpenPositionBumps,
         tick_lower_index: i32,
         tick_upper_index: i32,
     ) -> Result<()> {
-        Ok(())
+        return instructions::open_position::handler(ctx, tick_lower_index, tick_upper_index);
     }
 
     /// Open a position in a Whirlpool. A unique token will be minted to represent the position
-    /// in the users wallet. Additional Metaplex metadata is appended to identify the token.
+    /// in the users wallet. Additional Metaplex metadata is appended to identify the token, with
+    /// the position metadata URI template of the WhirlpoolsConfig if it has one.
     /// The position will start off with 0 liquidity.
     ///
+    /// ### Remaining Accounts
+    /// - The collection mint, collection metadata and collection master edition of the Whirlpool,
+    ///   if the Whirlpool has a position collection. The position is verified into the collection.
+    ///
     /// ### Parameters
-    /// - `tick_lower_index` - The tick specifying the lower end of the position range.
-    /// - `tick_upper_index` - The tick specifying the upper end of the position range.
+    /// - `tick_lower_index` - The tick specifying the lower end of the position range, or
+    ///                        `FULL_RANGE_TICK_INDEX` for the lowest usable tick of the pool.
+    /// - `tick_upper_index` - The tick specifying the upper end of the position range, or
+    ///                        `FULL_RANGE_TICK_INDEX` for the highest usable tick of the pool.
     ///
     /// #### Special Errors
     /// - `InvalidTickIndex` - If a provided tick is out of bounds, out of order or not a multiple of
     ///                        the tick-spacing in this pool.
-    pub fn open_position_with_metadata(
-        ctx: Context<OpenPositionWithMetadata>,
+    /// - `InvalidPositionCollection` - The collection mint is not the position collection of the
+    ///   Whirlpool.
+    /// - `PositionLimitReached` - The Whirlpool has its maximum number of open positions.
+    pub fn open_position_with_metadata<'info>(
+        ctx: Context<'_, '_, 'info, 'info, OpenPositionWithMetadata<'info>>,
         bumps: OpenPositionWithMetadataBumps,
         tick_lower_index: i32,
         tick_upper_index: i32,
     ) -> Result<()> {
-        Ok(())
+        return instructions::open_position_with_metadata::handler(
+            ctx,
+            tick_lower_index,
+            tick_upper_index,
+        );
     }
 
     /// Add liquidity to a position in the Whirlpool. This call also updates the position's accrued fees and rewards.
@@ -72,12 +94,18 @@ pub mod whirlpool {
     /// - `token_max_a` - The maximum amount of tokenA the user is willing to deposit.
     /// - `token_max_b` - The maximum amount of tokenB the user is willing to deposit.
     ///
+    /// ### Remaining Accounts
+    /// - `[0]` - The liquidity time accumulator of the position owner, writable, optional. Without
+    ///   it the liquidity time of the position since its last accrual is forfeited.
+    ///
     /// #### Special Errors
     /// - `LiquidityZero` - Provided liquidity amount is zero.
     /// - `LiquidityTooHigh` - Provided liquidity exceeds u128::max.
     /// - `TokenMaxExceeded` - The required token to perform this operation exceeds the user defined amount.
-    pub fn increase_liquidity(
-        ctx: Context<ModifyLiquidity>,
+    ///                        The required amounts of token A and B are set as return data as a
+    ///                        `QuoteHint`.
+    pub fn increase_liquidity<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ModifyLiquidity<'info>>,
         liquidity_amount: u128,
         token_max_a: u64,
         token_max_b: u64,
@@ -93,19 +121,32 @@ pub mod whirlpool {
     /// Withdraw liquidity from a position in the Whirlpool. This call also updates the position's accrued fees and rewards.
     ///
     /// ### Authority
-    /// - `position_authority` - authority that owns the token corresponding to this desired position.
+    /// - `position_authority` - authority that owns the token corresponding to this desired position,
+    ///                          or the withdraw authority of the position. The withdraw authority
+    ///                          can only withdraw to token accounts of the withdraw destination.
     ///
     /// ### Parameters
     /// - `liquidity_amount` - The total amount of Liquidity the user desires to withdraw.
     /// - `token_min_a` - The minimum amount of tokenA the user is willing to withdraw.
     /// - `token_min_b` - The minimum amount of tokenB the user is willing to withdraw.
     ///
+    /// ### Remaining Accounts
+    /// - `[0]` - The liquidity time accumulator of the position owner, writable, optional. Without
+    ///   it the liquidity time of the position since its last accrual is forfeited.
+    ///
     /// #### Special Errors
     /// - `LiquidityZero` - Provided liquidity amount is zero.
     /// - `LiquidityTooHigh` - Provided liquidity exceeds u128::max.
     /// - `TokenMinSubceeded` - The required token to perform this operation subceeds the user defined amount.
-    pub fn decrease_liquidity(
-        ctx: Context<ModifyLiquidity>,
+    ///                         The withdrawable amounts of token A and B are set as return data as
+    ///                         a `QuoteHint`.
+    /// - `PositionLiquidityLocked` - The withdrawal would take the position below its locked liquidity.
+    /// - `LiquidityRemovalCooldown` - The liquidity removal cooldown of the pool since the last
+    ///                                liquidity increase of the position has not passed.
+    /// - `InvalidWithdrawDestination` - The withdraw authority withdraws to token accounts not owned
+    ///   by the withdraw destination.
+    pub fn decrease_liquidity<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ModifyLiquidity<'info>>,
         liquidity_amount: u128,
         token_min_a: u64,
         token_min_b: u64,
@@ -121,48 +162,116 @@ pub mod whirlpool {
     /// Collect fees accrued for this position.
     ///
     /// ### Authority
-    /// - `position_authority` - authority that owns the token corresponding to this desired position.
-    pub fn collect_fees(ctx: Context<CollectFees>) -> Result<()> {
+    /// - `position_authority` - authority that owns the token corresponding to this desired position,
+    ///                          or a session key with the collect fees scope. The session authority
+    ///                          account is then passed as the first remaining account.
+    ///
+    /// ### Remaining Accounts
+    /// - The session authority, if the position authority is a session key.
+    /// - The collect routing of the position owner, optionally after the session authority. Routed
+    ///   mints must then be paid to their routed destination. The routing is opt-in per collect,
+    ///   collects without it are not bound by it.
+    ///
+    /// #### Special Errors
+    /// - `DestinationFrozen` - A destination token account is frozen. Owed amounts are kept.
+    /// - `CollectRouteMismatch` - A mint routed by the collect routing is paid to another account.
+    /// - `InvalidSessionDestination` - A session pays an unrouted mint to an account not owned by
+    ///   the owed destination of the position.
+    /// - `VaultFrozen` - A token vault of the pool is frozen.
+    pub fn collect_fees<'info>(ctx: Context<'_, '_, 'info, 'info, CollectFees<'info>>) -> Result<()> {
         return instructions::collect_fees::handler(ctx);
     }
 
-    /// Collect rewards accrued for this position.
+    /// Collect rewards accrued for this position. If the reward vault holds less than the amount
+    /// owed, the vault balance is paid out, the rest stays owed and `RewardShortfall` is emitted.
     ///
     /// ### Authority
-    /// - `position_authority` - authority that owns the token corresponding to this desired position.
-    pub fn collect_reward(ctx: Context<CollectReward>, reward_index: u8) -> Result<()> {
+    /// - `position_authority` - authority that owns the token corresponding to this desired position,
+    ///                          or a session key with the collect reward scope. The session authority
+    ///                          account is then passed as the first remaining account.
+    ///
+    /// ### Remaining Accounts
+    /// - The session authority, if the position authority is a session key.
+    /// - The collect routing of the position owner, optionally after the session authority. Routed
+    ///   mints must then be paid to their routed destination. The routing is opt-in per collect,
+    ///   collects without it are not bound by it.
+    /// - The reward escrow program and up to 6 accounts forwarded to it, if the reward has an
+    ///   escrow program and the vault holds less than the amount owed.
+    ///
+    /// #### Special Errors
+    /// - `DestinationFrozen` - A destination token account is frozen. Owed amounts are kept.
+    /// - `CollectRouteMismatch` - A mint routed by the collect routing is paid to another account.
+    /// - `InvalidSessionDestination` - A session pays an unrouted mint to an account not owned by
+    ///   the owed destination of the position.
+    /// - `RewardEscrowMissing` - The reward escrow program was not passed.
+    /// - `TooManyRewardEscrowAccounts` - More than 6 accounts were passed for the escrow program.
+    pub fn collect_reward<'info>(
+        ctx: Context<'_, '_, 'info, 'info, CollectReward<'info>>,
+        reward_index: u8,
+    ) -> Result<()> {
         return instructions::collect_reward::handler(ctx, reward_index);
     }
 
-    /// Perform a swap in this Whirlpool
+    /// Perform a swap in this Whirlpool. Returns the `SwapFeeBreakdown` of the fees charged on the
+    /// input token as return data and emits it in a `SwapFees` event.
     ///
     /// ### Authority
     /// - "token_authority" - The authority to withdraw tokens from the input token account.
     ///
+    /// ### Remaining Accounts
+    /// - The protocol stats account, writable, in builds with the `protocol-stats-swaps` feature.
+    /// - The trade log of the Whirlpool, writable, if the Whirlpool has a trade log.
+    /// - The fee schedule of the Whirlpool, if the Whirlpool has a fee schedule.
+    /// - The tick cross hook program and its accounts, if the Whirlpool has a tick cross hook.
+    /// - The after swap hook program and its accounts, if the Whirlpool has a hook.
+    ///
     /// ### Parameters
     /// - `amount` - The amount of input or output token to swap from (depending on exact_input).
     /// - `other_amount_threshold` - The maximum/minimum of input/output token to swap into (depending on exact_input).
-    /// - `sqrt_price_limit` - The maximum/minimum price the swap will swap to.
+    /// - `sqrt_price_limit` - The maximum/minimum price the swap will swap to. 0 for no limit, the
+    ///   swap then runs up to the min/max sqrt-price bound in its direction.
     /// - `exact_input` - Specifies the token the parameter `amount`represents. If true, the amount represents the input token of the swap.
     /// - `a_to_b` - The direction of the swap. True if swapping from A to B. False if swapping from B to A.
     ///
     /// #### Special Errors
     /// - `ZeroTradableAmount` - User provided parameter `amount` is 0.
+    /// - `AmountOutBelowMinimum` / `AmountInAboveMaximum` - The swap fails `other_amount_threshold`.
+    ///   The swapped amounts of token A and B are set as return data as a `QuoteHint`.
     /// - `InvalidSqrtPriceLimitDirection` - User provided parameter `sqrt_price_limit` does not match the direction of the trade.
     /// - `SqrtPriceOutOfBounds` - User provided parameter `sqrt_price_limit` is over Whirlppool's max/min bounds for sqrt-price.
     /// - `InvalidTickArraySequence` - User provided tick-arrays are not in sequential order required to proceed in this trade direction.
+    /// - `TickArrayAddressMismatch` - The first tick-array is not the PDA expected from the current tick and trade direction,
+    ///                                or the swap moved into a tick-array that does not follow the previous one.
     /// - `TickArraySequenceInvalidIndex` - The swap loop attempted to access an invalid array index during the query of the next initialized tick.
     /// - `TickArrayIndexOutofBounds` - The swap loop attempted to access an invalid array index during tick crossing.
     /// - `LiquidityOverflow` - Liquidity value overflowed 128bits during tick crossing.
     /// - `InvalidTickSpacing` - The swap pool was initialized with tick-spacing of 0.
-    pub fn swap(
-        ctx: Context<Swap>,
+    /// - `AfterSwapHookMissing` - The pool has an after swap hook but the hook program was not
+    ///                            provided as the first remaining account.
+    /// - `AfterSwapHookComputeExceeded` - The after swap hook consumed more than
+    ///                                    MAX_AFTER_SWAP_HOOK_COMPUTE_UNITS.
+    /// - `TickCrossHookMissing` - The pool has a tick cross hook, the swap crossed an initialized
+    ///                            tick and the hook program was not provided.
+    /// - `TooManyTickCrossings` - The pool has a tick cross hook and the swap crossed more than 8
+    ///                            initialized ticks.
+    /// - `TickCrossHookComputeExceeded` - The tick cross notifications consumed more than
+    ///                                    MAX_TICK_CROSS_HOOK_COMPUTE_UNITS.
+    /// - `InvalidTradeLogAccount` - The first remaining account is not the trade log of the pool.
+    /// - `InvalidFeeScheduleAccount` - The fee schedule account is not the one of the pool.
+    /// - `VaultFrozen` - A token vault of the pool is frozen.
+    /// - `UserAccountFrozen` - A token owner account is frozen.
+    /// - `SwapTruncated` - The swap would exhaust the compute budget. The amounts traded so far
+    ///                     and the price reached are set as return data, so simulating clients
+    ///                     can split the trade.
+    /// - `PoolPaused` - The pool was paused by the emergency authority.
+    pub fn swap<'info>(
+        ctx: Context<'_, '_, 'info, 'info, Swap<'info>>,
         amount: u64,
         other_amount_threshold: u64,
         sqrt_price_limit: u128,
         amount_specified_is_input: bool,
         a_to_b: bool,
-    ) -> Result<()> {
+    ) -> Result<SwapFeeBreakdown> {
         return instructions::swap::handler(
             ctx,
             amount,
@@ -173,6 +282,80 @@ pub mod whirlpool {
         );
     }
 
+    /// Perform a swap in this Whirlpool using the `SwapV2` account layout. The tick arrays and the
+    /// oracle are passed as remaining accounts to reduce the fixed account footprint of routed
+    /// transactions.
+    ///
+    /// ### Authority
+    /// - "token_authority" - The authority to withdraw tokens from the input token account.
+    ///
+    /// ### Remaining Accounts
+    /// - `[0..3]` - tick_array_0, tick_array_1 and tick_array_2, writable, in swap direction.
+    /// - `[3]` - The oracle PDA of the Whirlpool.
+    /// - `[4..]` - The trade log of the Whirlpool, writable, if the Whirlpool has a trade log, then
+    ///   the fee schedule of the Whirlpool, if it has a fee schedule.
+    /// - Then the tick cross hook program and its accounts, if the Whirlpool has a tick cross hook,
+    ///   then the after swap hook program and its accounts, if it has a hook.
+    ///
+    /// In builds with the `protocol-stats-swaps` feature the protocol stats account is passed at
+    /// `[4]`, ahead of the trade log, fee schedule and hook accounts.
+    ///
+    /// ### Parameters
+    /// Same as `swap`.
+    ///
+    /// #### Special Errors
+    /// Same as `swap`, and
+    /// - `RemainingAccountsMissing` - Fewer than four remaining accounts were provided.
+    /// - `InvalidTickArrayAccount` - A tick array is not writable or belongs to another Whirlpool.
+    /// - `ForeignProgramAccount` - A tick array is owned by the upstream Whirlpool program.
+    /// - `InvalidOracleAccount` - The oracle account is not the oracle PDA of the Whirlpool.
+    pub fn swap_v2<'info>(
+        ctx: Context<'_, '_, 'info, 'info, SwapV2<'info>>,
+        amount: u64,
+        other_amount_threshold: u64,
+        sqrt_price_limit: u128,
+        amount_specified_is_input: bool,
+        a_to_b: bool,
+    ) -> Result<SwapFeeBreakdown> {
+        return instructions::swap_v2::handler(
+            ctx,
+            amount,
+            other_amount_threshold,
+            sqrt_price_limit,
+            amount_specified_is_input,
+            a_to_b,
+        );
+    }
+
+    /// Perform a swap in this Whirlpool using the `SwapV2` account layout, with the parameters in
+    /// a versioned arguments struct. Parameters added after `swap` are optional extensions, so
+    /// new ones do not change the serialized arguments of existing integrators.
+    ///
+    /// ### Authority
+    /// - "token_authority" - The authority to withdraw tokens from the input token account.
+    ///
+    /// ### Remaining Accounts
+    /// Same as `swap_v2`.
+    ///
+    /// ### Parameters
+    /// - `args` - The parameters of `swap`, and the optional extensions:
+    ///   - `Deadline` - The unix timestamp after which the swap fails.
+    ///   - `FeeOnTop` - Charge the fee on top of the exact input amount instead of deducting it.
+    ///     The whole amount is traded, so the output only depends on the amount, and the input
+    ///     transferred is the amount plus the fee.
+    ///
+    /// #### Special Errors
+    /// Same as `swap_v2`, and
+    /// - `SwapDeadlineExceeded` - The swap was executed after its deadline.
+    /// - `FeeOnTopUnsupported` - A fee on top swap specifies its output amount, or the pool
+    ///                           withholds the fee of its direction from the output.
+    pub fn swap_v3<'info>(
+        ctx: Context<'_, '_, 'info, 'info, SwapV2<'info>>,
+        args: SwapV3Args,
+    ) -> Result<SwapFeeBreakdown> {
+        return instructions::swap_v3::handler(ctx, args);
+    }
+
     /// Close a position in a Whirlpool. Burns the position token in the owner's wallet.
     ///
     /// ### Authority
@@ -186,16 +369,1973 @@ pub mod whirlpool {
 
         /// Update the accrued fees and rewards for a position.
     ///
+    /// ### Remaining Accounts
+    /// - `[0]` - The liquidity time accumulator of the position owner, writable, optional.
+    /// - `[1]` - The position token account, required with the accumulator to identify the owner.
+    ///
     /// #### Special Errors
     /// - `TickNotFound` - Provided tick array account does not contain the tick for this position.
     /// - `LiquidityZero` - Position has zero liquidity and therefore already has the most updated fees and reward values.
-    pub fn update_fees_and_rewards(ctx: Context<UpdateFeesAndRewards>) -> Result<()> {
+    /// - `InvalidPositionTokenAmount` - The position token account does not hold the position token.
+    /// - `BoundaryTickUninitialized` - A boundary tick of the position is not initialized although
+    ///                                 the position has liquidity.
+    pub fn update_fees_and_rewards<'info>(
+        ctx: Context<'_, '_, 'info, 'info, UpdateFeesAndRewards<'info>>,
+    ) -> Result<()> {
         return instructions::update_fees_and_rewards::handler(ctx);
     }
-    pub fn initialize_tick_array(
-        ctx: Context<InitializeTickArray>,
+
+    /// Initializes a tick_array account to represent a tick-range in a Whirlpool. The tick array
+    /// is a PDA seeded by the whirlpool and its start tick index.
+    ///
+    /// ### Remaining Accounts
+    /// - Optionally the previous and/or next initialized tick array of the Whirlpool, writable, to
+    ///   link the new tick array into the `prev_start_tick_index`/`next_start_tick_index` hints.
+    /// - Optionally a rent sponsor of the Whirlpool, writable, to refund the rent of the tick array
+    ///   to the funder.
+    ///
+    /// ### Parameters
+    /// - `start_tick_index` - The starting tick index for this tick-array.
+    ///                        Has to be a multiple of TickArray size & the tick spacing of this pool.
+    ///
+    /// #### Special Errors
+    /// - `InvalidStartTick` - if the provided start tick is out of bounds or is not a multiple of
+    ///                        TICK_ARRAY_SIZE * tick spacing.
+    /// - `InvalidTickArrayLink` - A provided neighbour is not adjacent to the new tick array.
+    /// - `InvalidRentSponsor` - The rent sponsor does not sponsor the tick array, or more than one
+    ///                          rent sponsor was provided.
+    /// - `RentSponsorInsufficientFunds` - The rent sponsor cannot pay the rent of the tick array.
+    /// - `ForeignProgramAccount` - A provided neighbour is owned by the upstream Whirlpool program.
+    pub fn initialize_tick_array<'info>(
+        ctx: Context<'_, '_, 'info, 'info, InitializeTickArray<'info>>,
         start_tick_index: i32,
     ) -> Result<()> {
-        Ok(())
+        return instructions::initialize_tick_array::handler(ctx, start_tick_index);
+    }
+
+    /// Initializes a Whirlpool and seeds it with its first position in a single instruction, so
+    /// the pool is never tradable while empty or half-configured. The two tick arrays holding the
+    /// seed range boundaries are initialized and the position token is minted to the funder.
+    ///
+    /// ### Remaining Accounts
+    /// - The token badges of the mints with a freeze authority, which must allow it.
+    /// - The Pyth price updates of `oracle_price_check` and the mint price feeds of both mints, if
+    ///   `oracle_price_check` is given.
+    ///
+    /// ### Parameters
+    /// - `tick_spacing` - The desired tick spacing for this pool.
+    /// - `initial_sqrt_price` - The desired initial sqrt-price for this pool. Ignored if
+    ///                          `oracle_price_check` is given.
+    /// - `tick_lower_index` - The tick specifying the lower end of the seed position range.
+    /// - `tick_upper_index` - The tick specifying the upper end of the seed position range.
+    /// - `liquidity_amount` - The liquidity to deposit into the seed position.
+    /// - `token_max_a` - The maximum amount of tokenA the funder is willing to deposit.
+    /// - `token_max_b` - The maximum amount of tokenB the funder is willing to deposit.
+    /// - `liquidity_removal_cooldown` - The seconds since the last liquidity increase of a position
+    ///                                  before liquidity can be removed from it, 0 for none.
+    /// - `min_tick_index` - The lowest usable tick of the pool, tighter than the global bound. 0 for
+    ///                      the global bounds if `max_tick_index` is 0 too.
+    /// - `max_tick_index` - The highest usable tick of the pool, tighter than the global bound. 0
+    ///                      for the global bounds if `min_tick_index` is 0 too.
+    /// - `oracle_price_check` - The Pyth price updates of token A and token B to read the initial
+    ///                          price from, their maximum age and confidence rate. None to use
+    ///                          `initial_sqrt_price`.
+    ///
+    /// #### Special Errors
+    /// - `InvalidTokenMintOrder` - The order of mints have to be ordered by
+    /// - `SqrtPriceOutOfBounds` - provided initial_sqrt_price is not between 2^-64 to 2^64
+    /// - `InvalidPriceOracle` - A price update is missing or not a fully verified Pyth price update.
+    /// - `OraclePriceFeedMismatch` - A mint has no price feed or its update is of another feed.
+    /// - `StaleOraclePrice` - An oracle price is older than the maximum age.
+    /// - `OracleConfidenceTooWide` - An oracle confidence exceeds the maximum confidence rate.
+    /// - `InvalidTickIndex` - If a provided tick is out of bounds, out of order or not a multiple of
+    ///                        the tick-spacing in this pool.
+    /// - `TokenMaxExceeded` - The required token to perform this operation exceeds the user defined amount.
+    ///                        The required amounts of token A and B are set as return data as a
+    ///                        `QuoteHint`.
+    /// - `FreezeAuthorityNotAllowed` - A mint has a freeze authority and no token badge allowing it.
+    pub fn initialize_pool_with_liquidity<'info>(
+        ctx: Context<'_, '_, 'info, 'info, InitializePoolWithLiquidity<'info>>,
+        params: InitializePoolWithLiquidityParams,
+    ) -> Result<()> {
+        return instructions::initialize_pool_with_liquidity::handler(ctx, params);
+    }
+
+    /// Set the program invoked via CPI after every swap in this Whirlpool. The hook receives an
+    /// `after_swap` instruction carrying the swap summary and reads the Whirlpool as updated by
+    /// the swap. A hook may consume at most MAX_AFTER_SWAP_HOOK_COMPUTE_UNITS. Pass
+    /// `Pubkey::default()` to disable.
+    ///
+    /// ### Authority
+    /// - "fee_authority" - Set authority in the WhirlpoolConfig
+    ///
+    /// #### Special Errors
+    /// - `InvalidAfterSwapHook` - The hook cannot be the Whirlpool program itself.
+    pub fn set_after_swap_hook(
+        ctx: Context<SetAfterSwapHook>,
+        after_swap_hook: Pubkey,
+    ) -> Result<()> {
+        return instructions::set_after_swap_hook::handler(ctx, after_swap_hook);
+    }
+
+    /// Map one side of a Whirlpool to an SPL stake pool exchange rate. Amounts of that side are
+    /// accounted in underlying (SOL) units, so ticks track the SOL-denominated price and do not
+    /// drift as the liquid staking token appreciates. Only SPL stake pools are supported, Marinade
+    /// and Lido state accounts are rejected.
+    ///
+    /// ### Authority
+    /// - "fee_authority" - Set authority in the WhirlpoolConfig
+    ///
+    /// ### Parameters
+    /// - `exchange_rate_on_a` - True if token A is the rebasing token, false if it is token B.
+    ///
+    /// #### Special Errors
+    /// - `ExchangeRateModeLocked` - The pool owes tokens out of its vaults, has liquidity or has
+    ///                              positions not yet counted in its total liquidity.
+    /// - `InvalidExchangeRateOracle` - The oracle is not a valid SPL stake pool account.
+    pub fn set_exchange_rate_oracle(
+        ctx: Context<SetExchangeRateOracle>,
+        exchange_rate_on_a: bool,
+    ) -> Result<()> {
+        return instructions::set_exchange_rate_oracle::handler(ctx, exchange_rate_on_a);
+    }
+
+    /// Refresh the cached exchange rate of a Whirlpool from its exchange rate oracle.
+    ///
+    /// ### Authority
+    /// - None. Anyone can refresh the exchange rate.
+    ///
+    /// ### Remaining Accounts
+    /// - Optionally a keeper tip escrow of the Whirlpool, writable, the instructions sysvar and
+    ///   the keeper receiving the tip, writable, to tip the keeper from the escrow.
+    ///
+    /// #### Special Errors
+    /// - `InvalidExchangeRateOracle` - The oracle is not a valid SPL stake pool account.
+    /// - `InvalidKeeperTipEscrow` - The keeper tip escrow belongs to another Whirlpool.
+    pub fn refresh_exchange_rate<'info>(
+        ctx: Context<'_, '_, 'info, 'info, RefreshExchangeRate<'info>>,
+    ) -> Result<()> {
+        return instructions::refresh_exchange_rate::handler(ctx);
+    }
+
+    /// Lock the current liquidity of a position until the unlock timestamp and create a
+    /// `LiquidityLockReceipt` that third parties can read to verify the lock. Emits `PositionLocked`.
+    ///
+    /// ### Authority
+    /// - "position_authority" - The authority that owns the position token.
+    ///
+    /// ### Parameters
+    /// - `unlock_timestamp` - The unix timestamp at which the liquidity can be withdrawn again.
+    ///
+    /// #### Special Errors
+    /// - `InvalidUnlockTimestamp` - The unlock timestamp is not in the future.
+    /// - `LiquidityZero` - The position has no liquidity to lock.
+    pub fn lock_position(ctx: Context<LockPosition>, unlock_timestamp: u64) -> Result<()> {
+        return instructions::lock_position::handler(ctx, unlock_timestamp);
+    }
+
+    /// Extend the lock of a position, also locking liquidity added since it was last locked.
+    /// Emits `PositionLockExtended`.
+    ///
+    /// ### Authority
+    /// - "position_authority" - The authority that owns the position token.
+    ///
+    /// ### Parameters
+    /// - `unlock_timestamp` - The new unix timestamp at which the liquidity can be withdrawn again.
+    ///
+    /// #### Special Errors
+    /// - `InvalidUnlockTimestamp` - The unlock timestamp is not later than now and the current one.
+    /// - `LiquidityZero` - The position has no liquidity to lock.
+    pub fn extend_position_lock(
+        ctx: Context<ExtendPositionLock>,
+        unlock_timestamp: u64,
+    ) -> Result<()> {
+        return instructions::extend_position_lock::handler(ctx, unlock_timestamp);
+    }
+
+    /// Recompute the accounting invariants of a Whirlpool and emit `PoolIntegrityVerified` with
+    /// the result. The instruction does not fail on a discrepancy.
+    ///
+    /// ### Authority
+    /// - None. Anyone can verify a Whirlpool.
+    ///
+    /// ### Remaining Accounts
+    /// - Every initialized tick array of the Whirlpool, ordered by ascending start tick index.
+    ///
+    /// #### Special Errors
+    /// - `InvalidTickArrayAccount` - A tick array belongs to another Whirlpool.
+    /// - `ForeignProgramAccount` - A tick array is owned by the upstream Whirlpool program.
+    /// - `InvalidTickArraySequence` - The tick arrays are not in ascending order or are repeated.
+    pub fn verify_pool_integrity<'info>(
+        ctx: Context<'_, '_, 'info, 'info, VerifyPoolIntegrity<'info>>,
+    ) -> Result<()> {
+        return instructions::verify_pool_integrity::handler(ctx);
+    }
+
+    /// Authorize a session key to collect fees and/or rewards of a position on behalf of the
+    /// position owner until the expiry. The session is no longer accepted once the position token
+    /// changes owner.
+    ///
+    /// ### Authority
+    /// - "position_owner" - The wallet that owns the position token.
+    ///
+    /// ### Parameters
+    /// - `session_key` - The key allowed to act for the position owner.
+    /// - `expiry_timestamp` - The unix timestamp at which the session expires.
+    /// - `scopes` - Bitmask of `SESSION_SCOPE_COLLECT_FEES` and `SESSION_SCOPE_COLLECT_REWARD`.
+    ///
+    /// #### Special Errors
+    /// - `InvalidSessionExpiry` - The expiry is in the past or beyond the max session duration.
+    /// - `InvalidSessionScope` - The scopes are empty or contain unknown scopes.
+    pub fn create_session_authority(
+        ctx: Context<CreateSessionAuthority>,
+        session_key: Pubkey,
+        expiry_timestamp: u64,
+        scopes: u8,
+    ) -> Result<()> {
+        return instructions::create_session_authority::handler(
+            ctx,
+            session_key,
+            expiry_timestamp,
+            scopes,
+        );
+    }
+
+    /// Revoke a session authority and return its rent to the receiver.
+    ///
+    /// ### Authority
+    /// - "owner" - The position owner that created the session.
+    pub fn revoke_session_authority(ctx: Context<RevokeSessionAuthority>) -> Result<()> {
+        return instructions::revoke_session_authority::handler(ctx);
+    }
+
+    /// Re-mint the position token of a position whose token was burned directly by its holder.
+    /// The position is moved onto a new position mint and the token is minted to the recovery
+    /// authority of the position.
+    ///
+    /// ### Authority
+    /// - "recovery_authority" - The recovery authority set on the position by its token holder.
+    ///
+    /// #### Special Errors
+    /// - `InvalidRecoveryAuthority` - The signer is not the recovery authority of the position.
+    /// - `PositionTokenNotBurned` - The current position token still has a non-zero supply.
+    pub fn remint_position_token(ctx: Context<RemintPositionToken>) -> Result<()> {
+        return instructions::remint_position_token::handler(ctx);
+    }
+
+    /// Set the key allowed to re-mint the position token if the token is burned. The recovery
+    /// authority is cleared by `transfer_position`. Token transfers outside the program keep it,
+    /// so a new holder should set their own.
+    ///
+    /// ### Authority
+    /// - `position_authority` - authority that owns the token corresponding to this desired position.
+    ///
+    /// ### Parameters
+    /// - `recovery_authority` - The recovery authority, or the default pubkey to disable recovery.
+    pub fn set_position_recovery_authority(
+        ctx: Context<SetPositionRecoveryAuthority>,
+        recovery_authority: Pubkey,
+    ) -> Result<()> {
+        return instructions::set_position_recovery_authority::handler(ctx, recovery_authority);
+    }
+
+    /// Set whether a reward accrues to all liquidity of the Whirlpool instead of only to the
+    /// in-range liquidity, so campaigns can reward passive depth outside the current price.
+    ///
+    /// ### Authority
+    /// - "reward_authority" - The authority of the specified reward-index in this Whirlpool.
+    ///
+    /// ### Parameters
+    /// - `reward_index` - The reward index that we'd like to change.
+    /// - `accrues_to_all_liquidity` - True to accrue to all liquidity, false to accrue to in-range
+    ///   liquidity only.
+    ///
+    /// #### Special Errors
+    /// - `InvalidRewardIndex` - The reward index exceeds NUM_REWARDS.
+    /// - `RewardAccrualModeLocked` - The reward has already accrued growth.
+    /// - `TotalLiquidityIncomplete` - The total liquidity of the Whirlpool does not count every
+    ///   position yet, see `mark_total_liquidity_complete`.
+    /// - `RewardVaultAmountInsufficient` - The reward vault holds less than a day of emissions.
+    pub fn set_reward_accrual_mode(
+        ctx: Context<SetRewardMode>,
+        reward_index: u8,
+        accrues_to_all_liquidity: bool,
+    ) -> Result<()> {
+        return instructions::set_reward_accrual_mode::handler(
+            ctx,
+            reward_index,
+            accrues_to_all_liquidity,
+        );
+    }
+
+    /// Propose a new fee authority for the WhirlpoolsConfig. The fee authority is only transferred
+    /// once the proposed authority accepts, so a wrong address cannot brick the config. Proposing
+    /// the default pubkey cancels a pending proposal.
+    ///
+    /// ### Authority
+    /// - "fee_authority" - Set authority in the WhirlpoolConfig
+    pub fn propose_fee_authority(ctx: Context<ProposeFeeAuthority>) -> Result<()> {
+        return instructions::propose_fee_authority::handler(ctx);
+    }
+
+    /// Accept a proposed fee authority transfer and become the fee authority of the
+    /// WhirlpoolsConfig.
+    ///
+    /// ### Authority
+    /// - "pending_fee_authority" - The fee authority proposed by the current fee authority.
+    ///
+    /// #### Special Errors
+    /// - `NoPendingAuthority` - No fee authority transfer has been proposed.
+    pub fn accept_fee_authority(ctx: Context<AcceptFeeAuthority>) -> Result<()> {
+        return instructions::accept_fee_authority::handler(ctx);
+    }
+
+    /// Propose a new reward authority for a reward index of a Whirlpool. The reward authority is
+    /// only transferred once the proposed authority accepts. Proposing the default pubkey cancels
+    /// a pending proposal.
+    ///
+    /// ### Authority
+    /// - "reward_authority" - The authority of the specified reward-index in this Whirlpool.
+    ///
+    /// ### Parameters
+    /// - `reward_index` - The reward index that we'd like to change.
+    ///
+    /// #### Special Errors
+    /// - `InvalidRewardIndex` - The reward index exceeds NUM_REWARDS.
+    pub fn propose_reward_authority(
+        ctx: Context<ProposeRewardAuthority>,
+        reward_index: u8,
+    ) -> Result<()> {
+        return instructions::propose_reward_authority::handler(ctx, reward_index);
+    }
+
+    /// Accept a proposed reward authority transfer and become the authority of a reward index of
+    /// a Whirlpool.
+    ///
+    /// ### Authority
+    /// - "pending_reward_authority" - The reward authority proposed by the current reward
+    ///   authority.
+    ///
+    /// ### Parameters
+    /// - `reward_index` - The reward index that we'd like to change.
+    ///
+    /// #### Special Errors
+    /// - `InvalidRewardIndex` - The reward index exceeds NUM_REWARDS.
+    /// - `NoPendingAuthority` - No reward authority transfer has been proposed.
+    pub fn accept_reward_authority(
+        ctx: Context<AcceptRewardAuthority>,
+        reward_index: u8,
+    ) -> Result<()> {
+        return instructions::accept_reward_authority::handler(ctx, reward_index);
+    }
+
+    /// Redirect the fees and rewards collected through sessions to the token accounts of another
+    /// wallet, e.g. when the position owner's token accounts have been frozen. Owed amounts stay
+    /// on the position until they are collected into a destination that is not frozen.
+    ///
+    /// ### Authority
+    /// - `position_authority` - authority that owns the token corresponding to this desired position.
+    ///
+    /// ### Parameters
+    /// - `owed_destination` - The wallet to pay to, or the default pubkey to pay the position owner.
+    pub fn redirect_owed(ctx: Context<RedirectOwed>, owed_destination: Pubkey) -> Result<()> {
+        return instructions::redirect_owed::handler(ctx, owed_destination);
+    }
+
+    /// Create the trade log of a Whirlpool, a ring buffer of its most recent swaps (price, size,
+    /// direction and slot). Once created, every swap of the Whirlpool must pass the trade log.
+    ///
+    /// ### Authority
+    /// - "fee_authority" - Set authority in the WhirlpoolConfig
+    pub fn initialize_trade_log(ctx: Context<InitializeTradeLog>) -> Result<()> {
+        return instructions::initialize_trade_log::handler(ctx);
+    }
+
+    /// Mint the Metaplex collection NFT of a Whirlpool's positions. Positions opened with metadata
+    /// afterwards are verified into the collection, so marketplaces can group them by pool.
+    ///
+    /// ### Authority
+    /// - None. Anyone can pay for the collection of a Whirlpool, once.
+    pub fn initialize_position_collection(
+        ctx: Context<InitializePositionCollection>,
+    ) -> Result<()> {
+        return instructions::initialize_position_collection::handler(ctx);
+    }
+
+    /// Open a protocol owned position. Protocol positions are not represented by a position
+    /// token, they are managed by the fee authority of the WhirlpoolsConfig so that treasury
+    /// liquidity can be deployed without holding position NFTs.
+    ///
+    /// ### Authority
+    /// - "fee_authority" - Set authority in the WhirlpoolConfig
+    ///
+    /// ### Parameters
+    /// - `tick_lower_index` - The tick specifying the lower end of the position range, or
+    ///                        `FULL_RANGE_TICK_INDEX` for the lowest usable tick of the pool.
+    /// - `tick_upper_index` - The tick specifying the upper end of the position range, or
+    ///                        `FULL_RANGE_TICK_INDEX` for the highest usable tick of the pool.
+    ///
+    /// #### Special Errors
+    /// - `InvalidTickIndex` - If a provided tick is out of bounds, out of order or not a multiple of
+    ///                        the tick-spacing in this pool.
+    /// - `PositionLimitReached` - The Whirlpool has its maximum number of open positions.
+    pub fn open_protocol_position(
+        ctx: Context<OpenProtocolPosition>,
+        tick_lower_index: i32,
+        tick_upper_index: i32,
+    ) -> Result<()> {
+        return instructions::open_protocol_position::handler(
+            ctx,
+            tick_lower_index,
+            tick_upper_index,
+        );
+    }
+
+    /// Add liquidity to a protocol owned position. This call also updates the position's accrued
+    /// fees and rewards.
+    ///
+    /// ### Authority
+    /// - "fee_authority" - Set authority in the WhirlpoolConfig
+    ///
+    /// ### Parameters
+    /// - `liquidity_amount` - The total amount of Liquidity to deposit.
+    /// - `token_max_a` - The maximum amount of tokenA to deposit.
+    /// - `token_max_b` - The maximum amount of tokenB to deposit.
+    ///
+    /// #### Special Errors
+    /// - `LiquidityZero` - Provided liquidity amount is zero.
+    /// - `LiquidityTooHigh` - Provided liquidity exceeds u128::max.
+    /// - `TokenMaxExceeded` - The required token to perform this operation exceeds the user defined amount.
+    pub fn increase_protocol_liquidity(
+        ctx: Context<ModifyProtocolLiquidity>,
+        liquidity_amount: u128,
+        token_max_a: u64,
+        token_max_b: u64,
+    ) -> Result<()> {
+        return instructions::increase_protocol_liquidity::handler(
+            ctx,
+            liquidity_amount,
+            token_max_a,
+            token_max_b,
+        );
+    }
+
+    /// Withdraw liquidity from a protocol owned position. This call also updates the position's
+    /// accrued fees and rewards.
+    ///
+    /// ### Authority
+    /// - "fee_authority" - Set authority in the WhirlpoolConfig
+    ///
+    /// ### Parameters
+    /// - `liquidity_amount` - The total amount of Liquidity to withdraw.
+    /// - `token_min_a` - The minimum amount of tokenA to receive.
+    /// - `token_min_b` - The minimum amount of tokenB to receive.
+    ///
+    /// #### Special Errors
+    /// - `LiquidityZero` - Provided liquidity amount is zero.
+    /// - `LiquidityTooHigh` - Provided liquidity exceeds u128::max.
+    /// - `TokenMinSubceeded` - The required token to perform this operation subceeds the user defined amount.
+    pub fn decrease_protocol_liquidity(
+        ctx: Context<ModifyProtocolLiquidity>,
+        liquidity_amount: u128,
+        token_min_a: u64,
+        token_min_b: u64,
+    ) -> Result<()> {
+        return instructions::decrease_protocol_liquidity::handler(
+            ctx,
+            liquidity_amount,
+            token_min_a,
+            token_min_b,
+        );
+    }
+
+    /// Enable or disable swaps of a Whirlpool in one direction, e.g. to only allow buys of a
+    /// token during a guarded launch. Swaps in a disabled direction fail with
+    /// `SwapDirectionDisabled`.
+    ///
+    /// ### Authority
+    /// - "fee_authority" - Set authority in the WhirlpoolConfig
+    ///
+    /// ### Parameters
+    /// - `a_to_b` - The direction to update, true for swaps of token A into token B.
+    /// - `enabled` - Whether swaps in the direction are allowed.
+    pub fn set_swap_direction_enabled(
+        ctx: Context<SetSwapDirectionEnabled>,
+        a_to_b: bool,
+        enabled: bool,
+    ) -> Result<()> {
+        return instructions::set_swap_direction_enabled::handler(ctx, a_to_b, enabled);
+    }
+
+    /// Reset the price of a Whirlpool that has drained to zero liquidity, so that new liquidity
+    /// providers do not face immediate arbitrage against a stale price.
+    ///
+    /// ### Authority
+    /// - "fee_authority" - Set authority in the WhirlpoolConfig
+    ///
+    /// ### Parameters
+    /// - `sqrt_price` - The new sqrt price of the pool, in Q64.64.
+    ///
+    /// #### Special Errors
+    /// - `PoolHasLiquidity` - Positions of the pool still hold liquidity, or its total liquidity
+    ///   is not complete yet.
+    /// - `SqrtPriceOutOfBounds` - The provided sqrt price is out of bounds.
+    pub fn reset_pool_price(ctx: Context<ResetPoolPrice>, sqrt_price: u128) -> Result<()> {
+        return instructions::reset_pool_price::handler(ctx, sqrt_price);
+    }
+
+    /// Create the tick cache of a Whirlpool, a copy of the ticks around the current price that
+    /// lets swaps in the hot band run with one cache account instead of three tick arrays.
+    ///
+    /// ### Authority
+    /// - "fee_authority" - Set authority in the WhirlpoolConfig
+    pub fn initialize_tick_cache(ctx: Context<InitializeTickCache>) -> Result<()> {
+        return instructions::initialize_tick_cache::handler(ctx);
+    }
+
+    /// Write the ticks updated by swaps against the tick cache back to the tick arrays, and reload
+    /// the cache with the ticks around the current price.
+    ///
+    /// ### Authority
+    /// - None. Anyone can refresh the tick cache of a Whirlpool.
+    ///
+    /// ### Remaining Accounts
+    /// - Optionally a keeper tip escrow of the Whirlpool, writable, the instructions sysvar and
+    ///   the keeper receiving the tip, writable, to tip the keeper from the escrow.
+    /// - The tick arrays, writable, covering the current window of the cache if swaps updated its
+    ///   ticks, and covering the window around the current tick.
+    ///
+    /// #### Special Errors
+    /// - `InvalidTickArrayAccount` - A tick array is not writable or belongs to another Whirlpool.
+    /// - `ForeignProgramAccount` - A tick array is owned by the upstream Whirlpool program.
+    /// - `TickCacheTickArrayMissing` - A tick array covering a window was not provided.
+    /// - `InvalidKeeperTipEscrow` - The keeper tip escrow belongs to another Whirlpool.
+    pub fn refresh_tick_cache<'info>(
+        ctx: Context<'_, '_, 'info, 'info, RefreshTickCache<'info>>,
+    ) -> Result<()> {
+        return instructions::refresh_tick_cache::handler(ctx);
+    }
+
+    /// Perform a swap in this Whirlpool against its tick cache instead of tick arrays. The price
+    /// can only move within the window of the cache.
+    ///
+    /// ### Authority
+    /// - "token_authority" - The authority to withdraw tokens from the input token account.
+    ///
+    /// ### Remaining Accounts
+    /// - Same as `swap`.
+    ///
+    /// ### Parameters
+    /// Same as `swap`.
+    ///
+    /// #### Special Errors
+    /// Same as `swap`, and
+    /// - `TickCacheStale` - Tick arrays changed since the cache was last refreshed.
+    /// - `TickArraySequenceInvalidIndex` - The swap would move the price beyond the cached window.
+    pub fn swap_with_tick_cache<'info>(
+        ctx: Context<'_, '_, 'info, 'info, SwapWithTickCache<'info>>,
+        amount: u64,
+        other_amount_threshold: u64,
+        sqrt_price_limit: u128,
+        amount_specified_is_input: bool,
+        a_to_b: bool,
+    ) -> Result<SwapFeeBreakdown> {
+        return instructions::swap_with_tick_cache::handler(
+            ctx,
+            amount,
+            other_amount_threshold,
+            sqrt_price_limit,
+            amount_specified_is_input,
+            a_to_b,
+        );
+    }
+
+    /// Set the liquidator allowed to settle positions for liquidation, typically a PDA of a money
+    /// market accepting positions as collateral. Pass `Pubkey::default()` to disable.
+    ///
+    /// ### Authority
+    /// - "fee_authority" - Set authority in the WhirlpoolConfig
+    ///
+    /// ### Parameters
+    /// - `approved_liquidator` - The liquidator signer to approve.
+    pub fn set_approved_liquidator(
+        ctx: Context<SetApprovedLiquidator>,
+        approved_liquidator: Pubkey,
+    ) -> Result<()> {
+        return instructions::set_approved_liquidator::handler(ctx, approved_liquidator);
+    }
+
+    /// Settle a collateralized position in one call: update its fees, withdraw liquidity and
+    /// collect the fees into the liquidator's token accounts. Rewards stay owed to the position.
+    ///
+    /// ### Authority
+    /// - "liquidator" - The approved liquidator of the WhirlpoolConfig, holding or delegated the
+    ///   position token.
+    ///
+    /// ### Parameters
+    /// - `liquidity_amount` - The amount of liquidity to withdraw, zero to only settle fees.
+    /// - `token_min_a` - The minimum amount of tokenA to receive from the withdrawal.
+    /// - `token_min_b` - The minimum amount of tokenB to receive from the withdrawal.
+    ///
+    /// #### Special Errors
+    /// - `TokenMinSubceeded` - The withdrawal returns less than the user defined amount.
+    /// - `PositionLiquidityLocked` - The withdrawal would reduce liquidity below the locked amount.
+    /// - `LiquidityRemovalCooldown` - The liquidity removal cooldown of the pool since the last
+    ///                                liquidity increase of the position has not passed.
+    /// - `DestinationFrozen` - A liquidator token account is frozen.
+    pub fn settle_position_for_liquidation(
+        ctx: Context<SettlePositionForLiquidation>,
+        liquidity_amount: u128,
+        token_min_a: u64,
+        token_min_b: u64,
+    ) -> Result<()> {
+        return instructions::settle_position_for_liquidation::handler(
+            ctx,
+            liquidity_amount,
+            token_min_a,
+            token_min_b,
+        );
+    }
+
+    /// Initialize the liquidity time accumulator of an owner in a Whirlpool. Liquidity changes
+    /// and fee updates of the owner's positions accrue liquidity multiplied by seconds in range to
+    /// it when it is passed as a remaining account.
+    ///
+    /// ### Authority
+    /// - "owner" - The position owner the accumulator is created for, pays for the account.
+    pub fn initialize_liquidity_time_accumulator(
+        ctx: Context<InitializeLiquidityTimeAccumulator>,
+    ) -> Result<()> {
+        return instructions::initialize_liquidity_time_accumulator::handler(ctx);
+    }
+
+    /// Account all swap fees of a Whirlpool in token B, for liquidity providers that report in
+    /// the quote token. Swaps from token A then swap the whole input and withhold the fee from
+    /// the token B output, at the execution price of each swap step.
+    ///
+    /// ### Authority
+    /// - "fee_authority" - Set authority in the WhirlpoolConfig
+    ///
+    /// ### Parameters
+    /// - `fees_in_token_b` - Whether swap fees are accounted in token B only.
+    pub fn set_fees_in_token_b(ctx: Context<SetFeesInTokenB>, fees_in_token_b: bool) -> Result<()> {
+        return instructions::set_fees_in_token_b::handler(ctx, fees_in_token_b);
+    }
+
+    /// Initialize the referral earnings of a referrer in a Whirlpool. Swaps made with
+    /// `swap_with_referral` credit the referrer's share of the protocol fee to it.
+    ///
+    /// ### Authority
+    /// - "funder" - The account paying for the referral earnings account.
+    pub fn initialize_referral_earnings(ctx: Context<InitializeReferralEarnings>) -> Result<()> {
+        return instructions::initialize_referral_earnings::handler(ctx);
+    }
+
+    /// Perform a swap in this Whirlpool on behalf of a referrer. Takes the `swap` accounts and
+    /// remaining accounts, and credits the Whirlpool's referral fee rate of the protocol fee to
+    /// the referrer's referral earnings.
+    ///
+    /// ### Authority
+    /// - "token_authority" - The authority to withdraw tokens from the input token account.
+    ///
+    /// ### Parameters
+    /// Same as `swap`.
+    ///
+    /// #### Special Errors
+    /// Same as `swap`.
+    pub fn swap_with_referral<'info>(
+        ctx: Context<'_, '_, 'info, 'info, SwapWithReferral<'info>>,
+        amount: u64,
+        other_amount_threshold: u64,
+        sqrt_price_limit: u128,
+        amount_specified_is_input: bool,
+        a_to_b: bool,
+    ) -> Result<SwapFeeBreakdown> {
+        return instructions::swap_with_referral::handler(
+            ctx,
+            amount,
+            other_amount_threshold,
+            sqrt_price_limit,
+            amount_specified_is_input,
+            a_to_b,
+        );
+    }
+
+    /// Collect the referral fees owed to a referrer in a Whirlpool.
+    ///
+    /// ### Authority
+    /// - "referrer" - The referrer of the referral earnings account.
+    ///
+    /// #### Special Errors
+    /// - `DestinationFrozen` - A destination token account is frozen.
+    pub fn claim_referral_fees(ctx: Context<ClaimReferralFees>) -> Result<()> {
+        return instructions::claim_referral_fees::handler(ctx);
+    }
+
+    /// Set the share of the protocol fee paid to referrers of swaps in a Whirlpool.
+    ///
+    /// ### Authority
+    /// - "fee_authority" - Set authority in the WhirlpoolConfig
+    ///
+    /// ### Parameters
+    /// - `referral_fee_rate` - The referral fee rate in basis points of the protocol fee.
+    ///
+    /// #### Special Errors
+    /// - `ReferralFeeRateMaxExceeded` - If the provided referral_fee_rate exceeds MAX_REFERRAL_FEE_RATE.
+    pub fn set_referral_fee_rate(
+        ctx: Context<SetReferralFeeRate>,
+        referral_fee_rate: u16,
+    ) -> Result<()> {
+        return instructions::set_referral_fee_rate::handler(ctx, referral_fee_rate);
+    }
+
+    /// Open a position in a Whirlpool at an address derived from the owner, the Whirlpool, the
+    /// tick range and a nonce, for integrations that derive the addresses of their positions.
+    /// A unique token is minted to represent the position as with `open_position`.
+    ///
+    /// ### Authority
+    /// - `owner` - The owner of the position, who signs so that no other funder can open a
+    ///             position at an address derived from the owner.
+    ///
+    /// ### Parameters
+    /// - `tick_lower_index` - The tick specifying the lower end of the position range, or
+    ///                        `FULL_RANGE_TICK_INDEX` for the lowest usable tick of the pool.
+    /// - `tick_upper_index` - The tick specifying the upper end of the position range, or
+    ///                        `FULL_RANGE_TICK_INDEX` for the highest usable tick of the pool.
+    /// - `nonce` - Distinguishes positions of the same owner with the same tick range.
+    ///
+    /// #### Special Errors
+    /// - `InvalidTickIndex` - If a provided tick is out of bounds, out of order or not a multiple of
+    ///                        the tick-spacing in this pool.
+    /// - `PositionLimitReached` - The Whirlpool has its maximum number of open positions.
+    pub fn open_position_with_seeds(
+        ctx: Context<OpenPositionWithSeeds>,
+        tick_lower_index: i32,
+        tick_upper_index: i32,
+        _nonce: u64,
+    ) -> Result<()> {
+        return instructions::open_position_with_seeds::handler(
+            ctx,
+            tick_lower_index,
+            tick_upper_index,
+        );
+    }
+
+    /// Withdraw liquidity from a position using only tick and pool liquidity math, without
+    /// updating fee and reward growths. For recovering principal if fee or reward accounting
+    /// fails; the fees, rewards and liquidity time accrued by the withdrawn liquidity since the
+    /// position was last updated are forfeited.
+    ///
+    /// ### Authority
+    /// - "position_authority" - authority that owns the token corresponding to this desired position.
+    ///
+    /// ### Parameters
+    /// - `liquidity_amount` - The total amount of Liquidity the user desires to withdraw.
+    /// - `token_min_a` - The minimum amount of tokenA the user is willing to withdraw.
+    /// - `token_min_b` - The minimum amount of tokenB the user is willing to withdraw.
+    ///
+    /// #### Special Errors
+    /// - `LiquidityZero` - Provided liquidity amount is zero.
+    /// - `LiquidityTooHigh` - Provided liquidity exceeds u128::max.
+    /// - `TokenMinSubceeded` - The required token to perform this operation subceeds the user defined amount.
+    ///                         The withdrawable amounts of token A and B are set as return data as
+    ///                         a `QuoteHint`.
+    /// - `PositionLiquidityLocked` - The withdrawal would reduce liquidity below the locked amount.
+    /// - `LiquidityRemovalCooldown` - The liquidity removal cooldown of the pool since the last
+    ///                                liquidity increase of the position has not passed.
+    pub fn emergency_decrease_liquidity<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ModifyLiquidity<'info>>,
+        liquidity_amount: u128,
+        token_min_a: u64,
+        token_min_b: u64,
+    ) -> Result<()> {
+        return instructions::emergency_decrease_liquidity::handler(
+            ctx,
+            liquidity_amount,
+            token_min_a,
+            token_min_b,
+        );
+    }
+
+    /// Initialize the swap stats of a partner frontend in a Whirlpool. Swaps made with
+    /// `swap_with_partner` add their volume and fees to it.
+    ///
+    /// ### Authority
+    /// - "funder" - The account paying for the partner stats account.
+    pub fn initialize_partner_stats(ctx: Context<InitializePartnerStats>) -> Result<()> {
+        return instructions::initialize_partner_stats::handler(ctx);
+    }
+
+    /// Perform a swap in this Whirlpool on behalf of a partner frontend. Takes the `swap`
+    /// accounts and remaining accounts, and adds the token amounts and fee of the swap to the
+    /// partner's stats.
+    ///
+    /// ### Authority
+    /// - "token_authority" - The authority to withdraw tokens from the input token account.
+    ///
+    /// ### Parameters
+    /// Same as `swap`.
+    ///
+    /// #### Special Errors
+    /// Same as `swap`.
+    pub fn swap_with_partner<'info>(
+        ctx: Context<'_, '_, 'info, 'info, SwapWithPartner<'info>>,
+        amount: u64,
+        other_amount_threshold: u64,
+        sqrt_price_limit: u128,
+        amount_specified_is_input: bool,
+        a_to_b: bool,
+    ) -> Result<SwapFeeBreakdown> {
+        return instructions::swap_with_partner::handler(
+            ctx,
+            amount,
+            other_amount_threshold,
+            sqrt_price_limit,
+            amount_specified_is_input,
+            a_to_b,
+        );
+    }
+
+    /// Cap the number of open positions in a Whirlpool. Opening a position fails once the
+    /// Whirlpool has the maximum number of open positions, closing one frees its slot.
+    ///
+    /// ### Authority
+    /// - "fee_authority" - Set authority in the WhirlpoolConfig
+    ///
+    /// ### Parameters
+    /// - `max_positions` - The maximum number of open positions, 0 for no limit.
+    pub fn set_max_positions(ctx: Context<SetMaxPositions>, max_positions: u32) -> Result<()> {
+        return instructions::set_max_positions::handler(ctx, max_positions);
+    }
+
+    /// Set the escrow program that tops up a reward vault when positions collect more rewards
+    /// than the vault holds. `collect_reward` then asks the escrow program to release the missing
+    /// amount into the vault through its `release_rewards` instruction before paying out.
+    ///
+    /// ### Authority
+    /// - "reward_authority" - The authority of the specified reward-index in this Whirlpool.
+    ///
+    /// ### Parameters
+    /// - `reward_index` - The reward index that we'd like to set the escrow program for.
+    /// - `escrow_program` - The escrow program, Pubkey::default() to fund the vault directly.
+    ///
+    /// #### Special Errors
+    /// - `InvalidRewardIndex` - The reward index exceeds NUM_REWARDS.
+    /// - `InvalidRewardEscrowProgram` - The escrow program is the Whirlpool program.
+    pub fn set_reward_escrow_program(
+        ctx: Context<SetRewardEscrowProgram>,
+        reward_index: u8,
+        escrow_program: Pubkey,
+    ) -> Result<()> {
+        return instructions::set_reward_escrow_program::handler(ctx, reward_index, escrow_program);
+    }
+
+    /// Set the program notified via CPI of every initialized tick crossed by a swap in this
+    /// Whirlpool. The hook receives an `on_tick_cross` instruction per crossed tick, carrying the
+    /// tick index and the swap direction, and reads the Whirlpool as updated by the swap. The
+    /// notifications of a swap may consume at most MAX_TICK_CROSS_HOOK_COMPUTE_UNITS. Pass
+    /// `Pubkey::default()` to disable.
+    ///
+    /// ### Authority
+    /// - "fee_authority" - Set authority in the WhirlpoolConfig
+    ///
+    /// #### Special Errors
+    /// - `InvalidTickCrossHook` - The hook cannot be the Whirlpool program itself.
+    pub fn set_tick_cross_hook(
+        ctx: Context<SetTickCrossHook>,
+        tick_cross_hook: Pubkey,
+    ) -> Result<()> {
+        return instructions::set_tick_cross_hook::handler(ctx, tick_cross_hook);
+    }
+
+    /// Initializes a Whirlpool with the price, fee rates, fee growth globals and reward
+    /// configuration of an existing Whirlpool, to set up staging pools that mirror live ones. The
+    /// clone has the tick spacing of the source and starts without liquidity or tick arrays.
+    ///
+    /// ### Authority
+    /// - "fee_authority" - Set authority in the WhirlpoolConfig the clone is created in.
+    ///
+    /// ### Remaining Accounts
+    /// - One reward vault per initialized reward of the source, in reward order. Each must be a
+    ///   token account of the reward mint owned by the new Whirlpool.
+    /// - The token badges of the token mints with a freeze authority, after the reward vaults.
+    ///
+    /// #### Special Errors
+    /// - `InvalidTokenMintOrder` - The order of mints have to be ordered by
+    /// - `RemainingAccountsMissing` - A reward vault was not provided.
+    /// - `InvalidRewardVault` - A reward vault has the wrong mint or is not owned by the clone.
+    /// - `FreezeAuthorityNotAllowed` - A mint has a freeze authority and no token badge allowing it.
+    pub fn clone_pool_state<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ClonePoolState<'info>>,
+    ) -> Result<()> {
+        return instructions::clone_pool_state::handler(ctx);
+    }
+
+    /// Set a withdraw authority that can decrease the liquidity of the position without holding
+    /// the position token, e.g. an operations key while the position token stays in cold storage.
+    /// The withdraw authority can only withdraw to token accounts of the withdraw destination.
+    ///
+    /// ### Authority
+    /// - `position_authority` - authority that owns the token corresponding to this desired position.
+    ///
+    /// ### Parameters
+    /// - `withdraw_authority` - The withdraw authority, or the default pubkey to disable it.
+    /// - `withdraw_destination` - The wallet the withdraw authority must withdraw to.
+    pub fn set_withdraw_authority(
+        ctx: Context<SetWithdrawAuthority>,
+        withdraw_authority: Pubkey,
+        withdraw_destination: Pubkey,
+    ) -> Result<()> {
+        return instructions::set_withdraw_authority::handler(
+            ctx,
+            withdraw_authority,
+            withdraw_destination,
+        );
+    }
+
+    /// Initializes the singleton protocol stats account, counting the pools created from then on
+    /// and, in builds with the `protocol-stats-swaps` feature, the swaps and their protocol fees.
+    /// Pool creation requires the account.
+    ///
+    /// ### Authority
+    /// - "funder" - The account paying for the protocol stats account.
+    pub fn initialize_protocol_stats(ctx: Context<InitializeProtocolStats>) -> Result<()> {
+        return instructions::initialize_protocol_stats::handler(ctx);
+    }
+
+    /// Deposit a single token into a position. The share of the input the position needs in the
+    /// other token at the current price is swapped in the same Whirlpool first, then the largest
+    /// liquidity the two amounts provide is added to the position. Amounts that do not fit the
+    /// ratio after the swap's fee and price impact stay in the owner's token accounts.
+    ///
+    /// ### Authority
+    /// - "token_authority" - The authority of the position token, withdrawing the tokens from the
+    ///                       owner's token accounts.
+    ///
+    /// ### Remaining Accounts
+    /// Same as `swap`. The liquidity time of the position since its last accrual is forfeited.
+    ///
+    /// ### Parameters
+    /// - `amount` - The amount of the input token to deposit.
+    /// - `input_is_a` - True if the input token is token A, false if it is token B.
+    /// - `liquidity_min` - The minimum liquidity to add to the position, the single slippage bound
+    ///                     of the swap and the deposit.
+    ///
+    /// #### Special Errors
+    /// Same as `swap` and `increase_liquidity`, and
+    /// - `LiquidityUnderMinimum` - The deposit would add less liquidity than `liquidity_min`.
+    pub fn zap_in<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ZapIn<'info>>,
+        amount: u64,
+        input_is_a: bool,
+        liquidity_min: u128,
+    ) -> Result<()> {
+        return instructions::zap_in::handler(ctx, amount, input_is_a, liquidity_min);
+    }
+
+    /// Withdraw a position into a single token. The liquidity is removed from the position and its
+    /// fees owed are collected, then everything received in the other token is swapped into the
+    /// chosen token in the same Whirlpool. Rewards are not collected.
+    ///
+    /// ### Authority
+    /// - "token_authority" - The authority of the position token, receiving the tokens in the
+    ///                       owner's token accounts.
+    ///
+    /// ### Remaining Accounts
+    /// Same as `swap`. The liquidity time of the position since its last accrual is forfeited.
+    ///
+    /// ### Parameters
+    /// - `liquidity_amount` - The amount of liquidity to remove from the position.
+    /// - `output_is_a` - True if the position is withdrawn as token A, false if as token B.
+    /// - `min_out` - The minimum total amount of the output token received, the single slippage
+    ///               bound of the withdrawal and the swap.
+    ///
+    /// #### Special Errors
+    /// Same as `swap` and `decrease_liquidity`, and
+    /// - `AmountOutBelowMinimum` - Less than `min_out` of the output token was received.
+    pub fn zap_out<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ZapOut<'info>>,
+        liquidity_amount: u128,
+        output_is_a: bool,
+        min_out: u64,
+    ) -> Result<()> {
+        return instructions::zap_out::handler(ctx, liquidity_amount, output_is_a, min_out);
+    }
+
+    /// Opt the position in or out of auto compounding. While opted in, any keeper can compound the
+    /// fees owed of the position into its liquidity with `compound_for_position`, for a tip out of
+    /// those fees. Keepers cannot withdraw from the position.
+    ///
+    /// ### Authority
+    /// - `position_authority` - authority that owns the token corresponding to this desired position.
+    ///
+    /// ### Parameters
+    /// - `auto_compound` - True to allow keepers to compound the fees owed.
+    /// - `tip_rate` - The keeper tip, in basis points of the fees owed when compounding.
+    ///
+    /// #### Special Errors
+    /// - `AutoCompoundTipRateMaxExceeded` - If the provided tip_rate exceeds MAX_AUTO_COMPOUND_TIP_RATE.
+    pub fn set_auto_compound(
+        ctx: Context<SetAutoCompound>,
+        auto_compound: bool,
+        tip_rate: u16,
+    ) -> Result<()> {
+        return instructions::set_auto_compound::handler(ctx, auto_compound, tip_rate);
+    }
+
+    /// Compound the fees owed of a position that opted in to auto compounding. The keeper tip is
+    /// paid to the keeper's token accounts, then the largest liquidity the remaining fees provide
+    /// at the current price is added to the position. Fees that do not fit stay owed.
+    ///
+    /// ### Authority
+    /// - `keeper` - Any signer, receiving the tip.
+    ///
+    /// ### Remaining Accounts
+    /// - `[0]` - The liquidity time accumulator of the position owner, optional. The liquidity time
+    ///   of the position since its last accrual is forfeited when it is not passed.
+    ///
+    /// #### Special Errors
+    /// - `AutoCompoundDisabled` - The position has not opted in to auto compounding.
+    /// - `LiquidityZero` - The fees owed provide no liquidity.
+    pub fn compound_for_position<'info>(
+        ctx: Context<'_, '_, 'info, 'info, CompoundForPosition<'info>>,
+    ) -> Result<()> {
+        return instructions::compound_for_position::handler(ctx);
+    }
+
+    /// Import a position of the upstream Orca Whirlpool program into this program. A new position
+    /// with the range and liquidity of the upstream position is opened in a Whirlpool of the same
+    /// mints, and the tokens for that liquidity are deposited from the owner's token accounts.
+    /// Each upstream position can be imported once. The upstream position is not modified, its
+    /// liquidity can be withdrawn from the upstream program after the import.
+    ///
+    /// ### Authority
+    /// - "owner" - The holder of the upstream position token, receiving the new position token and
+    ///             depositing the tokens.
+    ///
+    /// ### Parameters
+    /// - `token_max_a` - The maximum amount of tokenA allowed to deposit.
+    /// - `token_max_b` - The maximum amount of tokenB allowed to deposit.
+    ///
+    /// #### Special Errors
+    /// - `InvalidUpstreamPosition` - The external position is not an upstream position, or its
+    ///                               position token is not held by the owner.
+    /// - `UpstreamWhirlpoolMismatch` - The upstream Whirlpool does not pair the same mints.
+    /// - `InvalidTickIndex` - The upstream range is not usable with the tick spacing of the
+    ///                        Whirlpool.
+    /// - `TokenMaxExceeded` - The required token to mirror the liquidity exceeds the user defined
+    ///                        amount.
+    pub fn import_external_position(
+        ctx: Context<ImportExternalPosition>,
+        token_max_a: u64,
+        token_max_b: u64,
+    ) -> Result<()> {
+        return instructions::import_external_position::handler(ctx, token_max_a, token_max_b);
+    }
+
+    /// Initializes a rent sponsor for the tick arrays of a Whirlpool starting within a range, so
+    /// protocols can pay the rent of tick arrays users initialize in incentivized ranges. Funded
+    /// sponsors refund the rent to the funder of `initialize_tick_array`.
+    ///
+    /// ### Authority
+    /// - "authority" - The authority allowed to withdraw the unused lamports of the sponsor.
+    ///
+    /// ### Parameters
+    /// - `start_tick_index_min` - The lowest start tick index of a sponsored tick array.
+    /// - `start_tick_index_max` - The highest start tick index of a sponsored tick array.
+    ///
+    /// #### Special Errors
+    /// - `InvalidRentSponsor` - The range is empty.
+    pub fn initialize_rent_sponsor(
+        ctx: Context<InitializeRentSponsor>,
+        start_tick_index_min: i32,
+        start_tick_index_max: i32,
+    ) -> Result<()> {
+        return instructions::initialize_rent_sponsor::handler(
+            ctx,
+            start_tick_index_min,
+            start_tick_index_max,
+        );
+    }
+
+    /// Fund a rent sponsor with lamports. Anyone can fund any sponsor.
+    ///
+    /// ### Parameters
+    /// - `lamports` - The amount of lamports to transfer from the funder to the sponsor.
+    pub fn fund_rent_sponsor(ctx: Context<FundRentSponsor>, lamports: u64) -> Result<()> {
+        return instructions::fund_rent_sponsor::handler(ctx, lamports);
+    }
+
+    /// Withdraw unused lamports from a rent sponsor. The sponsor keeps its own rent exemption.
+    ///
+    /// ### Authority
+    /// - "authority" - The authority of the rent sponsor.
+    ///
+    /// ### Parameters
+    /// - `lamports` - The amount of lamports to transfer to the destination.
+    ///
+    /// #### Special Errors
+    /// - `RentSponsorInsufficientFunds` - The sponsor holds fewer lamports above its rent exemption.
+    pub fn withdraw_rent_sponsor(ctx: Context<WithdrawRentSponsor>, lamports: u64) -> Result<()> {
+        return instructions::withdraw_rent_sponsor::handler(ctx, lamports);
+    }
+
+    /// Create the fee schedule of a Whirlpool, holding fee holidays programmed ahead by the fee
+    /// authority. Once created, every swap of the Whirlpool must pass the fee schedule.
+    ///
+    /// ### Authority
+    /// - "fee_authority" - Set authority in the WhirlpoolConfig
+    pub fn initialize_fee_schedule(ctx: Context<InitializeFeeSchedule>) -> Result<()> {
+        return instructions::initialize_fee_schedule::handler(ctx);
+    }
+
+    /// Program a temporary fee reduction of a Whirlpool in a slot of its fee schedule. Swaps
+    /// between the start and end timestamps pay the lowest of the fee rate of the Whirlpool and
+    /// the rates of the active fee holidays, without any fee change by the fee authority.
+    ///
+    /// ### Authority
+    /// - "fee_authority" - Set authority in the WhirlpoolConfig
+    ///
+    /// ### Parameters
+    /// - `index` - The slot of the fee holiday in the fee schedule.
+    /// - `start_timestamp` - The timestamp the fee holiday starts at.
+    /// - `end_timestamp` - The timestamp the fee holiday ends at, excluded. 0 clears the slot.
+    /// - `fee_rate` - The fee rate during the fee holiday, in thousandths of a basis point.
+    ///
+    /// #### Special Errors
+    /// - `InvalidFeeHoliday` - The slot is out of range, the fee holiday ends before it starts or
+    ///                         the fee rate exceeds MAX_FEE_RATE.
+    pub fn set_fee_holiday(
+        ctx: Context<SetFeeHoliday>,
+        index: u8,
+        start_timestamp: u64,
+        end_timestamp: u64,
+        fee_rate: u32,
+    ) -> Result<()> {
+        return instructions::set_fee_holiday::handler(
+            ctx,
+            index,
+            start_timestamp,
+            end_timestamp,
+            fee_rate,
+        );
+    }
+
+    /// Create a retroactive reward distributor of a Whirlpool, paying out rewards computed
+    /// off-chain from liquidity snapshots of the pool, and the vault holding them. The vault is
+    /// funded with plain token transfers.
+    ///
+    /// ### Authority
+    /// - "reward_authority" - The authority of the specified reward-index in this Whirlpool.
+    ///
+    /// ### Parameters
+    /// - `reward_index` - The reward index whose authority manages the distributor.
+    ///
+    /// #### Special Errors
+    /// - `InvalidRewardIndex` - The reward index exceeds NUM_REWARDS.
+    pub fn initialize_retro_reward(
+        ctx: Context<InitializeRetroReward>,
+        reward_index: u8,
+    ) -> Result<()> {
+        return instructions::initialize_retro_reward::handler(ctx, reward_index);
+    }
+
+    /// Set the merkle root of a retroactive reward distributor. Each leaf commits to the
+    /// cumulative amount a claimant has earned, so a new snapshot replaces the root and claimants
+    /// only receive what they have not claimed yet.
+    ///
+    /// ### Authority
+    /// - "reward_authority" - The authority of the reward-index of the distributor.
+    ///
+    /// ### Parameters
+    /// - `merkle_root` - The merkle root of the cumulative amounts of the claimants.
+    pub fn set_reward_merkle_root(
+        ctx: Context<SetRewardMerkleRoot>,
+        merkle_root: [u8; 32],
+    ) -> Result<()> {
+        return instructions::set_reward_merkle_root::handler(ctx, merkle_root);
+    }
+
+    /// Claim a retroactive reward from a distributor, proving the cumulative amount of the
+    /// claimant against the merkle root. The claimant receives the cumulative amount minus what
+    /// they already claimed, tracked in a claim account created on the first claim.
+    ///
+    /// ### Authority
+    /// - "claimant" - The claimant of the leaf.
+    ///
+    /// ### Parameters
+    /// - `cumulative_amount` - The cumulative amount of the claimant in the merkle tree.
+    /// - `proof` - The sibling hashes from the leaf up to the merkle root.
+    ///
+    /// #### Special Errors
+    /// - `InvalidMerkleProof` - The proof does not match the merkle root of the distributor.
+    /// - `RetroRewardAlreadyClaimed` - The cumulative amount has already been claimed.
+    pub fn claim_retro_reward(
+        ctx: Context<ClaimRetroReward>,
+        cumulative_amount: u64,
+        proof: Vec<[u8; 32]>,
+    ) -> Result<()> {
+        return instructions::claim_retro_reward::handler(ctx, cumulative_amount, proof);
+    }
+
+    /// Burn the position token of a position and mint a replacement carrying the current
+    /// position metadata, so positions opened without metadata, before the position collection or
+    /// before a change of the position metadata URI template adopt the current display standards. The position keeps its liquidity, fees and rewards
+    /// and is moved onto the new position mint.
+    ///
+    /// ### Authority
+    /// - "position_authority" - The holder of the position token, who receives the new token.
+    ///
+    /// ### Remaining Accounts
+    /// - The collection mint, metadata and master edition, if the Whirlpool has a position
+    ///   collection.
+    ///
+    /// #### Special Errors
+    /// - `InvalidPositionCollection` - The collection mint is not the one of the Whirlpool.
+    /// - `RemainingAccountsMissing` - The collection accounts are missing.
+    pub fn refresh_position_nft<'info>(
+        ctx: Context<'_, '_, 'info, 'info, RefreshPositionNft<'info>>,
+    ) -> Result<()> {
+        return instructions::refresh_position_nft::handler(ctx);
+    }
+
+    /// Return the fees and rewards a position can collect, including the growth accrued since the
+    /// position was last updated, without modifying any account. Meant to be simulated, so the
+    /// claimable amounts shown to users match what the collect instructions pay.
+    ///
+    /// #### Special Errors
+    /// - `TickNotFound` - A tick array does not hold the tick of the position it is passed for.
+    pub fn get_pending_fees_and_rewards(
+        ctx: Context<GetPendingFeesAndRewards>,
+    ) -> Result<PendingFeesAndRewards> {
+        return instructions::get_pending_fees_and_rewards::handler(ctx);
+    }
+
+    /// Fail unless the Whirlpool was swapped against within the given number of slots. Consumers
+    /// of the pool price, e.g. lending protocols, add it to their transaction or invoke it
+    /// through CPI to reject prices of pools nobody trades against.
+    ///
+    /// ### Parameters
+    /// - `require_recent_swap_within_slots` - The maximum number of slots since the last swap.
+    ///
+    /// #### Special Errors
+    /// - `StalePool` - The last swap is older than the given number of slots.
+    pub fn verify_recent_swap(
+        ctx: Context<VerifyRecentSwap>,
+        require_recent_swap_within_slots: u64,
+    ) -> Result<()> {
+        return instructions::verify_recent_swap::handler(ctx, require_recent_swap_within_slots);
+    }
+
+    /// Open a position in a Whirlpool as with `open_position`, succeeding as a no-op if the
+    /// position of the position mint already exists with the same Whirlpool and tick range and
+    /// its token is held by the owner. Retried transactions then do not fail on the position mint
+    /// already being in use.
+    ///
+    /// ### Parameters
+    /// - `tick_lower_index` - The tick specifying the lower end of the position range, or
+    ///                        `FULL_RANGE_TICK_INDEX` for the lowest usable tick of the pool.
+    /// - `tick_upper_index` - The tick specifying the upper end of the position range, or
+    ///                        `FULL_RANGE_TICK_INDEX` for the highest usable tick of the pool.
+    ///
+    /// #### Special Errors
+    /// - `InvalidTickIndex` - If a provided tick is out of bounds, out of order or not a multiple of
+    ///                        the tick-spacing in this pool.
+    /// - `PositionLimitReached` - The Whirlpool has its maximum number of open positions.
+    /// - `PositionMismatch` - The position exists with other parameters or its token is no longer
+    ///                        held by the owner.
+    pub fn open_position_idempotent(
+        ctx: Context<OpenPositionIdempotent>,
+        tick_lower_index: i32,
+        tick_upper_index: i32,
+    ) -> Result<()> {
+        return instructions::open_position_idempotent::handler(
+            ctx,
+            tick_lower_index,
+            tick_upper_index,
+        );
+    }
+
+    /// Create the token badge of a token mint in a WhirlpoolsConfig, holding the policy of the
+    /// config for the mint. Pools for mints with a freeze authority can only be created if the
+    /// token badge of the mint allows it.
+    ///
+    /// ### Authority
+    /// - "fee_authority" - Set authority in the WhirlpoolConfig
+    ///
+    /// ### Parameters
+    /// - `allow_freeze_authority` - True if pools may be created while the mint has a freeze
+    ///                              authority.
+    pub fn initialize_token_badge(
+        ctx: Context<InitializeTokenBadge>,
+        allow_freeze_authority: bool,
+    ) -> Result<()> {
+        return instructions::initialize_token_badge::handler(ctx, allow_freeze_authority);
+    }
+
+    /// Set whether pools may be created for the mint of a token badge while the mint has a
+    /// freeze authority. Existing pools are unaffected.
+    ///
+    /// ### Authority
+    /// - "fee_authority" - Set authority in the WhirlpoolConfig
+    ///
+    /// ### Parameters
+    /// - `allow_freeze_authority` - True if pools may be created while the mint has a freeze
+    ///                              authority.
+    pub fn set_token_badge_allow_freeze_authority(
+        ctx: Context<SetTokenBadgeAllowFreezeAuthority>,
+        allow_freeze_authority: bool,
+    ) -> Result<()> {
+        return instructions::set_token_badge_allow_freeze_authority::handler(
+            ctx,
+            allow_freeze_authority,
+        );
+    }
+
+    /// Set the Pyth price feed of a token mint, which pools of the WhirlpoolsConfig initialized
+    /// from an oracle price read the mint's price from. Creates the mint price feed account on
+    /// first use.
+    ///
+    /// ### Authority
+    /// - "fee_authority" - Set authority in the WhirlpoolConfig
+    ///
+    /// ### Parameters
+    /// - `feed_id` - The id of the Pyth price feed of the mint. The feeds of the two mints of a
+    ///               pool have to share a quote currency, e.g. USD.
+    pub fn set_mint_price_feed(ctx: Context<SetMintPriceFeed>, feed_id: [u8; 32]) -> Result<()> {
+        return instructions::set_mint_price_feed::handler(ctx, feed_id);
+    }
+
+    /// Persist the reward growth globals of a Whirlpool up to the current time and emit them in a
+    /// `RewardGrowthCheckpointed` event. Permissionless, so keepers can checkpoint emissions at
+    /// regular intervals for auditors while no liquidity events occur.
+    ///
+    /// #### Special Errors
+    /// - `InvalidTimestamp` - The current time is before the last reward update.
+    pub fn checkpoint_reward_growth(ctx: Context<CheckpointRewardGrowth>) -> Result<()> {
+        return instructions::checkpoint_reward_growth::handler(ctx);
+    }
+
+    /// Create the router registry of a WhirlpoolsConfig. Swaps made through `swap_with_router`
+    /// by CPI from an approved router program pay a discounted fee rate.
+    ///
+    /// ### Authority
+    /// - "fee_authority" - Set authority in the WhirlpoolConfig
+    ///
+    /// ### Parameters
+    /// - `fee_discount_rate` - The share of the fee rate waived for approved routers. Stored as a
+    ///                         basis point of the fee rate.
+    ///
+    /// #### Special Errors
+    /// - `RouterFeeDiscountRateMaxExceeded` - If the provided fee_discount_rate exceeds
+    ///                                        MAX_ROUTER_FEE_DISCOUNT_RATE.
+    pub fn initialize_router_registry(
+        ctx: Context<InitializeRouterRegistry>,
+        fee_discount_rate: u16,
+    ) -> Result<()> {
+        return instructions::initialize_router_registry::handler(ctx, fee_discount_rate);
+    }
+
+    /// Set the router program in a slot of the router registry.
+    ///
+    /// ### Authority
+    /// - "fee_authority" - Set authority in the WhirlpoolConfig
+    ///
+    /// ### Parameters
+    /// - `router_index` - The slot of the router in the registry.
+    /// - `router` - The approved router program, or the default pubkey to clear the slot.
+    ///
+    /// #### Special Errors
+    /// - `InvalidApprovedRouterIndex` - If the provided router_index is out of bounds.
+    pub fn set_approved_router(
+        ctx: Context<SetApprovedRouter>,
+        router_index: u8,
+        router: Pubkey,
+    ) -> Result<()> {
+        return instructions::set_approved_router::handler(ctx, router_index, router);
+    }
+
+    /// Set the fee discount rate of the approved routers of a router registry.
+    ///
+    /// ### Authority
+    /// - "fee_authority" - Set authority in the WhirlpoolConfig
+    ///
+    /// ### Parameters
+    /// - `fee_discount_rate` - The share of the fee rate waived for approved routers. Stored as a
+    ///                         basis point of the fee rate.
+    ///
+    /// #### Special Errors
+    /// - `RouterFeeDiscountRateMaxExceeded` - If the provided fee_discount_rate exceeds
+    ///                                        MAX_ROUTER_FEE_DISCOUNT_RATE.
+    pub fn set_router_fee_discount_rate(
+        ctx: Context<SetRouterFeeDiscountRate>,
+        fee_discount_rate: u16,
+    ) -> Result<()> {
+        return instructions::set_router_fee_discount_rate::handler(ctx, fee_discount_rate);
+    }
+
+    /// Perform a swap in this Whirlpool on behalf of a router program. Takes the `swap` accounts
+    /// and remaining accounts. If the swap is made by a direct CPI from the program of the
+    /// transaction level instruction and that program is approved in the router registry of the
+    /// config, the fee rate of the swap is discounted by the registry's fee discount rate. Swaps
+    /// nested deeper than the first CPI level pay the full fee rate.
+    ///
+    /// ### Authority
+    /// - "token_authority" - The authority to withdraw tokens from the input token account.
+    ///
+    /// ### Parameters
+    /// Same as `swap`.
+    ///
+    /// #### Special Errors
+    /// Same as `swap`.
+    pub fn swap_with_router<'info>(
+        ctx: Context<'_, '_, 'info, 'info, SwapWithRouter<'info>>,
+        amount: u64,
+        other_amount_threshold: u64,
+        sqrt_price_limit: u128,
+        amount_specified_is_input: bool,
+        a_to_b: bool,
+    ) -> Result<SwapFeeBreakdown> {
+        return instructions::swap_with_router::handler(
+            ctx,
+            amount,
+            other_amount_threshold,
+            sqrt_price_limit,
+            amount_specified_is_input,
+            a_to_b,
+        );
+    }
+
+    /// Set the change of the active liquidity of a Whirlpool within a slot above which swaps and
+    /// liquidity changes emit a `LiquidityShifted` event, letting monitoring detect liquidity
+    /// being pulled without diffing tick arrays.
+    ///
+    /// ### Authority
+    /// - "fee_authority" - Set authority in the WhirlpoolConfig
+    ///
+    /// ### Parameters
+    /// - `liquidity_shift_alert_rate` - The change in basis points of the active liquidity at the
+    ///                                  start of the slot, or 0 to disable the event.
+    pub fn set_liquidity_shift_alert_rate(
+        ctx: Context<SetLiquidityShiftAlertRate>,
+        liquidity_shift_alert_rate: u16,
+    ) -> Result<()> {
+        return instructions::set_liquidity_shift_alert_rate::handler(
+            ctx,
+            liquidity_shift_alert_rate,
+        );
+    }
+
+    /// Arbitrage two Whirlpools of the same pair atomically. Swaps `amount` of the profit token
+    /// into the other token in the first Whirlpool, then everything received back into the profit
+    /// token in the second Whirlpool, and asserts the net profit.
+    ///
+    /// ### Authority
+    /// - "token_authority" - The authority to withdraw tokens from the token accounts, shared by
+    ///                       both swaps.
+    ///
+    /// ### Remaining Accounts
+    /// The remaining accounts of `swap` for the first Whirlpool, followed by those for the second.
+    ///
+    /// ### Parameters
+    /// - `amount` - The amount of the profit token swapped in the first Whirlpool.
+    /// - `profit_in_a` - True if the arbitrage starts and ends in token A, false if in token B.
+    /// - `min_profit` - The minimum increase of the profit token balance.
+    /// - `remaining_accounts_split` - The number of remaining accounts of the first swap.
+    ///
+    /// #### Special Errors
+    /// Same as `swap`, and
+    /// - `InvalidArbitragePools` - The Whirlpools are the same or of different pairs, or the swaps
+    ///                             use different token accounts.
+    /// - `ArbitrageProfitBelowMinimum` - The profit token balance increased by less than
+    ///                                   `min_profit`.
+    pub fn arb_two_pools<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ArbTwoPools<'info>>,
+        amount: u64,
+        profit_in_a: bool,
+        min_profit: u64,
+        remaining_accounts_split: u8,
+    ) -> Result<()> {
+        return instructions::arb_two_pools::handler(
+            ctx,
+            amount,
+            profit_in_a,
+            min_profit,
+            remaining_accounts_split,
+        );
+    }
+
+    /// Set the surcharge on liquidity removed from a position of a Whirlpool within a number of
+    /// slots of the position's last liquidity increase. The surcharge stays in the vaults and is
+    /// added to the fee growth of the liquidity remaining in range, so just-in-time liquidity
+    /// pays back the fees it diluted.
+    ///
+    /// ### Authority
+    /// - "fee_authority" - Set authority in the WhirlpoolConfig
+    ///
+    /// ### Parameters
+    /// - `jit_surcharge_rate` - The surcharge in basis points of the removed token amounts, or 0
+    ///                          to disable it.
+    /// - `jit_surcharge_slots` - The slots after a liquidity increase within which removals are
+    ///                           surcharged, 0 for the same slot only.
+    ///
+    /// #### Special Errors
+    /// - `JitSurchargeRateMaxExceeded` - If the provided jit_surcharge_rate exceeds
+    ///                                   MAX_JIT_SURCHARGE_RATE.
+    pub fn set_jit_surcharge(
+        ctx: Context<SetJitSurcharge>,
+        jit_surcharge_rate: u16,
+        jit_surcharge_slots: u32,
+    ) -> Result<()> {
+        return instructions::set_jit_surcharge::handler(
+            ctx,
+            jit_surcharge_rate,
+            jit_surcharge_slots,
+        );
+    }
+
+    /// Transfer a position token to another token account of the position mint, recording the
+    /// new owner as the `owner_hint` of the position and emitting a `PositionTransferred` event,
+    /// so portfolio trackers see ownership changes without following SPL transfers.
+    ///
+    /// ### Authority
+    /// - "position_authority" - The owner or delegate of the position token.
+    pub fn transfer_position(ctx: Context<TransferPosition>) -> Result<()> {
+        return instructions::transfer_position::handler(ctx);
+    }
+
+    /// Set the fee rate of a Whirlpool. The fee rate is expressed in thousandths of a basis point,
+    /// so stable pools can charge fractions of the hundredth of a basis point the legacy fee rate
+    /// is limited to.
+    ///
+    /// ### Authority
+    /// - "fee_authority" - Set authority in the WhirlpoolConfig
+    ///
+    /// ### Parameters
+    /// - `fee_rate` - The rate in thousandths of a basis point charged on swaps.
+    ///
+    /// #### Special Errors
+    /// - `FeeRateMaxExceeded` - If the provided fee_rate exceeds MAX_FEE_RATE.
+    pub fn set_fee_rate(ctx: Context<SetFeeRate>, fee_rate: u32) -> Result<()> {
+        return instructions::set_fee_rate::handler(ctx, fee_rate);
+    }
+
+    /// Reallocate a Whirlpool created before the fee rate precision increase to the current
+    /// Whirlpool size and store its legacy fee rate as its fee rate in thousandths of a basis
+    /// point. The fee holidays of its fee schedule are converted to thousandths of a basis point
+    /// in the same instruction. A no-op for Whirlpools that are already migrated.
+    ///
+    /// ### Authority
+    /// - None. Anyone can migrate a Whirlpool, the funder pays the rent of the added bytes.
+    ///
+    /// #### Special Errors
+    /// - `InvalidFeeScheduleAccount` - The Whirlpool has a fee schedule and it is not provided.
+    pub fn migrate_fee_rate_precision(ctx: Context<MigrateFeeRatePrecision>) -> Result<()> {
+        return instructions::migrate_fee_rate_precision::handler(ctx);
+    }
+
+    /// Reallocate a tick array initialized before the paging hints, the initialized tick bitmap
+    /// and the schema version were added to the current TickArray size. The bitmap is rebuilt
+    /// from the ticks of the array. Tick arrays with the legacy layout cannot be used by swaps or
+    /// liquidity instructions until they are migrated. A no-op for tick arrays that are already
+    /// migrated.
+    ///
+    /// ### Authority
+    /// - None. Anyone can migrate a tick array, the funder pays the rent of the added bytes.
+    ///
+    /// ### Remaining Accounts
+    /// - Up to two migrated neighbouring tick arrays to link the tick array to, for the paging
+    ///   hints.
+    ///
+    /// #### Special Errors
+    /// - `InvalidTickArrayLink` - A provided neighbour is not adjacent to the tick array or still
+    ///                            has the legacy layout.
+    /// - `ForeignProgramAccount` - A provided neighbour is owned by the upstream Whirlpool program.
+    pub fn migrate_tick_array<'info>(
+        ctx: Context<'_, '_, 'info, 'info, MigrateTickArray<'info>>,
+    ) -> Result<()> {
+        return instructions::migrate_tick_array::handler(ctx);
+    }
+
+    /// Reallocate a position opened before the schema version and the other fields following
+    /// its reward infos were added to the current Position size. The added fields are zeroed,
+    /// so the position reads as schema version 0 with its opt-ins disabled, and its liquidity is
+    /// counted in the total liquidity of its Whirlpool. Positions with the legacy layout cannot
+    /// be used until they are migrated. A no-op for positions that are already migrated.
+    ///
+    /// ### Authority
+    /// - None. Anyone can migrate a position, the funder pays the rent of the added bytes.
+    ///
+    /// #### Special Errors
+    /// - `ConstraintHasOne` - The Whirlpool is not the one of the position.
+    pub fn migrate_position(ctx: Context<MigratePosition>) -> Result<()> {
+        return instructions::migrate_position::handler(ctx);
+    }
+
+    /// Mark the total liquidity of a Whirlpool created before it was tracked as complete, once
+    /// every position of the Whirlpool is migrated. Rewards can only accrue to all liquidity of
+    /// Whirlpools with a complete total liquidity.
+    ///
+    /// ### Authority
+    /// - "fee_authority" - Set authority in the WhirlpoolConfig
+    pub fn mark_total_liquidity_complete(ctx: Context<MarkTotalLiquidityComplete>) -> Result<()> {
+        return instructions::mark_total_liquidity_complete::handler(ctx);
+    }
+
+    /// Set the URI template of the metadata of position NFTs minted for the Whirlpools of a
+    /// WhirlpoolsConfig. `{mint}` in the template is replaced by the position mint, so the
+    /// metadata can be served per position. Existing position NFTs keep their URI until their
+    /// holder refreshes them with `refresh_position_nft`.
+    ///
+    /// ### Authority
+    /// - "fee_authority" - Set authority in the WhirlpoolConfig
+    ///
+    /// ### Parameters
+    /// - `uri_template` - The metadata URI template, or an empty string for the default URI.
+    ///
+    /// #### Special Errors
+    /// - `InvalidPositionMetadataUriTemplate` - The template is longer than
+    ///                                          MAX_POSITION_METADATA_URI_TEMPLATE_LEN or holds
+    ///                                          the mint placeholder more than once.
+    pub fn set_position_metadata_uri_template(
+        ctx: Context<SetPositionMetadataUriTemplate>,
+        uri_template: String,
+    ) -> Result<()> {
+        return instructions::set_position_metadata_uri_template::handler(ctx, uri_template);
+    }
+
+    /// Initializes a keeper tip escrow for the cranks of a Whirlpool. Keepers passing the escrow
+    /// to `refresh_exchange_rate` or `refresh_tick_cache` are paid the base tip and refunded the
+    /// priority fee of their transaction up to a cap, so keepers are paid inside the program.
+    ///
+    /// ### Authority
+    /// - "authority" - The authority allowed to change the tips and withdraw the unused lamports
+    ///                 of the escrow.
+    ///
+    /// ### Parameters
+    /// - `base_tip` - The lamports paid to the keeper of each crank.
+    /// - `max_priority_fee_tip` - The maximum lamports of priority fee refunded to the keeper of
+    ///                            each crank.
+    pub fn initialize_keeper_tip_escrow(
+        ctx: Context<InitializeKeeperTipEscrow>,
+        base_tip: u64,
+        max_priority_fee_tip: u64,
+    ) -> Result<()> {
+        return instructions::initialize_keeper_tip_escrow::handler(
+            ctx,
+            base_tip,
+            max_priority_fee_tip,
+        );
+    }
+
+    /// Fund a keeper tip escrow with lamports. Anyone can fund any escrow.
+    ///
+    /// ### Parameters
+    /// - `lamports` - The amount of lamports to transfer from the funder to the escrow.
+    pub fn fund_keeper_tip_escrow(ctx: Context<FundKeeperTipEscrow>, lamports: u64) -> Result<()> {
+        return instructions::fund_keeper_tip_escrow::handler(ctx, lamports);
+    }
+
+    /// Set the tips a keeper tip escrow pays to the keepers of each crank.
+    ///
+    /// ### Authority
+    /// - "authority" - The authority of the keeper tip escrow.
+    ///
+    /// ### Parameters
+    /// - `base_tip` - The lamports paid to the keeper of each crank.
+    /// - `max_priority_fee_tip` - The maximum lamports of priority fee refunded to the keeper of
+    ///                            each crank.
+    pub fn set_keeper_tips(
+        ctx: Context<SetKeeperTips>,
+        base_tip: u64,
+        max_priority_fee_tip: u64,
+    ) -> Result<()> {
+        return instructions::set_keeper_tips::handler(ctx, base_tip, max_priority_fee_tip);
+    }
+
+    /// Withdraw unused lamports from a keeper tip escrow. The escrow keeps its own rent exemption.
+    ///
+    /// ### Authority
+    /// - "authority" - The authority of the keeper tip escrow.
+    ///
+    /// ### Parameters
+    /// - `lamports` - The amount of lamports to transfer to the destination.
+    ///
+    /// #### Special Errors
+    /// - `KeeperTipEscrowInsufficientFunds` - The escrow holds fewer lamports above its rent
+    ///                                        exemption.
+    pub fn withdraw_keeper_tip_escrow(
+        ctx: Context<WithdrawKeeperTipEscrow>,
+        lamports: u64,
+    ) -> Result<()> {
+        return instructions::withdraw_keeper_tip_escrow::handler(ctx, lamports);
+    }
+
+    /// Propose a new emergency authority for a WhirlpoolsConfig. The emergency authority can only
+    /// pause pools and freeze admin parameter changes; it cannot move funds. The role is only
+    /// transferred once the proposed authority accepts. Proposing the default pubkey cancels a
+    /// pending proposal.
+    ///
+    /// ### Authority
+    /// - "authority" - The fee authority while no emergency authority is set, the current
+    ///                 emergency authority afterwards.
+    ///
+    /// #### Special Errors
+    /// - `InvalidEmergencyAuthority` - The signer may not propose the emergency authority.
+    pub fn propose_emergency_authority(ctx: Context<ProposeEmergencyAuthority>) -> Result<()> {
+        return instructions::propose_emergency_authority::handler(ctx);
+    }
+
+    /// Accept a proposed emergency authority transfer and become the emergency authority of the
+    /// WhirlpoolsConfig.
+    ///
+    /// ### Authority
+    /// - "pending_emergency_authority" - The emergency authority proposed by the current emergency
+    ///                                   authority, or by the fee authority while none is set.
+    ///
+    /// #### Special Errors
+    /// - `NoPendingAuthority` - No emergency authority transfer has been proposed.
+    pub fn accept_emergency_authority(ctx: Context<AcceptEmergencyAuthority>) -> Result<()> {
+        return instructions::accept_emergency_authority::handler(ctx);
+    }
+
+    /// Pause or unpause a Whirlpool. A paused pool rejects swaps and liquidity increases, while
+    /// liquidity removals and fee and reward collection stay possible.
+    ///
+    /// ### Authority
+    /// - "emergency_authority" - The emergency authority of the WhirlpoolsConfig.
+    ///
+    /// ### Parameters
+    /// - `paused` - Whether the pool is paused.
+    pub fn set_pool_paused(ctx: Context<SetPoolPaused>, paused: bool) -> Result<()> {
+        return instructions::set_pool_paused::handler(ctx, paused);
+    }
+
+    /// Freeze or unfreeze the admin parameter setters of a WhirlpoolsConfig. While frozen, the fee
+    /// authority cannot change pool or config parameters.
+    ///
+    /// ### Authority
+    /// - "emergency_authority" - The emergency authority of the WhirlpoolsConfig.
+    ///
+    /// ### Parameters
+    /// - `admin_frozen` - Whether admin parameter changes are frozen.
+    pub fn set_admin_frozen(ctx: Context<SetAdminFrozen>, admin_frozen: bool) -> Result<()> {
+        return instructions::set_admin_frozen::handler(ctx, admin_frozen);
+    }
+
+    /// Freeze a position and write an attestation of its owner, range, liquidity and amounts
+    /// owed for a bridge program. The fees and rewards of the position are updated first. Until
+    /// the bridge authority releases the attestation, the position cannot be modified, collected,
+    /// transferred or closed.
+    ///
+    /// ### Authority
+    /// - "position_authority" - The authority that owns the position token.
+    ///
+    /// #### Special Errors
+    /// - `PositionFrozen` - The position is already frozen.
+    /// - `TickNotFound` - Provided tick array account does not contain the tick for this position.
+    pub fn freeze_and_attest_position(ctx: Context<FreezeAndAttestPosition>) -> Result<()> {
+        return instructions::freeze_and_attest_position::handler(ctx);
+    }
+
+    /// Unfreeze an attested position and close its attestation, returning its rent to the
+    /// receiver.
+    ///
+    /// ### Authority
+    /// - "bridge_authority" - The bridge authority recorded in the attestation.
+    pub fn release_position_attestation(ctx: Context<ReleasePositionAttestation>) -> Result<()> {
+        return instructions::release_position_attestation::handler(ctx);
+    }
+
+    /// Remove the same share of liquidity from every position of a bundle of positions of a
+    /// Whirlpool in one instruction. Positions whose share rounds down to zero liquidity are
+    /// skipped.
+    ///
+    /// ### Authority
+    /// - "position_authority" - The authority that owns the position tokens, or the withdraw
+    ///                          authority of each position, withdrawing into token accounts of
+    ///                          its withdraw destination.
+    ///
+    /// ### Remaining Accounts
+    /// For each position, in groups of five:
+    /// - `[0]` - The position, writable.
+    /// - `[1]` - The position token account.
+    /// - `[2]` - The tick array containing the lower tick of the position, writable.
+    /// - `[3]` - The tick array containing the upper tick of the position, writable.
+    /// - `[4]` - The liquidity time accumulator of the position owner, writable. The program id
+    ///           instead forfeits the liquidity time of the position since its last accrual.
+    ///
+    /// ### Parameters
+    /// - `bps` - The share of the liquidity of each position to remove, in basis points.
+    /// - `token_min_a` - The minimum amount of tokenA the user is willing to withdraw in total.
+    /// - `token_min_b` - The minimum amount of tokenB the user is willing to withdraw in total.
+    ///
+    /// #### Special Errors
+    /// - `InvalidProRataRate` - `bps` is 0 or above 10000.
+    /// - `RemainingAccountsMissing` - No positions, or an incomplete group of accounts, were
+    ///                                provided.
+    /// - `PositionMismatch` - A position belongs to another Whirlpool.
+    /// - `InvalidWithdrawDestination` - The withdraw authority of a position withdraws into token
+    ///                                  accounts not owned by its withdraw destination.
+    /// - `InvalidLiquidityTimeAccumulator` - An accumulator is not the one of the Whirlpool and
+    ///                                       the position owner.
+    /// - `TokenMinSubceeded` - The total withdrawn is below `token_min_a` or `token_min_b`.
+    pub fn decrease_bundle_liquidity_pro_rata<'info>(
+        ctx: Context<'_, '_, 'info, 'info, DecreaseBundleLiquidityProRata<'info>>,
+        bps: u16,
+        token_min_a: u64,
+        token_min_b: u64,
+    ) -> Result<()> {
+        return instructions::decrease_bundle_liquidity_pro_rata::handler(
+            ctx,
+            bps,
+            token_min_a,
+            token_min_b,
+        );
+    }
+
+    /// Open a ladder of positions splitting a band into equally wide adjacent ranges, one per
+    /// liquidity weight, and deposit liquidity into them split by the weights.
+    ///
+    /// ### Authority
+    /// - "funder" - Pays the rent of the positions and deposits the tokens.
+    ///
+    /// ### Remaining Accounts
+    /// For each position, from the lowest range up, in groups of five:
+    /// - `[0]` - The position PDA of the position mint, writable.
+    /// - `[1]` - The position mint, a new keypair, signer and writable.
+    /// - `[2]` - The associated token account of the owner for the position mint, writable.
+    /// - `[3]` - The tick array containing the lower tick of the range, writable.
+    /// - `[4]` - The tick array containing the upper tick of the range, writable.
+    ///
+    /// ### Parameters
+    /// - `tick_lower_index` - The lower tick index of the band.
+    /// - `tick_upper_index` - The upper tick index of the band.
+    /// - `liquidity_amount` - The total liquidity to deposit across the positions.
+    /// - `liquidity_weights` - The relative share of the liquidity of each position.
+    /// - `token_max_a` - The maximum amount of tokenA the funder is willing to deposit in total.
+    /// - `token_max_b` - The maximum amount of tokenB the funder is willing to deposit in total.
+    ///
+    /// #### Special Errors
+    /// - `InvalidLadder` - There are no or more than 8 weights, the weights sum to 0, or the band
+    ///                     does not split into equal ranges that are multiples of the tick
+    ///                     spacing.
+    /// - `RemainingAccountsMissing` - The remaining accounts do not match the number of weights.
+    /// - `PositionMismatch` - A position account is not the PDA of its position mint.
+    /// - `TokenMaxExceeded` - The total deposited exceeds `token_max_a` or `token_max_b`.
+    pub fn open_ladder_positions<'info>(
+        ctx: Context<'_, '_, 'info, 'info, OpenLadderPositions<'info>>,
+        tick_lower_index: i32,
+        tick_upper_index: i32,
+        liquidity_amount: u128,
+        liquidity_weights: Vec<u16>,
+        token_max_a: u64,
+        token_max_b: u64,
+    ) -> Result<()> {
+        return instructions::open_ladder_positions::handler(
+            ctx,
+            tick_lower_index,
+            tick_upper_index,
+            liquidity_amount,
+            liquidity_weights,
+            token_max_a,
+            token_max_b,
+        );
+    }
+
+    /// Set the tenure boost of a reward, so positions that stay in range earn more of it. Positions
+    /// earn the base rate of the rewards they accrue, rising linearly to the full rewards over the
+    /// boost period of tenure. Tenure starts at the first checkpoint finding the position in range,
+    /// decreasing liquidity restarts it and a checkpoint finding the position out of range clears
+    /// it. The range is only sampled at checkpoints: a position that leaves the range and returns
+    /// between two checkpoints keeps its tenure. The withheld rewards stay in the reward vault.
+    ///
+    /// ### Authority
+    /// - "reward_authority" - The authority of the specified reward-index in this Whirlpool.
+    ///
+    /// ### Parameters
+    /// - `reward_index` - The reward index that we'd like to change.
+    /// - `boost_period` - The seconds of tenure to earn the full rewards, 0 to disable the tenure
+    ///                    boost.
+    /// - `boost_base_rate` - The share of the rewards earned without tenure, in basis points.
+    ///
+    /// #### Special Errors
+    /// - `InvalidRewardIndex` - The reward index exceeds NUM_REWARDS.
+    /// - `InvalidRewardTenureBoost` - The base rate exceeds REWARD_TENURE_BOOST_RATE_MUL_VALUE.
+    pub fn set_reward_tenure_boost(
+        ctx: Context<SetRewardTenureBoost>,
+        reward_index: u8,
+        boost_period: u32,
+        boost_base_rate: u16,
+    ) -> Result<()> {
+        return instructions::set_reward_tenure_boost::handler(
+            ctx,
+            reward_index,
+            boost_period,
+            boost_base_rate,
+        );
+    }
+
+    /// Initializes the collect routing of an owner, mapping mints to the token accounts fees and
+    /// rewards of that mint are preferably collected into.
+    ///
+    /// ### Authority
+    /// - "owner" - The owner the routing is derived from.
+    pub fn initialize_collect_routing(ctx: Context<InitializeCollectRouting>) -> Result<()> {
+        return instructions::initialize_collect_routing::handler(ctx);
+    }
+
+    /// Route the fees and rewards of a mint to a destination token account. Session keys of the
+    /// owner may collect into routed destinations when the collect routing is passed, so harvests
+    /// of many pools land in the right treasury accounts.
+    ///
+    /// ### Authority
+    /// - "owner" - The owner of the collect routing.
+    ///
+    /// ### Parameters
+    /// - `route_index` - The route slot to set.
+    /// - `mint` - The mint to route, or the default pubkey to clear the slot.
+    /// - `destination` - The token account to collect the mint into.
+    ///
+    /// #### Special Errors
+    /// - `InvalidCollectRoute` - The slot exceeds MAX_COLLECT_ROUTES, the mint is routed in
+    ///                           another slot or the destination is the default pubkey.
+    pub fn set_collect_route(
+        ctx: Context<SetCollectRoute>,
+        route_index: u8,
+        mint: Pubkey,
+        destination: Pubkey,
+    ) -> Result<()> {
+        return instructions::set_collect_route::handler(ctx, route_index, mint, destination);
+    }
+
+    /// Set whether the emissions of a reward are per unit of liquidity per second instead of
+    /// split among the liquidity it accrues to, so the reward rate of a position does not depend
+    /// on other liquidity entering or leaving the Whirlpool. The emissions per second of the
+    /// reward are then read per unit of liquidity, up to the liquidity cap. Liquidity beyond the
+    /// cap splits the emissions of the capped liquidity, so total emissions stay bounded.
+    ///
+    /// ### Authority
+    /// - "reward_authority" - The authority of the specified reward-index in this Whirlpool.
+    ///
+    /// ### Parameters
+    /// - `reward_index` - The reward index that we'd like to change.
+    /// - `emissions_liquidity_cap` - Liquidity the emissions per unit of liquidity apply to at
+    ///   most, or 0 to split emissions among the liquidity.
+    ///
+    /// #### Special Errors
+    /// - `InvalidRewardIndex` - The reward index exceeds NUM_REWARDS.
+    /// - `RewardVaultAmountInsufficient` - The reward vault holds less than a day of the
+    ///   emissions of the capped liquidity.
+    pub fn set_reward_emission_mode(
+        ctx: Context<SetRewardMode>,
+        reward_index: u8,
+        emissions_liquidity_cap: u128,
+    ) -> Result<()> {
+        return instructions::set_reward_emission_mode::handler(
+            ctx,
+            reward_index,
+            emissions_liquidity_cap,
+        );
     }
 }
@@ -0,0 +1,238 @@
+//! Read-only fee accounting for off-chain consumers.
+//!
+//! Fee growth only changes on swaps, which always bring the global fee growth of a Whirlpool up to
+//! date. The fee growth inside of any tick range can therefore be computed from the loaded pool and
+//! tick array accounts alone, without sending an `update_fees_and_rewards` transaction first.
+use anchor_lang::prelude::*;
+
+use crate::{
+    manager::tick_manager,
+    state::{Position, TickArray, TickArrayRefs, Whirlpool},
+};
+
+/// Computes the fee growth inside of a tick range, as the Q64.64 fee growth per unit of liquidity
+/// for token A and token B.
+///
+/// # Parameters
+/// - `whirlpool` - The Whirlpool the tick range belongs to
+/// - `tick_array_lower` - The tick array holding `tick_lower_index`
+/// - `tick_array_upper` - The tick array holding `tick_upper_index`
+///
+/// # Errors
+/// - `TickNotFound`: - A tick index is not an initializable tick held by its tick array.
+pub fn fee_growths_inside(
+    whirlpool: &Whirlpool,
+    tick_array_lower: &TickArray,
+    tick_lower_index: i32,
+    tick_array_upper: &TickArray,
+    tick_upper_index: i32,
+) -> Result<(u128, u128)> {
+    let tick_lower = tick_array_lower.get_tick(tick_lower_index, whirlpool.tick_spacing)?;
+    let tick_upper = tick_array_upper.get_tick(tick_upper_index, whirlpool.tick_spacing)?;
+
+    Ok(tick_manager::next_fee_growths_inside(
+        whirlpool.tick_current_index,
+        tick_lower,
+        tick_lower_index,
+        tick_upper,
+        tick_upper_index,
+        whirlpool.fee_growth_global_a,
+        whirlpool.fee_growth_global_b,
+    ))
+}
+
+/// Computes the fees a position could collect for token A and token B, including the fees
+/// accrued since the position was last updated.
+pub fn unclaimed_fees(
+    whirlpool: &Whirlpool,
+    position: &Position,
+    tick_array_lower: &TickArray,
+    tick_array_upper: &TickArray,
+) -> Result<(u64, u64)> {
+    position.pending_fees(
+        whirlpool,
+        &TickArrayRefs {
+            lower: tick_array_lower,
+            upper: tick_array_upper,
+        },
+    )
+}
+
+#[cfg(test)]
+mod fee_accounting_tests {
+    use super::*;
+    use crate::{
+        errors::ErrorCode,
+        math::Q64_RESOLUTION,
+        state::{
+            position_builder::PositionBuilder, whirlpool_builder::WhirlpoolBuilder,
+            PositionRewardInfo, TickUpdate, WhirlpoolRewardInfo,
+        },
+    };
+
+    const TICK_SPACING: u16 = 8;
+
+    fn tick_array_with_tick(
+        start_tick_index: i32,
+        tick_index: i32,
+        fee_growth_outside_a: u128,
+        fee_growth_outside_b: u128,
+    ) -> TickArray {
+        let mut tick_array = TickArray {
+            start_tick_index,
+            ..TickArray::default()
+        };
+        tick_array
+            .update_tick(
+                tick_index,
+                TICK_SPACING,
+                &TickUpdate {
+                    initialized: true,
+                    liquidity_gross: 1000,
+                    fee_growth_outside_a,
+                    fee_growth_outside_b,
+                    ..Default::default()
+                },
+            )
+            .unwrap();
+        tick_array
+    }
+
+    fn whirlpool(tick_current_index: i32) -> Whirlpool {
+        WhirlpoolBuilder::new()
+            .tick_spacing(TICK_SPACING)
+            .tick_current_index(tick_current_index)
+            .fee_growth_global_a(100 << Q64_RESOLUTION)
+            .fee_growth_global_b(200 << Q64_RESOLUTION)
+            .build()
+    }
+
+    #[test]
+    fn test_fee_growths_inside_current_tick_in_range() {
+        let tick_array_lower = tick_array_with_tick(-704, -16, 10 << Q64_RESOLUTION, 0);
+        let tick_array_upper = tick_array_with_tick(0, 16, 0, 20 << Q64_RESOLUTION);
+
+        let fee_growths =
+            fee_growths_inside(&whirlpool(0), &tick_array_lower, -16, &tick_array_upper, 16)
+                .unwrap();
+
+        assert_eq!(fee_growths, (90 << Q64_RESOLUTION, 180 << Q64_RESOLUTION));
+    }
+
+    #[test]
+    fn test_fee_growths_inside_current_tick_below_range() {
+        let mut tick_array =
+            tick_array_with_tick(0, 16, 60 << Q64_RESOLUTION, 50 << Q64_RESOLUTION);
+        tick_array
+            .update_tick(
+                32,
+                TICK_SPACING,
+                &TickUpdate {
+                    initialized: true,
+                    liquidity_gross: 1000,
+                    fee_growth_outside_a: 40 << Q64_RESOLUTION,
+                    fee_growth_outside_b: 20 << Q64_RESOLUTION,
+                    ..Default::default()
+                },
+            )
+            .unwrap();
+
+        let fee_growths =
+            fee_growths_inside(&whirlpool(-8), &tick_array, 16, &tick_array, 32).unwrap();
+
+        assert_eq!(fee_growths, (20 << Q64_RESOLUTION, 30 << Q64_RESOLUTION));
+    }
+
+    #[test]
+    fn test_fee_growths_inside_tick_not_in_array() {
+        let tick_array = tick_array_with_tick(0, 16, 0, 0);
+
+        let result = fee_growths_inside(&whirlpool(0), &tick_array, -16, &tick_array, 16);
+
+        assert_eq!(result.unwrap_err(), ErrorCode::TickNotFound.into());
+    }
+
+    #[test]
+    fn test_unclaimed_fees() {
+        let tick_array_lower = tick_array_with_tick(-704, -16, 10 << Q64_RESOLUTION, 0);
+        let tick_array_upper = tick_array_with_tick(0, 16, 0, 20 << Q64_RESOLUTION);
+        let position = PositionBuilder::new(-16, 16)
+            .liquidity(100)
+            .fee_owed_a(5)
+            .fee_owed_b(7)
+            .fee_growth_checkpoint_a(80 << Q64_RESOLUTION)
+            .fee_growth_checkpoint_b(170 << Q64_RESOLUTION)
+            .build();
+
+        let fees = unclaimed_fees(
+            &whirlpool(0),
+            &position,
+            &tick_array_lower,
+            &tick_array_upper,
+        )
+        .unwrap();
+
+        assert_eq!(fees, (5 + 100 * 10, 7 + 100 * 10));
+    }
+
+    #[test]
+    fn test_unclaimed_fees_position_without_liquidity() {
+        let tick_array_lower = tick_array_with_tick(-704, -16, 10 << Q64_RESOLUTION, 0);
+        let tick_array_upper = tick_array_with_tick(0, 16, 0, 20 << Q64_RESOLUTION);
+        let position = PositionBuilder::new(-16, 16)
+            .fee_owed_a(5)
+            .fee_owed_b(7)
+            .build();
+
+        let fees = unclaimed_fees(
+            &whirlpool(0),
+            &position,
+            &tick_array_lower,
+            &tick_array_upper,
+        )
+        .unwrap();
+
+        assert_eq!(fees, (5, 7));
+    }
+
+    #[test]
+    fn test_pending_rewards_include_emissions_since_last_update() {
+        let tick_array_lower = tick_array_with_tick(-704, -16, 0, 0);
+        let tick_array_upper = tick_array_with_tick(0, 16, 0, 0);
+        let whirlpool = WhirlpoolBuilder::new()
+            .tick_spacing(TICK_SPACING)
+            .liquidity(100)
+            .reward_info(
+                0,
+                WhirlpoolRewardInfo {
+                    mint: Pubkey::new_unique(),
+                    emissions_per_second_x64: 10 << Q64_RESOLUTION,
+                    ..Default::default()
+                },
+            )
+            .build();
+        let position = PositionBuilder::new(-16, 16)
+            .liquidity(100)
+            .reward_info(
+                0,
+                PositionRewardInfo {
+                    amount_owed: 3,
+                    ..Default::default()
+                },
+            )
+            .build();
+
+        let rewards = position
+            .pending_rewards(
+                &whirlpool,
+                &TickArrayRefs {
+                    lower: &tick_array_lower,
+                    upper: &tick_array_upper,
+                },
+                10,
+            )
+            .unwrap();
+
+        assert_eq!(rewards, [3 + 100, 0, 0]);
+    }
+}
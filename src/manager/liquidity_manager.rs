@@ -1,21 +1,27 @@
 use super::{
-    position_manager::next_position_modify_liquidity_update,
+    position_manager::{apply_reward_tenure_boost, next_position_modify_liquidity_update},
     tick_manager::{
         next_fee_growths_inside, next_reward_growths_inside, next_tick_modify_liquidity_update,
+        verify_boundary_ticks_initialized,
     },
     whirlpool_manager::{next_whirlpool_liquidity, next_whirlpool_reward_infos},
 };
 use crate::{
     errors::ErrorCode,
-    math::{get_amount_delta_a, get_amount_delta_b, sqrt_price_from_tick_index},
+    math::{
+        add_liquidity_delta, get_amount_delta_a, get_amount_delta_b,
+        get_amount_delta_rounding_dust, get_liquidity_from_amount_a, get_liquidity_from_amount_b,
+        mul_u256, sqrt_price_from_tick_index, U256Muldiv,
+    },
     state::*,
 };
 use anchor_lang::prelude::*;
-use anchor_lang::prelude::{AccountLoader, ProgramError};
+use anchor_lang::prelude::AccountLoader;
 
 #[cfg_attr(not(target_arch = "bpf"),derive(Debug))]
 pub struct ModifyLiquidityUpdate {
     pub whirlpool_liquidity: u128,
+    pub whirlpool_total_liquidity: u128,
     pub tick_lower_update: TickUpdate,
     pub tick_upper_update: TickUpdate,
     pub reward_infos: [WhirlpoolRewardInfo; NUM_REWARDS],
@@ -97,6 +103,8 @@ fn _calculate_modify_liquidity(
         return Err(ErrorCode::LiquidityZero.into());
     }
 
+    verify_boundary_ticks_initialized(position.liquidity, tick_lower, tick_upper)?;
+
     let next_reward_infos = next_whirlpool_reward_infos(whirlpool, timestamp)?;
 
     let next_global_liquidity = next_whirlpool_liquidity(
@@ -106,6 +114,8 @@ fn _calculate_modify_liquidity(
         liquidity_delta,
     )?;
 
+    let next_total_liquidity = add_liquidity_delta(whirlpool.total_liquidity, liquidity_delta)?;
+
     let tick_lower_update = next_tick_modify_liquidity_update(
         tick_lower,
         tick_lower_index,
@@ -138,8 +148,8 @@ fn _calculate_modify_liquidity(
         whirlpool.fee_growth_global_b,
     );
 
-    let reward_growths_inside = next_reward_growths_inside(
-        whirlpool.tick_current_index,
+    let reward_growths_inside = position_reward_growths_inside(
+        whirlpool,
         tick_lower,
         tick_lower_index,
         tick_upper,
@@ -147,16 +157,24 @@ fn _calculate_modify_liquidity(
         &next_reward_infos,
     );
 
-    let position_update = next_position_modify_liquidity_update(
+    let mut position_update = next_position_modify_liquidity_update(
         position,
         liquidity_delta,
         fee_growth_inside_a,
         fee_growth_inside_b,
         &reward_growths_inside,
     )?;
+    apply_reward_tenure_boost(
+        whirlpool,
+        position,
+        &mut position_update,
+        liquidity_delta,
+        timestamp,
+    );
 
     Ok(ModifyLiquidityUpdate {
         whirlpool_liquidity: next_global_liquidity,
+        whirlpool_total_liquidity: next_total_liquidity,
         reward_infos: next_reward_infos,
         position_update,
         tick_lower_update,
@@ -164,6 +182,160 @@ fn _calculate_modify_liquidity(
     })
 }
 
+fn position_reward_growths_inside(
+    whirlpool: &Whirlpool,
+    tick_lower: &Tick,
+    tick_lower_index: i32,
+    tick_upper: &Tick,
+    tick_upper_index: i32,
+    next_reward_infos: &[WhirlpoolRewardInfo; NUM_REWARDS],
+) -> [u128; NUM_REWARDS] {
+    let mut reward_growths_inside = next_reward_growths_inside(
+        whirlpool.tick_current_index,
+        tick_lower,
+        tick_lower_index,
+        tick_upper,
+        tick_upper_index,
+        next_reward_infos,
+    );
+    // Rewards accruing to all liquidity grow for a position wherever its range is.
+    for i in 0..NUM_REWARDS {
+        if whirlpool.reward_accrues_to_all_liquidity[i] && next_reward_infos[i].initialized() {
+            reward_growths_inside[i] = next_reward_infos[i].growth_global_x64;
+        }
+    }
+    reward_growths_inside
+}
+
+// Calculates the fee and reward owed of a position up to the given timestamp without modifying
+// any account. Unlike `calculate_fee_and_reward_growths`, positions without liquidity are allowed
+// and simply keep their owed amounts.
+pub fn calculate_pending_position_update(
+    whirlpool: &Whirlpool,
+    position: &Position,
+    tick_arrays: &TickArrayRefs,
+    timestamp: u64,
+) -> Result<PositionUpdate> {
+    let tick_lower = tick_arrays
+        .lower
+        .get_tick(position.tick_lower_index, whirlpool.tick_spacing)?;
+    let tick_upper = tick_arrays
+        .upper
+        .get_tick(position.tick_upper_index, whirlpool.tick_spacing)?;
+    verify_boundary_ticks_initialized(position.liquidity, tick_lower, tick_upper)?;
+
+    let next_reward_infos = next_whirlpool_reward_infos(whirlpool, timestamp)?;
+
+    let (fee_growth_inside_a, fee_growth_inside_b) = next_fee_growths_inside(
+        whirlpool.tick_current_index,
+        tick_lower,
+        position.tick_lower_index,
+        tick_upper,
+        position.tick_upper_index,
+        whirlpool.fee_growth_global_a,
+        whirlpool.fee_growth_global_b,
+    );
+    let reward_growths_inside = position_reward_growths_inside(
+        whirlpool,
+        tick_lower,
+        position.tick_lower_index,
+        tick_upper,
+        position.tick_upper_index,
+        &next_reward_infos,
+    );
+
+    let mut position_update = next_position_modify_liquidity_update(
+        position,
+        0,
+        fee_growth_inside_a,
+        fee_growth_inside_b,
+        &reward_growths_inside,
+    )?;
+    apply_reward_tenure_boost(whirlpool, position, &mut position_update, 0, timestamp);
+    Ok(position_update)
+}
+
+// Calculates the state changes of removing liquidity from a position with only tick and pool
+// liquidity math. Fee and reward growths are not updated, so the position forfeits the fees and
+// rewards accrued by the removed liquidity since its last checkpoint.
+pub fn calculate_emergency_decrease_liquidity(
+    whirlpool: &Whirlpool,
+    position: &Position,
+    tick_array_lower: &TickArray,
+    tick_array_upper: &TickArray,
+    liquidity_delta: i128,
+) -> Result<ModifyLiquidityUpdate> {
+    let tick_lower =
+        tick_array_lower.get_tick(position.tick_lower_index, whirlpool.tick_spacing)?;
+    let tick_upper =
+        tick_array_upper.get_tick(position.tick_upper_index, whirlpool.tick_spacing)?;
+
+    _calculate_emergency_decrease_liquidity(
+        whirlpool,
+        position,
+        tick_lower,
+        tick_upper,
+        liquidity_delta,
+    )
+}
+
+fn _calculate_emergency_decrease_liquidity(
+    whirlpool: &Whirlpool,
+    position: &Position,
+    tick_lower: &Tick,
+    tick_upper: &Tick,
+    liquidity_delta: i128,
+) -> Result<ModifyLiquidityUpdate> {
+    if liquidity_delta >= 0 {
+        return Err(ErrorCode::LiquidityZero.into());
+    }
+
+    // Decreasing liquidity keeps the growths outside of ticks that stay initialized, the reward
+    // infos are only read when a tick is initialized.
+    let tick_lower_update = next_tick_modify_liquidity_update(
+        tick_lower,
+        position.tick_lower_index,
+        whirlpool.tick_current_index,
+        whirlpool.fee_growth_global_a,
+        whirlpool.fee_growth_global_b,
+        &whirlpool.reward_infos,
+        liquidity_delta,
+        false,
+    )?;
+    let tick_upper_update = next_tick_modify_liquidity_update(
+        tick_upper,
+        position.tick_upper_index,
+        whirlpool.tick_current_index,
+        whirlpool.fee_growth_global_a,
+        whirlpool.fee_growth_global_b,
+        &whirlpool.reward_infos,
+        liquidity_delta,
+        true,
+    )?;
+
+    Ok(ModifyLiquidityUpdate {
+        whirlpool_liquidity: next_whirlpool_liquidity(
+            whirlpool,
+            position.tick_upper_index,
+            position.tick_lower_index,
+            liquidity_delta,
+        )?,
+        whirlpool_total_liquidity: add_liquidity_delta(whirlpool.total_liquidity, liquidity_delta)?,
+        reward_infos: whirlpool.reward_infos,
+        position_update: PositionUpdate {
+            liquidity: add_liquidity_delta(position.liquidity, liquidity_delta)?,
+            fee_growth_checkpoint_a: position.fee_growth_checkpoint_a,
+            fee_owed_a: position.fee_owed_a,
+            fee_growth_checkpoint_b: position.fee_growth_checkpoint_b,
+            fee_owed_b: position.fee_owed_b,
+            reward_infos: position.reward_infos,
+            reward_tenure_boost_start_timestamp: 0,
+        },
+        tick_lower_update,
+        tick_upper_update,
+    })
+}
+
 pub fn calculate_liquidity_token_deltas(
     current_tick_index: i32,
     sqrt_price: u128,
@@ -177,7 +349,7 @@ pub fn calculate_liquidity_token_deltas(
     let mut delta_a: u64 = 0;
     let mut delta_b: u64 = 0;
 
-    let liquidity: u128 = liquidity_delta.abs() as u128;
+    let liquidity: u128 = liquidity_delta.unsigned_abs();
     let round_up = liquidity_delta > 0;
 
     let lower_price = sqrt_price_from_tick_index(position.tick_lower_index);
@@ -198,14 +370,112 @@ pub fn calculate_liquidity_token_deltas(
     Ok((delta_a, delta_b))
 }
 
+// Calculates the portion of a single token deposit to swap into the other token, so that the
+// two amounts match the ratio the position requires at the current price. Fees and price impact
+// of the swap are not accounted for, the deposit leaves what does not fit the ratio undeposited.
+pub fn calculate_zap_swap_amount(
+    current_tick_index: i32,
+    sqrt_price: u128,
+    position: &Position,
+    amount: u64,
+    input_is_a: bool,
+) -> Result<u64> {
+    if current_tick_index < position.tick_lower_index {
+        // current tick below position, the position only holds token A
+        return Ok(if input_is_a { 0 } else { amount });
+    }
+    if current_tick_index >= position.tick_upper_index {
+        // current tick above position, the position only holds token B
+        return Ok(if input_is_a { amount } else { 0 });
+    }
+
+    let lower_price = sqrt_price_from_tick_index(position.tick_lower_index);
+    let upper_price = sqrt_price_from_tick_index(position.tick_upper_index);
+
+    // Value of the tokens per unit of liquidity, in token B:
+    // value_a = (1 / sqrt_price - 1 / upper_price) * sqrt_price^2
+    // value_b = sqrt_price - lower_price
+    let (value_a, _) = mul_u256(upper_price - sqrt_price, sqrt_price)
+        .div(U256Muldiv::new(0, upper_price), false);
+    let value_a = value_a.try_into_u128()?;
+    let value_b = sqrt_price - lower_price;
+    let value_swapped = if input_is_a { value_b } else { value_a };
+
+    let (swap_amount, _) = mul_u256(amount as u128, value_swapped)
+        .div(U256Muldiv::new(0, value_a + value_b), false);
+    Ok(swap_amount.try_into_u128()? as u64)
+}
+
+// Calculates the largest liquidity the given token amounts can provide to the position at the
+// current price.
+pub fn calculate_liquidity_from_amounts(
+    current_tick_index: i32,
+    sqrt_price: u128,
+    position: &Position,
+    amount_a: u64,
+    amount_b: u64,
+) -> Result<u128> {
+    let lower_price = sqrt_price_from_tick_index(position.tick_lower_index);
+    let upper_price = sqrt_price_from_tick_index(position.tick_upper_index);
+
+    Ok(if current_tick_index < position.tick_lower_index {
+        get_liquidity_from_amount_a(lower_price, upper_price, amount_a)?
+    } else if current_tick_index < position.tick_upper_index {
+        get_liquidity_from_amount_a(sqrt_price, upper_price, amount_a)?
+            .min(get_liquidity_from_amount_b(lower_price, sqrt_price, amount_b)?)
+    } else {
+        get_liquidity_from_amount_b(lower_price, upper_price, amount_b)?
+    })
+}
+
+// Records the rounding dust of the token deltas of a liquidity change on the Whirlpool. Deposits
+// are rounded up and withdrawals rounded down, so the dust is always retained by the pool.
+pub fn record_liquidity_rounding_dust(
+    whirlpool: &mut Whirlpool,
+    position: &Position,
+    liquidity_delta: i128,
+) {
+    let liquidity: u128 = liquidity_delta.unsigned_abs();
+
+    let lower_price = sqrt_price_from_tick_index(position.tick_lower_index);
+    let upper_price = sqrt_price_from_tick_index(position.tick_upper_index);
+    let current_price = whirlpool.sqrt_price;
+
+    let (rounding_dust_a, rounding_dust_b) =
+        if whirlpool.tick_current_index < position.tick_lower_index {
+            (
+                get_amount_delta_rounding_dust(lower_price, upper_price, liquidity, true),
+                0,
+            )
+        } else if whirlpool.tick_current_index < position.tick_upper_index {
+            (
+                get_amount_delta_rounding_dust(current_price, upper_price, liquidity, true),
+                get_amount_delta_rounding_dust(lower_price, current_price, liquidity, false),
+            )
+        } else {
+            (
+                0,
+                get_amount_delta_rounding_dust(lower_price, upper_price, liquidity, false),
+            )
+        };
+
+    whirlpool.add_rounding_dust(rounding_dust_a, rounding_dust_b);
+}
+
+/// Write a liquidity update to the whirlpool, the position and its range ticks. `tick_array_upper`
+/// is None if the upper tick is in `tick_array_lower`.
 pub fn sync_modify_liquidity_values<'info>(
     whirlpool: &mut Whirlpool,
     position: &mut Position,
     tick_array_lower: &mut TickArray,
-    tick_array_upper: &mut TickArray,
-    modify_liquidity_update: ModifyLiquidityUpdate,
+    mut tick_array_upper: Option<&mut TickArray>,
+    modify_liquidity_update: &ModifyLiquidityUpdate,
     reward_last_updated_timestamp: u64,
 ) -> Result<()> {
+    #[cfg(feature = "paranoid-checks")]
+    let (whirlpool_liquidity_before, position_liquidity_before) =
+        (whirlpool.liquidity, position.liquidity);
+
     position.update(&modify_liquidity_update.position_update);
 
     tick_array_lower.update_tick(
@@ -214,7 +484,11 @@ pub fn sync_modify_liquidity_values<'info>(
         &modify_liquidity_update.tick_lower_update,
     )?;
 
-    tick_array_upper.update_tick(
+    let tick_array_for_upper = match tick_array_upper {
+        Some(ref mut tick_array_upper) => &mut **tick_array_upper,
+        None => &mut *tick_array_lower,
+    };
+    tick_array_for_upper.update_tick(
         position.tick_upper_index,
         whirlpool.tick_spacing,
         &modify_liquidity_update.tick_upper_update,
@@ -225,6 +499,20 @@ pub fn sync_modify_liquidity_values<'info>(
         modify_liquidity_update.whirlpool_liquidity,
         reward_last_updated_timestamp,
     );
+    whirlpool.update_total_liquidity(modify_liquidity_update.whirlpool_total_liquidity);
+
+    #[cfg(feature = "paranoid-checks")]
+    crate::util::verify_liquidity_sums(
+        whirlpool_liquidity_before,
+        position_liquidity_before,
+        whirlpool,
+        position,
+        tick_array_lower.get_tick(position.tick_lower_index, whirlpool.tick_spacing)?,
+        tick_array_upper
+            .as_deref()
+            .unwrap_or(tick_array_lower)
+            .get_tick(position.tick_upper_index, whirlpool.tick_spacing)?,
+    )?;
 
     Ok(())
 }
@@ -1202,7 +1490,6 @@ mod calculate_modify_liquidity_unit_tests {
                         fee_growth_outside_b: to_x64(20),
                         // 2 = (1 + (100/100)) - 0
                         reward_growths_outside: create_reward_growths(to_x64(2)),
-                        ..Default::default()
                     }
                 );
 
@@ -1261,6 +1548,7 @@ mod calculate_modify_liquidity_unit_tests {
                                 // 8 = 0.83 * 10
                                 8,
                             ),
+                            reward_tenure_boost_start_timestamp: 0,
                         },
                         tick_lower_update: TickUpdate {
                             initialized: true,
@@ -1618,6 +1906,7 @@ mod calculate_modify_liquidity_unit_tests {
                             fee_growth_checkpoint_b: to_x64(50),
                             fee_owed_b: 500,
                             reward_infos: create_position_reward_infos(to_x64(20), 200),
+                            reward_tenure_boost_start_timestamp: 0,
                         },
                         tick_lower_update: TickUpdate {
                             initialized: true,
@@ -1681,6 +1970,7 @@ mod calculate_modify_liquidity_unit_tests {
                             fee_growth_checkpoint_b: to_x64(20),
                             fee_owed_b: 200,
                             reward_infos: create_position_reward_infos(to_x64(3), 30),
+                            reward_tenure_boost_start_timestamp: 0,
                         },
                         tick_lower_update: TickUpdate::default(),
                         tick_upper_update: TickUpdate::default(),
@@ -1724,6 +2014,7 @@ mod calculate_modify_liquidity_unit_tests {
                             fee_growth_checkpoint_b: to_x64(20),
                             fee_owed_b: 200,
                             reward_infos: create_position_reward_infos(to_x64(3), 30),
+                            reward_tenure_boost_start_timestamp: 0,
                         },
                         tick_lower_update: TickUpdate {
                             initialized: true,
@@ -1923,6 +2214,7 @@ mod calculate_modify_liquidity_unit_tests {
                         fee_growth_checkpoint_b: to_x64(20),
                         fee_owed_b: 200,
                         reward_infos: create_position_reward_infos(to_x64(3), 30),
+                        reward_tenure_boost_start_timestamp: 0,
                     },
                     tick_lower_update: TickUpdate {
                         initialized: true,
@@ -2075,6 +2367,7 @@ mod calculate_modify_liquidity_unit_tests {
                         fee_growth_checkpoint_b: to_x64(20),
                         fee_owed_b: 200,
                         reward_infos: create_position_reward_infos(to_x64(3), 30),
+                        reward_tenure_boost_start_timestamp: 0,
                     },
                     tick_lower_update: TickUpdate {
                         initialized: true,
@@ -2227,6 +2520,7 @@ mod calculate_modify_liquidity_unit_tests {
                         fee_growth_checkpoint_b: to_x64(20),
                         fee_owed_b: 200,
                         reward_infos: create_position_reward_infos(to_x64(3), 30),
+                        reward_tenure_boost_start_timestamp: 0,
                     },
                     tick_lower_update: TickUpdate {
                         initialized: true,
@@ -2294,9 +2588,122 @@ mod calculate_modify_liquidity_unit_tests {
         }
     }
 
+    mod emergency_decrease {
+        use crate::{
+            manager::liquidity_manager::_calculate_emergency_decrease_liquidity, state::*,
+            util::*,
+        };
+
+        // Removing liquidity leaves fee and reward checkpoints and the pool reward infos as is.
+        #[test]
+        fn checkpoints_not_updated() {
+            let mut test = LiquidityTestFixture::new(LiquidityTestFixtureInfo {
+                curr_index_loc: CurrIndex::Inside,
+                whirlpool_liquidity: 1000,
+                position_liquidity: 100,
+                tick_lower_liquidity_gross: 100,
+                tick_upper_liquidity_gross: 200,
+                fee_growth_global_a: to_x64(10),
+                fee_growth_global_b: to_x64(20),
+                reward_infos: create_whirlpool_reward_infos(to_x64(1), to_x64(2)),
+            });
+            test.position.fee_owed_a = 5;
+
+            let update = _calculate_emergency_decrease_liquidity(
+                &test.whirlpool,
+                &test.position,
+                &test.tick_lower,
+                &test.tick_upper,
+                -100,
+            )
+            .unwrap();
+
+            assert_eq!(update.whirlpool_liquidity, 900);
+            assert_eq!(update.whirlpool_total_liquidity, 900);
+            assert_eq!(update.reward_infos, test.whirlpool.reward_infos);
+            assert_eq!(update.position_update.liquidity, 0);
+            assert_eq!(update.position_update.fee_owed_a, 5);
+            assert_eq!(
+                update.position_update.fee_growth_checkpoint_a,
+                test.position.fee_growth_checkpoint_a
+            );
+            assert_eq!(update.tick_lower_update, TickUpdate::default());
+            assert_eq!(update.tick_upper_update.liquidity_gross, 100);
+            assert_eq!(update.tick_upper_update.liquidity_net, -100);
+        }
+
+        #[test]
+        fn liquidity_increase_rejected() {
+            let test = LiquidityTestFixture::new(LiquidityTestFixtureInfo {
+                curr_index_loc: CurrIndex::Inside,
+                whirlpool_liquidity: 100,
+                position_liquidity: 100,
+                tick_lower_liquidity_gross: 100,
+                tick_upper_liquidity_gross: 100,
+                fee_growth_global_a: 0,
+                fee_growth_global_b: 0,
+                reward_infos: create_whirlpool_reward_infos(0, 0),
+            });
+            assert!(_calculate_emergency_decrease_liquidity(
+                &test.whirlpool,
+                &test.position,
+                &test.tick_lower,
+                &test.tick_upper,
+                100,
+            )
+            .is_err());
+        }
+    }
+
     mod fees_and_rewards {
         use crate::{manager::liquidity_manager::_calculate_modify_liquidity, state::*, util::*};
 
+        // Rewards flagged to accrue to all liquidity grow for an out-of-range position.
+        #[test]
+        fn reward_accrues_to_out_of_range_position_when_flagged() {
+            let mut test = LiquidityTestFixture::new(LiquidityTestFixtureInfo {
+                curr_index_loc: CurrIndex::Below,
+                whirlpool_liquidity: 0,
+                position_liquidity: 100,
+                tick_lower_liquidity_gross: 100,
+                tick_upper_liquidity_gross: 100,
+                fee_growth_global_a: 0,
+                fee_growth_global_b: 0,
+                reward_infos: create_whirlpool_reward_infos(to_x64(1), 0),
+            });
+            test.whirlpool.reward_accrues_to_all_liquidity = [true, false, false];
+
+            let update = _calculate_modify_liquidity(
+                &test.whirlpool,
+                &test.position,
+                &test.tick_lower,
+                &test.tick_upper,
+                test.position.tick_lower_index,
+                test.position.tick_upper_index,
+                0,
+                100,
+            )
+            .unwrap();
+
+            // 1 = 100 seconds * 1 / 100 total liquidity, nothing accrues to in-range liquidity
+            assert_eq!(
+                WhirlpoolRewardInfo::to_reward_growths(&update.reward_infos),
+                [to_x64(1), 0, 0]
+            );
+            assert_eq!(update.whirlpool_total_liquidity, 100);
+            assert_eq!(
+                update.position_update.reward_infos,
+                [
+                    PositionRewardInfo {
+                        growth_inside_checkpoint: to_x64(1),
+                        amount_owed: 100,
+                    },
+                    PositionRewardInfo::default(),
+                    PositionRewardInfo::default(),
+                ]
+            );
+        }
+
         // Add liquidity to new position, accrue fees and rewards, remove all liquidity.
         // This test checks that accrued fees and rewards are properly accounted even when all
         // liquidity has been removed from a position and the ticks are still initialized.
@@ -2376,6 +2783,7 @@ mod calculate_modify_liquidity_unit_tests {
                     fee_growth_checkpoint_b: to_x64(198), // 220 - 20 - 2
                     fee_owed_b: 2000,
                     reward_infos: create_position_reward_infos(317116300394406928234, 9),
+                    reward_tenure_boost_start_timestamp: 0,
                 }
             );
             test.apply_update(&update, 200);
@@ -2407,6 +2815,7 @@ mod calculate_modify_liquidity_unit_tests {
                         fee_growth_checkpoint_b: to_x64(218), // 240 - 20 - 2
                         fee_owed_b: 5000,
                         reward_infos: create_position_reward_infos(318720365096468628374, 22),
+                        reward_tenure_boost_start_timestamp: 0,
                     },
                     tick_lower_update: TickUpdate {
                         initialized: true,
@@ -2542,6 +2951,7 @@ mod calculate_modify_liquidity_unit_tests {
                             fee_growth_checkpoint_b: to_x64(20),
                             fee_owed_b: 20000,
                             reward_infos: create_position_reward_infos(16769767339735956014, 909),
+                            reward_tenure_boost_start_timestamp: 0,
                         },
                         tick_lower_update: TickUpdate {
                             initialized: true,
@@ -2666,6 +3076,7 @@ mod calculate_modify_liquidity_unit_tests {
                             fee_growth_checkpoint_b: to_x64(20),
                             fee_owed_b: 20000,
                             reward_infos: create_position_reward_infos(16769767339735956014, 909),
+                            reward_tenure_boost_start_timestamp: 0,
                         },
                         tick_lower_update: TickUpdate {
                             initialized: true,
@@ -2801,6 +3212,7 @@ mod calculate_modify_liquidity_unit_tests {
                             fee_growth_checkpoint_b: to_x64(20),
                             fee_owed_b: 20000,
                             reward_infos: create_position_reward_infos(16769767339735956014, 909),
+                            reward_tenure_boost_start_timestamp: 0,
                         },
                         tick_lower_update: TickUpdate {
                             initialized: true,
@@ -2922,6 +3334,7 @@ mod calculate_modify_liquidity_unit_tests {
                             fee_growth_checkpoint_b: to_x64(90),
                             fee_owed_b: 90000,
                             reward_infos: create_position_reward_infos(67079069358943824058, 3636),
+                            reward_tenure_boost_start_timestamp: 0,
                         },
                         tick_lower_update: TickUpdate {
                             initialized: true,
@@ -3038,6 +3451,7 @@ mod calculate_modify_liquidity_unit_tests {
                             fee_growth_checkpoint_b: to_x64(110),
                             fee_owed_b: 110000,
                             reward_infos: create_position_reward_infos(to_x64(5), 5000),
+                            reward_tenure_boost_start_timestamp: 0,
                         },
                         tick_lower_update: TickUpdate {
                             initialized: true,
@@ -3168,6 +3582,7 @@ mod calculate_modify_liquidity_unit_tests {
                             fee_growth_checkpoint_b: to_x64(90),
                             fee_owed_b: 90000,
                             reward_infos: create_position_reward_infos(to_x64(4), 4000),
+                            reward_tenure_boost_start_timestamp: 0,
                         },
                         tick_lower_update: TickUpdate {
                             initialized: true,
@@ -3303,6 +3718,7 @@ mod calculate_modify_liquidity_unit_tests {
                             fee_owed_b: 20000,
                             // 0.909 = 0.909 - (-3) - (0.909 - -2.0909)
                             reward_infos: create_position_reward_infos(16769767339735956014, 909),
+                            reward_tenure_boost_start_timestamp: 0,
                         },
                         tick_lower_update: TickUpdate {
                             initialized: true,
@@ -3430,6 +3846,7 @@ mod calculate_modify_liquidity_unit_tests {
                             fee_growth_checkpoint_b: to_x64(20),
                             fee_owed_b: 20000,
                             reward_infos: create_position_reward_infos(16769767339735956014, 909),
+                            reward_tenure_boost_start_timestamp: 0,
                         },
                         tick_lower_update: TickUpdate {
                             initialized: true,
@@ -3570,6 +3987,7 @@ mod calculate_modify_liquidity_unit_tests {
                             fee_owed_b: 20000,
                             // 0.909 = 0.909 - (-4) - (0.909 - (-3.0909))
                             reward_infos: create_position_reward_infos(16769767339735956014, 909),
+                            reward_tenure_boost_start_timestamp: 0,
                         },
                         tick_lower_update: TickUpdate {
                             initialized: true,
@@ -3597,4 +4015,119 @@ mod calculate_modify_liquidity_unit_tests {
             }
         }
     }
+    // Position with liquidity whose boundary tick lost its initialized state, e.g. after state
+    // corruption. The growths inside would wrap around and inflate the fees owed, so updating the
+    // position fails instead.
+    mod corrupted_boundary_ticks {
+        use crate::{manager::liquidity_manager::_calculate_modify_liquidity, util::*};
+
+        fn calculate_with_ticks(tick_lower_initialized: bool, tick_upper_initialized: bool) {
+            let mut test = LiquidityTestFixture::new(LiquidityTestFixtureInfo {
+                curr_index_loc: CurrIndex::Inside,
+                whirlpool_liquidity: 100,
+                position_liquidity: 10,
+                tick_lower_liquidity_gross: 10,
+                tick_upper_liquidity_gross: 10,
+                fee_growth_global_a: to_x64(100),
+                fee_growth_global_b: to_x64(100),
+                reward_infos: create_whirlpool_reward_infos(to_x64(1), 0),
+            });
+            test.tick_lower.initialized = tick_lower_initialized;
+            test.tick_upper.initialized = tick_upper_initialized;
+            _calculate_modify_liquidity(
+                &test.whirlpool,
+                &test.position,
+                &test.tick_lower,
+                &test.tick_upper,
+                test.position.tick_lower_index,
+                test.position.tick_upper_index,
+                0,
+                100,
+            )
+            .unwrap();
+        }
+
+        #[test]
+        #[should_panic(expected = "BoundaryTickUninitialized")]
+        fn uninitialized_lower_tick() {
+            calculate_with_ticks(false, true);
+        }
+
+        #[test]
+        #[should_panic(expected = "BoundaryTickUninitialized")]
+        fn uninitialized_upper_tick() {
+            calculate_with_ticks(true, false);
+        }
+
+        #[test]
+        fn initialized_ticks() {
+            calculate_with_ticks(true, true);
+        }
+    }
+}
+
+#[cfg(test)]
+mod zap_tests {
+    use super::*;
+
+    fn position(tick_lower_index: i32, tick_upper_index: i32) -> Position {
+        Position {
+            tick_lower_index,
+            tick_upper_index,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_zap_swap_amount_out_of_range() {
+        let sqrt_price = sqrt_price_from_tick_index(0);
+        let above = position(128, 256);
+        assert_eq!(
+            calculate_zap_swap_amount(0, sqrt_price, &above, 1_000, true).unwrap(),
+            0
+        );
+        assert_eq!(
+            calculate_zap_swap_amount(0, sqrt_price, &above, 1_000, false).unwrap(),
+            1_000
+        );
+
+        let below = position(-256, -128);
+        assert_eq!(
+            calculate_zap_swap_amount(0, sqrt_price, &below, 1_000, true).unwrap(),
+            1_000
+        );
+        assert_eq!(
+            calculate_zap_swap_amount(0, sqrt_price, &below, 1_000, false).unwrap(),
+            0
+        );
+    }
+
+    #[test]
+    fn test_zap_swap_amount_in_range_matches_position_ratio() {
+        let sqrt_price = sqrt_price_from_tick_index(0);
+        let position = position(-1280, 640);
+        let amount = 1_000_000;
+
+        // At price 1 the swapped amount of A is received as the same amount of B.
+        let swap_amount =
+            calculate_zap_swap_amount(0, sqrt_price, &position, amount, true).unwrap();
+        let liquidity = calculate_liquidity_from_amounts(
+            0,
+            sqrt_price,
+            &position,
+            amount - swap_amount,
+            swap_amount,
+        )
+        .unwrap();
+        let (delta_a, delta_b) =
+            calculate_liquidity_token_deltas(0, sqrt_price, &position, liquidity as i128).unwrap();
+        assert!(delta_a <= amount - swap_amount && delta_a + 2 >= amount - swap_amount);
+        assert!(delta_b <= swap_amount && delta_b + 2 >= swap_amount);
+
+        // A range mostly below the price holds mostly B.
+        assert!(swap_amount > amount / 2);
+        let swap_amount_b =
+            calculate_zap_swap_amount(0, sqrt_price, &position, amount, false).unwrap();
+        assert!((amount - swap_amount).abs_diff(swap_amount_b) <= 1);
+    }
 }
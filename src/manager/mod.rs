@@ -3,3 +3,9 @@ pub mod position_manager;
 pub mod swap_manager;
 pub mod tick_manager;
 pub mod whirlpool_manager;
+
+#[cfg(not(target_arch = "bpf"))]
+pub mod fee_accounting;
+
+#[cfg(test)]
+mod swap_conformance_tests;
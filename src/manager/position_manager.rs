@@ -1,7 +1,7 @@
 use crate::{
     errors::ErrorCode,
-    math::{add_liquidity_delta, checked_mul_shift_right},
-    state::{Position, PositionUpdate, NUM_REWARDS},
+    math::{add_liquidity_delta, checked_mul_shift_right, REWARD_TENURE_BOOST_RATE_MUL_VALUE},
+    state::{Position, PositionUpdate, Whirlpool, NUM_REWARDS},
 };
 
 pub fn next_position_modify_liquidity_update(
@@ -53,14 +53,80 @@ pub fn next_position_modify_liquidity_update(
     Ok(update)
 }
 
+// Share of the rewards paid to a position with `tenure_seconds` of tenure, in basis points. Rises
+// linearly from the base rate to the full rewards over the boost period.
+pub fn reward_tenure_boost_rate(
+    boost_period: u32,
+    boost_base_rate: u16,
+    tenure_seconds: u64,
+) -> u16 {
+    if boost_period == 0 || boost_base_rate >= REWARD_TENURE_BOOST_RATE_MUL_VALUE {
+        return REWARD_TENURE_BOOST_RATE_MUL_VALUE;
+    }
+    let elapsed = tenure_seconds.min(boost_period as u64);
+    let ramp = (REWARD_TENURE_BOOST_RATE_MUL_VALUE - boost_base_rate) as u64 * elapsed
+        / boost_period as u64;
+    boost_base_rate + ramp as u16
+}
+
+// Applies the reward tenure boosts of the Whirlpool to the rewards a position update accrued since
+// the position's last checkpoint, and moves the start of its tenure. The boost rate is taken from
+// the tenure up to this checkpoint; the tenure restarts when liquidity is decreased and is cleared
+// when the position is out of range or empty at the checkpoint. The range is only sampled here, so
+// tenure measures the time since a checkpoint last found the position out of range, not continuous
+// in-range time: a price excursion out of the range between two checkpoints goes unnoticed.
+pub fn apply_reward_tenure_boost(
+    whirlpool: &Whirlpool,
+    position: &Position,
+    update: &mut PositionUpdate,
+    liquidity_delta: i128,
+    timestamp: u64,
+) {
+    if !whirlpool.has_reward_tenure_boost() {
+        update.reward_tenure_boost_start_timestamp = position.reward_tenure_boost_start_timestamp;
+        return;
+    }
+
+    let tenure_seconds = match position.reward_tenure_boost_start_timestamp {
+        0 => 0,
+        start => timestamp.saturating_sub(start),
+    };
+    for i in 0..NUM_REWARDS {
+        let rate = reward_tenure_boost_rate(
+            whirlpool.reward_tenure_boost_periods[i],
+            whirlpool.reward_tenure_boost_base_rates[i],
+            tenure_seconds,
+        );
+        let amount_owed = position.reward_infos[i].amount_owed;
+        let accrued = update.reward_infos[i].amount_owed.wrapping_sub(amount_owed);
+        let boosted = accrued as u128 * rate as u128 / REWARD_TENURE_BOOST_RATE_MUL_VALUE as u128;
+        update.reward_infos[i].amount_owed = amount_owed.wrapping_add(boosted as u64);
+    }
+
+    let in_range = whirlpool.tick_current_index >= position.tick_lower_index
+        && whirlpool.tick_current_index < position.tick_upper_index;
+    update.reward_tenure_boost_start_timestamp = if !in_range || update.liquidity == 0 {
+        0
+    } else if liquidity_delta < 0 || position.reward_tenure_boost_start_timestamp == 0 {
+        timestamp
+    } else {
+        position.reward_tenure_boost_start_timestamp
+    };
+}
+
 #[cfg(test)]
 mod position_manager_unit_tests {
     use crate::{
         math::{add_liquidity_delta, Q64_RESOLUTION},
-        state::{position_builder::PositionBuilder, Position, PositionRewardInfo, NUM_REWARDS},
+        state::{
+            position_builder::PositionBuilder, Position, PositionRewardInfo, PositionUpdate,
+            Whirlpool, NUM_REWARDS,
+        },
     };
 
-    use super::next_position_modify_liquidity_update;
+    use super::{
+        apply_reward_tenure_boost, next_position_modify_liquidity_update, reward_tenure_boost_rate,
+    };
 
     #[test]
     fn ok_positive_liquidity_delta_fee_growth() {
@@ -319,4 +385,122 @@ mod position_manager_unit_tests {
             ]
         )
     }
+
+    #[test]
+    fn test_reward_tenure_boost_rate() {
+        assert_eq!(reward_tenure_boost_rate(0, 2_500, 0), 10_000);
+        assert_eq!(reward_tenure_boost_rate(1_000, 2_500, 0), 2_500);
+        assert_eq!(reward_tenure_boost_rate(1_000, 2_500, 500), 6_250);
+        assert_eq!(reward_tenure_boost_rate(1_000, 2_500, 1_000), 10_000);
+        assert_eq!(reward_tenure_boost_rate(1_000, 2_500, u64::MAX), 10_000);
+        assert_eq!(reward_tenure_boost_rate(1_000, 10_000, 0), 10_000);
+    }
+
+    fn boosted_whirlpool(tick_current_index: i32) -> Whirlpool {
+        let mut whirlpool = Whirlpool {
+            tick_current_index,
+            ..Default::default()
+        };
+        whirlpool
+            .update_reward_tenure_boost(0, 1_000, 2_000)
+            .unwrap();
+        whirlpool
+    }
+
+    fn update_accruing(position: &Position, reward_amount: u64) -> PositionUpdate {
+        let mut update = PositionUpdate {
+            liquidity: position.liquidity,
+            reward_infos: position.reward_infos,
+            ..Default::default()
+        };
+        for reward_info in update.reward_infos.iter_mut() {
+            reward_info.amount_owed += reward_amount;
+        }
+        update
+    }
+
+    #[test]
+    fn apply_reward_tenure_boost_scales_accrued_rewards() {
+        let whirlpool = boosted_whirlpool(0);
+        let mut position = PositionBuilder::new(-10, 10).liquidity(1_000).build();
+        position.reward_infos[0].amount_owed = 50;
+        position.reward_tenure_boost_start_timestamp = 500;
+
+        let mut update = update_accruing(&position, 1_000);
+        apply_reward_tenure_boost(&whirlpool, &position, &mut update, 0, 1_000);
+        // Half of the boost period in range: 20% + 80% * 0.5 of the accrued rewards.
+        assert_eq!(update.reward_infos[0].amount_owed, 50 + 600);
+        // The other rewards have no tenure boost.
+        assert_eq!(update.reward_infos[1].amount_owed, 1_000);
+        assert_eq!(update.reward_tenure_boost_start_timestamp, 500);
+    }
+
+    #[test]
+    fn apply_reward_tenure_boost_starts_and_resets_tenure() {
+        let whirlpool = boosted_whirlpool(0);
+        let position = PositionBuilder::new(-10, 10).liquidity(1_000).build();
+
+        // Not tracked yet: base rate, tenure starts now.
+        let mut update = update_accruing(&position, 1_000);
+        apply_reward_tenure_boost(&whirlpool, &position, &mut update, 0, 1_000);
+        assert_eq!(update.reward_infos[0].amount_owed, 200);
+        assert_eq!(update.reward_tenure_boost_start_timestamp, 1_000);
+
+        let mut position = position;
+        position.reward_tenure_boost_start_timestamp = 1_000;
+
+        // Increases keep the tenure, decreases restart it.
+        let mut update = update_accruing(&position, 0);
+        apply_reward_tenure_boost(&whirlpool, &position, &mut update, 10, 1_500);
+        assert_eq!(update.reward_tenure_boost_start_timestamp, 1_000);
+        let mut update = update_accruing(&position, 0);
+        apply_reward_tenure_boost(&whirlpool, &position, &mut update, -10, 1_500);
+        assert_eq!(update.reward_tenure_boost_start_timestamp, 1_500);
+
+        // Out of range at the checkpoint clears it.
+        let whirlpool = boosted_whirlpool(10);
+        let mut update = update_accruing(&position, 0);
+        apply_reward_tenure_boost(&whirlpool, &position, &mut update, 0, 1_500);
+        assert_eq!(update.reward_tenure_boost_start_timestamp, 0);
+    }
+
+    #[test]
+    fn apply_reward_tenure_boost_samples_range_at_checkpoints_only() {
+        let mut position = PositionBuilder::new(-10, 10).liquidity(1_000).build();
+        position.reward_tenure_boost_start_timestamp = 1_000;
+
+        // The price left the range after the last checkpoint and came back before this one. Only
+        // the current tick is sampled, so the position keeps its tenure and the full boost.
+        let whirlpool = boosted_whirlpool(0);
+        let mut update = update_accruing(&position, 1_000);
+        apply_reward_tenure_boost(&whirlpool, &position, &mut update, 0, 2_000);
+        assert_eq!(update.reward_infos[0].amount_owed, 1_000);
+        assert_eq!(update.reward_tenure_boost_start_timestamp, 1_000);
+
+        // A checkpoint taken while the price is out of the range clears the tenure, and the next
+        // checkpoint in range starts a new one.
+        let whirlpool = boosted_whirlpool(-20);
+        let mut update = update_accruing(&position, 0);
+        apply_reward_tenure_boost(&whirlpool, &position, &mut update, 0, 1_500);
+        assert_eq!(update.reward_tenure_boost_start_timestamp, 0);
+        position.update(&update);
+
+        let whirlpool = boosted_whirlpool(0);
+        let mut update = update_accruing(&position, 1_000);
+        apply_reward_tenure_boost(&whirlpool, &position, &mut update, 0, 2_000);
+        assert_eq!(update.reward_infos[0].amount_owed, 200);
+        assert_eq!(update.reward_tenure_boost_start_timestamp, 2_000);
+    }
+
+    #[test]
+    fn apply_reward_tenure_boost_disabled_keeps_rewards() {
+        let whirlpool = Whirlpool::default();
+        let mut position = PositionBuilder::new(-10, 10).liquidity(1_000).build();
+        position.reward_tenure_boost_start_timestamp = 7;
+
+        let mut update = update_accruing(&position, 1_000);
+        apply_reward_tenure_boost(&whirlpool, &position, &mut update, -10, 1_000);
+        assert_eq!(update.reward_infos[0].amount_owed, 1_000);
+        assert_eq!(update.reward_tenure_boost_start_timestamp, 7);
+    }
 }
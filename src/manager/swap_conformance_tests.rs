@@ -0,0 +1,141 @@
+//! Replays the swap conformance vectors in `fixtures/swap_conformance_vectors.json` through the
+//! swap manager, so changes to the swap math that diverge from the recorded results fail.
+//!
+//! A vector is a snapshot of the Whirlpool and the tick arrays a swap traverses, the swap
+//! parameters and the expected result. Expected results have to come from the upstream program,
+//! either from its swap manager or from mainnet swaps, and each vector records its source. Only
+//! the expected fields present in a vector are checked.
+use std::cell::RefCell;
+
+use serde_json::Value;
+
+use crate::manager::swap_manager::*;
+use crate::math::fee_rate_from_legacy;
+use crate::state::{
+    tick_builder::TickBuilder, whirlpool_builder::WhirlpoolBuilder, TickArray, TickUpdate,
+    Whirlpool,
+};
+use crate::util::SwapTickSequence;
+
+const VECTORS: &str = include_str!("fixtures/swap_conformance_vectors.json");
+
+fn string_field<'a>(value: &'a Value, key: &str) -> &'a str {
+    value[key]
+        .as_str()
+        .unwrap_or_else(|| panic!("missing string field {}", key))
+}
+
+fn u128_field(value: &Value, key: &str) -> u128 {
+    string_field(value, key).parse().unwrap()
+}
+
+fn i128_field(value: &Value, key: &str) -> i128 {
+    string_field(value, key).parse().unwrap()
+}
+
+fn u64_field(value: &Value, key: &str) -> u64 {
+    string_field(value, key).parse().unwrap()
+}
+
+fn i64_field(value: &Value, key: &str) -> i64 {
+    value[key]
+        .as_i64()
+        .unwrap_or_else(|| panic!("missing integer field {}", key))
+}
+
+fn bool_field(value: &Value, key: &str) -> bool {
+    value[key]
+        .as_bool()
+        .unwrap_or_else(|| panic!("missing bool field {}", key))
+}
+
+fn build_whirlpool(value: &Value) -> Whirlpool {
+    WhirlpoolBuilder::new()
+        .tick_spacing(i64_field(value, "tick_spacing") as u16)
+        // Vectors record the legacy fee rate, in hundredths of a basis point
+        .fee_rate(fee_rate_from_legacy(i64_field(value, "fee_rate") as u16))
+        .protocol_fee_rate(i64_field(value, "protocol_fee_rate") as u16)
+        .liquidity(u128_field(value, "liquidity"))
+        .sqrt_price(u128_field(value, "sqrt_price"))
+        .tick_current_index(i64_field(value, "tick_current_index") as i32)
+        .fee_growth_global_a(u128_field(value, "fee_growth_global_a"))
+        .fee_growth_global_b(u128_field(value, "fee_growth_global_b"))
+        .build()
+}
+
+fn build_tick_array(value: &Value, tick_spacing: u16) -> TickArray {
+    let mut tick_array = TickArray {
+        start_tick_index: i64_field(value, "start_tick_index") as i32,
+        ..Default::default()
+    };
+    for tick in value["ticks"].as_array().unwrap() {
+        let update = TickUpdate::from(
+            &TickBuilder::default()
+                .initialized(true)
+                .liquidity_net(i128_field(tick, "liquidity_net"))
+                .build(),
+        );
+        tick_array
+            .update_tick(i64_field(tick, "index") as i32, tick_spacing, &update)
+            .unwrap();
+    }
+    tick_array
+}
+
+fn replay(vector: &Value) {
+    let name = string_field(vector, "name");
+    let whirlpool = build_whirlpool(&vector["whirlpool"]);
+    let tick_arrays: Vec<RefCell<TickArray>> = vector["tick_arrays"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|tick_array| RefCell::new(build_tick_array(tick_array, whirlpool.tick_spacing)))
+        .collect();
+    let mut tick_sequence = SwapTickSequence::new(
+        tick_arrays[0].borrow_mut(),
+        tick_arrays.get(1).map(|tick_array| tick_array.borrow_mut()),
+        tick_arrays.get(2).map(|tick_array| tick_array.borrow_mut()),
+    );
+
+    let swap_params = &vector["swap"];
+    let post_swap = swap(
+        &whirlpool,
+        &mut tick_sequence,
+        u64_field(swap_params, "amount"),
+        u128_field(swap_params, "sqrt_price_limit"),
+        bool_field(swap_params, "amount_specified_is_input"),
+        bool_field(swap_params, "a_to_b"),
+        whirlpool.reward_last_updated_timestamp,
+    )
+    .unwrap_or_else(|error| panic!("{}: swap failed with {:?}", name, error));
+
+    let expected = vector["expected"].as_object().unwrap();
+    for (key, value) in expected {
+        let matches = match key.as_str() {
+            "amount_a" => post_swap.amount_a == u64_field(&vector["expected"], key),
+            "amount_b" => post_swap.amount_b == u64_field(&vector["expected"], key),
+            "next_tick_index" => post_swap.next_tick_index as i64 == value.as_i64().unwrap(),
+            "next_liquidity" => post_swap.next_liquidity == u128_field(&vector["expected"], key),
+            "next_sqrt_price" => post_swap.next_sqrt_price == u128_field(&vector["expected"], key),
+            "next_fee_growth_global" => {
+                post_swap.next_fee_growth_global == u128_field(&vector["expected"], key)
+            }
+            "next_protocol_fee" => {
+                post_swap.next_protocol_fee == u64_field(&vector["expected"], key)
+            }
+            "total_fee" => post_swap.total_fee == u64_field(&vector["expected"], key),
+            _ => panic!("{}: unknown expected field {}", name, key),
+        };
+        assert!(matches, "{}: {} does not match {}", name, key, value);
+    }
+}
+
+#[test]
+fn replay_swap_conformance_vectors() {
+    let vectors: Value = serde_json::from_str(VECTORS).unwrap();
+    let vectors = vectors["vectors"].as_array().unwrap();
+    assert!(!vectors.is_empty());
+    for vector in vectors {
+        replay(vector);
+    }
+}
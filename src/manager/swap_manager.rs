@@ -5,7 +5,9 @@ use crate::{
     },
     math::*,
     state::*,
-    util::SwapTickSequence,
+    util::{
+        validate_sqrt_price_limit, verify_swap_compute_budget, SwapProgress, SwapTickSequence,
+    },
 };
 use anchor_lang::prelude::*;
 use std::convert::TryInto;
@@ -20,6 +22,23 @@ pub struct PostSwapUpdate {
     pub next_fee_growth_global: u128,
     pub next_reward_infos: [WhirlpoolRewardInfo; NUM_REWARDS],
     pub next_protocol_fee: u64,
+    pub total_fee: u64,
+    pub rounding_dust_a: u64,
+    pub rounding_dust_b: u64,
+    pub crossed_initialized_tick: bool,
+    // Initialized ticks crossed by the swap, in crossing order
+    pub crossed_tick_indexes: Vec<i32>,
+    pub is_fee_in_token_a: bool,
+}
+
+/// Amount, price limit and direction of a swap.
+#[derive(Clone, Copy, Default)]
+#[cfg_attr(not(target_arch = "bpf"), derive(Debug))]
+pub struct SwapParams {
+    pub amount: u64,
+    pub sqrt_price_limit: u128,
+    pub amount_specified_is_input: bool,
+    pub a_to_b: bool,
 }
 
 pub fn swap(
@@ -31,24 +50,98 @@ pub fn swap(
     a_to_b: bool,
     timestamp: u64,
 ) -> Result<PostSwapUpdate> {
-    if sqrt_price_limit < MIN_SQRT_PRICE_X64 || sqrt_price_limit > MAX_SQRT_PRICE_X64 {
-        return Err(ErrorCode::SqrtPriceOutOfBounds.into());
-    }
+    swap_with_fee_rate(
+        whirlpool,
+        whirlpool.effective_fee_rate(),
+        swap_tick_sequence,
+        SwapParams {
+            amount,
+            sqrt_price_limit,
+            amount_specified_is_input,
+            a_to_b,
+        },
+        timestamp,
+    )
+}
 
-    if a_to_b && sqrt_price_limit > whirlpool.sqrt_price
-        || !a_to_b && sqrt_price_limit < whirlpool.sqrt_price
-    {
-        return Err(ErrorCode::InvalidSqrtPriceLimitDirection.into());
-    }
+/// Same as `swap`, charging the given fee rate instead of the fee rate of the Whirlpool, e.g. a
+/// fee holiday rate.
+pub fn swap_with_fee_rate(
+    whirlpool: &Whirlpool,
+    fee_rate: u32,
+    swap_tick_sequence: &mut SwapTickSequence,
+    params: SwapParams,
+    timestamp: u64,
+) -> Result<PostSwapUpdate> {
+    swap_with_fee_mode(
+        whirlpool,
+        fee_rate,
+        swap_tick_sequence,
+        params,
+        false,
+        timestamp,
+    )
+}
+
+/// Same as `swap_with_fee_rate` for an exact input swap that charges the fee on top of `amount`
+/// instead of deducting it from it. The whole `amount` is traded against the curve, so the output
+/// only depends on `amount`, and the input of the swap is `amount` plus the fee.
+///
+/// # Errors
+/// - `FeeOnTopUnsupported` - The pool withholds the fee of this direction from the output.
+pub fn swap_fee_on_top(
+    whirlpool: &Whirlpool,
+    fee_rate: u32,
+    swap_tick_sequence: &mut SwapTickSequence,
+    amount: u64,
+    sqrt_price_limit: u128,
+    a_to_b: bool,
+    timestamp: u64,
+) -> Result<PostSwapUpdate> {
+    swap_with_fee_mode(
+        whirlpool,
+        fee_rate,
+        swap_tick_sequence,
+        SwapParams {
+            amount,
+            sqrt_price_limit,
+            amount_specified_is_input: true,
+            a_to_b,
+        },
+        true,
+        timestamp,
+    )
+}
+
+fn swap_with_fee_mode(
+    whirlpool: &Whirlpool,
+    fee_rate: u32,
+    swap_tick_sequence: &mut SwapTickSequence,
+    params: SwapParams,
+    fee_on_top: bool,
+    timestamp: u64,
+) -> Result<PostSwapUpdate> {
+    let SwapParams {
+        amount,
+        sqrt_price_limit,
+        amount_specified_is_input,
+        a_to_b,
+    } = params;
+    validate_sqrt_price_limit(sqrt_price_limit, whirlpool.sqrt_price, a_to_b)?;
 
     if amount == 0 {
         return Err(ErrorCode::ZeroTradableAmount.into());
     }
 
     let tick_spacing = whirlpool.tick_spacing;
-    let fee_rate = whirlpool.fee_rate;
     let protocol_fee_rate = whirlpool.protocol_fee_rate;
     let next_reward_infos = next_whirlpool_reward_infos(whirlpool, timestamp)?;
+    let is_fee_in_token_a = whirlpool.is_fee_in_token_a(a_to_b);
+    // Swaps from token A into a pool that accounts fees in token B pay the fee from the output.
+    let is_fee_on_output = a_to_b && !is_fee_in_token_a;
+    if fee_on_top && (is_fee_on_output || !amount_specified_is_input) {
+        return Err(ErrorCode::FeeOnTopUnsupported.into());
+    }
 
     let mut amount_remaining: u64 = amount;
     let mut amount_calculated: u64 = 0;
@@ -56,14 +149,33 @@ pub fn swap(
     let mut curr_tick_index = whirlpool.tick_current_index;
     let mut curr_liquidity = whirlpool.liquidity;
     let mut curr_protocol_fee: u64 = 0;
+    let mut curr_total_fee: u64 = 0;
+    // Fees charged on top of the specified amount, added to the input at the end of the swap
+    let mut curr_fee_on_top: u64 = 0;
+    let mut curr_rounding_dust_input: u64 = 0;
+    let mut curr_rounding_dust_output: u64 = 0;
+    let mut crossed_initialized_tick = false;
+    let mut crossed_tick_indexes = Vec::new();
     let mut curr_array_index: usize = 0;
-    let mut curr_fee_growth_global_input = if a_to_b {
+    let mut curr_fee_growth_global = if is_fee_in_token_a {
         whirlpool.fee_growth_global_a
     } else {
         whirlpool.fee_growth_global_b
     };
 
+    let mut is_first_step = true;
     while amount_remaining > 0 && sqrt_price_limit != curr_sqrt_price {
+        // The first step always runs, a swap truncated before it would return no progress.
+        if !is_first_step {
+            verify_swap_compute_budget(whirlpool, || SwapProgress {
+                amount_traded: amount - amount_remaining,
+                amount_calculated,
+                sqrt_price: curr_sqrt_price,
+                tick_current_index: curr_tick_index,
+            })?;
+        }
+        is_first_step = false;
+
         let (next_array_index, next_tick_index) = swap_tick_sequence
             .get_next_initialized_tick_index(
                 curr_tick_index,
@@ -75,22 +187,53 @@ pub fn swap(
         let (next_tick_sqrt_price, sqrt_price_target) =
             get_next_sqrt_prices(next_tick_index, sqrt_price_limit, a_to_b);
 
-        let swap_computation = compute_swap(
-            amount_remaining,
-            fee_rate,
-            curr_liquidity,
-            curr_sqrt_price,
-            sqrt_price_target,
-            amount_specified_is_input,
-            a_to_b,
-        )?;
+        let swap_computation = if is_fee_on_output {
+            compute_swap_with_output_fee(
+                amount_remaining,
+                fee_rate,
+                curr_liquidity,
+                curr_sqrt_price,
+                sqrt_price_target,
+                amount_specified_is_input,
+                a_to_b,
+            )?
+        } else if fee_on_top {
+            compute_swap_with_fee_on_top(
+                amount_remaining,
+                fee_rate,
+                curr_liquidity,
+                curr_sqrt_price,
+                sqrt_price_target,
+                a_to_b,
+            )?
+        } else {
+            compute_swap(
+                amount_remaining,
+                fee_rate,
+                curr_liquidity,
+                curr_sqrt_price,
+                sqrt_price_target,
+                amount_specified_is_input,
+                a_to_b,
+            )?
+        };
+        let input_fee_amount = if is_fee_on_output {
+            0
+        } else if fee_on_top {
+            curr_fee_on_top = curr_fee_on_top
+                .checked_add(swap_computation.fee_amount)
+                .ok_or(ErrorCode::AmountCalcOverflow)?;
+            0
+        } else {
+            swap_computation.fee_amount
+        };
 
         if amount_specified_is_input {
             amount_remaining = amount_remaining
                 .checked_sub(swap_computation.amount_in)
                 .ok_or(ErrorCode::AmountRemainingOverflow)?;
             amount_remaining = amount_remaining
-                .checked_sub(swap_computation.fee_amount)
+                .checked_sub(input_fee_amount)
                 .ok_or(ErrorCode::AmountRemainingOverflow)?;
 
             amount_calculated = amount_calculated
@@ -105,19 +248,38 @@ pub fn swap(
                 .checked_add(swap_computation.amount_in)
                 .ok_or(ErrorCode::AmountCalcOverflow)?;
             amount_calculated = amount_calculated
-                .checked_add(swap_computation.fee_amount)
+                .checked_add(input_fee_amount)
                 .ok_or(ErrorCode::AmountCalcOverflow)?;
         }
 
-        let (next_protocol_fee, next_fee_growth_global_input) = calculate_fees(
+        let (next_protocol_fee, next_fee_growth_global) = calculate_fees(
             swap_computation.fee_amount,
             protocol_fee_rate,
             curr_liquidity,
             curr_protocol_fee,
-            curr_fee_growth_global_input,
+            curr_fee_growth_global,
         );
         curr_protocol_fee = next_protocol_fee;
-        curr_fee_growth_global_input = next_fee_growth_global_input;
+        curr_fee_growth_global = next_fee_growth_global;
+        curr_total_fee = curr_total_fee
+            .checked_add(swap_computation.fee_amount)
+            .ok_or(ErrorCode::AmountCalcOverflow)?;
+
+        // The input of a step is rounded up and the output rounded down.
+        curr_rounding_dust_input =
+            curr_rounding_dust_input.saturating_add(get_amount_delta_rounding_dust(
+                curr_sqrt_price,
+                swap_computation.next_price,
+                curr_liquidity,
+                a_to_b,
+            ));
+        curr_rounding_dust_output =
+            curr_rounding_dust_output.saturating_add(get_amount_delta_rounding_dust(
+                curr_sqrt_price,
+                swap_computation.next_price,
+                curr_liquidity,
+                !a_to_b,
+            ));
 
         if swap_computation.next_price == next_tick_sqrt_price {
             let (next_tick, next_tick_initialized) = swap_tick_sequence
@@ -125,10 +287,12 @@ pub fn swap(
                 .map_or_else(|_| (None, false), |tick| (Some(tick), tick.initialized));
 
             if next_tick_initialized {
-                let (fee_growth_global_a, fee_growth_global_b) = if a_to_b {
-                    (curr_fee_growth_global_input, whirlpool.fee_growth_global_b)
+                crossed_initialized_tick = true;
+                crossed_tick_indexes.push(next_tick_index);
+                let (fee_growth_global_a, fee_growth_global_b) = if is_fee_in_token_a {
+                    (curr_fee_growth_global, whirlpool.fee_growth_global_b)
                 } else {
-                    (whirlpool.fee_growth_global_a, curr_fee_growth_global_input)
+                    (whirlpool.fee_growth_global_a, curr_fee_growth_global)
                 };
 
                 let (update, next_liquidity) = calculate_update(
@@ -180,10 +344,18 @@ pub fn swap(
         curr_sqrt_price = swap_computation.next_price;
     }
 
+    let amount_specified_traded = (amount - amount_remaining)
+        .checked_add(curr_fee_on_top)
+        .ok_or(ErrorCode::AmountCalcOverflow)?;
     let (amount_a, amount_b) = if a_to_b == amount_specified_is_input {
-        (amount - amount_remaining, amount_calculated)
+        (amount_specified_traded, amount_calculated)
     } else {
-        (amount_calculated, amount - amount_remaining)
+        (amount_calculated, amount_specified_traded)
+    };
+    let (rounding_dust_a, rounding_dust_b) = if a_to_b {
+        (curr_rounding_dust_input, curr_rounding_dust_output)
+    } else {
+        (curr_rounding_dust_output, curr_rounding_dust_input)
     };
 
     Ok(PostSwapUpdate {
@@ -192,12 +364,109 @@ pub fn swap(
         next_liquidity: curr_liquidity,
         next_tick_index: curr_tick_index,
         next_sqrt_price: curr_sqrt_price,
-        next_fee_growth_global: curr_fee_growth_global_input,
+        next_fee_growth_global: curr_fee_growth_global,
         next_reward_infos,
         next_protocol_fee: curr_protocol_fee,
+        total_fee: curr_total_fee,
+        rounding_dust_a,
+        rounding_dust_b,
+        crossed_initialized_tick,
+        crossed_tick_indexes,
+        is_fee_in_token_a,
     })
 }
 
+/// Compute a swap step that takes its fee from the output token instead of the input token. The
+/// whole input is swapped at the step's price and the fee is withheld from the output, the same
+/// as swapping the input side fee at the execution price of the step.
+///
+/// For exact output swaps `amount_remaining` is the output left to receive after fees, so the step
+/// targets the gross output that covers the fee.
+fn compute_swap_with_output_fee(
+    amount_remaining: u64,
+    fee_rate: u32,
+    liquidity: u128,
+    sqrt_price_current: u128,
+    sqrt_price_target: u128,
+    amount_specified_is_input: bool,
+    a_to_b: bool,
+) -> Result<SwapStepComputation> {
+    let amount_step = if amount_specified_is_input {
+        amount_remaining
+    } else {
+        let gross_amount = checked_mul_div_round_up(
+            amount_remaining as u128,
+            FEE_RATE_MUL_VALUE,
+            FEE_RATE_MUL_VALUE - fee_rate as u128,
+        )?;
+        gross_amount.min(u64::MAX as u128) as u64
+    };
+
+    let mut swap_computation = compute_swap(
+        amount_step,
+        0,
+        liquidity,
+        sqrt_price_current,
+        sqrt_price_target,
+        amount_specified_is_input,
+        a_to_b,
+    )?;
+
+    let gross_amount_out = swap_computation.amount_out;
+    let fee_amount = checked_mul_div_round_up(
+        gross_amount_out as u128,
+        fee_rate as u128,
+        FEE_RATE_MUL_VALUE,
+    )? as u64;
+    let mut amount_out = gross_amount_out - fee_amount;
+    if !amount_specified_is_input {
+        // Rounding the gross output up can overshoot, the surplus goes to the fee.
+        amount_out = amount_out.min(amount_remaining);
+    }
+    swap_computation.amount_out = amount_out;
+    swap_computation.fee_amount = gross_amount_out - amount_out;
+
+    Ok(swap_computation)
+}
+
+/// Compute a swap step that charges its fee on top of the input. The whole remaining amount is
+/// traded against the curve, and the fee is the share of the step's gross input the fee rate
+/// would deduct from it.
+fn compute_swap_with_fee_on_top(
+    amount_remaining: u64,
+    fee_rate: u32,
+    liquidity: u128,
+    sqrt_price_current: u128,
+    sqrt_price_target: u128,
+    a_to_b: bool,
+) -> Result<SwapStepComputation> {
+    let mut swap_computation = compute_swap(
+        amount_remaining,
+        0,
+        liquidity,
+        sqrt_price_current,
+        sqrt_price_target,
+        true,
+        a_to_b,
+    )?;
+
+    // A step that stops short of its target consumes the whole remaining amount, the rounding
+    // surplus is traded into the pool instead of being left as a remainder too small to move the
+    // price.
+    if swap_computation.next_price != sqrt_price_target {
+        swap_computation.amount_in = amount_remaining;
+    }
+    swap_computation.fee_amount = checked_mul_div_round_up(
+        swap_computation.amount_in as u128,
+        fee_rate as u128,
+        FEE_RATE_MUL_VALUE - fee_rate as u128,
+    )?
+    .try_into()
+    .map_err(|_| ErrorCode::AmountCalcOverflow)?;
+
+    Ok(swap_computation)
+}
+
 fn calculate_fees(
     fee_amount: u64,
     protocol_fee_rate: u16,
@@ -236,18 +505,11 @@ fn calculate_update(
     reward_infos: &[WhirlpoolRewardInfo; NUM_REWARDS],
 ) -> Result<(TickUpdate, u128)> {
     // Use updated fee_growth for crossing tick
-    // Use -liquidity_net if going left, +liquidity_net going right
-    let signed_liquidity_net = if a_to_b {
-        -tick.liquidity_net
-    } else {
-        tick.liquidity_net
-    };
-
     let update =
         next_tick_cross_update(tick, fee_growth_global_a, fee_growth_global_b, reward_infos)?;
 
     // Update the global liquidity to reflect the new current tick
-    let next_liquidity = add_liquidity_delta(liquidity, signed_liquidity_net)?;
+    let next_liquidity = apply_liquidity_net(liquidity, tick.liquidity_net, a_to_b)?;
 
     Ok((update, next_liquidity))
 }
@@ -321,6 +583,7 @@ mod swap_liquidity_tests {
                 end_reward_growths: [10, 10, 10],
             },
         );
+        assert_eq!(post_swap.crossed_tick_indexes, vec![448, 720]);
         let tick_lower = tick_sequence.get_tick(0, 448, TS_8).unwrap();
         assert_swap_tick_state(
             &tick_lower,
@@ -2535,3 +2798,212 @@ mod swap_error_tests {
         swap_test_info.run(&mut tick_sequence, 100);
     }
 }
+
+#[cfg(test)]
+mod swap_fee_tests {
+    use super::*;
+    use crate::util::test_utils::swap_test_fixture::*;
+
+    #[test]
+    /// An exact input swap within a single range charges the fee rate on the input amount and
+    /// reports the protocol share as part of the total fee.
+    fn total_fee_includes_protocol_fee() {
+        let swap_test_info = SwapTestFixture::new(SwapTestFixtureInfo {
+            tick_spacing: TS_8,
+            liquidity: 1_000_000_000_000,
+            curr_tick_index: 352,
+            start_tick_index: 0,
+            trade_amount: 1_000_000,
+            sqrt_price_limit: sqrt_price_from_tick_index(0),
+            amount_specified_is_input: true,
+            a_to_b: true,
+            fee_rate: 30_000,
+            protocol_fee_rate: 2500,
+            ..Default::default()
+        });
+        let mut tick_sequence = SwapTickSequence::new(
+            swap_test_info.tick_arrays[0].borrow_mut(),
+            Some(swap_test_info.tick_arrays[1].borrow_mut()),
+            Some(swap_test_info.tick_arrays[2].borrow_mut()),
+        );
+        let post_swap = swap_test_info.run(&mut tick_sequence, 100);
+        assert_eq!(post_swap.amount_a, 1_000_000);
+        assert_eq!(post_swap.total_fee, 3000);
+        assert_eq!(post_swap.next_protocol_fee, 750);
+    }
+
+    #[test]
+    /// A single step swap rounds each of its token deltas at most once in favor of the pool.
+    fn rounding_dust_is_at_most_one_per_step() {
+        let swap_test_info = SwapTestFixture::new(SwapTestFixtureInfo {
+            tick_spacing: TS_8,
+            liquidity: 1_000_000_000_000,
+            curr_tick_index: 352,
+            start_tick_index: 0,
+            trade_amount: 1_000_000,
+            sqrt_price_limit: sqrt_price_from_tick_index(0),
+            amount_specified_is_input: true,
+            a_to_b: true,
+            fee_rate: 30_000,
+            ..Default::default()
+        });
+        let mut tick_sequence = SwapTickSequence::new(
+            swap_test_info.tick_arrays[0].borrow_mut(),
+            Some(swap_test_info.tick_arrays[1].borrow_mut()),
+            Some(swap_test_info.tick_arrays[2].borrow_mut()),
+        );
+        let post_swap = swap_test_info.run(&mut tick_sequence, 100);
+        assert!(post_swap.rounding_dust_a <= 1);
+        assert!(post_swap.rounding_dust_b <= 1);
+        assert_eq!(post_swap.rounding_dust_a + post_swap.rounding_dust_b, 2);
+    }
+
+    #[test]
+    /// An exact input swap from token A into a pool that accounts fees in token B swaps the whole
+    /// input and withholds the fee from the output.
+    fn fees_in_token_b_exact_input_a_to_b() {
+        let fixture_info = || SwapTestFixtureInfo {
+            tick_spacing: TS_8,
+            liquidity: 1_000_000_000_000,
+            curr_tick_index: 352,
+            start_tick_index: 0,
+            trade_amount: 1_000_000,
+            sqrt_price_limit: sqrt_price_from_tick_index(0),
+            amount_specified_is_input: true,
+            a_to_b: true,
+            fee_rate: 30_000,
+            protocol_fee_rate: 2500,
+            ..Default::default()
+        };
+        let no_fee_info = SwapTestFixture::new(SwapTestFixtureInfo {
+            fee_rate: 0,
+            ..fixture_info()
+        });
+        let mut swap_test_info = SwapTestFixture::new(fixture_info());
+        swap_test_info.whirlpool.fees_in_token_b = true;
+
+        let no_fee_swap = no_fee_info.run(
+            &mut SwapTickSequence::new(no_fee_info.tick_arrays[0].borrow_mut(), None, None),
+            100,
+        );
+        let post_swap = swap_test_info.run(
+            &mut SwapTickSequence::new(swap_test_info.tick_arrays[0].borrow_mut(), None, None),
+            100,
+        );
+
+        assert!(!post_swap.is_fee_in_token_a);
+        assert_eq!(post_swap.amount_a, 1_000_000);
+        assert_eq!(post_swap.next_sqrt_price, no_fee_swap.next_sqrt_price);
+        assert_eq!(
+            post_swap.total_fee as u128,
+            checked_mul_div_round_up(no_fee_swap.amount_b as u128, 30_000, FEE_RATE_MUL_VALUE)
+                .unwrap()
+        );
+        assert_eq!(
+            post_swap.amount_b,
+            no_fee_swap.amount_b - post_swap.total_fee
+        );
+        assert_eq!(post_swap.next_protocol_fee, post_swap.total_fee / 4);
+    }
+
+    #[test]
+    /// An exact output swap from token A into a pool that accounts fees in token B delivers the
+    /// requested output net of the fee.
+    fn fees_in_token_b_exact_output_a_to_b() {
+        let mut swap_test_info = SwapTestFixture::new(SwapTestFixtureInfo {
+            tick_spacing: TS_8,
+            liquidity: 1_000_000_000_000,
+            curr_tick_index: 352,
+            start_tick_index: 0,
+            trade_amount: 1_000_000,
+            sqrt_price_limit: sqrt_price_from_tick_index(0),
+            amount_specified_is_input: false,
+            a_to_b: true,
+            fee_rate: 30_000,
+            ..Default::default()
+        });
+        swap_test_info.whirlpool.fees_in_token_b = true;
+        let post_swap = swap_test_info.run(
+            &mut SwapTickSequence::new(swap_test_info.tick_arrays[0].borrow_mut(), None, None),
+            100,
+        );
+
+        assert_eq!(post_swap.amount_b, 1_000_000);
+        assert!(post_swap.total_fee >= 3000);
+        assert!(post_swap.total_fee <= 3010);
+    }
+
+    #[test]
+    /// A fee on top swap trades the whole specified amount, so its output matches a swap without
+    /// fees, and charges the fee in addition to the specified amount.
+    fn fee_on_top_exact_input() {
+        let fixture_info = || SwapTestFixtureInfo {
+            tick_spacing: TS_8,
+            liquidity: 1_000_000_000_000,
+            curr_tick_index: 352,
+            start_tick_index: 0,
+            trade_amount: 1_000_000,
+            sqrt_price_limit: sqrt_price_from_tick_index(0),
+            amount_specified_is_input: true,
+            a_to_b: true,
+            fee_rate: 30_000,
+            protocol_fee_rate: 2500,
+            ..Default::default()
+        };
+        let no_fee_info = SwapTestFixture::new(SwapTestFixtureInfo {
+            fee_rate: 0,
+            ..fixture_info()
+        });
+        let swap_test_info = SwapTestFixture::new(fixture_info());
+
+        let no_fee_swap = no_fee_info.run(
+            &mut SwapTickSequence::new(no_fee_info.tick_arrays[0].borrow_mut(), None, None),
+            100,
+        );
+        let post_swap = swap_fee_on_top(
+            &swap_test_info.whirlpool,
+            30_000,
+            &mut SwapTickSequence::new(swap_test_info.tick_arrays[0].borrow_mut(), None, None),
+            1_000_000,
+            sqrt_price_from_tick_index(0),
+            true,
+            100,
+        )
+        .unwrap();
+
+        assert_eq!(post_swap.amount_b, no_fee_swap.amount_b);
+        assert_eq!(post_swap.next_sqrt_price, no_fee_swap.next_sqrt_price);
+        // 1_000_000 * 0.003 / 0.997, rounded up
+        assert_eq!(post_swap.total_fee, 3010);
+        assert_eq!(post_swap.amount_a, 1_000_000 + 3010);
+        assert_eq!(post_swap.next_protocol_fee, 752);
+    }
+
+    #[test]
+    /// Fee on top swaps are rejected in directions that withhold the fee from the output.
+    fn fee_on_top_rejects_fee_on_output() {
+        let mut swap_test_info = SwapTestFixture::new(SwapTestFixtureInfo {
+            tick_spacing: TS_8,
+            liquidity: 1_000_000_000_000,
+            curr_tick_index: 352,
+            start_tick_index: 0,
+            trade_amount: 1_000_000,
+            sqrt_price_limit: sqrt_price_from_tick_index(0),
+            amount_specified_is_input: true,
+            a_to_b: true,
+            fee_rate: 30_000,
+            ..Default::default()
+        });
+        swap_test_info.whirlpool.fees_in_token_b = true;
+        let result = swap_fee_on_top(
+            &swap_test_info.whirlpool,
+            30_000,
+            &mut SwapTickSequence::new(swap_test_info.tick_arrays[0].borrow_mut(), None, None),
+            1_000_000,
+            sqrt_price_from_tick_index(0),
+            true,
+            100,
+        );
+        assert_eq!(result.unwrap_err(), ErrorCode::FeeOnTopUnsupported.into());
+    }
+}
@@ -1,6 +1,6 @@
 use crate::{
     errors::ErrorCode,
-    math::add_liquidity_delta,
+    math::{add_liquidity_delta, update_liquidity_net},
     state::{Tick, TickUpdate, WhirlpoolRewardInfo, NUM_REWARDS},
 };
 
@@ -69,15 +69,7 @@ pub fn next_tick_modify_liquidity_update(
             )
         };
 
-    let liquidity_net = if is_upper_tick {
-        tick.liquidity_net
-            .checked_sub(liquidity_delta)
-            .ok_or(ErrorCode::LiquidityNetError)?
-    } else {
-        tick.liquidity_net
-            .checked_add(liquidity_delta)
-            .ok_or(ErrorCode::LiquidityNetError)?
-    };
+    let liquidity_net = update_liquidity_net(tick.liquidity_net, liquidity_delta, is_upper_tick)?;
 
     Ok(TickUpdate {
         initialized: true,
@@ -91,6 +83,25 @@ pub fn next_tick_modify_liquidity_update(
 
 // Calculates the fee growths inside of tick_lower and tick_upper based on their
 // index relative to tick_current_index.
+/// Verify that the boundary ticks of a position with liquidity are initialized. The liquidity of a
+/// position is always referenced by both of its boundary ticks, so an uninitialized boundary tick
+/// means corrupted tick state, and the growths inside computed from it would wrap around and
+/// credit the position with arbitrarily large fees and rewards.
+///
+/// # Errors
+/// - `BoundaryTickUninitialized` - The position has liquidity and a boundary tick is not
+///   initialized.
+pub fn verify_boundary_ticks_initialized(
+    position_liquidity: u128,
+    tick_lower: &Tick,
+    tick_upper: &Tick,
+) -> Result<(), ErrorCode> {
+    if position_liquidity > 0 && (!tick_lower.initialized || !tick_upper.initialized) {
+        return Err(ErrorCode::BoundaryTickUninitialized);
+    }
+    Ok(())
+}
+
 pub fn next_fee_growths_inside(
     tick_current_index: i32,
     tick_lower: &Tick,
@@ -149,7 +160,7 @@ pub fn next_reward_growths_inside(
     tick_upper: &Tick,
     tick_upper_index: i32,
     reward_infos: &[WhirlpoolRewardInfo; NUM_REWARDS],
-) -> ([u128; NUM_REWARDS]) {
+) -> [u128; NUM_REWARDS] {
     let mut reward_growths_inside = [0; NUM_REWARDS];
 
     for i in 0..NUM_REWARDS {
@@ -196,7 +207,7 @@ mod tick_manager_tests {
         errors::ErrorCode,
         manager::tick_manager::{
             next_fee_growths_inside, next_tick_cross_update, next_tick_modify_liquidity_update,
-            TickUpdate,
+            verify_boundary_ticks_initialized, TickUpdate,
         },
         math::Q64_RESOLUTION,
         state::{tick_builder::TickBuilder, Tick, WhirlpoolRewardInfo, NUM_REWARDS},
@@ -221,6 +232,27 @@ mod tick_manager_tests {
         }
     }
 
+    #[test]
+    fn test_verify_boundary_ticks_initialized() {
+        let initialized = Tick {
+            initialized: true,
+            ..Default::default()
+        };
+        let uninitialized = Tick::default();
+
+        assert!(verify_boundary_ticks_initialized(10, &initialized, &initialized).is_ok());
+        // Positions without liquidity may reference uninitialized ticks before their first deposit.
+        assert!(verify_boundary_ticks_initialized(0, &uninitialized, &uninitialized).is_ok());
+        assert_eq!(
+            verify_boundary_ticks_initialized(10, &uninitialized, &initialized),
+            Err(ErrorCode::BoundaryTickUninitialized)
+        );
+        assert_eq!(
+            verify_boundary_ticks_initialized(10, &initialized, &uninitialized),
+            Err(ErrorCode::BoundaryTickUninitialized)
+        );
+    }
+
     #[test]
     fn test_next_fee_growths_inside() {
         struct Test<'a> {
@@ -529,7 +561,6 @@ mod tick_manager_tests {
                         100 << Q64_RESOLUTION,
                     ],
                 },
-                ..Default::default()
             },
             Test {
                 name: "lower tick +liquidity already initialized, growths not set",
@@ -551,7 +582,6 @@ mod tick_manager_tests {
                     liquidity_gross: 42169,
                     ..Default::default()
                 },
-                ..Default::default()
             },
             Test {
                 name: "upper tick +liquidity already initialized, growths not set, liquidity net should be subtracted",
@@ -662,7 +692,6 @@ mod tick_manager_tests {
                     liquidity_net: 42069,
                     liquidity_gross: 42069,
                     reward_growths_outside: [0, 250, 0],
-                    ..Default::default()
                 },
             }
         ] {
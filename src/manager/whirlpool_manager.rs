@@ -13,8 +13,8 @@ pub fn next_whirlpool_reward_infos(
         return Err(ErrorCode::InvalidTimestamp.into());
     }
 
-    // No-op if no liquidity or no change in timestamp
-    if whirlpool.liquidity == 0 || next_timestamp == curr_timestamp {
+    // No-op if no change in timestamp or no reward is emitting, which is the case for most pools
+    if next_timestamp == curr_timestamp || whirlpool.reward_emissions_zero {
         return Ok(whirlpool.reward_infos);
     }
 
@@ -27,15 +27,33 @@ pub fn next_whirlpool_reward_infos(
         }
         let reward_info = &mut next_reward_infos[i];
 
-        // Calculate the new reward growth delta.
+        // Rewards accrue either to the in-range liquidity or to all liquidity of the pool.
+        // No-op if there is no liquidity to accrue to.
+        let liquidity = if whirlpool.reward_accrues_to_all_liquidity[i] {
+            whirlpool.total_liquidity
+        } else {
+            whirlpool.liquidity
+        };
+        if liquidity == 0 {
+            continue;
+        }
+
+        // Calculate the new reward growth delta. Emissions per unit of liquidity grow each unit
+        // by the emission rate up to the liquidity cap, beyond it the emissions of the capped
+        // liquidity are split like other emissions.
         // If the calculation overflows, set the delta value to zero.
         // This will halt reward distributions for this reward.
-        let reward_growth_delta = checked_mul_div(
-            time_delta,
-            reward_info.emissions_per_second_x64,
-            whirlpool.liquidity,
-        )
-        .unwrap_or(0);
+        let liquidity_cap = whirlpool.reward_emissions_liquidity_caps[i];
+        let reward_growth_delta = if liquidity_cap != 0 && liquidity <= liquidity_cap {
+            time_delta
+                .checked_mul(reward_info.emissions_per_second_x64)
+                .unwrap_or(0)
+        } else {
+            whirlpool
+                .reward_total_emissions_per_second_x64(i)
+                .and_then(|emissions| checked_mul_div(time_delta, emissions, liquidity).ok())
+                .unwrap_or(0)
+        };
 
         // Add the reward growth delta to the global reward growth.
         let curr_growth_global = reward_info.growth_global_x64;
@@ -117,6 +135,80 @@ mod whirlpool_manager_tests {
         );
     }
 
+    #[test]
+    fn test_next_whirlpool_reward_infos_all_liquidity_out_of_range() {
+        let mut whirlpool = init_test_whirlpool(0, 1577854800);
+        whirlpool.total_liquidity = 100;
+        whirlpool.reward_accrues_to_all_liquidity[0] = true;
+
+        let result = next_whirlpool_reward_infos(&whirlpool, 1577854810);
+        assert_eq!(
+            WhirlpoolRewardInfo::to_reward_growths(&result.unwrap()),
+            [
+                (100 << Q64_RESOLUTION) + (1 << Q64_RESOLUTION),
+                200 << Q64_RESOLUTION,
+                300 << Q64_RESOLUTION
+            ]
+        );
+    }
+
+    #[test]
+    fn test_next_whirlpool_reward_infos_emissions_per_liquidity() {
+        let mut whirlpool = init_test_whirlpool(100, 1577854800);
+        whirlpool.reward_emissions_liquidity_caps[0] = 1_000;
+        whirlpool.reward_emissions_liquidity_caps[2] = 1_000;
+
+        // Per liquidity growth does not depend on the liquidity in range, up to the cap
+        let expected = [
+            (100 << Q64_RESOLUTION) + (100 << Q64_RESOLUTION),
+            (200 << Q64_RESOLUTION) + (0b11 << (Q64_RESOLUTION - 1)) / 10,
+            (300 << Q64_RESOLUTION) + (5 << Q64_RESOLUTION),
+        ];
+        let result = next_whirlpool_reward_infos(&whirlpool, 1577854810);
+        assert_eq!(
+            WhirlpoolRewardInfo::to_reward_growths(&result.unwrap()),
+            expected
+        );
+
+        whirlpool.liquidity = 1_000;
+        let result = next_whirlpool_reward_infos(&whirlpool, 1577854810);
+        let growths = WhirlpoolRewardInfo::to_reward_growths(&result.unwrap());
+        assert_eq!(growths[0], expected[0]);
+        assert_eq!(growths[2], expected[2]);
+
+        // Beyond the cap, the emissions of the capped liquidity are split
+        whirlpool.liquidity = 4_000;
+        let result = next_whirlpool_reward_infos(&whirlpool, 1577854810);
+        let growths = WhirlpoolRewardInfo::to_reward_growths(&result.unwrap());
+        assert_eq!(growths[0], (100 << Q64_RESOLUTION) + (25 << Q64_RESOLUTION));
+        assert_eq!(
+            growths[2],
+            (300 << Q64_RESOLUTION) + (0b101 << (Q64_RESOLUTION - 2))
+        );
+    }
+
+    #[test]
+    fn test_next_whirlpool_reward_infos_emissions_zero_no_op() {
+        let mut whirlpool = init_test_whirlpool(100, 1577854800);
+        for index in 0..NUM_REWARDS {
+            let reward_infos = whirlpool.reward_infos;
+            whirlpool
+                .update_emissions(index, reward_infos, 1577854800, 0)
+                .unwrap();
+        }
+        assert!(whirlpool.reward_emissions_zero);
+
+        let result = next_whirlpool_reward_infos(&whirlpool, 1577855800);
+        assert_eq!(
+            WhirlpoolRewardInfo::to_reward_growths(&result.unwrap()),
+            [
+                100 << Q64_RESOLUTION,
+                200 << Q64_RESOLUTION,
+                300 << Q64_RESOLUTION
+            ]
+        );
+    }
+
     #[test]
     fn test_next_whirlpool_reward_infos_same_timestamp_no_op() {
         let whirlpool = init_test_whirlpool(100, 1577854800);
@@ -88,4 +88,113 @@ pub fn div_round_up_if_u256(
     };
 
     Ok(result.try_into_u128()?)
-}
\ No newline at end of file
+}
+
+/// Integer square root, rounded down. Newton's method starts from the power of two above the
+/// root, found from the bit length, so it converges from above in a few iterations.
+pub fn sqrt_u128(n: u128) -> u64 {
+    if n < 2 {
+        return n as u64;
+    }
+
+    let bits = 128 - n.leading_zeros();
+    let mut x = 1u128 << ((bits + 1) >> 1);
+    loop {
+        let y = (x + n / x) >> 1;
+        if y >= x {
+            return x as u64;
+        }
+        x = y;
+    }
+}
+
+// Number of fractional bits computed by log2_q64, the remaining bits of the result are zero.
+pub const LOG2_Q64_FRACTION_BITS: u32 = 32;
+
+/// Base 2 logarithm of a Q64.64 number, as a signed Q64.64 number. The integer part comes from
+/// the position of the most significant bit, the fraction from repeatedly squaring the mantissa
+/// normalized to [1, 2) in Q1.63, one bit per squaring.
+///
+/// The result is rounded down to `LOG2_Q64_FRACTION_BITS` fractional bits.
+pub fn log2_q64(n: u128) -> Result<i128, ErrorCode> {
+    if n == 0 {
+        return Err(ErrorCode::LogOfZero);
+    }
+
+    let msb = 127 - n.leading_zeros();
+    let integer = (msb as i128 - Q64_RESOLUTION as i128) << Q64_RESOLUTION;
+
+    let mut r = if msb >= 63 {
+        n >> (msb - 63)
+    } else {
+        n << (63 - msb)
+    };
+    let mut fraction: u128 = 0;
+    for bit in 1..=LOG2_Q64_FRACTION_BITS {
+        r = (r * r) >> 63;
+        if r >= 1u128 << 64 {
+            r >>= 1;
+            fraction |= 1u128 << (Q64_RESOLUTION as u32 - bit);
+        }
+    }
+
+    Ok(integer + fraction as i128)
+}
+
+#[cfg(test)]
+mod bit_math_tests {
+    use super::*;
+
+    #[test]
+    fn test_sqrt_u128() {
+        assert_eq!(sqrt_u128(0), 0);
+        assert_eq!(sqrt_u128(1), 1);
+        assert_eq!(sqrt_u128(3), 1);
+        assert_eq!(sqrt_u128(4), 2);
+        assert_eq!(sqrt_u128(99), 9);
+        assert_eq!(sqrt_u128(1 << 64), 1 << 32);
+        assert_eq!(sqrt_u128(u128::MAX), u64::MAX);
+    }
+
+    #[test]
+    fn test_sqrt_u128_rounds_down() {
+        for n in [
+            2u128,
+            1_000_000_007,
+            u64::MAX as u128,
+            (u64::MAX as u128) * (u64::MAX as u128) - 1,
+            0x1234_5678_9abc_def0_1234_5678_9abc_def0,
+        ] {
+            let root = sqrt_u128(n) as u128;
+            assert!(root * root <= n);
+            assert!(n / (root + 1) < root + 1);
+        }
+    }
+
+    #[test]
+    fn test_log2_q64_powers_of_two() {
+        assert_eq!(log2_q64(TO_Q64).unwrap(), 0);
+        assert_eq!(log2_q64(TO_Q64 << 1).unwrap(), 1 << 64);
+        assert_eq!(log2_q64(TO_Q64 >> 1).unwrap(), -(1 << 64));
+        assert_eq!(log2_q64(1).unwrap(), -64 << 64);
+        assert_eq!(log2_q64(1 << 127).unwrap(), 63 << 64);
+    }
+
+    #[test]
+    fn test_log2_q64_fraction() {
+        for (n, expected) in [
+            (3 * TO_Q64, 3f64.log2()),
+            (TO_Q64 / 3, (1f64 / 3f64).log2()),
+            (TO_Q64 + 1, 0.0),
+            (u128::MAX, 64.0),
+        ] {
+            let log2 = log2_q64(n).unwrap() as f64 / TO_Q64 as f64;
+            assert!((log2 - expected).abs() < 1e-9, "{} != {}", log2, expected);
+        }
+    }
+
+    #[test]
+    fn test_log2_q64_zero() {
+        assert_eq!(log2_q64(0), Err(ErrorCode::LogOfZero));
+    }
+}
@@ -0,0 +1,94 @@
+use std::convert::TryInto;
+
+use crate::errors::ErrorCode;
+
+use super::{checked_mul_div_round_up_if, TO_Q64};
+
+// Converts an amount of a rebasing token into underlying units given a Q64.64 exchange rate
+// of underlying units per token unit.
+pub fn token_to_underlying_amount(
+    amount: u64,
+    exchange_rate_x64: u128,
+    round_up: bool,
+) -> Result<u64, ErrorCode> {
+    checked_mul_div_round_up_if(amount as u128, exchange_rate_x64, TO_Q64, round_up)?
+        .try_into()
+        .map_err(|_| ErrorCode::AmountExceedsU64)
+}
+
+// Converts an amount of underlying units back into the rebasing token given a Q64.64 exchange
+// rate of underlying units per token unit.
+pub fn underlying_to_token_amount(
+    amount: u64,
+    exchange_rate_x64: u128,
+    round_up: bool,
+) -> Result<u64, ErrorCode> {
+    checked_mul_div_round_up_if(amount as u128, TO_Q64, exchange_rate_x64, round_up)?
+        .try_into()
+        .map_err(|_| ErrorCode::AmountExceedsU64)
+}
+
+#[cfg(test)]
+mod exchange_rate_math_tests {
+    use super::*;
+
+    // 1.5 underlying units per token unit
+    const RATE_1_5_X64: u128 = 3 << 63;
+
+    #[test]
+    fn test_token_to_underlying_amount() {
+        assert_eq!(
+            token_to_underlying_amount(3, RATE_1_5_X64, false).unwrap(),
+            4
+        );
+        assert_eq!(
+            token_to_underlying_amount(3, RATE_1_5_X64, true).unwrap(),
+            5
+        );
+        assert_eq!(
+            token_to_underlying_amount(4, RATE_1_5_X64, true).unwrap(),
+            6
+        );
+    }
+
+    #[test]
+    fn test_underlying_to_token_amount() {
+        assert_eq!(
+            underlying_to_token_amount(4, RATE_1_5_X64, false).unwrap(),
+            2
+        );
+        assert_eq!(
+            underlying_to_token_amount(4, RATE_1_5_X64, true).unwrap(),
+            3
+        );
+        assert_eq!(
+            underlying_to_token_amount(6, RATE_1_5_X64, true).unwrap(),
+            4
+        );
+    }
+
+    #[test]
+    fn test_round_trip_never_exceeds_input() {
+        for amount in 0..1000u64 {
+            let underlying = token_to_underlying_amount(amount, RATE_1_5_X64, false).unwrap();
+            let token = underlying_to_token_amount(underlying, RATE_1_5_X64, true).unwrap();
+            assert!(token <= amount);
+        }
+    }
+
+    #[test]
+    fn test_underlying_to_token_amount_zero_rate() {
+        assert_eq!(
+            underlying_to_token_amount(1, 0, false).unwrap_err(),
+            ErrorCode::DivideByZero
+        );
+    }
+
+    #[test]
+    fn test_underlying_to_token_amount_exceeds_u64() {
+        assert_eq!(
+            underlying_to_token_amount(u64::MAX, 1 << 63, false).unwrap_err(),
+            ErrorCode::AmountExceedsU64
+        );
+    }
+}
@@ -1,5 +1,7 @@
 use crate::errors::ErrorCode;
 
+use super::{increasing_price_order, mul_u256, U256Muldiv};
+
 // Adds a signed liquidity delta to a given integer liquidity amount.
 // Errors on overflow or underflow.
 pub fn add_liquidity_delta(liquidity: u128, delta: i128) -> Result<u128, ErrorCode> {
@@ -12,11 +14,46 @@ pub fn add_liquidity_delta(liquidity: u128, delta: i128) -> Result<u128, ErrorCo
             .ok_or(ErrorCode::LiquidityOverflow)
     } else {
         liquidity
-            .checked_sub(delta.abs() as u128)
+            .checked_sub(delta.unsigned_abs())
             .ok_or(ErrorCode::LiquidityUnderflow)
     }
 }
 
+// Applies the liquidity_net of a tick crossed in the swap direction to the active liquidity.
+// liquidity_net is added going right (b to a) and subtracted going left (a to b).
+// Errors on overflow or underflow.
+pub fn apply_liquidity_net(
+    liquidity: u128,
+    liquidity_net: i128,
+    a_to_b: bool,
+) -> Result<u128, ErrorCode> {
+    if a_to_b == (liquidity_net > 0) {
+        liquidity
+            .checked_sub(liquidity_net.unsigned_abs())
+            .ok_or(ErrorCode::LiquidityUnderflow)
+    } else {
+        liquidity
+            .checked_add(liquidity_net.unsigned_abs())
+            .ok_or(ErrorCode::LiquidityOverflow)
+    }
+}
+
+// Updates the liquidity_net of a position boundary tick for a liquidity delta of the position.
+// The delta is added on the lower tick and subtracted on the upper tick.
+// Errors if liquidity_net leaves the i128 range.
+pub fn update_liquidity_net(
+    liquidity_net: i128,
+    liquidity_delta: i128,
+    is_upper_tick: bool,
+) -> Result<i128, ErrorCode> {
+    if is_upper_tick {
+        liquidity_net.checked_sub(liquidity_delta)
+    } else {
+        liquidity_net.checked_add(liquidity_delta)
+    }
+    .ok_or(ErrorCode::LiquidityNetError)
+}
+
 // Converts an unsigned liquidity amount to a signed liquidity delta
 pub fn convert_to_liquidity_delta(
     liquidity_amount: u128,
@@ -35,10 +72,51 @@ pub fn convert_to_liquidity_delta(
     })
 }
 
+// Liquidity provided by an amount of token A over a price range, rounded down.
+// L = Δt_a * sqrt_price_lower * sqrt_price_upper / (sqrt_price_upper - sqrt_price_lower)
+// Saturates at u128::MAX.
+pub fn get_liquidity_from_amount_a(
+    sqrt_price_0: u128,
+    sqrt_price_1: u128,
+    amount_a: u64,
+) -> Result<u128, ErrorCode> {
+    let (sqrt_price_lower, sqrt_price_upper) = increasing_price_order(sqrt_price_0, sqrt_price_1);
+    let sqrt_price_diff = sqrt_price_upper - sqrt_price_lower;
+    if sqrt_price_diff == 0 {
+        return Err(ErrorCode::DivideByZero);
+    }
+
+    let numerator = mul_u256(sqrt_price_lower, sqrt_price_upper)
+        .shift_word_right()
+        .mul(U256Muldiv::new(0, amount_a as u128));
+    let (liquidity, _) = numerator.div(U256Muldiv::new(0, sqrt_price_diff), false);
+
+    Ok(liquidity.try_into_u128().unwrap_or(u128::MAX))
+}
+
+// Liquidity provided by an amount of token B over a price range, rounded down.
+// L = Δt_b / (sqrt_price_upper - sqrt_price_lower)
+pub fn get_liquidity_from_amount_b(
+    sqrt_price_0: u128,
+    sqrt_price_1: u128,
+    amount_b: u64,
+) -> Result<u128, ErrorCode> {
+    let (sqrt_price_lower, sqrt_price_upper) = increasing_price_order(sqrt_price_0, sqrt_price_1);
+    let sqrt_price_diff = sqrt_price_upper - sqrt_price_lower;
+    if sqrt_price_diff == 0 {
+        return Err(ErrorCode::DivideByZero);
+    }
+
+    Ok(((amount_b as u128) << 64) / sqrt_price_diff)
+}
+
 #[cfg(test)]
 mod liquidity_math_tests {
-    use super::add_liquidity_delta;
     use super::ErrorCode;
+    use super::{
+        add_liquidity_delta, apply_liquidity_net, get_liquidity_from_amount_a,
+        get_liquidity_from_amount_b, update_liquidity_net,
+    };
 
     #[test]
     fn test_valid_add_liquidity_delta() {
@@ -58,4 +136,76 @@ mod liquidity_math_tests {
         let result = add_liquidity_delta(u128::MIN, -1);
         assert_eq!(result.unwrap_err(), ErrorCode::LiquidityUnderflow);
     }
+
+    #[test]
+    fn test_add_liquidity_delta_min_delta() {
+        assert_eq!(
+            add_liquidity_delta(u128::MAX, i128::MIN).unwrap(),
+            u128::MAX >> 1
+        );
+        let result = add_liquidity_delta(i128::MAX as u128, i128::MIN);
+        assert_eq!(result.unwrap_err(), ErrorCode::LiquidityUnderflow);
+    }
+
+    #[test]
+    fn test_apply_liquidity_net() {
+        assert_eq!(apply_liquidity_net(100, 40, false).unwrap(), 140);
+        assert_eq!(apply_liquidity_net(100, 40, true).unwrap(), 60);
+        assert_eq!(apply_liquidity_net(100, -40, false).unwrap(), 60);
+        assert_eq!(apply_liquidity_net(100, -40, true).unwrap(), 140);
+        assert_eq!(apply_liquidity_net(100, 0, true).unwrap(), 100);
+        assert_eq!(
+            apply_liquidity_net(u128::MAX, i128::MIN, false).unwrap(),
+            u128::MAX >> 1
+        );
+    }
+
+    #[test]
+    fn test_invalid_apply_liquidity_net() {
+        let result = apply_liquidity_net(u128::MAX, i128::MIN, true);
+        assert_eq!(result.unwrap_err(), ErrorCode::LiquidityOverflow);
+        let result = apply_liquidity_net(0, i128::MIN, false);
+        assert_eq!(result.unwrap_err(), ErrorCode::LiquidityUnderflow);
+        let result = apply_liquidity_net(10, 11, true);
+        assert_eq!(result.unwrap_err(), ErrorCode::LiquidityUnderflow);
+    }
+
+    #[test]
+    fn test_update_liquidity_net() {
+        assert_eq!(update_liquidity_net(0, 100, false).unwrap(), 100);
+        assert_eq!(update_liquidity_net(0, 100, true).unwrap(), -100);
+        assert_eq!(update_liquidity_net(-100, -100, true).unwrap(), 0);
+        let result = update_liquidity_net(i128::MAX, 1, false);
+        assert_eq!(result.unwrap_err(), ErrorCode::LiquidityNetError);
+        let result = update_liquidity_net(i128::MIN, 1, true);
+        assert_eq!(result.unwrap_err(), ErrorCode::LiquidityNetError);
+    }
+
+    #[test]
+    fn test_liquidity_from_amounts_round_trip() {
+        use crate::math::{get_amount_delta_a, get_amount_delta_b, sqrt_price_from_tick_index};
+
+        let sqrt_price_lower = sqrt_price_from_tick_index(-1000);
+        let sqrt_price_upper = sqrt_price_from_tick_index(2000);
+
+        let liquidity =
+            get_liquidity_from_amount_a(sqrt_price_lower, sqrt_price_upper, 1_000_000).unwrap();
+        let amount_a =
+            get_amount_delta_a(sqrt_price_lower, sqrt_price_upper, liquidity, true).unwrap();
+        assert!(amount_a <= 1_000_000 && amount_a > 999_990);
+
+        let liquidity =
+            get_liquidity_from_amount_b(sqrt_price_lower, sqrt_price_upper, 1_000_000).unwrap();
+        let amount_b =
+            get_amount_delta_b(sqrt_price_lower, sqrt_price_upper, liquidity, true).unwrap();
+        assert!(amount_b <= 1_000_000 && amount_b > 999_990);
+    }
+
+    #[test]
+    fn test_liquidity_from_amounts_empty_range() {
+        assert_eq!(
+            get_liquidity_from_amount_b(1 << 64, 1 << 64, 1).unwrap_err(),
+            ErrorCode::DivideByZero
+        );
+    }
 }
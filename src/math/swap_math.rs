@@ -14,7 +14,7 @@ pub struct SwapStepComputation {
 
 pub fn compute_swap(
     amount_remaining: u64,
-    fee_rate: u16,
+    fee_rate: u32,
     liquidity: u128,
     sqrt_price_current: u128,
     sqrt_price_target: u128,
@@ -162,7 +162,7 @@ mod fuzz_tests {
         fn test_compute_swap(
             amount in 1..u64::MAX,
             liquidity in 1..u32::MAX as u128,
-            fee_rate in 1..u16::MAX,
+            fee_rate in 1..MAX_FEE_RATE,
             price_0 in MIN_SQRT_PRICE_X64..MAX_SQRT_PRICE_X64,
             price_1 in MIN_SQRT_PRICE_X64..MAX_SQRT_PRICE_X64,
             amount_specified_is_input in proptest::bool::ANY,
@@ -212,7 +212,7 @@ mod fuzz_tests {
         fn test_compute_swap_inversion(
             amount in 1..u64::MAX,
             liquidity in 1..u32::MAX as u128,
-            fee_rate in 1..u16::MAX,
+            fee_rate in 1..MAX_FEE_RATE,
             price_0 in MIN_SQRT_PRICE_X64..MAX_SQRT_PRICE_X64,
             price_1 in MIN_SQRT_PRICE_X64..MAX_SQRT_PRICE_X64,
             amount_specified_is_input in proptest::bool::ANY,
@@ -555,7 +555,7 @@ mod unit_tests {
     }
 
     mod test_compute_swap {
-        const TWO_PCT: u16 = 20000;
+        const TWO_PCT: u32 = 200_000;
         use std::convert::TryInto;
 
         use super::*;
@@ -570,7 +570,7 @@ mod unit_tests {
             let price_limit = 4;
 
             // Calculate fee given fee percentage
-            let fee_amount = div_round_up((amount * u128::from(TWO_PCT)).into(), 1_000_000)
+            let fee_amount = div_round_up((amount * u128::from(TWO_PCT)).into(), 10_000_000)
                 .ok()
                 .unwrap();
 
@@ -921,7 +921,7 @@ mod unit_tests {
 
     fn test_swap(
         amount_remaining: u64,
-        fee_rate: u16,
+        fee_rate: u32,
         liquidity: u128,
         sqrt_price_current: u128,
         sqrt_price_target_limit: u128,
@@ -1,4 +1,5 @@
 use crate::math::u256_math::*;
+use crate::state::{MAX_TICK_INDEX, MIN_TICK_INDEX};
 use std::convert::TryInto;
 
 // Max/Min sqrt_price derived from max/min tick-index
@@ -232,6 +233,55 @@ fn get_sqrt_price_negative_tick(tick: i32) -> u128 {
     ratio
 }
 
+/// Direction to move a tick index that is not a multiple of the tick spacing.
+#[derive(Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(not(target_arch = "bpf"), derive(Debug))]
+pub enum TickAlignment {
+    Down,
+    Up,
+}
+
+/// Add a number of ticks to a tick index, saturating at the max/min tick-index.
+pub fn add_ticks_saturating(tick_index: i32, ticks: i32) -> i32 {
+    tick_index
+        .saturating_add(ticks)
+        .clamp(MIN_TICK_INDEX, MAX_TICK_INDEX)
+}
+
+/// Align a tick index to a multiple of the tick spacing, towards negative infinity for `Down`
+/// and positive infinity for `Up`. Tick indexes already aligned are returned as is.
+pub fn align_tick_to_spacing(tick_index: i32, tick_spacing: u16, direction: TickAlignment) -> i32 {
+    let tick_spacing = tick_spacing as i32;
+    let aligned_down = tick_index.div_euclid(tick_spacing) * tick_spacing;
+    match direction {
+        TickAlignment::Up if aligned_down != tick_index => aligned_down + tick_spacing,
+        _ => aligned_down,
+    }
+}
+
+#[cfg(test)]
+mod tick_alignment_tests {
+    use super::*;
+
+    #[test]
+    fn test_add_ticks_saturating() {
+        assert_eq!(add_ticks_saturating(100, -64), 36);
+        assert_eq!(add_ticks_saturating(MAX_TICK_INDEX - 1, 64), MAX_TICK_INDEX);
+        assert_eq!(add_ticks_saturating(MIN_TICK_INDEX + 1, -64), MIN_TICK_INDEX);
+        assert_eq!(add_ticks_saturating(i32::MIN, i32::MIN), MIN_TICK_INDEX);
+    }
+
+    #[test]
+    fn test_align_tick_to_spacing() {
+        assert_eq!(align_tick_to_spacing(130, 64, TickAlignment::Down), 128);
+        assert_eq!(align_tick_to_spacing(130, 64, TickAlignment::Up), 192);
+        assert_eq!(align_tick_to_spacing(-130, 64, TickAlignment::Down), -192);
+        assert_eq!(align_tick_to_spacing(-130, 64, TickAlignment::Up), -128);
+        assert_eq!(align_tick_to_spacing(-128, 64, TickAlignment::Down), -128);
+        assert_eq!(align_tick_to_spacing(-128, 64, TickAlignment::Up), -128);
+    }
+}
+
 #[cfg(test)]
 mod fuzz_tests {
 
@@ -6,14 +6,20 @@ use super::{
     U256Muldiv, MAX_SQRT_PRICE_X64, MIN_SQRT_PRICE_X64,
 };
 
-// Fee rate is represented as hundredths of a basis point.
-// Fee amount = total_amount * fee_rate / 1_000_000.
+// Fee rate is represented as thousandths of a basis point.
+// Fee amount = total_amount * fee_rate / 10_000_000.
 // Max fee rate supported is 1%.
-pub const MAX_FEE_RATE: u16 = 10_000;
+pub const MAX_FEE_RATE: u32 = 100_000;
 
-// Assuming that FEE_RATE is represented as hundredths of a basis point
-// We want FEE_RATE_MUL_VALUE = 1/FEE_RATE_UNIT, so 1e6
-pub const FEE_RATE_MUL_VALUE: u128 = 1_000_000;
+// Assuming that FEE_RATE is represented as thousandths of a basis point
+// We want FEE_RATE_MUL_VALUE = 1/FEE_RATE_UNIT, so 1e7
+pub const FEE_RATE_MUL_VALUE: u128 = 10_000_000;
+
+// Legacy fee rates (fee tier defaults, fee holidays and the `fee_rate` field of Whirlpools) are
+// represented as hundredths of a basis point, so one legacy unit is 10 fee rate units.
+// Max legacy fee rate supported is 1%.
+pub const MAX_LEGACY_FEE_RATE: u16 = 10_000;
+pub const LEGACY_FEE_RATE_MULTIPLIER: u32 = 10;
 
 // Protocol fee rate is represented as a basis point.
 // Protocol fee amount = fee_amount * protocol_fee_rate / 10_000.
@@ -24,6 +30,31 @@ pub const MAX_PROTOCOL_FEE_RATE: u16 = 2_500;
 // We want PROTOCOL_FEE_RATE_MUL_VALUE = 1/PROTOCOL_FEE_UNIT, so 1e4
 pub const PROTOCOL_FEE_RATE_MUL_VALUE: u128 = 10_000;
 
+// Referral fee rate is represented as a basis point of the protocol fee.
+// Referral fee amount = protocol_fee * referral_fee_rate / 10_000.
+// Max referral fee rate supported is 50% of the protocol fee.
+pub const MAX_REFERRAL_FEE_RATE: u16 = 5_000;
+
+// Auto compound tip rate is represented as a basis point of the compounded fees.
+// Tip amount = fee_owed * auto_compound_tip_rate / 10_000.
+// Max auto compound tip rate supported is 10% of the fees.
+pub const MAX_AUTO_COMPOUND_TIP_RATE: u16 = 1_000;
+
+// Router fee discount rate is represented as a basis point of the fee rate.
+// Discounted fee rate = fee_rate - fee_rate * router_fee_discount_rate / 10_000.
+// Max router fee discount rate supported is 20% of the fee rate.
+pub const MAX_ROUTER_FEE_DISCOUNT_RATE: u16 = 2_000;
+
+// JIT surcharge rate is represented as a basis point of the removed token amounts.
+// Surcharge amount = removed_amount * jit_surcharge_rate / 10_000.
+// Max JIT surcharge rate supported is 10% of the removed amounts.
+pub const MAX_JIT_SURCHARGE_RATE: u16 = 1_000;
+
+// Reward tenure boost base rate is represented as a basis point of the rewards accrued in range.
+// Boosted reward = reward * (base_rate + (10_000 - base_rate) * tenure / period) / 10_000.
+// A base rate of 10_000 pays the full rewards without any tenure.
+pub const REWARD_TENURE_BOOST_RATE_MUL_VALUE: u16 = 10_000;
+
 //
 // Get change in token_a corresponding to a change in price
 //
@@ -63,7 +94,7 @@ pub fn get_amount_delta_a(
     };
 
     if result > u64::MAX as u128 {
-        return Err(ErrorCode::TokenMaxExceeded);
+        return Err(ErrorCode::AmountExceedsU64);
     }
 
     return Ok(result as u64);
@@ -91,6 +122,29 @@ pub fn get_amount_delta_b(
     checked_mul_shift_right_round_up_if(liquidity, price_upper - price_lower, round_up)
 }
 
+// Rounding dust of a token delta, the difference between the delta rounded up and rounded down.
+// The pool always rounds in its own favor, so this is the amount it retains over the exact delta.
+// Deltas that cannot be computed in either direction are reported as zero dust.
+pub fn get_amount_delta_rounding_dust(
+    sqrt_price_0: u128,
+    sqrt_price_1: u128,
+    liquidity: u128,
+    is_token_a: bool,
+) -> u64 {
+    let get_amount_delta = if is_token_a {
+        get_amount_delta_a
+    } else {
+        get_amount_delta_b
+    };
+    match (
+        get_amount_delta(sqrt_price_0, sqrt_price_1, liquidity, true),
+        get_amount_delta(sqrt_price_0, sqrt_price_1, liquidity, false),
+    ) {
+        (Ok(rounded_up), Ok(rounded_down)) => rounded_up - rounded_down,
+        _ => 0,
+    }
+}
+
 pub fn increasing_price_order(sqrt_price_0: u128, sqrt_price_1: u128) -> (u128, u128) {
     if sqrt_price_0 > sqrt_price_1 {
         (sqrt_price_1, sqrt_price_0)
@@ -99,6 +153,22 @@ pub fn increasing_price_order(sqrt_price_0: u128, sqrt_price_1: u128) -> (u128,
     }
 }
 
+// Fee rate after waiving `fee_discount_rate` basis points of it, rounding the discount down.
+pub fn discounted_fee_rate(fee_rate: u32, fee_discount_rate: u16) -> u32 {
+    let discount = fee_rate as u128 * fee_discount_rate as u128 / PROTOCOL_FEE_RATE_MUL_VALUE;
+    fee_rate - discount as u32
+}
+
+// Fee rate of a legacy fee rate expressed in hundredths of a basis point.
+pub fn fee_rate_from_legacy(legacy_fee_rate: u16) -> u32 {
+    legacy_fee_rate as u32 * LEGACY_FEE_RATE_MULTIPLIER
+}
+
+// Legacy fee rate of a fee rate, rounding down the thousandths of a basis point it cannot express.
+pub fn fee_rate_to_legacy(fee_rate: u32) -> u16 {
+    (fee_rate / LEGACY_FEE_RATE_MULTIPLIER).min(u16::MAX as u32) as u16
+}
+
 //
 // Get change in price corresponding to a change in token_a supply
 //
@@ -145,9 +215,9 @@ pub fn get_next_sqrt_price_from_a_round_up(
 
     let price = div_round_up_if_u256(numerator, denominator, true)?;
     if price < MIN_SQRT_PRICE_X64 {
-        return Err(ErrorCode::TokenMinSubceeded);
+        return Err(ErrorCode::PriceBelowMinimum);
     } else if price > MAX_SQRT_PRICE_X64 {
-        return Err(ErrorCode::TokenMaxExceeded);
+        return Err(ErrorCode::PriceAboveMaximum);
     }
 
     Ok(price)
@@ -262,3 +332,67 @@ pub fn get_next_sqrt_price(
         )
     }
 }
+
+#[cfg(test)]
+mod token_math_error_tests {
+    use super::*;
+
+    #[test]
+    fn test_discounted_fee_rate() {
+        assert_eq!(discounted_fee_rate(30_000, 0), 30_000);
+        assert_eq!(discounted_fee_rate(30_000, MAX_ROUTER_FEE_DISCOUNT_RATE), 24_000);
+        assert_eq!(discounted_fee_rate(3, 1_000), 3);
+        assert_eq!(discounted_fee_rate(MAX_FEE_RATE, 10_000), 0);
+    }
+
+    #[test]
+    fn test_legacy_fee_rate_conversion() {
+        assert_eq!(fee_rate_from_legacy(3_000), 30_000);
+        assert_eq!(fee_rate_from_legacy(MAX_LEGACY_FEE_RATE), MAX_FEE_RATE);
+        assert_eq!(fee_rate_to_legacy(30_000), 3_000);
+        assert_eq!(fee_rate_to_legacy(30), 3);
+        assert_eq!(fee_rate_to_legacy(3), 0);
+        assert_eq!(fee_rate_to_legacy(u32::MAX), u16::MAX);
+    }
+
+    #[test]
+    fn test_get_amount_delta_a_exceeds_u64() {
+        let result = get_amount_delta_a(MIN_SQRT_PRICE_X64, MAX_SQRT_PRICE_X64, 1 << 64, true);
+        assert_eq!(result.unwrap_err(), ErrorCode::AmountExceedsU64);
+    }
+
+    #[test]
+    fn test_get_amount_delta_rounding_dust() {
+        let sqrt_price_lower = 1 << 64;
+        let sqrt_price_upper = (1 << 64) + 1;
+        assert_eq!(
+            get_amount_delta_rounding_dust(sqrt_price_lower, sqrt_price_upper, 1 << 63, false),
+            1
+        );
+        assert_eq!(
+            get_amount_delta_rounding_dust(sqrt_price_lower, sqrt_price_upper, 1 << 64, false),
+            0
+        );
+        assert_eq!(
+            get_amount_delta_rounding_dust(sqrt_price_lower, sqrt_price_upper, 1 << 63, true),
+            1
+        );
+        assert_eq!(
+            get_amount_delta_rounding_dust(MIN_SQRT_PRICE_X64, MAX_SQRT_PRICE_X64, 1 << 64, true),
+            0
+        );
+    }
+
+    #[test]
+    fn test_get_next_sqrt_price_from_a_below_minimum() {
+        let result = get_next_sqrt_price_from_a_round_up(MIN_SQRT_PRICE_X64, 1, u64::MAX, true);
+        assert_eq!(result.unwrap_err(), ErrorCode::PriceBelowMinimum);
+    }
+
+    #[test]
+    fn test_get_next_sqrt_price_from_a_above_maximum() {
+        let result =
+            get_next_sqrt_price_from_a_round_up(MAX_SQRT_PRICE_X64, u64::MAX as u128, 1, false);
+        assert_eq!(result.unwrap_err(), ErrorCode::PriceAboveMaximum);
+    }
+}
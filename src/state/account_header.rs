@@ -0,0 +1,49 @@
+use anchor_lang::prelude::*;
+
+// Schema version written into newly created tick arrays and positions. Accounts created before
+// the header existed read as version 0.
+pub const ACCOUNT_SCHEMA_VERSION: u8 = 1;
+
+/// Identifying header of an account that belongs to a pool, so raw account data can be
+/// attributed without knowing how it was derived.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct AccountHeader {
+    pub schema_version: u8,
+    pub whirlpool: Pubkey,
+}
+
+impl AccountHeader {
+    /// Returns true if the account belongs to the whirlpool and was written with a schema this
+    /// program understands.
+    pub fn belongs_to(&self, whirlpool: &Pubkey) -> bool {
+        self.whirlpool == *whirlpool && self.schema_version <= ACCOUNT_SCHEMA_VERSION
+    }
+}
+
+#[cfg(test)]
+mod account_header_tests {
+    use super::*;
+
+    #[test]
+    fn test_belongs_to() {
+        let whirlpool = Pubkey::new_unique();
+        let header = AccountHeader {
+            schema_version: ACCOUNT_SCHEMA_VERSION,
+            whirlpool,
+        };
+        assert!(header.belongs_to(&whirlpool));
+        assert!(!header.belongs_to(&Pubkey::new_unique()));
+
+        let legacy = AccountHeader {
+            schema_version: 0,
+            ..header
+        };
+        assert!(legacy.belongs_to(&whirlpool));
+
+        let unknown = AccountHeader {
+            schema_version: ACCOUNT_SCHEMA_VERSION + 1,
+            ..header
+        };
+        assert!(!unknown.belongs_to(&whirlpool));
+    }
+}
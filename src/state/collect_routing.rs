@@ -0,0 +1,123 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::ErrorCode;
+
+pub const MAX_COLLECT_ROUTES: usize = 16;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, PartialEq)]
+#[cfg_attr(not(target_arch = "bpf"), derive(Debug))]
+pub struct CollectRoute {
+    pub mint: Pubkey,        // 32
+    pub destination: Pubkey, // 32
+}
+
+/// Token accounts an owner prefers fees and rewards of each mint to be collected into. When the
+/// routing is passed to a collect, a routed mint must be collected into its destination. Passing
+/// the routing is opt-in per collect, collects without it are not bound by it. Session keys of the
+/// owner may collect into a routed destination in addition to the token accounts of the owed
+/// destination of the position.
+#[account]
+#[derive(Default)]
+pub struct CollectRouting {
+    pub owner: Pubkey, // 32
+    // Unused slots hold the default route
+    pub routes: [CollectRoute; MAX_COLLECT_ROUTES], // 64 * 16
+}
+
+impl CollectRouting {
+    pub const LEN: usize = 8 + 32 + 64 * MAX_COLLECT_ROUTES;
+
+    pub fn address(owner: &Pubkey) -> Pubkey {
+        Pubkey::find_program_address(&[b"collect_routing", owner.as_ref()], &crate::ID).0
+    }
+
+    pub fn initialize(&mut self, owner: Pubkey) {
+        self.owner = owner;
+    }
+
+    /// Set the route at `index`. The default mint clears the slot. A mint can only be routed
+    /// once.
+    pub fn update_route(&mut self, index: usize, mint: Pubkey, destination: Pubkey) -> Result<()> {
+        if index >= MAX_COLLECT_ROUTES {
+            return Err(ErrorCode::InvalidCollectRoute.into());
+        }
+        if mint == Pubkey::default() {
+            self.routes[index] = CollectRoute::default();
+            return Ok(());
+        }
+        let routed_elsewhere = self
+            .routes
+            .iter()
+            .enumerate()
+            .any(|(i, route)| i != index && route.mint == mint);
+        if routed_elsewhere || destination == Pubkey::default() {
+            return Err(ErrorCode::InvalidCollectRoute.into());
+        }
+        self.routes[index] = CollectRoute { mint, destination };
+        Ok(())
+    }
+
+    /// Destination routed for `mint`, if any.
+    pub fn destination_for(&self, mint: &Pubkey) -> Option<Pubkey> {
+        if *mint == Pubkey::default() {
+            return None;
+        }
+        self.routes
+            .iter()
+            .find(|route| route.mint == *mint)
+            .map(|route| route.destination)
+    }
+
+    pub fn routes_to(&self, mint: &Pubkey, destination: &Pubkey) -> bool {
+        self.destination_for(mint) == Some(*destination)
+    }
+}
+
+#[cfg(test)]
+mod collect_routing_tests {
+    use super::*;
+
+    #[test]
+    fn test_routes_to() {
+        let mint = Pubkey::new_unique();
+        let destination = Pubkey::new_unique();
+        let mut routing = CollectRouting::default();
+        assert!(!routing.routes_to(&mint, &destination));
+        assert!(!routing.routes_to(&Pubkey::default(), &Pubkey::default()));
+
+        routing.update_route(5, mint, destination).unwrap();
+        assert_eq!(routing.destination_for(&mint), Some(destination));
+        assert!(routing.routes_to(&mint, &destination));
+        assert!(!routing.routes_to(&mint, &Pubkey::new_unique()));
+        assert!(!routing.routes_to(&Pubkey::new_unique(), &destination));
+
+        routing
+            .update_route(5, Pubkey::default(), destination)
+            .unwrap();
+        assert!(!routing.routes_to(&mint, &destination));
+    }
+
+    #[test]
+    fn test_update_route_rejects_invalid_routes() {
+        let mint = Pubkey::new_unique();
+        let mut routing = CollectRouting::default();
+        assert_eq!(
+            routing.update_route(MAX_COLLECT_ROUTES, mint, Pubkey::new_unique()),
+            Err(ErrorCode::InvalidCollectRoute.into())
+        );
+        assert_eq!(
+            routing.update_route(0, mint, Pubkey::default()),
+            Err(ErrorCode::InvalidCollectRoute.into())
+        );
+
+        routing.update_route(0, mint, Pubkey::new_unique()).unwrap();
+        assert_eq!(
+            routing.update_route(1, mint, Pubkey::new_unique()),
+            Err(ErrorCode::InvalidCollectRoute.into())
+        );
+        // Re-routing the mint in its own slot is allowed.
+        let destination = Pubkey::new_unique();
+        routing.update_route(0, mint, destination).unwrap();
+        assert_eq!(routing.destination_for(&mint), Some(destination));
+    }
+}
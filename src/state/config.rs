@@ -9,15 +9,128 @@ pub struct WhirlpoolsConfig {
     pub reward_emissions_super_authority: Pubkey,
 
     pub default_protocol_fee_rate: u16,
+
+    // Fee authority proposed by the current fee authority, pending acceptance
+    pub pending_fee_authority: Pubkey,
+
+    // Liquidator allowed to settle positions delegated to it, e.g. the PDA of a money market
+    pub approved_liquidator: Pubkey,
+
+    // URI of the metadata of position NFTs, with `{mint}` replaced by the position mint. Empty
+    // for the default position metadata URI
+    pub position_metadata_uri_template: String,
+
+    // Cold storage authority that can only pause Whirlpools and freeze the admin parameters, so a
+    // compromised fee authority can be stopped without an authority able to move funds
+    pub emergency_authority: Pubkey,
+    // Set by the emergency authority to reject parameter changes by the fee authority
+    pub admin_frozen: bool,
+    // Emergency authority proposed by the current emergency authority, or by the fee authority
+    // while none is set, pending acceptance
+    pub pending_emergency_authority: Pubkey,
 }
 
+// Maximum length of a position metadata URI template. Rendered with a position mint it stays
+// within the 200 bytes Metaplex allows for a metadata URI.
+pub const MAX_POSITION_METADATA_URI_TEMPLATE_LEN: usize = 128;
+
+// Placeholder of a position metadata URI template replaced by the position mint
+pub const POSITION_METADATA_URI_MINT_PLACEHOLDER: &str = "{mint}";
+
 impl WhirlpoolsConfig {
-    pub const LEN: usize = 8 + 96 + 4;
+    pub const LEN: usize =
+        8 + 96 + 4 + 32 + 32 + 4 + MAX_POSITION_METADATA_URI_TEMPLATE_LEN + 32 + 1 + 32;
 
     pub fn update_fee_authority(&mut self, fee_authority: Pubkey) {
         self.fee_authority = fee_authority;
     }
 
+    /// Propose a new fee authority. The fee authority only changes once the proposed authority
+    /// accepts. Proposing the default pubkey cancels a pending proposal.
+    pub fn propose_fee_authority(&mut self, fee_authority: Pubkey) {
+        self.pending_fee_authority = fee_authority;
+    }
+
+    /// Make the pending fee authority the fee authority.
+    pub fn accept_fee_authority(&mut self) -> Result<()> {
+        if self.pending_fee_authority == Pubkey::default() {
+            return Err(ErrorCode::NoPendingAuthority.into());
+        }
+        self.update_fee_authority(self.pending_fee_authority);
+        self.pending_fee_authority = Pubkey::default();
+
+        Ok(())
+    }
+
+    /// Set the position metadata URI template. The template can hold the mint placeholder at most
+    /// once, an empty template restores the default URI.
+    pub fn update_position_metadata_uri_template(&mut self, uri_template: String) -> Result<()> {
+        if uri_template.len() > MAX_POSITION_METADATA_URI_TEMPLATE_LEN
+            || uri_template
+                .matches(POSITION_METADATA_URI_MINT_PLACEHOLDER)
+                .count()
+                > 1
+        {
+            return Err(ErrorCode::InvalidPositionMetadataUriTemplate.into());
+        }
+        self.position_metadata_uri_template = uri_template;
+
+        Ok(())
+    }
+
+    /// Metadata URI of the position NFT of a position mint, None when the config uses the default
+    /// URI.
+    pub fn position_metadata_uri(&self, position_mint: &Pubkey) -> Option<String> {
+        if self.position_metadata_uri_template.is_empty() {
+            return None;
+        }
+        Some(self.position_metadata_uri_template.replace(
+            POSITION_METADATA_URI_MINT_PLACEHOLDER,
+            &position_mint.to_string(),
+        ))
+    }
+
+    /// Propose a new emergency authority. The fee authority can only propose the first emergency
+    /// authority, afterwards only the emergency authority can hand over its role. The emergency
+    /// authority only changes once the proposed authority accepts. Proposing the default pubkey
+    /// cancels a pending proposal.
+    pub fn propose_emergency_authority(
+        &mut self,
+        signer: &Pubkey,
+        emergency_authority: Pubkey,
+    ) -> Result<()> {
+        let allowed = if self.emergency_authority == Pubkey::default() {
+            *signer == self.fee_authority
+        } else {
+            *signer == self.emergency_authority
+        };
+        if !allowed {
+            return Err(ErrorCode::InvalidEmergencyAuthority.into());
+        }
+        self.pending_emergency_authority = emergency_authority;
+
+        Ok(())
+    }
+
+    /// Make the pending emergency authority the emergency authority.
+    pub fn accept_emergency_authority(&mut self) -> Result<()> {
+        if self.pending_emergency_authority == Pubkey::default() {
+            return Err(ErrorCode::NoPendingAuthority.into());
+        }
+        self.emergency_authority = self.pending_emergency_authority;
+        self.pending_emergency_authority = Pubkey::default();
+
+        Ok(())
+    }
+
+    pub fn update_admin_frozen(&mut self, admin_frozen: bool) {
+        self.admin_frozen = admin_frozen;
+    }
+
+    pub fn update_approved_liquidator(&mut self, approved_liquidator: Pubkey) {
+        self.approved_liquidator = approved_liquidator;
+    }
+
     pub fn update_collect_protocol_fees_authority(
         &mut self,
         collect_protocol_fees_authority: Pubkey,
@@ -59,3 +172,91 @@ impl WhirlpoolsConfig {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod config_tests {
+    use super::*;
+
+    fn test_config(fee_authority: Pubkey) -> WhirlpoolsConfig {
+        WhirlpoolsConfig {
+            fee_authority,
+            collect_protocol_fees_authority: Pubkey::default(),
+            reward_emissions_super_authority: Pubkey::default(),
+            default_protocol_fee_rate: 0,
+            pending_fee_authority: Pubkey::default(),
+            approved_liquidator: Pubkey::default(),
+            position_metadata_uri_template: String::new(),
+            emergency_authority: Pubkey::default(),
+            admin_frozen: false,
+            pending_emergency_authority: Pubkey::default(),
+        }
+    }
+
+    #[test]
+    fn test_position_metadata_uri_template() {
+        let mut config = test_config(Pubkey::default());
+        let position_mint = Pubkey::new_unique();
+        assert_eq!(config.position_metadata_uri(&position_mint), None);
+
+        config
+            .update_position_metadata_uri_template("https://cdn.example/{mint}.json".to_string())
+            .unwrap();
+        assert_eq!(
+            config.position_metadata_uri(&position_mint),
+            Some(format!("https://cdn.example/{}.json", position_mint))
+        );
+
+        assert_eq!(
+            config.update_position_metadata_uri_template("{mint}/{mint}".to_string()),
+            Err(ErrorCode::InvalidPositionMetadataUriTemplate.into())
+        );
+        assert_eq!(
+            config.update_position_metadata_uri_template(
+                "a".repeat(MAX_POSITION_METADATA_URI_TEMPLATE_LEN + 1)
+            ),
+            Err(ErrorCode::InvalidPositionMetadataUriTemplate.into())
+        );
+    }
+
+    #[test]
+    fn test_propose_and_accept_emergency_authority() {
+        let fee_authority = Pubkey::new_unique();
+        let emergency_authority = Pubkey::new_unique();
+        let mut config = test_config(fee_authority);
+        assert_eq!(
+            config.propose_emergency_authority(&emergency_authority, emergency_authority),
+            Err(ErrorCode::InvalidEmergencyAuthority.into())
+        );
+        assert_eq!(
+            config.accept_emergency_authority(),
+            Err(ErrorCode::NoPendingAuthority.into())
+        );
+        config
+            .propose_emergency_authority(&fee_authority, emergency_authority)
+            .unwrap();
+        assert_eq!(config.emergency_authority, Pubkey::default());
+        config.accept_emergency_authority().unwrap();
+        assert_eq!(config.emergency_authority, emergency_authority);
+        assert_eq!(config.pending_emergency_authority, Pubkey::default());
+
+        // The fee authority cannot replace the emergency authority once it is set.
+        assert_eq!(
+            config.propose_emergency_authority(&fee_authority, fee_authority),
+            Err(ErrorCode::InvalidEmergencyAuthority.into())
+        );
+        let next_emergency_authority = Pubkey::new_unique();
+        config
+            .propose_emergency_authority(&emergency_authority, next_emergency_authority)
+            .unwrap();
+        // The role stays with the current emergency authority until the proposal is accepted.
+        assert_eq!(config.emergency_authority, emergency_authority);
+        config
+            .propose_emergency_authority(&emergency_authority, Pubkey::default())
+            .unwrap();
+        assert_eq!(
+            config.accept_emergency_authority(),
+            Err(ErrorCode::NoPendingAuthority.into())
+        );
+        assert_eq!(config.emergency_authority, emergency_authority);
+    }
+}
@@ -0,0 +1,189 @@
+use anchor_lang::{prelude::*, Discriminator};
+
+use crate::{
+    errors::ErrorCode,
+    math::{fee_rate_from_legacy, MAX_FEE_RATE},
+};
+
+// Number of fee holidays a fee schedule can hold at once
+pub const MAX_FEE_HOLIDAYS: usize = 4;
+
+/// Temporary fee rate of a Whirlpool, applied to swaps from the start timestamp until before the
+/// end timestamp.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, PartialEq)]
+#[cfg_attr(not(target_arch = "bpf"), derive(Debug))]
+pub struct FeeHoliday {
+    pub start_timestamp: u64, // 8
+    pub end_timestamp: u64,   // 8
+    // Stored as thousandths of a basis point
+    pub fee_rate: u32, // 4
+}
+
+impl FeeHoliday {
+    pub const LEN: usize = 20;
+
+    pub fn is_active(&self, timestamp: u64) -> bool {
+        self.start_timestamp <= timestamp && timestamp < self.end_timestamp
+    }
+}
+
+/// Fee reductions programmed ahead by the fee authority of a Whirlpool, e.g. for a campaign.
+/// Swaps of the Whirlpool pay the lowest of its fee rate and the rates of the active holidays.
+#[account]
+#[derive(Default)]
+pub struct FeeSchedule {
+    pub whirlpool: Pubkey,                        // 32
+    pub holidays: [FeeHoliday; MAX_FEE_HOLIDAYS], // 80
+}
+
+/// Fee holiday of fee schedules created before the fee rate precision increase, with its fee rate
+/// in hundredths of a basis point.
+#[derive(AnchorDeserialize)]
+struct LegacyFeeHoliday {
+    start_timestamp: u64, // 8
+    end_timestamp: u64,   // 8
+    fee_rate: u16,        // 2
+}
+
+impl FeeSchedule {
+    pub const LEN: usize = 8 + 32 + FeeHoliday::LEN * MAX_FEE_HOLIDAYS;
+    pub const LEGACY_LEN: usize = 8 + 32 + 18 * MAX_FEE_HOLIDAYS;
+
+    pub fn initialize(&mut self, whirlpool: Pubkey) {
+        self.whirlpool = whirlpool;
+    }
+
+    /// Deserialize a fee schedule stored with the legacy layout, converting the fee rates of its
+    /// holidays to thousandths of a basis point.
+    pub fn try_from_legacy_data(data: &[u8]) -> Result<FeeSchedule> {
+        if data.len() != FeeSchedule::LEGACY_LEN || data[..8] != FeeSchedule::DISCRIMINATOR {
+            return Err(ErrorCode::InvalidFeeScheduleAccount.into());
+        }
+        let mut data = &data[8..];
+        let whirlpool = Pubkey::deserialize(&mut data)?;
+        let mut holidays = [FeeHoliday::default(); MAX_FEE_HOLIDAYS];
+        for holiday in holidays.iter_mut() {
+            let legacy_holiday = LegacyFeeHoliday::deserialize(&mut data)?;
+            *holiday = FeeHoliday {
+                start_timestamp: legacy_holiday.start_timestamp,
+                end_timestamp: legacy_holiday.end_timestamp,
+                fee_rate: fee_rate_from_legacy(legacy_holiday.fee_rate),
+            };
+        }
+
+        Ok(FeeSchedule {
+            whirlpool,
+            holidays,
+        })
+    }
+
+    /// Program a fee holiday in a slot of the schedule. A holiday with an end timestamp of 0
+    /// clears the slot.
+    pub fn update_holiday(&mut self, index: usize, holiday: FeeHoliday) -> Result<()> {
+        if index >= MAX_FEE_HOLIDAYS {
+            return Err(ErrorCode::InvalidFeeHoliday.into());
+        }
+        if holiday.end_timestamp != 0
+            && (holiday.start_timestamp >= holiday.end_timestamp || holiday.fee_rate > MAX_FEE_RATE)
+        {
+            return Err(ErrorCode::InvalidFeeHoliday.into());
+        }
+        self.holidays[index] = holiday;
+
+        Ok(())
+    }
+
+    /// Fee rate of swaps at the timestamp, never above the base fee rate of the Whirlpool.
+    pub fn fee_rate_at(&self, base_fee_rate: u32, timestamp: u64) -> u32 {
+        self.holidays
+            .iter()
+            .filter(|holiday| holiday.is_active(timestamp))
+            .map(|holiday| holiday.fee_rate)
+            .fold(base_fee_rate, u32::min)
+    }
+}
+
+#[cfg(test)]
+mod fee_schedule_tests {
+    use super::*;
+
+    fn holiday(start_timestamp: u64, end_timestamp: u64, fee_rate: u32) -> FeeHoliday {
+        FeeHoliday {
+            start_timestamp,
+            end_timestamp,
+            fee_rate,
+        }
+    }
+
+    #[test]
+    fn test_update_holiday() {
+        let mut schedule = FeeSchedule::default();
+        assert!(schedule.update_holiday(0, holiday(100, 200, 5_000)).is_ok());
+        assert!(schedule.update_holiday(0, FeeHoliday::default()).is_ok());
+        assert!(schedule
+            .update_holiday(MAX_FEE_HOLIDAYS, holiday(100, 200, 5_000))
+            .is_err());
+        assert!(schedule
+            .update_holiday(1, holiday(200, 200, 5_000))
+            .is_err());
+        assert!(schedule
+            .update_holiday(1, holiday(100, 200, MAX_FEE_RATE + 1))
+            .is_err());
+    }
+
+    #[test]
+    fn test_fee_rate_at() {
+        let mut schedule = FeeSchedule::default();
+        schedule
+            .update_holiday(0, holiday(100, 200, 10_000))
+            .unwrap();
+        schedule
+            .update_holiday(1, holiday(150, 300, 5_000))
+            .unwrap();
+        schedule
+            .update_holiday(2, holiday(400, 500, 50_000))
+            .unwrap();
+        schedule.update_holiday(3, holiday(600, 700, 1)).unwrap();
+
+        assert_eq!(schedule.fee_rate_at(30_000, 99), 30_000);
+        assert_eq!(schedule.fee_rate_at(30_000, 100), 10_000);
+        assert_eq!(schedule.fee_rate_at(30_000, 150), 5_000);
+        assert_eq!(schedule.fee_rate_at(30_000, 250), 5_000);
+        assert_eq!(schedule.fee_rate_at(30_000, 300), 30_000);
+        // Holidays never raise the fee rate
+        assert_eq!(schedule.fee_rate_at(30_000, 450), 30_000);
+        assert_eq!(schedule.fee_rate_at(30_000, 650), 1);
+    }
+
+    #[test]
+    fn test_try_from_legacy_data() {
+        let whirlpool = Pubkey::new_unique();
+        let mut data = FeeSchedule::DISCRIMINATOR.to_vec();
+        data.extend_from_slice(whirlpool.as_ref());
+        for (start_timestamp, end_timestamp, fee_rate) in [
+            (100u64, 200u64, 1000u16),
+            (150, 300, 500),
+            (0, 0, 0),
+            (400, 500, 10_000),
+        ] {
+            data.extend_from_slice(&start_timestamp.to_le_bytes());
+            data.extend_from_slice(&end_timestamp.to_le_bytes());
+            data.extend_from_slice(&fee_rate.to_le_bytes());
+        }
+
+        let schedule = FeeSchedule::try_from_legacy_data(&data).unwrap();
+        assert_eq!(schedule.whirlpool, whirlpool);
+        assert_eq!(schedule.holidays[0], holiday(100, 200, 10_000));
+        assert_eq!(schedule.holidays[1], holiday(150, 300, 5_000));
+        assert_eq!(schedule.holidays[2], FeeHoliday::default());
+        assert_eq!(schedule.holidays[3], holiday(400, 500, MAX_FEE_RATE));
+
+        let mut migrated = Vec::new();
+        schedule.try_serialize(&mut migrated).unwrap();
+        assert_eq!(migrated.len(), FeeSchedule::LEN);
+
+        assert!(FeeSchedule::try_from_legacy_data(&migrated).is_err());
+        data[0] ^= 1;
+        assert!(FeeSchedule::try_from_legacy_data(&data).is_err());
+    }
+}
@@ -1,11 +1,12 @@
 use crate::state::WhirlpoolsConfig;
-use crate::{errors::ErrorCode, math::MAX_FEE_RATE};
+use crate::{errors::ErrorCode, math::MAX_LEGACY_FEE_RATE};
 use anchor_lang::prelude::*;
 
 #[account]
 pub struct FeeTier {
     pub whirlpools_config: Pubkey,
     pub tick_spacing: u16,
+    // Stored as hundredths of a basis point
     pub default_fee_rate: u16,
 }
 
@@ -25,7 +26,7 @@ impl FeeTier {
     }
 
     pub fn update_default_fee_rate(&mut self, default_fee_rate: u16) -> Result<()> {
-        if default_fee_rate > MAX_FEE_RATE {
+        if default_fee_rate > MAX_LEGACY_FEE_RATE {
             return Err(ErrorCode::FeeRateMaxExceeded.into());
         }
         self.default_fee_rate = default_fee_rate;
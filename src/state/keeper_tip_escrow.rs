@@ -0,0 +1,60 @@
+use anchor_lang::prelude::*;
+
+/// Lamports escrow tipping the keepers that crank a Whirlpool, e.g. refresh its exchange rate or
+/// its tick cache. Each crank pays the base tip and refunds the priority fee of its transaction up
+/// to a cap. Anyone can fund it, only the authority can change the tips and withdraw what is
+/// left.
+#[account]
+#[derive(Default)]
+pub struct KeeperTipEscrow {
+    pub whirlpool: Pubkey, // 32
+    pub authority: Pubkey, // 32
+    // Lamports paid to the keeper of each crank
+    pub base_tip: u64, // 8
+    // Maximum lamports of the priority fee of a crank transaction refunded on top of the base tip
+    pub max_priority_fee_tip: u64, // 8
+}
+
+impl KeeperTipEscrow {
+    pub const LEN: usize = 8 + 64 + 16;
+
+    pub fn initialize(
+        &mut self,
+        whirlpool: Pubkey,
+        authority: Pubkey,
+        base_tip: u64,
+        max_priority_fee_tip: u64,
+    ) {
+        self.whirlpool = whirlpool;
+        self.authority = authority;
+        self.update_tips(base_tip, max_priority_fee_tip);
+    }
+
+    pub fn update_tips(&mut self, base_tip: u64, max_priority_fee_tip: u64) {
+        self.base_tip = base_tip;
+        self.max_priority_fee_tip = max_priority_fee_tip;
+    }
+
+    /// Tip of a crank whose transaction paid `priority_fee` lamports of priority fee.
+    pub fn tip_for(&self, priority_fee: u64) -> u64 {
+        self.base_tip
+            .saturating_add(priority_fee.min(self.max_priority_fee_tip))
+    }
+}
+
+#[cfg(test)]
+mod keeper_tip_escrow_tests {
+    use super::*;
+
+    #[test]
+    fn test_tip_for() {
+        let mut escrow = KeeperTipEscrow::default();
+        escrow.initialize(Pubkey::new_unique(), Pubkey::new_unique(), 5_000, 10_000);
+        assert_eq!(escrow.tip_for(0), 5_000);
+        assert_eq!(escrow.tip_for(4_000), 9_000);
+        assert_eq!(escrow.tip_for(50_000), 15_000);
+
+        escrow.update_tips(u64::MAX, 10_000);
+        assert_eq!(escrow.tip_for(50_000), u64::MAX);
+    }
+}
@@ -0,0 +1,45 @@
+use anchor_lang::prelude::*;
+
+use super::Position;
+
+/// Proof that a position's liquidity is locked, derived from the position address so that
+/// launch platforms can verify the lock length and size on-chain.
+#[account]
+#[derive(Default)]
+pub struct LiquidityLockReceipt {
+    pub whirlpool: Pubkey,         // 32
+    pub position: Pubkey,          // 32
+    pub locker: Pubkey,            // 32
+    pub locked_liquidity: u128,    // 16
+    pub lock_start_timestamp: u64, // 8
+    pub unlock_timestamp: u64,     // 8
+    pub bump: u8,                  // 1
+}
+
+impl LiquidityLockReceipt {
+    pub const LEN: usize = 8 + 96 + 16 + 16 + 1;
+
+    pub fn initialize(
+        &mut self,
+        position: &Account<Position>,
+        locker: Pubkey,
+        bump: u8,
+        timestamp: u64,
+        unlock_timestamp: u64,
+    ) {
+        self.whirlpool = position.whirlpool;
+        self.position = position.key();
+        self.locker = locker;
+        self.bump = bump;
+        self.update(position.liquidity, timestamp, unlock_timestamp);
+    }
+
+    pub fn update(&mut self, locked_liquidity: u128, timestamp: u64, unlock_timestamp: u64) {
+        // An expired lock that is extended starts a new lock period.
+        if self.unlock_timestamp <= timestamp {
+            self.lock_start_timestamp = timestamp;
+        }
+        self.locked_liquidity = locked_liquidity;
+        self.unlock_timestamp = unlock_timestamp;
+    }
+}
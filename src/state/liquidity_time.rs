@@ -0,0 +1,77 @@
+use anchor_lang::prelude::*;
+
+use super::Position;
+
+/// Cumulative liquidity multiplied by seconds in range of the positions of an owner in a
+/// Whirlpool, so governance can weight votes or airdrops by LP contribution without an indexer.
+///
+/// Time is accrued per position from its previous accrual, and counts as in range if the position
+/// is in range when it is accrued. Accruals happen on liquidity changes and fee updates of the
+/// position, so the accumulator is an approximation that improves with the update frequency.
+#[account]
+#[derive(Default)]
+pub struct LiquidityTimeAccumulator {
+    pub whirlpool: Pubkey, // 32
+    pub owner: Pubkey,     // 32
+
+    // Sum of liquidity multiplied by seconds in range
+    pub liquidity_seconds: u128,     // 16
+    pub last_updated_timestamp: u64, // 8
+}
+
+impl LiquidityTimeAccumulator {
+    pub const LEN: usize = 8 + 64 + 24;
+
+    pub fn initialize(&mut self, whirlpool: Pubkey, owner: Pubkey) {
+        self.whirlpool = whirlpool;
+        self.owner = owner;
+    }
+
+    /// Accrue the liquidity time of a position since its previous accrual. The first accrual of a
+    /// position only starts tracking it.
+    pub fn accrue(&mut self, position: &mut Position, tick_current_index: i32, timestamp: u64) {
+        let last_accrued_timestamp = position.liquidity_time_checkpoint;
+        if last_accrued_timestamp != 0
+            && tick_current_index >= position.tick_lower_index
+            && tick_current_index < position.tick_upper_index
+        {
+            let elapsed = timestamp.saturating_sub(last_accrued_timestamp) as u128;
+            self.liquidity_seconds = self
+                .liquidity_seconds
+                .saturating_add(position.liquidity.saturating_mul(elapsed));
+        }
+        position.checkpoint_liquidity_time(timestamp);
+        self.last_updated_timestamp = timestamp;
+    }
+}
+
+#[cfg(test)]
+mod liquidity_time_tests {
+    use super::*;
+    use crate::state::position_builder::PositionBuilder;
+
+    #[test]
+    fn test_accrue_in_range() {
+        let mut accumulator = LiquidityTimeAccumulator::default();
+        let mut position = PositionBuilder::new(-10, 10).liquidity(1000).build();
+
+        accumulator.accrue(&mut position, 0, 100);
+        assert_eq!(accumulator.liquidity_seconds, 0);
+        assert_eq!(position.liquidity_time_checkpoint, 100);
+
+        accumulator.accrue(&mut position, 0, 130);
+        assert_eq!(accumulator.liquidity_seconds, 30_000);
+        assert_eq!(accumulator.last_updated_timestamp, 130);
+    }
+
+    #[test]
+    fn test_accrue_out_of_range() {
+        let mut accumulator = LiquidityTimeAccumulator::default();
+        let mut position = PositionBuilder::new(-10, 10).liquidity(1000).build();
+        position.checkpoint_liquidity_time(100);
+
+        accumulator.accrue(&mut position, 10, 130);
+        assert_eq!(accumulator.liquidity_seconds, 0);
+        assert_eq!(position.liquidity_time_checkpoint, 130);
+    }
+}
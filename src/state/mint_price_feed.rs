@@ -0,0 +1,42 @@
+use anchor_lang::prelude::*;
+
+/// Pyth price feed of a token mint, configured by a WhirlpoolsConfig. Pools of the config read
+/// their initial price from the feeds of their two mints, which have to share a quote currency.
+#[account]
+#[derive(Default)]
+pub struct MintPriceFeed {
+    pub whirlpools_config: Pubkey, // 32
+    pub token_mint: Pubkey,        // 32
+    // Id of the Pyth feed, the price updates of the mint have to be posted for
+    pub feed_id: [u8; 32], // 32
+}
+
+impl MintPriceFeed {
+    pub const LEN: usize = 8 + 64 + 32;
+
+    pub fn initialize(&mut self, whirlpools_config: Pubkey, token_mint: Pubkey, feed_id: [u8; 32]) {
+        self.whirlpools_config = whirlpools_config;
+        self.token_mint = token_mint;
+        self.feed_id = feed_id;
+    }
+
+    pub fn is_feed_of(&self, whirlpools_config: &Pubkey, token_mint: &Pubkey) -> bool {
+        self.whirlpools_config == *whirlpools_config && self.token_mint == *token_mint
+    }
+}
+
+#[cfg(test)]
+mod mint_price_feed_tests {
+    use super::*;
+
+    #[test]
+    fn test_is_feed_of() {
+        let whirlpools_config = Pubkey::new_unique();
+        let token_mint = Pubkey::new_unique();
+        let mut price_feed = MintPriceFeed::default();
+        price_feed.initialize(whirlpools_config, token_mint, [1; 32]);
+        assert!(price_feed.is_feed_of(&whirlpools_config, &token_mint));
+        assert!(!price_feed.is_feed_of(&Pubkey::new_unique(), &token_mint));
+        assert!(!price_feed.is_feed_of(&whirlpools_config, &Pubkey::new_unique()));
+    }
+}
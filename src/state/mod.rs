@@ -1,11 +1,47 @@
+pub mod account_header;
+pub mod collect_routing;
 pub mod config;
+pub mod fee_schedule;
 pub mod fee_tier;
+pub mod keeper_tip_escrow;
+pub mod liquidity_lock_receipt;
+pub mod liquidity_time;
+pub mod mint_price_feed;
+pub mod partner;
 pub mod position;
+pub mod position_attestation;
+pub mod protocol_stats;
+pub mod referral;
+pub mod rent_sponsor;
+pub mod retro_reward;
+pub mod router_registry;
+pub mod session_authority;
 pub mod tick;
+pub mod tick_cache;
+pub mod token_badge;
+pub mod trade_log;
 pub mod whirlpool;
 
 pub use self::whirlpool::*;
+pub use account_header::*;
+pub use collect_routing::*;
 pub use config::*;
+pub use fee_schedule::*;
 pub use fee_tier::*;
+pub use keeper_tip_escrow::*;
+pub use liquidity_lock_receipt::*;
+pub use liquidity_time::*;
+pub use mint_price_feed::*;
+pub use partner::*;
 pub use position::*;
+pub use position_attestation::*;
+pub use protocol_stats::*;
+pub use referral::*;
+pub use rent_sponsor::*;
+pub use retro_reward::*;
+pub use router_registry::*;
+pub use session_authority::*;
 pub use tick::*;
+pub use tick_cache::*;
+pub use token_badge::*;
+pub use trade_log::*;
@@ -0,0 +1,59 @@
+use anchor_lang::prelude::*;
+
+/// Swap volume a partner frontend routed through a Whirlpool, for revenue sharing measured from
+/// on-chain data. Amounts are token amounts as transferred by the swaps.
+#[account]
+#[derive(Default)]
+pub struct PartnerStats {
+    pub whirlpool: Pubkey, // 32
+    pub partner: Pubkey,   // 32
+
+    pub volume_a: u128,  // 16
+    pub volume_b: u128,  // 16
+    pub fees_a: u128,    // 16
+    pub fees_b: u128,    // 16
+    pub swap_count: u64, // 8
+}
+
+impl PartnerStats {
+    pub const LEN: usize = 8 + 64 + 64 + 8;
+
+    pub fn initialize(&mut self, whirlpool: Pubkey, partner: Pubkey) {
+        self.whirlpool = whirlpool;
+        self.partner = partner;
+    }
+
+    pub fn record_swap(
+        &mut self,
+        amount_a: u64,
+        amount_b: u64,
+        total_fee: u64,
+        is_fee_in_token_a: bool,
+    ) {
+        self.volume_a = self.volume_a.saturating_add(amount_a as u128);
+        self.volume_b = self.volume_b.saturating_add(amount_b as u128);
+        if is_fee_in_token_a {
+            self.fees_a = self.fees_a.saturating_add(total_fee as u128);
+        } else {
+            self.fees_b = self.fees_b.saturating_add(total_fee as u128);
+        }
+        self.swap_count = self.swap_count.saturating_add(1);
+    }
+}
+
+#[cfg(test)]
+mod partner_stats_tests {
+    use super::*;
+
+    #[test]
+    fn test_record_swap() {
+        let mut stats = PartnerStats::default();
+        stats.record_swap(100, 200, 1, true);
+        stats.record_swap(50, 25, 2, false);
+        assert_eq!(stats.volume_a, 150);
+        assert_eq!(stats.volume_b, 225);
+        assert_eq!(stats.fees_a, 1);
+        assert_eq!(stats.fees_b, 2);
+        assert_eq!(stats.swap_count, 2);
+    }
+}
@@ -1,8 +1,13 @@
 use anchor_lang::prelude::*;
 
-use crate::{errors::ErrorCode, state::NUM_REWARDS};
+use crate::{
+    errors::ErrorCode,
+    manager::liquidity_manager::calculate_pending_position_update,
+    math::{MAX_AUTO_COMPOUND_TIP_RATE, PROTOCOL_FEE_RATE_MUL_VALUE},
+    state::NUM_REWARDS,
+};
 
-use super::{Tick, Whirlpool};
+use super::{AccountHeader, Tick, TickArrayRefs, Whirlpool, ACCOUNT_SCHEMA_VERSION};
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Default, Copy)]
 pub struct OpenPositionBumps {
@@ -32,10 +37,57 @@ pub struct Position {
     pub fee_owed_b: u64,               // 8
 
     pub reward_infos: [PositionRewardInfo; NUM_REWARDS], // 72
+
+    // Liquidity that cannot be withdrawn before the lock expiry
+    pub locked_liquidity: u128,     // 16
+    pub lock_expiry_timestamp: u64, // 8
+
+    // Wallet that sessions must pay collected fees and rewards to, the position owner if unset
+    pub owed_destination: Pubkey, // 32
+
+    // Timestamp liquidity time was last accrued or forfeited at, zero if never tracked
+    pub liquidity_time_checkpoint: u64, // 8
+
+    // Key allowed to decrease liquidity into token accounts of the withdraw destination,
+    // Pubkey::default() when disabled
+    pub withdraw_authority: Pubkey,   // 32
+    pub withdraw_destination: Pubkey, // 32
+
+    // Opt-in for keepers to compound the fees owed into the position, paid the tip rate in basis
+    // points of the fees owed
+    pub auto_compound: bool,         // 1
+    pub auto_compound_tip_rate: u16, // 2
+
+    // Timestamp of the last liquidity increase, for the liquidity removal cooldown of the pool
+    pub last_liquidity_increase_timestamp: u64, // 8
+    // Slot of the last liquidity increase, for the JIT liquidity surcharge of the pool
+    pub last_liquidity_increase_slot: u64, // 8
+
+    // Owner of the position token as of its last `transfer_position`, the original owner if it was
+    // never transferred through the program. Transfers outside the program are not reflected
+    pub owner_hint: Pubkey, // 32
+
+    // Layout version the position was opened with, see `AccountHeader`
+    pub schema_version: u8, // 1
+
+    // Set while a bridge attestation of the position is outstanding, see `PositionAttestation`
+    pub frozen: bool, // 1
+
+    // Start of the tenure the reward tenure boosts of the pool are based on, 0 if the position was
+    // out of range or empty at its last checkpoint. Restarted by liquidity decreases. The range is
+    // only sampled at checkpoints, leaving and re-entering it in between keeps the tenure
+    pub reward_tenure_boost_start_timestamp: u64, // 8
+
+    // Key allowed to re-mint the position token if it is burned, set by the position token holder
+    // and cleared by `transfer_position`. Pubkey::default() when disabled
+    pub recovery_authority: Pubkey, // 32
 }
 
 impl Position {
-    pub const LEN: usize = 8 + 136 + 72;
+    pub const LEN: usize = 8 + 136 + 72 + 24 + 32 + 8 + 64 + 3 + 16 + 32 + 1 + 1 + 8 + 32;
+    // Size of positions opened before the fields following `reward_infos` were appended, see
+    // `migrate_position`. The appended fields read as zero, their disabled or unset state
+    pub const LEGACY_LEN: usize = 8 + 136 + 72;
 
     pub fn is_position_empty<'info>(position: &Position) -> bool {
         let fees_not_owed = position.fee_owed_a == 0 && position.fee_owed_b == 0;
@@ -53,12 +105,14 @@ impl Position {
         self.fee_owed_a = update.fee_owed_a;
         self.fee_owed_b = update.fee_owed_b;
         self.reward_infos = update.reward_infos;
+        self.reward_tenure_boost_start_timestamp = update.reward_tenure_boost_start_timestamp;
     }
 
     pub fn open_position(
         &mut self,
         whirlpool: &Account<Whirlpool>,
         position_mint: Pubkey,
+        owner: Pubkey,
         tick_lower_index: i32,
         tick_upper_index: i32,
     ) -> Result<()> {
@@ -68,15 +122,137 @@ impl Position {
         {
             return Err(ErrorCode::InvalidTickIndex.into());
         }
+        whirlpool.verify_position_in_tick_bounds(tick_lower_index, tick_upper_index)?;
 
+        self.schema_version = ACCOUNT_SCHEMA_VERSION;
         self.whirlpool = whirlpool.key();
         self.position_mint = position_mint;
+        self.owner_hint = owner;
 
         self.tick_lower_index = tick_lower_index;
         self.tick_upper_index = tick_upper_index;
         Ok(())
     }
 
+    /// The identifying header of the position.
+    pub fn describe(&self) -> AccountHeader {
+        AccountHeader {
+            schema_version: self.schema_version,
+            whirlpool: self.whirlpool,
+        }
+    }
+
+    pub fn update_frozen(&mut self, frozen: bool) {
+        self.frozen = frozen;
+    }
+
+    pub fn verify_not_frozen(&self) -> Result<()> {
+        if self.frozen {
+            return Err(ErrorCode::PositionFrozen.into());
+        }
+        Ok(())
+    }
+
+    pub fn update_owner_hint(&mut self, owner_hint: Pubkey) {
+        self.owner_hint = owner_hint;
+    }
+
+    /// Allow a key to re-mint the position token if it is burned. Setting the default pubkey
+    /// disables recovery.
+    pub fn update_recovery_authority(&mut self, recovery_authority: Pubkey) {
+        self.recovery_authority = recovery_authority;
+    }
+
+    pub fn is_recovery_authority(&self, authority: &Pubkey) -> bool {
+        self.recovery_authority != Pubkey::default() && self.recovery_authority == *authority
+    }
+
+    /// Point the position at a newly minted position token after the original was burned.
+    pub fn update_position_mint(&mut self, position_mint: Pubkey) {
+        self.position_mint = position_mint;
+    }
+
+    /// Lock the current liquidity of the position until the expiry timestamp.
+    pub fn lock_liquidity(&mut self, lock_expiry_timestamp: u64) {
+        self.locked_liquidity = self.liquidity;
+        self.lock_expiry_timestamp = lock_expiry_timestamp;
+    }
+
+    /// Returns true if withdrawing down to the given liquidity would break an active lock.
+    pub fn is_liquidity_locked(&self, next_liquidity: u128, timestamp: u64) -> bool {
+        timestamp < self.lock_expiry_timestamp && next_liquidity < self.locked_liquidity
+    }
+
+    /// Redirect collections made through sessions to another wallet. Setting the default pubkey
+    /// restores the position owner as the destination.
+    pub fn update_owed_destination(&mut self, owed_destination: Pubkey) {
+        self.owed_destination = owed_destination;
+    }
+
+    /// Wallet that collections made through sessions must pay to.
+    pub fn owed_destination_or(&self, position_owner: Pubkey) -> Pubkey {
+        if self.owed_destination == Pubkey::default() {
+            position_owner
+        } else {
+            self.owed_destination
+        }
+    }
+
+    /// Allow a key other than the position token holder to decrease liquidity, with the tokens
+    /// paid to the withdraw destination only. Setting the default pubkey disables it.
+    pub fn update_withdraw_authority(
+        &mut self,
+        withdraw_authority: Pubkey,
+        withdraw_destination: Pubkey,
+    ) {
+        self.withdraw_authority = withdraw_authority;
+        self.withdraw_destination = withdraw_destination;
+    }
+
+    pub fn is_withdraw_authority(&self, authority: &Pubkey) -> bool {
+        self.withdraw_authority != Pubkey::default() && self.withdraw_authority == *authority
+    }
+
+    pub fn update_auto_compound(&mut self, auto_compound: bool, tip_rate: u16) -> Result<()> {
+        if tip_rate > MAX_AUTO_COMPOUND_TIP_RATE {
+            return Err(ErrorCode::AutoCompoundTipRateMaxExceeded.into());
+        }
+        self.auto_compound = auto_compound;
+        self.auto_compound_tip_rate = tip_rate;
+
+        Ok(())
+    }
+
+    pub fn record_liquidity_increase(&mut self, timestamp: u64, slot: u64) {
+        self.last_liquidity_increase_timestamp = timestamp;
+        self.last_liquidity_increase_slot = slot;
+    }
+
+    /// Move the keeper tip out of the fees owed and return it.
+    pub fn take_auto_compound_tip(&mut self) -> (u64, u64) {
+        let tip_rate = self.auto_compound_tip_rate as u128;
+        let tip_a = ((self.fee_owed_a as u128) * tip_rate / PROTOCOL_FEE_RATE_MUL_VALUE) as u64;
+        let tip_b = ((self.fee_owed_b as u128) * tip_rate / PROTOCOL_FEE_RATE_MUL_VALUE) as u64;
+        self.fee_owed_a -= tip_a;
+        self.fee_owed_b -= tip_b;
+        (tip_a, tip_b)
+    }
+
+    /// Pay a liquidity deposit out of the fees owed, which are already held by the vaults.
+    pub fn spend_fees_owed(&mut self, amount_a: u64, amount_b: u64) -> Result<()> {
+        if amount_a > self.fee_owed_a || amount_b > self.fee_owed_b {
+            return Err(ErrorCode::TokenMaxExceeded.into());
+        }
+        self.fee_owed_a -= amount_a;
+        self.fee_owed_b -= amount_b;
+
+        Ok(())
+    }
+
+    pub fn checkpoint_liquidity_time(&mut self, timestamp: u64) {
+        self.liquidity_time_checkpoint = timestamp;
+    }
+
     pub fn reset_fees_owed(&mut self) {
         self.fee_owed_a = 0;
         self.fee_owed_b = 0;
@@ -85,6 +261,42 @@ impl Position {
     pub fn update_reward_owed(&mut self, index: usize, amount_owed: u64) {
         self.reward_infos[index].amount_owed = amount_owed;
     }
+
+    /// Token amounts of fees `collect_fees` would pay for token A and token B, including the fees
+    /// accrued since the position was last updated, without modifying the position.
+    pub fn pending_fees(
+        &self,
+        whirlpool: &Whirlpool,
+        tick_arrays: &TickArrayRefs,
+    ) -> Result<(u64, u64)> {
+        // Fee growths do not depend on time, so the rewards are left where they were last updated.
+        let update = calculate_pending_position_update(
+            whirlpool,
+            self,
+            tick_arrays,
+            whirlpool.reward_last_updated_timestamp,
+        )?;
+        Ok((
+            whirlpool.to_token_amount(update.fee_owed_a, true, false)?,
+            whirlpool.to_token_amount(update.fee_owed_b, false, false)?,
+        ))
+    }
+
+    /// Rewards `collect_reward` would owe for each reward index at `timestamp`, including the
+    /// rewards emitted since the position was last updated, without modifying the position.
+    pub fn pending_rewards(
+        &self,
+        whirlpool: &Whirlpool,
+        tick_arrays: &TickArrayRefs,
+        timestamp: u64,
+    ) -> Result<[u64; NUM_REWARDS]> {
+        let update = calculate_pending_position_update(whirlpool, self, tick_arrays, timestamp)?;
+        let mut rewards = [0; NUM_REWARDS];
+        for (reward, reward_info) in rewards.iter_mut().zip(update.reward_infos.iter()) {
+            *reward = reward_info.amount_owed;
+        }
+        Ok(rewards)
+    }
 }
 
 #[derive(Copy, Clone, AnchorSerialize, AnchorDeserialize, Default, PartialEq)]
@@ -104,6 +316,7 @@ pub struct PositionUpdate {
     pub fee_growth_checkpoint_b: u128,
     pub fee_owed_b: u64,
     pub reward_infos: [PositionRewardInfo; NUM_REWARDS],
+    pub reward_tenure_boost_start_timestamp: u64,
 }
 
 #[cfg(test)]
@@ -143,6 +356,21 @@ mod is_position_empty_tests {
                     amount_owed: reward_owed_2,
                 },
             ],
+            locked_liquidity: 0,
+            lock_expiry_timestamp: 0,
+            owed_destination: Pubkey::default(),
+            liquidity_time_checkpoint: 0,
+            withdraw_authority: Pubkey::default(),
+            withdraw_destination: Pubkey::default(),
+            auto_compound: false,
+            auto_compound_tip_rate: 0,
+            last_liquidity_increase_timestamp: 0,
+            last_liquidity_increase_slot: 0,
+            owner_hint: test_program_id(),
+            schema_version: ACCOUNT_SCHEMA_VERSION,
+            frozen: false,
+            reward_tenure_boost_start_timestamp: 0,
+            recovery_authority: Pubkey::default(),
         }
     }
 
@@ -187,6 +415,119 @@ mod is_position_empty_tests {
         let pos = build_test_position(0, 0, 0, 0, 0, 100);
         assert_eq!(Position::is_position_empty(&pos), false);
     }
+
+    #[test]
+    fn test_liquidity_locked() {
+        let mut pos = build_test_position(100, 0, 0, 0, 0, 0);
+        pos.lock_liquidity(1000);
+        assert!(pos.is_liquidity_locked(99, 999));
+        assert!(!pos.is_liquidity_locked(100, 999));
+        assert!(!pos.is_liquidity_locked(0, 1000));
+    }
+
+    #[test]
+    fn test_owed_destination() {
+        let mut pos = build_test_position(100, 0, 0, 0, 0, 0);
+        let owner = Pubkey::new_unique();
+        let destination = Pubkey::new_unique();
+        assert_eq!(pos.owed_destination_or(owner), owner);
+
+        pos.update_owed_destination(destination);
+        assert_eq!(pos.owed_destination_or(owner), destination);
+
+        pos.update_owed_destination(Pubkey::default());
+        assert_eq!(pos.owed_destination_or(owner), owner);
+    }
+
+    #[test]
+    fn test_withdraw_authority() {
+        let mut pos = build_test_position(100, 0, 0, 0, 0, 0);
+        let authority = Pubkey::new_unique();
+        assert!(!pos.is_withdraw_authority(&authority));
+        assert!(!pos.is_withdraw_authority(&Pubkey::default()));
+
+        pos.update_withdraw_authority(authority, Pubkey::new_unique());
+        assert!(pos.is_withdraw_authority(&authority));
+        assert!(!pos.is_withdraw_authority(&Pubkey::new_unique()));
+
+        pos.update_withdraw_authority(Pubkey::default(), Pubkey::default());
+        assert!(!pos.is_withdraw_authority(&authority));
+    }
+
+    #[test]
+    fn test_recovery_authority() {
+        let mut pos = build_test_position(100, 0, 0, 0, 0, 0);
+        // Opening the position does not make its owner a recovery authority.
+        assert!(!pos.is_recovery_authority(&pos.owner_hint));
+        assert!(!pos.is_recovery_authority(&Pubkey::default()));
+
+        let authority = Pubkey::new_unique();
+        pos.update_recovery_authority(authority);
+        assert!(pos.is_recovery_authority(&authority));
+        assert!(!pos.is_recovery_authority(&Pubkey::new_unique()));
+
+        pos.update_recovery_authority(Pubkey::default());
+        assert!(!pos.is_recovery_authority(&authority));
+    }
+
+    #[test]
+    fn test_auto_compound_tip() {
+        let mut pos = build_test_position(100, 1_000, 55, 0, 0, 0);
+        assert!(pos
+            .update_auto_compound(true, MAX_AUTO_COMPOUND_TIP_RATE + 1)
+            .is_err());
+
+        pos.update_auto_compound(true, 500).unwrap();
+        assert_eq!(pos.take_auto_compound_tip(), (50, 2));
+        assert_eq!((pos.fee_owed_a, pos.fee_owed_b), (950, 53));
+
+        assert!(pos.spend_fees_owed(951, 0).is_err());
+        pos.spend_fees_owed(900, 53).unwrap();
+        assert_eq!((pos.fee_owed_a, pos.fee_owed_b), (50, 0));
+    }
+
+    #[test]
+    fn test_describe() {
+        let pos = build_test_position(100, 0, 0, 0, 0, 0);
+        let header = pos.describe();
+        assert_eq!(header.schema_version, ACCOUNT_SCHEMA_VERSION);
+        assert!(header.belongs_to(&test_program_id()));
+        assert!(!header.belongs_to(&Pubkey::new_unique()));
+    }
+
+    #[test]
+    fn test_frozen() {
+        let mut pos = build_test_position(100, 0, 0, 0, 0, 0);
+        assert!(pos.verify_not_frozen().is_ok());
+
+        pos.update_frozen(true);
+        assert_eq!(
+            pos.verify_not_frozen().unwrap_err(),
+            ErrorCode::PositionFrozen.into()
+        );
+
+        pos.update_frozen(false);
+        assert!(pos.verify_not_frozen().is_ok());
+    }
+
+    #[test]
+    fn test_legacy_layout_reallocated_with_zeroed_data() {
+        let pos = build_test_position(100, 10, 20, 1, 2, 3);
+        let mut data = Vec::new();
+        pos.try_serialize(&mut data).unwrap();
+        data.truncate(Position::LEGACY_LEN);
+        data.resize(Position::LEN, 0);
+
+        let migrated = Position::try_deserialize(&mut data.as_slice()).unwrap();
+        assert_eq!(migrated.liquidity, 100);
+        assert_eq!((migrated.fee_owed_a, migrated.fee_owed_b), (10, 20));
+        assert_eq!(migrated.reward_infos, pos.reward_infos);
+        assert_eq!(migrated.describe().schema_version, 0);
+        assert_eq!(migrated.owed_destination, Pubkey::default());
+        assert_eq!(migrated.withdraw_authority, Pubkey::default());
+        assert_eq!(migrated.recovery_authority, Pubkey::default());
+        assert!(!migrated.frozen);
+    }
 }
 
 #[cfg(test)]
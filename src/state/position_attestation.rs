@@ -0,0 +1,53 @@
+use anchor_lang::prelude::*;
+
+use super::{Position, NUM_REWARDS};
+
+/// Snapshot of a frozen position for a bridge program, derived from the position address. The
+/// position cannot be modified, collected, transferred or closed until the bridge authority
+/// releases it, so the snapshot stays accurate while the position is represented elsewhere.
+#[account]
+#[derive(Default)]
+pub struct PositionAttestation {
+    pub whirlpool: Pubkey,               // 32
+    pub position: Pubkey,                // 32
+    pub position_mint: Pubkey,           // 32
+    pub owner: Pubkey,                   // 32
+    pub bridge_authority: Pubkey,        // 32
+    pub tick_lower_index: i32,           // 4
+    pub tick_upper_index: i32,           // 4
+    pub liquidity: u128,                 // 16
+    pub fee_owed_a: u64,                 // 8
+    pub fee_owed_b: u64,                 // 8
+    pub reward_owed: [u64; NUM_REWARDS], // 24
+    pub frozen_timestamp: u64,           // 8
+    pub bump: u8,                        // 1
+}
+
+impl PositionAttestation {
+    pub const LEN: usize = 8 + 160 + 8 + 16 + 16 + 24 + 8 + 1;
+
+    pub fn initialize(
+        &mut self,
+        position: &Account<Position>,
+        owner: Pubkey,
+        bridge_authority: Pubkey,
+        bump: u8,
+        timestamp: u64,
+    ) {
+        self.whirlpool = position.whirlpool;
+        self.position = position.key();
+        self.position_mint = position.position_mint;
+        self.owner = owner;
+        self.bridge_authority = bridge_authority;
+        self.tick_lower_index = position.tick_lower_index;
+        self.tick_upper_index = position.tick_upper_index;
+        self.liquidity = position.liquidity;
+        self.fee_owed_a = position.fee_owed_a;
+        self.fee_owed_b = position.fee_owed_b;
+        for (reward_owed, reward_info) in self.reward_owed.iter_mut().zip(position.reward_infos) {
+            *reward_owed = reward_info.amount_owed;
+        }
+        self.frozen_timestamp = timestamp;
+        self.bump = bump;
+    }
+}
@@ -0,0 +1,42 @@
+use anchor_lang::prelude::*;
+
+/// Program wide statistics for operator dashboards, kept in a singleton PDA. Pools are counted
+/// from the creation of the account. Swaps are only recorded by builds with the
+/// `protocol-stats-swaps` feature.
+#[account]
+#[derive(Default)]
+pub struct ProtocolStats {
+    pub pool_count: u64, // 8
+    pub swap_count: u64, // 8
+    // Protocol fees accrued by swaps, summed in token units of whichever mint the fee was taken in
+    pub protocol_fees: u128, // 16
+}
+
+impl ProtocolStats {
+    pub const LEN: usize = 8 + 16 + 16;
+
+    pub fn record_pool(&mut self) {
+        self.pool_count = self.pool_count.saturating_add(1);
+    }
+
+    pub fn record_swap(&mut self, protocol_fee: u64) {
+        self.swap_count = self.swap_count.saturating_add(1);
+        self.protocol_fees = self.protocol_fees.saturating_add(protocol_fee as u128);
+    }
+}
+
+#[cfg(test)]
+mod protocol_stats_tests {
+    use super::*;
+
+    #[test]
+    fn test_record_pool_and_swaps() {
+        let mut stats = ProtocolStats::default();
+        stats.record_pool();
+        stats.record_swap(10);
+        stats.record_swap(5);
+        assert_eq!(stats.pool_count, 1);
+        assert_eq!(stats.swap_count, 2);
+        assert_eq!(stats.protocol_fees, 15);
+    }
+}
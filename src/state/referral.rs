@@ -0,0 +1,37 @@
+use anchor_lang::prelude::*;
+
+/// Referral fees earned by a referrer on swaps of a Whirlpool. Swaps credit the referrer's share
+/// of the protocol fee here instead of transferring it, and the referrer claims the balance with
+/// `claim_referral_fees`.
+#[account]
+#[derive(Default)]
+pub struct ReferralEarnings {
+    pub whirlpool: Pubkey, // 32
+    pub referrer: Pubkey,  // 32
+
+    // Unclaimed referral fees, in pool units
+    pub fee_owed_a: u64, // 8
+    pub fee_owed_b: u64, // 8
+}
+
+impl ReferralEarnings {
+    pub const LEN: usize = 8 + 64 + 16;
+
+    pub fn initialize(&mut self, whirlpool: Pubkey, referrer: Pubkey) {
+        self.whirlpool = whirlpool;
+        self.referrer = referrer;
+    }
+
+    pub fn add_fee(&mut self, referral_fee: u64, is_token_fee_in_a: bool) {
+        if is_token_fee_in_a {
+            self.fee_owed_a = self.fee_owed_a.saturating_add(referral_fee);
+        } else {
+            self.fee_owed_b = self.fee_owed_b.saturating_add(referral_fee);
+        }
+    }
+
+    pub fn reset_fees_owed(&mut self) {
+        self.fee_owed_a = 0;
+        self.fee_owed_b = 0;
+    }
+}
@@ -0,0 +1,65 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::ErrorCode;
+
+/// Lamports escrow paying the rent of tick arrays initialized in a sponsored range of a
+/// Whirlpool, e.g. the range of a liquidity incentive. Anyone can fund it, only the authority can
+/// withdraw what is left.
+#[account]
+#[derive(Default)]
+pub struct RentSponsor {
+    pub whirlpool: Pubkey, // 32
+    pub authority: Pubkey, // 32
+    // Start tick indexes of the sponsored tick arrays, inclusive
+    pub start_tick_index_min: i32, // 4
+    pub start_tick_index_max: i32, // 4
+}
+
+impl RentSponsor {
+    pub const LEN: usize = 8 + 64 + 8;
+
+    pub fn initialize(
+        &mut self,
+        whirlpool: Pubkey,
+        authority: Pubkey,
+        start_tick_index_min: i32,
+        start_tick_index_max: i32,
+    ) -> Result<()> {
+        if start_tick_index_min > start_tick_index_max {
+            return Err(ErrorCode::InvalidRentSponsor.into());
+        }
+        self.whirlpool = whirlpool;
+        self.authority = authority;
+        self.start_tick_index_min = start_tick_index_min;
+        self.start_tick_index_max = start_tick_index_max;
+
+        Ok(())
+    }
+
+    pub fn is_sponsored(&self, whirlpool: &Pubkey, start_tick_index: i32) -> bool {
+        self.whirlpool == *whirlpool
+            && (self.start_tick_index_min..=self.start_tick_index_max).contains(&start_tick_index)
+    }
+}
+
+#[cfg(test)]
+mod rent_sponsor_tests {
+    use super::*;
+
+    #[test]
+    fn test_is_sponsored() {
+        let whirlpool = Pubkey::new_unique();
+        let mut sponsor = RentSponsor::default();
+        assert!(sponsor
+            .initialize(whirlpool, Pubkey::new_unique(), 88, -88)
+            .is_err());
+
+        sponsor
+            .initialize(whirlpool, Pubkey::new_unique(), -88, 88)
+            .unwrap();
+        assert!(sponsor.is_sponsored(&whirlpool, -88));
+        assert!(sponsor.is_sponsored(&whirlpool, 88));
+        assert!(!sponsor.is_sponsored(&whirlpool, 176));
+        assert!(!sponsor.is_sponsored(&Pubkey::new_unique(), 0));
+    }
+}
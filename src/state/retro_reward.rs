@@ -0,0 +1,85 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::ErrorCode;
+
+/// Distributor of retroactive rewards of a Whirlpool, computed off-chain from liquidity snapshots
+/// and committed to by a merkle root. Leaves hold the cumulative amount each claimant has earned,
+/// so the root can be replaced with every new snapshot and claimants only receive the difference
+/// with what they already claimed.
+#[account]
+#[derive(Default)]
+pub struct RetroRewardDistributor {
+    pub whirlpool: Pubkey, // 32
+    // Reward index of the Whirlpool whose authority manages the distributor
+    pub reward_index: u8,      // 1
+    pub mint: Pubkey,          // 32
+    pub vault: Pubkey,         // 32
+    pub merkle_root: [u8; 32], // 32
+    pub total_claimed: u64,    // 8
+}
+
+impl RetroRewardDistributor {
+    pub const LEN: usize = 8 + 32 + 1 + 64 + 32 + 8;
+
+    pub fn initialize(&mut self, whirlpool: Pubkey, reward_index: u8, mint: Pubkey, vault: Pubkey) {
+        self.whirlpool = whirlpool;
+        self.reward_index = reward_index;
+        self.mint = mint;
+        self.vault = vault;
+    }
+
+    pub fn update_merkle_root(&mut self, merkle_root: [u8; 32]) {
+        self.merkle_root = merkle_root;
+    }
+
+    /// Record a claim of a verified cumulative amount and return the amount to transfer.
+    ///
+    /// # Errors
+    /// - `RetroRewardAlreadyClaimed` - The claimant already claimed the cumulative amount
+    pub fn claim(&mut self, claim: &mut RetroRewardClaim, cumulative_amount: u64) -> Result<u64> {
+        if cumulative_amount <= claim.amount_claimed {
+            return Err(ErrorCode::RetroRewardAlreadyClaimed.into());
+        }
+        let amount = cumulative_amount - claim.amount_claimed;
+        claim.amount_claimed = cumulative_amount;
+        self.total_claimed = self
+            .total_claimed
+            .checked_add(amount)
+            .ok_or(ErrorCode::AmountCalcOverflow)?;
+
+        Ok(amount)
+    }
+}
+
+/// Cumulative amount claimed by a claimant from a retroactive reward distributor.
+#[account]
+#[derive(Default)]
+pub struct RetroRewardClaim {
+    pub distributor: Pubkey, // 32
+    pub claimant: Pubkey,    // 32
+    pub amount_claimed: u64, // 8
+}
+
+impl RetroRewardClaim {
+    pub const LEN: usize = 8 + 64 + 8;
+}
+
+#[cfg(test)]
+mod retro_reward_tests {
+    use super::*;
+
+    #[test]
+    fn test_claim_pays_difference_with_amount_claimed() {
+        let mut distributor = RetroRewardDistributor::default();
+        let mut claim = RetroRewardClaim::default();
+
+        assert_eq!(distributor.claim(&mut claim, 100).unwrap(), 100);
+        assert!(distributor.claim(&mut claim, 100).is_err());
+        assert!(distributor.claim(&mut claim, 50).is_err());
+
+        // A later snapshot raises the cumulative amount
+        assert_eq!(distributor.claim(&mut claim, 250).unwrap(), 150);
+        assert_eq!(claim.amount_claimed, 250);
+        assert_eq!(distributor.total_claimed, 250);
+    }
+}
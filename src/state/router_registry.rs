@@ -0,0 +1,93 @@
+use anchor_lang::prelude::*;
+
+use crate::{errors::ErrorCode, math::MAX_ROUTER_FEE_DISCOUNT_RATE};
+
+pub const MAX_APPROVED_ROUTERS: usize = 8;
+
+/// Router programs approved by a WhirlpoolsConfig. Swaps these programs make by CPI, as
+/// determined through instruction introspection, pay a discounted fee rate.
+#[account]
+#[derive(Default)]
+pub struct RouterRegistry {
+    pub whirlpools_config: Pubkey, // 32
+    // Stored as a basis point of the fee rate
+    pub fee_discount_rate: u16, // 2
+    // Unused slots hold the default pubkey
+    pub routers: [Pubkey; MAX_APPROVED_ROUTERS], // 32 * 8
+}
+
+impl RouterRegistry {
+    pub const LEN: usize = 8 + 32 + 2 + 32 * MAX_APPROVED_ROUTERS;
+
+    pub fn initialize(&mut self, whirlpools_config: Pubkey, fee_discount_rate: u16) -> Result<()> {
+        self.whirlpools_config = whirlpools_config;
+        self.update_fee_discount_rate(fee_discount_rate)
+    }
+
+    pub fn update_fee_discount_rate(&mut self, fee_discount_rate: u16) -> Result<()> {
+        if fee_discount_rate > MAX_ROUTER_FEE_DISCOUNT_RATE {
+            return Err(ErrorCode::RouterFeeDiscountRateMaxExceeded.into());
+        }
+        self.fee_discount_rate = fee_discount_rate;
+        Ok(())
+    }
+
+    /// Set the router program at `index`. The default pubkey clears the slot.
+    pub fn update_router(&mut self, index: usize, router: Pubkey) -> Result<()> {
+        match self.routers.get_mut(index) {
+            Some(slot) => {
+                *slot = router;
+                Ok(())
+            }
+            None => Err(ErrorCode::InvalidApprovedRouterIndex.into()),
+        }
+    }
+
+    pub fn is_approved_router(&self, program_id: &Pubkey) -> bool {
+        *program_id != Pubkey::default() && self.routers.contains(program_id)
+    }
+
+    /// Fee discount rate for a swap invoked by `program_id`, 0 if it is not an approved router.
+    pub fn fee_discount_rate_for(&self, program_id: &Pubkey) -> u16 {
+        if self.is_approved_router(program_id) {
+            self.fee_discount_rate
+        } else {
+            0
+        }
+    }
+}
+
+#[cfg(test)]
+mod router_registry_tests {
+    use super::*;
+
+    #[test]
+    fn test_fee_discount_rate_for_approved_routers() {
+        let router = Pubkey::new_unique();
+        let mut registry = RouterRegistry::default();
+        registry.initialize(Pubkey::new_unique(), 1_000).unwrap();
+        assert_eq!(registry.fee_discount_rate_for(&router), 0);
+        assert_eq!(registry.fee_discount_rate_for(&Pubkey::default()), 0);
+
+        registry.update_router(3, router).unwrap();
+        assert_eq!(registry.fee_discount_rate_for(&router), 1_000);
+        assert_eq!(registry.fee_discount_rate_for(&Pubkey::new_unique()), 0);
+
+        registry.update_router(3, Pubkey::default()).unwrap();
+        assert_eq!(registry.fee_discount_rate_for(&router), 0);
+    }
+
+    #[test]
+    fn test_update_bounds() {
+        let mut registry = RouterRegistry::default();
+        assert!(registry
+            .update_router(MAX_APPROVED_ROUTERS, Pubkey::new_unique())
+            .is_err());
+        assert!(registry
+            .update_fee_discount_rate(MAX_ROUTER_FEE_DISCOUNT_RATE + 1)
+            .is_err());
+        assert!(registry
+            .update_fee_discount_rate(MAX_ROUTER_FEE_DISCOUNT_RATE)
+            .is_ok());
+    }
+}
@@ -0,0 +1,127 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::ErrorCode;
+
+// Handlers a session key can act in on behalf of the position owner.
+pub const SESSION_SCOPE_COLLECT_FEES: u8 = 1 << 0;
+pub const SESSION_SCOPE_COLLECT_REWARD: u8 = 1 << 1;
+pub const SESSION_SCOPE_ALL: u8 = SESSION_SCOPE_COLLECT_FEES | SESSION_SCOPE_COLLECT_REWARD;
+
+// Longest a session can be authorized for in one go, in seconds.
+pub const MAX_SESSION_DURATION: u64 = 60 * 60 * 24 * 30;
+
+#[account]
+#[derive(Default)]
+pub struct SessionAuthority {
+    pub position_mint: Pubkey, // 32
+    pub owner: Pubkey,         // 32
+    pub session_key: Pubkey,   // 32
+    pub expiry_timestamp: u64, // 8
+    pub scopes: u8,            // 1
+    pub bump: u8,              // 1
+}
+
+impl SessionAuthority {
+    pub const LEN: usize = 8 + 96 + 8 + 2;
+
+    pub fn initialize(
+        &mut self,
+        position_mint: Pubkey,
+        owner: Pubkey,
+        session_key: Pubkey,
+        expiry_timestamp: u64,
+        scopes: u8,
+        bump: u8,
+        timestamp: u64,
+    ) -> Result<()> {
+        if !(timestamp + 1..=timestamp + MAX_SESSION_DURATION).contains(&expiry_timestamp) {
+            return Err(ErrorCode::InvalidSessionExpiry.into());
+        }
+        if scopes == 0 || scopes & !SESSION_SCOPE_ALL != 0 {
+            return Err(ErrorCode::InvalidSessionScope.into());
+        }
+
+        self.position_mint = position_mint;
+        self.owner = owner;
+        self.session_key = session_key;
+        self.expiry_timestamp = expiry_timestamp;
+        self.scopes = scopes;
+        self.bump = bump;
+
+        Ok(())
+    }
+
+    /// Returns true if the session allows the session key to act for the owner on the position
+    /// token in the given scope at the given time.
+    pub fn is_valid_for(
+        &self,
+        position_mint: &Pubkey,
+        owner: &Pubkey,
+        session_key: &Pubkey,
+        scope: u8,
+        timestamp: u64,
+    ) -> bool {
+        self.position_mint == *position_mint
+            && self.owner == *owner
+            && self.session_key == *session_key
+            && self.scopes & scope == scope
+            && timestamp < self.expiry_timestamp
+    }
+}
+
+#[cfg(test)]
+mod session_authority_tests {
+    use super::*;
+
+    fn session(scopes: u8) -> (SessionAuthority, Pubkey, Pubkey, Pubkey) {
+        let position_mint = Pubkey::new_unique();
+        let owner = Pubkey::new_unique();
+        let session_key = Pubkey::new_unique();
+        let mut session = SessionAuthority::default();
+        session
+            .initialize(position_mint, owner, session_key, 1000, scopes, 255, 100)
+            .unwrap();
+        (session, position_mint, owner, session_key)
+    }
+
+    #[test]
+    fn test_session_valid_in_scope_before_expiry() {
+        let (session, mint, owner, key) = session(SESSION_SCOPE_COLLECT_FEES);
+        assert!(session.is_valid_for(&mint, &owner, &key, SESSION_SCOPE_COLLECT_FEES, 999));
+        assert!(!session.is_valid_for(&mint, &owner, &key, SESSION_SCOPE_COLLECT_FEES, 1000));
+        assert!(!session.is_valid_for(&mint, &owner, &key, SESSION_SCOPE_COLLECT_REWARD, 999));
+    }
+
+    #[test]
+    fn test_session_invalid_after_owner_change() {
+        let (session, mint, _, key) = session(SESSION_SCOPE_ALL);
+        let new_owner = Pubkey::new_unique();
+        assert!(!session.is_valid_for(&mint, &new_owner, &key, SESSION_SCOPE_COLLECT_FEES, 500));
+    }
+
+    #[test]
+    fn test_session_initialize_rejects_invalid_params() {
+        let key = Pubkey::new_unique();
+        let mut session = SessionAuthority::default();
+        assert!(session
+            .initialize(key, key, key, 100, SESSION_SCOPE_ALL, 255, 100)
+            .is_err());
+        assert!(session
+            .initialize(
+                key,
+                key,
+                key,
+                100 + MAX_SESSION_DURATION + 1,
+                SESSION_SCOPE_ALL,
+                255,
+                100
+            )
+            .is_err());
+        assert!(session
+            .initialize(key, key, key, 1000, 0, 255, 100)
+            .is_err());
+        assert!(session
+            .initialize(key, key, key, 1000, 1 << 7, 255, 100)
+            .is_err());
+    }
+}
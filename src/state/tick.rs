@@ -1,8 +1,9 @@
 use crate::errors::ErrorCode;
+use crate::math::{align_tick_to_spacing, TickAlignment};
 use crate::state::NUM_REWARDS;
 use anchor_lang::prelude::*;
 
-use super::Whirlpool;
+use super::{AccountHeader, Whirlpool, ACCOUNT_SCHEMA_VERSION};
 
 // Max & min tick index based on sqrt(1.0001) & max.min price of 2^64
 pub const MAX_TICK_INDEX: i32 = 443636;
@@ -13,8 +14,16 @@ pub const MIN_TICK_INDEX: i32 = -443636;
 pub const TICK_ARRAY_SIZE: i32 = 88;
 pub const TICK_ARRAY_SIZE_USIZE: usize = 88;
 
-#[zero_copy]
-#[repr(packed)]
+// Position range bound selecting the lowest or highest usable tick of the tick spacing, to open
+// full range positions without computing the bounds client side.
+pub const FULL_RANGE_TICK_INDEX: i32 = i32::MIN;
+
+// Paging hints of a tick array without a previous or next initialized tick array.
+pub const NO_PREV_TICK_ARRAY: i32 = i32::MIN;
+pub const NO_NEXT_TICK_ARRAY: i32 = i32::MAX;
+
+#[zero_copy(unsafe)]
+#[repr(C, packed)]
 #[derive(Default, PartialEq)]
 #[cfg_attr(not(target_arch = "bpf"),derive(Debug))]
 pub struct Tick {
@@ -114,6 +123,47 @@ impl Tick {
     pub fn bound_tick_index(tick_index: i32) -> i32 {
         tick_index.max(MIN_TICK_INDEX).min(MAX_TICK_INDEX)
     }
+
+    /// Get the lowest and highest usable tick index for the given tick spacing, the bounds of a
+    /// full range position.
+    ///
+    /// # Parameters
+    /// - `tick_spacing` - A u16 integer of the tick spacing for this whirlpool
+    ///
+    /// # Returns
+    /// - `(i32, i32)` The lowest and highest tick index that are multiples of the tick spacing
+    pub fn full_range_tick_indexes(tick_spacing: u16) -> (i32, i32) {
+        let max_usable_tick_index =
+            align_tick_to_spacing(MAX_TICK_INDEX, tick_spacing, TickAlignment::Down);
+        (-max_usable_tick_index, max_usable_tick_index)
+    }
+
+    /// Replace the bounds of a position range given as `FULL_RANGE_TICK_INDEX` with the lowest
+    /// or highest usable tick index of the whirlpool. Other bounds are returned as is.
+    ///
+    /// # Parameters
+    /// - `tick_lower_index` - The lower bound of the position range
+    /// - `tick_upper_index` - The upper bound of the position range
+    /// - `usable_tick_range` - The lowest and highest usable tick index of the whirlpool
+    pub fn resolve_full_range(
+        tick_lower_index: i32,
+        tick_upper_index: i32,
+        usable_tick_range: (i32, i32),
+    ) -> (i32, i32) {
+        let (full_range_lower, full_range_upper) = usable_tick_range;
+        (
+            if tick_lower_index == FULL_RANGE_TICK_INDEX {
+                full_range_lower
+            } else {
+                tick_lower_index
+            },
+            if tick_upper_index == FULL_RANGE_TICK_INDEX {
+                full_range_upper
+            } else {
+                tick_upper_index
+            },
+        )
+    }
 }
 
 #[derive(Default, PartialEq)]
@@ -140,13 +190,25 @@ impl TickUpdate {
     }
 }
 
-#[account(zero_copy)]
-#[repr(packed)]
+#[account(zero_copy(unsafe))]
+#[repr(C, packed)]
 #[cfg_attr(not(target_arch = "bpf"),derive(Debug))]
 pub struct TickArray {
     pub start_tick_index: i32,
     pub ticks: [Tick; TICK_ARRAY_SIZE_USIZE],
     pub whirlpool: Pubkey,
+
+    // Start tick indices of the neighbouring initialized tick arrays, for indexers to page
+    // through the initialized arrays of a pool. Only hints, not used by the program.
+    pub prev_start_tick_index: i32,
+    pub next_start_tick_index: i32,
+
+    // Bit i is set if ticks[i] is initialized, so searches can skip uninitialized ticks a word at
+    // a time instead of reading every tick.
+    pub initialized_bitmap: u128,
+
+    // Layout version the array was initialized with, see `AccountHeader`.
+    pub schema_version: u8,
 }
 
 impl Default for TickArray {
@@ -156,12 +218,19 @@ impl Default for TickArray {
             whirlpool: Pubkey::default(),
             ticks: [Tick::default(); TICK_ARRAY_SIZE_USIZE],
             start_tick_index: 0,
+            prev_start_tick_index: NO_PREV_TICK_ARRAY,
+            next_start_tick_index: NO_NEXT_TICK_ARRAY,
+            initialized_bitmap: 0,
+            schema_version: 0,
         }
     }
 }
 
 impl TickArray {
-    pub const LEN: usize = 8 + 36 + (Tick::LEN * TICK_ARRAY_SIZE_USIZE);
+    pub const LEN: usize = 8 + 36 + 8 + 16 + 1 + (Tick::LEN * TICK_ARRAY_SIZE_USIZE);
+    // Size of tick arrays initialized before the paging hints, the initialized tick bitmap and
+    // the schema version were appended, see `migrate_tick_array`
+    pub const LEGACY_LEN: usize = 8 + 36 + (Tick::LEN * TICK_ARRAY_SIZE_USIZE);
 
     /// Search for the next initialized tick in this array.
     ///
@@ -197,22 +266,47 @@ impl TickArray {
             curr_offset += 1;
         }
 
-        while curr_offset >= 0 && curr_offset < TICK_ARRAY_SIZE {
-            let curr_tick = self.ticks[curr_offset as usize];
-            if curr_tick.initialized {
-                return Ok(Some(
-                    (curr_offset * tick_spacing as i32) + self.start_tick_index,
-                ));
-            }
+        let next_offset = if a_to_b {
+            Self::highest_set_bit_at_or_below(self.initialized_bitmap, curr_offset)
+        } else {
+            Self::lowest_set_bit_at_or_above(self.initialized_bitmap, curr_offset)
+        };
 
-            curr_offset = if a_to_b {
-                curr_offset - 1
-            } else {
-                curr_offset + 1
-            };
+        Ok(next_offset.map(|offset| (offset * tick_spacing as i32) + self.start_tick_index))
+    }
+
+    // Offset of the highest set bit of the bitmap at or below the offset.
+    fn highest_set_bit_at_or_below(bitmap: u128, offset: i32) -> Option<i32> {
+        if offset < 0 {
+            return None;
         }
+        let masked = if offset >= TICK_ARRAY_SIZE {
+            bitmap
+        } else {
+            bitmap & ((1u128 << (offset + 1)) - 1)
+        };
+        if masked == 0 {
+            None
+        } else {
+            Some(127 - masked.leading_zeros() as i32)
+        }
+    }
 
-        Ok(None)
+    // Offset of the lowest set bit of the bitmap at or above the offset.
+    fn lowest_set_bit_at_or_above(bitmap: u128, offset: i32) -> Option<i32> {
+        if offset >= TICK_ARRAY_SIZE {
+            return None;
+        }
+        let masked = if offset <= 0 {
+            bitmap
+        } else {
+            bitmap & !((1u128 << offset) - 1)
+        };
+        if masked == 0 {
+            None
+        } else {
+            Some(masked.trailing_zeros() as i32)
+        }
     }
 
     /// Initialize the TickArray object
@@ -232,8 +326,80 @@ impl TickArray {
             return Err(ErrorCode::InvalidStartTick.into());
         }
 
+        self.schema_version = ACCOUNT_SCHEMA_VERSION;
         self.whirlpool = whirlpool.key();
         self.start_tick_index = start_tick_index;
+        self.prev_start_tick_index = NO_PREV_TICK_ARRAY;
+        self.next_start_tick_index = NO_NEXT_TICK_ARRAY;
+        Ok(())
+    }
+
+    /// Fill the fields appended to a legacy tick array once it was reallocated to `TickArray::LEN`
+    /// with zeroed data. The bitmap is rebuilt from the initialized flags of the ticks, and the
+    /// array is left unlinked until it is linked to its neighbours.
+    pub fn migrate_legacy_layout(&mut self) {
+        self.schema_version = ACCOUNT_SCHEMA_VERSION;
+        self.prev_start_tick_index = NO_PREV_TICK_ARRAY;
+        self.next_start_tick_index = NO_NEXT_TICK_ARRAY;
+        self.initialized_bitmap = self
+            .ticks
+            .iter()
+            .enumerate()
+            .filter(|(_, tick)| tick.initialized)
+            .fold(0, |bitmap, (offset, _)| bitmap | (1u128 << offset));
+    }
+
+    /// The identifying header of the tick array.
+    pub fn describe(&self) -> AccountHeader {
+        AccountHeader {
+            schema_version: self.schema_version,
+            whirlpool: self.whirlpool,
+        }
+    }
+
+    /// Link a newly initialized tick array into the paging list, between `prev` and the array
+    /// `prev` currently points to.
+    ///
+    /// # Errors
+    /// - `InvalidTickArrayLink` - `prev` belongs to another pool or this array does not fit between
+    ///   `prev` and its next array.
+    pub fn link_after(&mut self, prev: &mut TickArray) -> Result<()> {
+        let start_tick_index = self.start_tick_index;
+        let prev_start_tick_index = prev.start_tick_index;
+        let prev_next_start_tick_index = prev.next_start_tick_index;
+        if prev.whirlpool != self.whirlpool
+            || prev_start_tick_index >= start_tick_index
+            || prev_next_start_tick_index <= start_tick_index
+        {
+            return Err(ErrorCode::InvalidTickArrayLink.into());
+        }
+
+        self.prev_start_tick_index = prev_start_tick_index;
+        self.next_start_tick_index = prev_next_start_tick_index;
+        prev.next_start_tick_index = start_tick_index;
+        Ok(())
+    }
+
+    /// Link a newly initialized tick array into the paging list, between `next` and the array
+    /// `next` currently points back to.
+    ///
+    /// # Errors
+    /// - `InvalidTickArrayLink` - `next` belongs to another pool or this array does not fit between
+    ///   `next` and its previous array.
+    pub fn link_before(&mut self, next: &mut TickArray) -> Result<()> {
+        let start_tick_index = self.start_tick_index;
+        let next_start_tick_index = next.start_tick_index;
+        let next_prev_start_tick_index = next.prev_start_tick_index;
+        if next.whirlpool != self.whirlpool
+            || next_start_tick_index <= start_tick_index
+            || next_prev_start_tick_index >= start_tick_index
+        {
+            return Err(ErrorCode::InvalidTickArrayLink.into());
+        }
+
+        self.prev_start_tick_index = next_prev_start_tick_index;
+        self.next_start_tick_index = next_start_tick_index;
+        next.prev_start_tick_index = start_tick_index;
         Ok(())
     }
 
@@ -284,6 +450,11 @@ impl TickArray {
             return Err(ErrorCode::TickNotFound.into());
         }
         self.ticks.get_mut(offset as usize).unwrap().update(update);
+        if update.initialized {
+            self.initialized_bitmap |= 1u128 << offset;
+        } else {
+            self.initialized_bitmap &= !(1u128 << offset);
+        }
         Ok(())
     }
 
@@ -321,6 +492,44 @@ impl TickArray {
         self.start_tick_index + TICK_ARRAY_SIZE * (tick_spacing as i32) > MAX_TICK_INDEX
     }
 
+    /// Sum the net liquidity of the initialized ticks in this array at or below a tick index.
+    pub fn liquidity_net_at_or_below(&self, tick_index: i32, tick_spacing: u16) -> i128 {
+        let mut liquidity_net = 0i128;
+        for (offset, tick) in self.ticks.iter().enumerate() {
+            let index = self.start_tick_index + offset as i32 * tick_spacing as i32;
+            if index > tick_index {
+                break;
+            }
+            if tick.initialized {
+                liquidity_net += tick.liquidity_net;
+            }
+        }
+        liquidity_net
+    }
+
+    /// Start tick index of the tick array that holds the given tick index.
+    pub fn start_tick_index_for(tick_index: i32, tick_spacing: u16) -> i32 {
+        let ticks_in_array = TICK_ARRAY_SIZE * tick_spacing as i32;
+        let mut start = tick_index / ticks_in_array;
+        if tick_index < 0 && tick_index % ticks_in_array != 0 {
+            start -= 1;
+        }
+        start * ticks_in_array
+    }
+
+    /// Address of the tick array PDA of a whirlpool with the given start tick index.
+    pub fn find_address(whirlpool: &Pubkey, start_tick_index: i32) -> Pubkey {
+        Pubkey::find_program_address(
+            &[
+                b"tick_array",
+                whirlpool.as_ref(),
+                start_tick_index.to_string().as_bytes(),
+            ],
+            &crate::ID,
+        )
+        .0
+    }
+
     // Calculates an offset from a tick index that can be used to access the tick data
     pub fn tick_offset(&self, tick_index: i32, tick_spacing: u16) -> Result<isize> {
         if tick_spacing == 0 {
@@ -331,6 +540,12 @@ impl TickArray {
     }
 }
 
+/// Tick arrays holding the lower and upper ticks of a position, which may be the same array.
+pub struct TickArrayRefs<'a> {
+    pub lower: &'a TickArray,
+    pub upper: &'a TickArray,
+}
+
 fn get_offset(tick_index: i32, start_tick_index: i32, tick_spacing: u16) -> isize {
     // TODO: replace with i32.div_floor once not experimental
     let lhs = tick_index - start_tick_index;
@@ -554,6 +769,44 @@ mod check_is_valid_start_tick_tests {
     }
 }
 
+#[cfg(test)]
+mod full_range_tests {
+    use super::*;
+
+    #[test]
+    fn test_full_range_tick_indexes() {
+        assert_eq!(Tick::full_range_tick_indexes(1), (MIN_TICK_INDEX, MAX_TICK_INDEX));
+        assert_eq!(Tick::full_range_tick_indexes(64), (-443584, 443584));
+        for tick_spacing in [1u16, 8, 64, 128, 256, 32768] {
+            let (lower, upper) = Tick::full_range_tick_indexes(tick_spacing);
+            assert!(Tick::check_is_usable_tick(lower, tick_spacing));
+            assert!(Tick::check_is_usable_tick(upper, tick_spacing));
+            assert!(!Tick::check_is_usable_tick(lower - tick_spacing as i32, tick_spacing));
+            assert!(!Tick::check_is_usable_tick(upper + tick_spacing as i32, tick_spacing));
+        }
+    }
+
+    #[test]
+    fn test_resolve_full_range() {
+        assert_eq!(
+            Tick::resolve_full_range(
+                FULL_RANGE_TICK_INDEX,
+                FULL_RANGE_TICK_INDEX,
+                Tick::full_range_tick_indexes(64)
+            ),
+            (-443584, 443584)
+        );
+        assert_eq!(
+            Tick::resolve_full_range(-128, FULL_RANGE_TICK_INDEX, (-256, 256)),
+            (-128, 256)
+        );
+        assert_eq!(
+            Tick::resolve_full_range(-128, 128, Tick::full_range_tick_indexes(64)),
+            (-128, 128)
+        );
+    }
+}
+
 #[cfg(test)]
 mod check_is_out_of_bounds_tests {
     use super::*;
@@ -625,3 +878,207 @@ mod array_update_tests {
         assert_eq!(*result, expected);
     }
 }
+
+#[cfg(test)]
+mod next_init_tick_index_tests {
+    use super::*;
+
+    fn initialize_ticks(array: &mut TickArray, offsets: &[i32], tick_spacing: u16) {
+        let start_tick_index = array.start_tick_index;
+        for offset in offsets {
+            let update = TickUpdate {
+                initialized: true,
+                ..Default::default()
+            };
+            array
+                .update_tick(
+                    start_tick_index + offset * tick_spacing as i32,
+                    tick_spacing,
+                    &update,
+                )
+                .unwrap();
+        }
+    }
+
+    // Reference linear scan over the initialized flags of the ticks.
+    fn linear_search(array: &TickArray, offset: i32, a_to_b: bool) -> Option<i32> {
+        let mut curr_offset = if a_to_b { offset } else { offset + 1 };
+        while (0..TICK_ARRAY_SIZE).contains(&curr_offset) {
+            if array.ticks[curr_offset as usize].initialized {
+                return Some(curr_offset);
+            }
+            curr_offset += if a_to_b { -1 } else { 1 };
+        }
+        None
+    }
+
+    #[test]
+    fn test_bitmap_search_matches_linear_scan() {
+        let mut array = TickArray::default();
+        initialize_ticks(&mut array, &[0, 5, 6, 63, 64, 87], 1);
+
+        // b_to_a searches start one tick to the left of the array.
+        for &(a_to_b, min_offset) in &[(true, 0), (false, -1)] {
+            for offset in min_offset..TICK_ARRAY_SIZE + min_offset {
+                let expected = linear_search(&array, offset, a_to_b);
+                let result = array.get_next_init_tick_index(offset, 1, a_to_b).unwrap();
+                assert_eq!(result, expected, "offset {} a_to_b {}", offset, a_to_b);
+            }
+        }
+    }
+
+    #[test]
+    fn test_bitmap_cleared_when_tick_uninitialized() {
+        let mut array = TickArray::default();
+        initialize_ticks(&mut array, &[10], 1);
+        array
+            .update_tick(10, 1, &TickUpdate::default())
+            .unwrap();
+
+        assert_eq!(array.get_next_init_tick_index(87, 1, true).unwrap(), None);
+    }
+
+    #[test]
+    fn test_b_to_a_search_from_previous_array() {
+        let mut array = TickArray {
+            start_tick_index: 88 * 4,
+            ..Default::default()
+        };
+        initialize_ticks(&mut array, &[0], 4);
+        let start_tick_index = array.start_tick_index;
+
+        // The tick just below the array starts a b_to_a search at its first tick.
+        assert_eq!(
+            array
+                .get_next_init_tick_index(start_tick_index - 4, 4, false)
+                .unwrap(),
+            Some(start_tick_index)
+        );
+    }
+}
+
+#[cfg(test)]
+mod start_tick_index_tests {
+    use super::*;
+
+    #[test]
+    fn test_start_tick_index_for_positive() {
+        assert_eq!(TickArray::start_tick_index_for(0, 8), 0);
+        assert_eq!(TickArray::start_tick_index_for(703, 8), 0);
+        assert_eq!(TickArray::start_tick_index_for(704, 8), 704);
+    }
+
+    #[test]
+    fn test_start_tick_index_for_negative() {
+        assert_eq!(TickArray::start_tick_index_for(-1, 8), -704);
+        assert_eq!(TickArray::start_tick_index_for(-704, 8), -704);
+        assert_eq!(TickArray::start_tick_index_for(-705, 8), -1408);
+    }
+}
+
+#[cfg(test)]
+mod link_tests {
+    use super::*;
+
+    fn tick_array(whirlpool: Pubkey, start_tick_index: i32) -> TickArray {
+        TickArray {
+            whirlpool,
+            start_tick_index,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_link_between_neighbours() {
+        let whirlpool = Pubkey::new_unique();
+        let mut lower = tick_array(whirlpool, -704);
+        let mut upper = tick_array(whirlpool, 704);
+        upper.link_after(&mut lower).unwrap();
+
+        let mut middle = tick_array(whirlpool, 0);
+        middle.link_after(&mut lower).unwrap();
+        middle.link_before(&mut upper).unwrap();
+
+        assert_eq!({ lower.next_start_tick_index }, 0);
+        assert_eq!({ middle.prev_start_tick_index }, -704);
+        assert_eq!({ middle.next_start_tick_index }, 704);
+        assert_eq!({ upper.prev_start_tick_index }, 0);
+        assert_eq!({ upper.next_start_tick_index }, NO_NEXT_TICK_ARRAY);
+    }
+
+    #[test]
+    fn test_link_rejects_non_adjacent_array() {
+        let whirlpool = Pubkey::new_unique();
+        let mut lower = tick_array(whirlpool, -704);
+        let mut upper = tick_array(whirlpool, 704);
+        upper.link_after(&mut lower).unwrap();
+
+        let mut outside = tick_array(whirlpool, 1408);
+        assert!(outside.link_after(&mut lower).is_err());
+        assert!(tick_array(Pubkey::new_unique(), 0)
+            .link_after(&mut lower)
+            .is_err());
+    }
+}
+
+#[cfg(test)]
+mod liquidity_net_tests {
+    use super::*;
+
+    #[test]
+    fn sums_initialized_ticks_at_or_below_index() {
+        let mut array = TickArray {
+            start_tick_index: -16,
+            ..Default::default()
+        };
+        array.ticks[0].initialized = true;
+        array.ticks[0].liquidity_net = 100;
+        array.ticks[2].initialized = true;
+        array.ticks[2].liquidity_net = 50;
+        array.ticks[3].liquidity_net = 1000;
+        array.ticks[4].initialized = true;
+        array.ticks[4].liquidity_net = -100;
+
+        assert_eq!(array.liquidity_net_at_or_below(-17, 8), 0);
+        assert_eq!(array.liquidity_net_at_or_below(-16, 8), 100);
+        assert_eq!(array.liquidity_net_at_or_below(15, 8), 150);
+        assert_eq!(array.liquidity_net_at_or_below(16, 8), 50);
+    }
+}
+
+#[cfg(test)]
+mod layout_tests {
+    use super::*;
+
+    #[test]
+    fn test_zero_copy_layout() {
+        assert_eq!(Tick::LEN, std::mem::size_of::<Tick>());
+        assert_eq!(TickArray::LEN, 8 + std::mem::size_of::<TickArray>());
+    }
+
+    #[test]
+    fn test_migrate_legacy_layout() {
+        // A legacy array reallocated with zeroed data for the appended fields
+        let mut array = TickArray {
+            start_tick_index: -704,
+            prev_start_tick_index: 0,
+            next_start_tick_index: 0,
+            ..TickArray::default()
+        };
+        array.ticks[0].initialized = true;
+        array.ticks[87].initialized = true;
+        array.migrate_legacy_layout();
+
+        assert_eq!(array.describe().schema_version, ACCOUNT_SCHEMA_VERSION);
+        assert_eq!(
+            { array.prev_start_tick_index },
+            NO_PREV_TICK_ARRAY
+        );
+        assert_eq!(
+            { array.next_start_tick_index },
+            NO_NEXT_TICK_ARRAY
+        );
+        assert_eq!(array.get_next_init_tick_index(-16, 8, true).unwrap(), Some(-704));
+        assert_eq!(array.get_next_init_tick_index(-704, 8, false).unwrap(), Some(-8));
+    }
+}
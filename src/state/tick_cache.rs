@@ -0,0 +1,241 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::ErrorCode;
+use crate::math::{align_tick_to_spacing, TickAlignment};
+
+use super::{Tick, TickArray, TickUpdate, MAX_TICK_INDEX, MIN_TICK_INDEX, TICK_ARRAY_SIZE};
+
+/// Copy of the ticks around the current price of a Whirlpool, so swaps in the hot band can run
+/// with one cache account instead of three tick arrays.
+///
+/// The window has the shape of a tick array, but starts half an array below the current tick
+/// instead of at an array boundary. While swaps cross ticks of the window, the cache is ahead of
+/// the tick arrays it was loaded from until `refresh_tick_cache` writes its ticks back.
+#[account(zero_copy(unsafe))]
+#[repr(C, packed)]
+pub struct TickCache {
+    pub whirlpool: Pubkey, // 32
+    pub window: TickArray, // 36 + 8 + 16 + Tick::LEN * 88
+}
+
+impl Default for TickCache {
+    #[inline]
+    fn default() -> TickCache {
+        TickCache {
+            whirlpool: Pubkey::default(),
+            window: TickArray::default(),
+        }
+    }
+}
+
+impl TickCache {
+    pub const LEN: usize = 8 + 32 + (TickArray::LEN - 8);
+
+    pub fn initialize(&mut self, whirlpool: Pubkey) {
+        self.whirlpool = whirlpool;
+        self.window.whirlpool = whirlpool;
+    }
+
+    /// Start tick index of the window centered on the current tick, kept within the tick arrays
+    /// of the pool.
+    pub fn window_start_for(tick_current_index: i32, tick_spacing: u16) -> i32 {
+        let centered_start =
+            align_tick_to_spacing(tick_current_index, tick_spacing, TickAlignment::Down)
+                - (TICK_ARRAY_SIZE / 2) * tick_spacing as i32;
+        let min_start = TickArray::start_tick_index_for(MIN_TICK_INDEX, tick_spacing);
+        let max_start = TickArray::start_tick_index_for(MAX_TICK_INDEX, tick_spacing);
+        centered_start.max(min_start).min(max_start)
+    }
+
+    /// Load the window starting at `start_tick_index` from the tick arrays covering it.
+    ///
+    /// # Errors
+    /// - `TickCacheTickArrayMissing` - A tick array covering the window was not provided.
+    pub fn load_window(
+        &mut self,
+        start_tick_index: i32,
+        tick_arrays: &[&mut TickArray],
+        tick_spacing: u16,
+    ) -> Result<()> {
+        let mut initialized_bitmap = 0u128;
+        for offset in 0..TICK_ARRAY_SIZE {
+            let tick_index = start_tick_index + offset * tick_spacing as i32;
+            let tick = if Tick::check_is_usable_tick(tick_index, tick_spacing) {
+                let position = find_tick_array(tick_arrays, tick_index, tick_spacing)?;
+                *tick_arrays[position].get_tick(tick_index, tick_spacing)?
+            } else {
+                Tick::default()
+            };
+            if tick.initialized {
+                initialized_bitmap |= 1u128 << offset;
+            }
+            self.window.ticks[offset as usize] = tick;
+        }
+
+        self.window.start_tick_index = start_tick_index;
+        self.window.initialized_bitmap = initialized_bitmap;
+        Ok(())
+    }
+
+    /// Copy the ticks of the window back into the tick arrays covering it.
+    ///
+    /// # Errors
+    /// - `TickCacheTickArrayMissing` - A tick array covering the window was not provided.
+    pub fn write_back(&self, tick_arrays: &mut [&mut TickArray], tick_spacing: u16) -> Result<()> {
+        let start_tick_index = self.window.start_tick_index;
+        for offset in 0..TICK_ARRAY_SIZE {
+            let tick_index = start_tick_index + offset * tick_spacing as i32;
+            if !Tick::check_is_usable_tick(tick_index, tick_spacing) {
+                continue;
+            }
+            let position = find_tick_array(tick_arrays, tick_index, tick_spacing)?;
+            tick_arrays[position].update_tick(
+                tick_index,
+                tick_spacing,
+                &TickUpdate::from(&self.window.ticks[offset as usize]),
+            )?;
+        }
+        Ok(())
+    }
+}
+
+fn find_tick_array(
+    tick_arrays: &[&mut TickArray],
+    tick_index: i32,
+    tick_spacing: u16,
+) -> Result<usize> {
+    tick_arrays
+        .iter()
+        .position(|tick_array| tick_array.check_in_array_bounds(tick_index, tick_spacing))
+        .ok_or_else(|| ErrorCode::TickCacheTickArrayMissing.into())
+}
+
+#[cfg(test)]
+mod tick_cache_tests {
+    use super::*;
+
+    const TICK_SPACING: u16 = 8;
+
+    fn tick_array(start_tick_index: i32, initialized_ticks: &[i32]) -> TickArray {
+        let mut tick_array = TickArray {
+            start_tick_index,
+            ..Default::default()
+        };
+        for tick_index in initialized_ticks {
+            tick_array
+                .update_tick(
+                    *tick_index,
+                    TICK_SPACING,
+                    &TickUpdate {
+                        initialized: true,
+                        liquidity_gross: 10,
+                        ..Default::default()
+                    },
+                )
+                .unwrap();
+        }
+        tick_array
+    }
+
+    #[test]
+    fn test_window_start_for() {
+        assert_eq!(TickCache::window_start_for(0, TICK_SPACING), -352);
+        assert_eq!(TickCache::window_start_for(-1, TICK_SPACING), -360);
+        assert_eq!(TickCache::window_start_for(705, TICK_SPACING), 352);
+        for tick_spacing in [1, 8, 64, 128] {
+            for tick_index in [MIN_TICK_INDEX, MAX_TICK_INDEX] {
+                let window_start = TickCache::window_start_for(tick_index, tick_spacing);
+                assert!(
+                    window_start >= TickArray::start_tick_index_for(MIN_TICK_INDEX, tick_spacing)
+                );
+                assert!(
+                    window_start <= TickArray::start_tick_index_for(MAX_TICK_INDEX, tick_spacing)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_load_window_across_tick_arrays() {
+        let mut lower = tick_array(-704, &[-16, -704]);
+        let mut upper = tick_array(0, &[8, 600]);
+        let mut tick_cache = TickCache::default();
+
+        tick_cache
+            .load_window(-352, &[&mut lower, &mut upper], TICK_SPACING)
+            .unwrap();
+
+        let window = tick_cache.window;
+        let start_tick_index = window.start_tick_index;
+        assert_eq!(start_tick_index, -352);
+        assert!(window.get_tick(-16, TICK_SPACING).unwrap().initialized);
+        assert!(window.get_tick(8, TICK_SPACING).unwrap().initialized);
+        assert_eq!(
+            window
+                .get_next_init_tick_index(344, TICK_SPACING, true)
+                .unwrap(),
+            Some(8)
+        );
+        assert_eq!(
+            window
+                .get_next_init_tick_index(-8, TICK_SPACING, true)
+                .unwrap(),
+            Some(-16)
+        );
+        assert_eq!(
+            window
+                .get_next_init_tick_index(8, TICK_SPACING, false)
+                .unwrap(),
+            None
+        );
+    }
+
+    #[test]
+    fn test_load_window_missing_tick_array() {
+        let mut upper = tick_array(0, &[]);
+        let mut tick_cache = TickCache::default();
+
+        let result = tick_cache.load_window(-352, &[&mut upper], TICK_SPACING);
+
+        assert_eq!(
+            result.unwrap_err(),
+            ErrorCode::TickCacheTickArrayMissing.into()
+        );
+    }
+
+    #[test]
+    fn test_write_back_updates_tick_arrays() {
+        let mut lower = tick_array(-704, &[-16]);
+        let mut upper = tick_array(0, &[8]);
+        let mut tick_cache = TickCache::default();
+        tick_cache
+            .load_window(-352, &[&mut lower, &mut upper], TICK_SPACING)
+            .unwrap();
+
+        let crossed = TickUpdate {
+            initialized: true,
+            liquidity_gross: 10,
+            fee_growth_outside_a: 100,
+            ..Default::default()
+        };
+        tick_cache
+            .window
+            .update_tick(-16, TICK_SPACING, &crossed)
+            .unwrap();
+        tick_cache
+            .write_back(&mut [&mut lower, &mut upper], TICK_SPACING)
+            .unwrap();
+
+        let fee_growth_outside_a = lower
+            .get_tick(-16, TICK_SPACING)
+            .unwrap()
+            .fee_growth_outside_a;
+        assert_eq!(fee_growth_outside_a, 100);
+        assert!(upper.get_tick(8, TICK_SPACING).unwrap().initialized);
+    }
+
+    #[test]
+    fn test_tick_cache_len() {
+        assert_eq!(TickCache::LEN, 8 + std::mem::size_of::<TickCache>());
+    }
+}
@@ -0,0 +1,60 @@
+use anchor_lang::prelude::*;
+
+/// Policy of a WhirlpoolsConfig for a token mint, e.g. whether pools may be created for the mint
+/// although its freeze authority could freeze the token vaults of the pools.
+#[account]
+#[derive(Default)]
+pub struct TokenBadge {
+    pub whirlpools_config: Pubkey, // 32
+    pub token_mint: Pubkey,        // 32
+    // True if pools may be created for the mint while it has a freeze authority
+    pub allow_freeze_authority: bool, // 1
+}
+
+impl TokenBadge {
+    pub const LEN: usize = 8 + 64 + 1;
+
+    pub fn initialize(
+        &mut self,
+        whirlpools_config: Pubkey,
+        token_mint: Pubkey,
+        allow_freeze_authority: bool,
+    ) {
+        self.whirlpools_config = whirlpools_config;
+        self.token_mint = token_mint;
+        self.allow_freeze_authority = allow_freeze_authority;
+    }
+
+    pub fn update_allow_freeze_authority(&mut self, allow_freeze_authority: bool) {
+        self.allow_freeze_authority = allow_freeze_authority;
+    }
+
+    pub fn permits_freeze_authority(
+        &self,
+        whirlpools_config: &Pubkey,
+        token_mint: &Pubkey,
+    ) -> bool {
+        self.whirlpools_config == *whirlpools_config
+            && self.token_mint == *token_mint
+            && self.allow_freeze_authority
+    }
+}
+
+#[cfg(test)]
+mod token_badge_tests {
+    use super::*;
+
+    #[test]
+    fn test_permits_freeze_authority() {
+        let whirlpools_config = Pubkey::new_unique();
+        let token_mint = Pubkey::new_unique();
+        let mut badge = TokenBadge::default();
+        badge.initialize(whirlpools_config, token_mint, false);
+        assert!(!badge.permits_freeze_authority(&whirlpools_config, &token_mint));
+
+        badge.update_allow_freeze_authority(true);
+        assert!(badge.permits_freeze_authority(&whirlpools_config, &token_mint));
+        assert!(!badge.permits_freeze_authority(&Pubkey::new_unique(), &token_mint));
+        assert!(!badge.permits_freeze_authority(&whirlpools_config, &Pubkey::new_unique()));
+    }
+}
@@ -0,0 +1,120 @@
+use anchor_lang::prelude::*;
+
+// Number of most recent trades kept in a trade log.
+pub const TRADE_LOG_SIZE: usize = 128;
+
+#[zero_copy(unsafe)]
+#[repr(C, packed)]
+#[derive(Default, PartialEq)]
+#[cfg_attr(not(target_arch = "bpf"), derive(Debug))]
+pub struct TradeRecord {
+    // Q64.64 sqrt price after the trade
+    pub sqrt_price: u128, // 16
+    pub amount_in: u64,   // 8
+    pub amount_out: u64,  // 8
+    pub slot: u64,        // 8
+    pub a_to_b: bool,     // 1
+}
+
+impl TradeRecord {
+    pub const LEN: usize = 41;
+}
+
+/// Ring buffer of the most recent swaps of a Whirlpool, so that traders can poll a single account
+/// for recent trades.
+#[account(zero_copy(unsafe))]
+#[repr(C, packed)]
+pub struct TradeLog {
+    pub whirlpool: Pubkey, // 32
+    // Number of trades recorded since the log was created. The next trade is written at
+    // `trade_count % TRADE_LOG_SIZE`.
+    pub trade_count: u64,                       // 8
+    pub records: [TradeRecord; TRADE_LOG_SIZE], // 41 * 128
+}
+
+impl Default for TradeLog {
+    #[inline]
+    fn default() -> TradeLog {
+        TradeLog {
+            whirlpool: Pubkey::default(),
+            trade_count: 0,
+            records: [TradeRecord::default(); TRADE_LOG_SIZE],
+        }
+    }
+}
+
+impl TradeLog {
+    pub const LEN: usize = 8 + 40 + TradeRecord::LEN * TRADE_LOG_SIZE;
+
+    pub fn initialize(&mut self, whirlpool: Pubkey) {
+        self.whirlpool = whirlpool;
+    }
+
+    /// Record a trade, overwriting the oldest record once the log is full.
+    pub fn append(&mut self, record: TradeRecord) {
+        let trade_count = self.trade_count;
+        self.records[(trade_count % TRADE_LOG_SIZE as u64) as usize] = record;
+        self.trade_count = trade_count.wrapping_add(1);
+    }
+
+    /// Up to `count` of the most recent trades, newest first.
+    pub fn recent_records(&self, count: usize) -> Vec<TradeRecord> {
+        let trade_count = self.trade_count;
+        let available = trade_count.min(TRADE_LOG_SIZE as u64) as usize;
+        (1..=count.min(available))
+            .map(|back| {
+                let index = (trade_count - back as u64) % TRADE_LOG_SIZE as u64;
+                self.records[index as usize]
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod trade_log_tests {
+    use super::*;
+
+    fn record(amount_in: u64) -> TradeRecord {
+        TradeRecord {
+            amount_in,
+            ..Default::default()
+        }
+    }
+
+    fn amounts_in(records: &[TradeRecord]) -> Vec<u64> {
+        records.iter().map(|record| record.amount_in).collect()
+    }
+
+    #[test]
+    fn test_recent_records_before_wrap() {
+        let mut trade_log = TradeLog::default();
+        for amount_in in 1..=3 {
+            trade_log.append(record(amount_in));
+        }
+
+        assert_eq!(amounts_in(&trade_log.recent_records(2)), vec![3, 2]);
+        assert_eq!(amounts_in(&trade_log.recent_records(10)), vec![3, 2, 1]);
+    }
+
+    #[test]
+    fn test_append_overwrites_oldest_record() {
+        let mut trade_log = TradeLog::default();
+        for amount_in in 1..=(TRADE_LOG_SIZE as u64 + 2) {
+            trade_log.append(record(amount_in));
+        }
+
+        let trade_count = trade_log.trade_count;
+        assert_eq!(trade_count, TRADE_LOG_SIZE as u64 + 2);
+        let recent = trade_log.recent_records(TRADE_LOG_SIZE + 1);
+        assert_eq!(recent.len(), TRADE_LOG_SIZE);
+        let amounts = amounts_in(&recent);
+        assert_eq!(amounts[0], TRADE_LOG_SIZE as u64 + 2);
+        assert_eq!(amounts[TRADE_LOG_SIZE - 1], 3);
+    }
+
+    #[test]
+    fn test_trade_log_len() {
+        assert_eq!(TradeRecord::LEN, std::mem::size_of::<TradeRecord>());
+        assert_eq!(TradeLog::LEN, 8 + std::mem::size_of::<TradeLog>());
+    }
+}
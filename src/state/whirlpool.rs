@@ -1,13 +1,16 @@
 use crate::{
     errors::ErrorCode,
     math::{
-        tick_index_from_sqrt_price, MAX_FEE_RATE, MAX_PROTOCOL_FEE_RATE, MAX_SQRT_PRICE_X64,
-        MIN_SQRT_PRICE_X64,
+        fee_rate_from_legacy, fee_rate_to_legacy, mul_u256, sqrt_price_from_tick_index,
+        tick_index_from_sqrt_price, token_to_underlying_amount, underlying_to_token_amount,
+        MAX_FEE_RATE, MAX_JIT_SURCHARGE_RATE, MAX_PROTOCOL_FEE_RATE, MAX_REFERRAL_FEE_RATE,
+        checked_mul_shift_right, MAX_SQRT_PRICE_X64, MIN_SQRT_PRICE_X64,
+        PROTOCOL_FEE_RATE_MUL_VALUE, Q64_RESOLUTION, REWARD_TENURE_BOOST_RATE_MUL_VALUE,
     },
 };
 use anchor_lang::prelude::*;
 
-use super::WhirlpoolsConfig;
+use super::{Position, Tick, WhirlpoolsConfig};
 
 #[account]
 #[derive(Default)]
@@ -19,7 +22,7 @@ pub struct Whirlpool {
     pub tick_spacing: u16,          // 2
     pub tick_spacing_seed: [u8; 2], // 2
 
-    // Stored as hundredths of a basis point
+    // Stored as hundredths of a basis point, rounded down from `precise_fee_rate`
     // u16::MAX corresponds to ~6.5%
     pub fee_rate: u16, // 2
 
@@ -52,13 +55,143 @@ pub struct Whirlpool {
     pub reward_last_updated_timestamp: u64, // 8
 
     pub reward_infos: [WhirlpoolRewardInfo; NUM_REWARDS], // 384
+
+    // Program invoked after every swap, Pubkey::default() when disabled
+    pub after_swap_hook: Pubkey, // 32
+
+    // Exchange rate oracle of the rebasing token side, Pubkey::default() when disabled
+    pub exchange_rate_oracle: Pubkey, // 32
+    // Q64.64 underlying units per token unit of the rebasing side
+    pub exchange_rate_x64: u128, // 16
+    // True if the rebasing side is token A, false if it is token B
+    pub exchange_rate_on_a: bool, // 1
+
+    // Sum of the liquidity of all positions, in range or not
+    pub total_liquidity: u128, // 16
+    // Rewards that accrue to all liquidity instead of only to in-range liquidity
+    pub reward_accrues_to_all_liquidity: [bool; NUM_REWARDS], // 3
+
+    // Reward authorities proposed by the current reward authorities, pending acceptance
+    pub pending_reward_authorities: [Pubkey; NUM_REWARDS], // 96
+
+    // Trade log swaps are recorded to, Pubkey::default() when disabled
+    pub trade_log: Pubkey, // 32
+
+    // Metaplex collection mint positions are verified into, Pubkey::default() when not created
+    pub position_collection_mint: Pubkey, // 32
+
+    // Swap directions disabled by the fee authority, e.g. to only allow buys during a launch
+    pub swap_a_to_b_disabled: bool, // 1
+    pub swap_b_to_a_disabled: bool, // 1
+
+    // Cumulative amounts retained by the pool from rounding token deltas in its favor, in pool units
+    pub rounding_dust_a: u64, // 8
+    pub rounding_dust_b: u64, // 8
+
+    // Tick cache swaps can run against, Pubkey::default() when disabled
+    pub tick_cache: Pubkey, // 32
+    // True if tick arrays changed since the tick cache was loaded
+    pub tick_cache_stale: bool, // 1
+    // True if swaps changed ticks of the tick cache that are not written back to the tick arrays
+    pub tick_cache_ahead: bool, // 1
+
+    // True if swap fees are accounted in token B only, fees on token A input are taken from the
+    // token B output instead
+    pub fees_in_token_b: bool, // 1
+
+    // Share of the protocol fee paid to the referrer of a swap. Stored as basis points
+    pub referral_fee_rate: u16, // 2
+
+    // Cumulative token amounts swapped through the pool and swap fees charged, for analytics
+    pub lifetime_volume_a: u128, // 16
+    pub lifetime_volume_b: u128, // 16
+    pub lifetime_fees_a: u128,   // 16
+    pub lifetime_fees_b: u128,   // 16
+
+    // Maximum number of open positions, 0 if unlimited. Positions opened before the counter was
+    // added are not counted
+    pub max_positions: u32,  // 4
+    pub open_positions: u32, // 4
+
+    // Escrow programs that release rewards into the reward vaults on collection,
+    // Pubkey::default() when the reward vault is funded directly
+    pub reward_escrow_programs: [Pubkey; NUM_REWARDS], // 96
+
+    // Program notified of every initialized tick crossed by a swap, Pubkey::default() when
+    // disabled
+    pub tick_cross_hook: Pubkey, // 32
+
+    // Fee schedule of temporary fee reductions applied by swaps, Pubkey::default() when disabled
+    pub fee_schedule: Pubkey, // 32
+
+    // Slot of the last swap, for consumers of the pool price to detect stale pools
+    pub last_swap_slot: u64, // 8
+
+    // Change of the active liquidity within a slot, in basis points of the liquidity at the start
+    // of the slot, above which a `LiquidityShifted` event is emitted. 0 when disabled
+    pub liquidity_shift_alert_rate: u16, // 2
+    // Slot of the last active liquidity change and the active liquidity at the start of that slot
+    pub liquidity_shift_slot: u64,                  // 8
+    pub liquidity_shift_slot_start_liquidity: u128, // 16
+
+    // True if the emission rates of all rewards are zero, so reward growth updates can be skipped.
+    // Maintained by the reward emission updates, false for pools created before the flag
+    pub reward_emissions_zero: bool, // 1
+
+    // Seconds since the last liquidity increase of a position before liquidity can be removed from
+    // it, deterring just-in-time liquidity. 0 when disabled
+    pub liquidity_removal_cooldown: u32, // 4
+
+    // Surcharge on liquidity removed within `jit_surcharge_slots` slots of its position's last
+    // liquidity increase, in basis points of the removed amounts. Redistributed to the in-range
+    // liquidity through the fee growth. 0 when disabled
+    pub jit_surcharge_rate: u16,  // 2
+    pub jit_surcharge_slots: u32, // 4
+
+    // Usable tick range of the pool, tighter than the global tick bounds. Both 0 when the pool
+    // uses the global bounds
+    pub min_tick_index: i32, // 4
+    pub max_tick_index: i32, // 4
+
+    // Fee rate charged on swaps, stored as thousandths of a basis point. 0 in Whirlpools created
+    // before the fee rate precision increase, which charge their legacy `fee_rate` until migrated
+    pub precise_fee_rate: u32, // 4
+
+    // Set by the emergency authority of the config to stop swaps and liquidity increases. Removing
+    // liquidity and collecting stay possible
+    pub paused: bool, // 1
+
+    // Reward tenure boosts: positions earn the base rate, in basis points, of the rewards they
+    // accrue, rising linearly to the full rewards over the boost period of tenure, the time since a
+    // checkpoint last found them out of range, empty or decreasing liquidity. The withheld share
+    // stays in the reward vault. A period of 0 disables the tenure boost of a reward
+    pub reward_tenure_boost_periods: [u32; NUM_REWARDS], // 12
+    pub reward_tenure_boost_base_rates: [u16; NUM_REWARDS], // 6
+
+    // Non-zero if the emissions of a reward are per unit of liquidity it accrues to per second
+    // instead of split among that liquidity, so the reward rate of a position does not depend on
+    // others. Liquidity beyond the cap splits the emissions of the capped liquidity, bounding the
+    // total emissions of the reward
+    pub reward_emissions_liquidity_caps: [u128; NUM_REWARDS], // 48
+
+    // Token amounts the program deposited into the vaults and has not paid out yet. Tokens sent to
+    // the vaults directly are not counted
+    pub vault_owed_a: u64, // 8
+    pub vault_owed_b: u64, // 8
+
+    // True if `total_liquidity` counts every position of the pool. Pools created before the counter
+    // count their positions as the positions are migrated, until the fee authority marks the
+    // counter complete
+    pub total_liquidity_complete: bool, // 1
 }
 
 // Number of rewards supported by Whirlpools
 pub const NUM_REWARDS: usize = 3;
 
+const DAY_IN_SECONDS: u128 = 60 * 60 * 24;
+
 impl Whirlpool {
-    pub const LEN: usize = 8 + 261 + 384;
+    pub const LEN: usize = 8 + 261 + 384 + 32 + 49 + 19 + 96 + 32 + 32 + 2 + 16 + 34 + 1 + 2 + 64 + 8 + 96 + 32 + 32 + 8 + 26 + 1 + 4 + 6 + 8 + 4 + 1 + 18 + 48 + 16 + 1;
     pub fn seeds(&self) -> [&[u8]; 6] {
         [
             &b"whirlpool"[..],
@@ -76,7 +209,7 @@ impl Whirlpool {
         bump: u8,
         tick_spacing: u16,
         sqrt_price: u128,
-        default_fee_rate: u16,
+        default_fee_rate: u32,
         token_mint_a: Pubkey,
         token_vault_a: Pubkey,
         token_mint_b: Pubkey,
@@ -117,6 +250,8 @@ impl Whirlpool {
         self.reward_infos =
             [WhirlpoolRewardInfo::new(whirlpools_config.reward_emissions_super_authority);
                 NUM_REWARDS];
+        self.reward_emissions_zero = true;
+        self.total_liquidity_complete = true;
 
         Ok(())
     }
@@ -145,6 +280,122 @@ impl Whirlpool {
         self.liquidity = liquidity;
     }
 
+    pub fn update_total_liquidity(&mut self, total_liquidity: u128) {
+        self.total_liquidity = total_liquidity;
+    }
+
+    /// Count the liquidity of a position opened before the total liquidity was tracked, as the
+    /// position is migrated.
+    pub fn count_migrated_position_liquidity(&mut self, liquidity: u128) -> Result<()> {
+        self.total_liquidity = self
+            .total_liquidity
+            .checked_add(liquidity)
+            .ok_or(ErrorCode::LiquidityOverflow)?;
+        Ok(())
+    }
+
+    pub fn mark_total_liquidity_complete(&mut self) {
+        self.total_liquidity_complete = true;
+    }
+
+    /// Set whether the reward at the specified index accrues to all liquidity of the pool instead
+    /// of only the in-range liquidity. Growth is tracked differently in each mode, so the mode can
+    /// only be changed before the reward has accrued any growth. Rewards accruing to all liquidity
+    /// are split by the total liquidity, which has to count every position.
+    pub fn update_reward_accrues_to_all_liquidity(
+        &mut self,
+        index: usize,
+        accrues_to_all_liquidity: bool,
+    ) -> Result<()> {
+        if index >= NUM_REWARDS {
+            return Err(ErrorCode::InvalidRewardIndex.into());
+        }
+        if self.reward_infos[index].growth_global_x64 != 0 {
+            return Err(ErrorCode::RewardAccrualModeLocked.into());
+        }
+        if accrues_to_all_liquidity && !self.total_liquidity_complete {
+            return Err(ErrorCode::TotalLiquidityIncomplete.into());
+        }
+        self.reward_accrues_to_all_liquidity[index] = accrues_to_all_liquidity;
+
+        Ok(())
+    }
+
+    /// Update the liquidity cap of the per liquidity emissions of the reward at the specified
+    /// index. A cap of 0 splits the emissions among the liquidity instead. Rewards have to be
+    /// brought up to date first, emissions since the last update are accounted under the new mode
+    /// otherwise.
+    pub fn update_reward_emissions_liquidity_cap(
+        &mut self,
+        index: usize,
+        emissions_liquidity_cap: u128,
+    ) -> Result<()> {
+        if index >= NUM_REWARDS {
+            return Err(ErrorCode::InvalidRewardIndex.into());
+        }
+        self.reward_emissions_liquidity_caps[index] = emissions_liquidity_cap;
+
+        Ok(())
+    }
+
+    /// Emissions per second of the reward at the specified index across all the liquidity it
+    /// accrues to, Q64.64. Per liquidity emissions are counted for the capped liquidity. None if
+    /// the emissions overflow, which halts the reward.
+    pub fn reward_total_emissions_per_second_x64(&self, index: usize) -> Option<u128> {
+        let emissions_per_second_x64 = self.reward_infos[index].emissions_per_second_x64;
+        match self.reward_emissions_liquidity_caps[index] {
+            0 => Some(emissions_per_second_x64),
+            liquidity_cap => emissions_per_second_x64.checked_mul(liquidity_cap),
+        }
+    }
+
+    /// Verify the reward vault holds at least one day of the total emissions of the reward at the
+    /// specified index.
+    ///
+    /// # Errors
+    /// - `RewardVaultAmountInsufficient` - The vault holds less than a day of emissions.
+    pub fn verify_reward_vault_covers_emissions(
+        &self,
+        index: usize,
+        reward_vault_amount: u64,
+    ) -> Result<()> {
+        let emissions_per_day = self
+            .reward_total_emissions_per_second_x64(index)
+            .and_then(|emissions| checked_mul_shift_right(DAY_IN_SECONDS, emissions).ok())
+            .ok_or(ErrorCode::RewardVaultAmountInsufficient)?;
+        if reward_vault_amount < emissions_per_day {
+            return Err(ErrorCode::RewardVaultAmountInsufficient.into());
+        }
+        Ok(())
+    }
+
+    /// Update the tenure boost of the reward at the specified index. A `boost_period` of 0 disables
+    /// it.
+    pub fn update_reward_tenure_boost(
+        &mut self,
+        index: usize,
+        boost_period: u32,
+        boost_base_rate: u16,
+    ) -> Result<()> {
+        if index >= NUM_REWARDS {
+            return Err(ErrorCode::InvalidRewardIndex.into());
+        }
+        if boost_base_rate > REWARD_TENURE_BOOST_RATE_MUL_VALUE {
+            return Err(ErrorCode::InvalidRewardTenureBoost.into());
+        }
+        self.reward_tenure_boost_periods[index] = boost_period;
+        self.reward_tenure_boost_base_rates[index] = boost_base_rate;
+
+        Ok(())
+    }
+
+    /// True if the tenure boost of any reward is enabled.
+    pub fn has_reward_tenure_boost(&self) -> bool {
+        self.reward_tenure_boost_periods
+            .iter()
+            .any(|period| *period != 0)
+    }
+
     /// Update the reward authority at the specified Whirlpool reward index.
     pub fn update_reward_authority(&mut self, index: usize, authority: Pubkey) -> Result<()> {
         if index >= NUM_REWARDS {
@@ -155,6 +406,54 @@ impl Whirlpool {
         Ok(())
     }
 
+    /// Propose a new reward authority at the specified Whirlpool reward index. The authority only
+    /// changes once the proposed authority accepts, so a mistyped address cannot take over the
+    /// reward. Proposing the default pubkey cancels a pending proposal.
+    pub fn propose_reward_authority(&mut self, index: usize, authority: Pubkey) -> Result<()> {
+        if index >= NUM_REWARDS {
+            return Err(ErrorCode::InvalidRewardIndex.into());
+        }
+        self.pending_reward_authorities[index] = authority;
+
+        Ok(())
+    }
+
+    /// Set the escrow program that releases rewards into the vault at the specified Whirlpool
+    /// reward index, or Pubkey::default() to fund the vault directly.
+    pub fn update_reward_escrow_program(
+        &mut self,
+        index: usize,
+        escrow_program: Pubkey,
+    ) -> Result<()> {
+        if index >= NUM_REWARDS {
+            return Err(ErrorCode::InvalidRewardIndex.into());
+        }
+        if escrow_program == crate::ID {
+            return Err(ErrorCode::InvalidRewardEscrowProgram.into());
+        }
+        self.reward_escrow_programs[index] = escrow_program;
+
+        Ok(())
+    }
+
+    pub fn has_reward_escrow_program(&self, index: usize) -> bool {
+        self.reward_escrow_programs[index].ne(&Pubkey::default())
+    }
+
+    /// Make the pending reward authority at the specified Whirlpool reward index the reward
+    /// authority.
+    pub fn accept_reward_authority(&mut self, index: usize) -> Result<()> {
+        if index >= NUM_REWARDS {
+            return Err(ErrorCode::InvalidRewardIndex.into());
+        }
+        let pending_authority = self.pending_reward_authorities[index];
+        if pending_authority == Pubkey::default() {
+            return Err(ErrorCode::NoPendingAuthority.into());
+        }
+        self.pending_reward_authorities[index] = Pubkey::default();
+        self.update_reward_authority(index, pending_authority)
+    }
+
     pub fn update_emissions(
         &mut self,
         index: usize,
@@ -167,10 +466,18 @@ impl Whirlpool {
         }
         self.update_rewards(reward_infos, timestamp);
         self.reward_infos[index].emissions_per_second_x64 = emissions_per_second_x64;
+        self.update_reward_emissions_zero();
 
         Ok(())
     }
 
+    fn update_reward_emissions_zero(&mut self) {
+        self.reward_emissions_zero = self
+            .reward_infos
+            .iter()
+            .all(|reward_info| reward_info.emissions_per_second_x64 == 0);
+    }
+
     pub fn initialize_reward(&mut self, index: usize, mint: Pubkey, vault: Pubkey) -> Result<()> {
         if index >= NUM_REWARDS {
             return Err(ErrorCode::InvalidRewardIndex.into());
@@ -218,15 +525,73 @@ impl Whirlpool {
         }
     }
 
-    pub fn update_fee_rate(&mut self, fee_rate: u16) -> Result<()> {
+    /// Add the token amounts of a swap and its total fee to the lifetime counters.
+    pub fn record_swap_volume(
+        &mut self,
+        amount_a: u64,
+        amount_b: u64,
+        total_fee: u64,
+        is_fee_in_token_a: bool,
+    ) {
+        self.lifetime_volume_a = self.lifetime_volume_a.saturating_add(amount_a as u128);
+        self.lifetime_volume_b = self.lifetime_volume_b.saturating_add(amount_b as u128);
+        if is_fee_in_token_a {
+            self.lifetime_fees_a = self.lifetime_fees_a.saturating_add(total_fee as u128);
+        } else {
+            self.lifetime_fees_b = self.lifetime_fees_b.saturating_add(total_fee as u128);
+        }
+    }
+
+    pub fn update_max_positions(&mut self, max_positions: u32) {
+        self.max_positions = max_positions;
+    }
+
+    /// Count a newly opened position.
+    ///
+    /// # Errors
+    /// - `PositionLimitReached` - The Whirlpool already has its maximum number of open positions.
+    pub fn add_open_position(&mut self) -> Result<()> {
+        if self.max_positions != 0 && self.open_positions >= self.max_positions {
+            return Err(ErrorCode::PositionLimitReached.into());
+        }
+        self.open_positions += 1;
+        Ok(())
+    }
+
+    /// Stop counting a closed position. Positions opened before the counter was added were never
+    /// counted, so the counter does not go below zero.
+    pub fn remove_open_position(&mut self) {
+        self.open_positions = self.open_positions.saturating_sub(1);
+    }
+
+    pub fn update_fee_rate(&mut self, fee_rate: u32) -> Result<()> {
         if fee_rate > MAX_FEE_RATE {
             return Err(ErrorCode::FeeRateMaxExceeded.into());
         }
-        self.fee_rate = fee_rate;
+        self.precise_fee_rate = fee_rate;
+        self.fee_rate = fee_rate_to_legacy(fee_rate);
 
         Ok(())
     }
 
+    /// Fee rate charged on swaps, in thousandths of a basis point. Whirlpools that have not been
+    /// migrated yet charge their legacy fee rate.
+    pub fn effective_fee_rate(&self) -> u32 {
+        if self.precise_fee_rate == 0 {
+            fee_rate_from_legacy(self.fee_rate)
+        } else {
+            self.precise_fee_rate
+        }
+    }
+
+    /// Store the legacy fee rate of a Whirlpool created before the fee rate precision increase as
+    /// its precise fee rate. A no-op for Whirlpools that already have one.
+    pub fn migrate_fee_rate_precision(&mut self) {
+        if self.precise_fee_rate == 0 {
+            self.precise_fee_rate = fee_rate_from_legacy(self.fee_rate);
+        }
+    }
+
     pub fn update_protocol_fee_rate(&mut self, protocol_fee_rate: u16) -> Result<()> {
         if protocol_fee_rate > MAX_PROTOCOL_FEE_RATE {
             return Err(ErrorCode::ProtocolFeeRateMaxExceeded.into());
@@ -236,6 +601,469 @@ impl Whirlpool {
         Ok(())
     }
 
+    pub fn update_referral_fee_rate(&mut self, referral_fee_rate: u16) -> Result<()> {
+        if referral_fee_rate > MAX_REFERRAL_FEE_RATE {
+            return Err(ErrorCode::ReferralFeeRateMaxExceeded.into());
+        }
+        self.referral_fee_rate = referral_fee_rate;
+
+        Ok(())
+    }
+
+    /// Move the referral share of a swap's protocol fee out of the protocol fees owed and return
+    /// it, for crediting to the referrer.
+    pub fn take_referral_fee(&mut self, protocol_fee: u64, is_token_fee_in_a: bool) -> u64 {
+        let referral_fee = ((protocol_fee as u128) * (self.referral_fee_rate as u128)
+            / PROTOCOL_FEE_RATE_MUL_VALUE) as u64;
+        if is_token_fee_in_a {
+            self.protocol_fee_owed_a -= referral_fee;
+        } else {
+            self.protocol_fee_owed_b -= referral_fee;
+        }
+        referral_fee
+    }
+
+    pub fn update_after_swap_hook(&mut self, after_swap_hook: Pubkey) -> Result<()> {
+        if after_swap_hook == crate::ID {
+            return Err(ErrorCode::InvalidAfterSwapHook.into());
+        }
+        self.after_swap_hook = after_swap_hook;
+
+        Ok(())
+    }
+
+    pub fn has_after_swap_hook(&self) -> bool {
+        self.after_swap_hook.ne(&Pubkey::default())
+    }
+
+    pub fn update_tick_cross_hook(&mut self, tick_cross_hook: Pubkey) -> Result<()> {
+        if tick_cross_hook == crate::ID {
+            return Err(ErrorCode::InvalidTickCrossHook.into());
+        }
+        self.tick_cross_hook = tick_cross_hook;
+
+        Ok(())
+    }
+
+    pub fn has_tick_cross_hook(&self) -> bool {
+        self.tick_cross_hook.ne(&Pubkey::default())
+    }
+
+    /// Set a new price for a pool that has no liquidity left, so new liquidity providers are not
+    /// immediately arbitraged against a stale price.
+    pub fn reset_sqrt_price(&mut self, sqrt_price: u128) -> Result<()> {
+        // Positions not counted in the total liquidity yet may still hold liquidity.
+        if self.liquidity != 0 || self.total_liquidity != 0 || !self.total_liquidity_complete {
+            return Err(ErrorCode::PoolHasLiquidity.into());
+        }
+        if !(MIN_SQRT_PRICE_X64..=MAX_SQRT_PRICE_X64).contains(&sqrt_price) {
+            return Err(ErrorCode::SqrtPriceOutOfBounds.into());
+        }
+        self.verify_sqrt_price_in_tick_bounds(sqrt_price)?;
+        self.sqrt_price = sqrt_price;
+        self.tick_current_index = tick_index_from_sqrt_price(&sqrt_price);
+
+        Ok(())
+    }
+
+    /// Set the usable tick range of the pool, or clear it with both bounds 0. The bounds have to
+    /// be usable ticks of the pool and contain the current price.
+    pub fn update_tick_bounds(&mut self, min_tick_index: i32, max_tick_index: i32) -> Result<()> {
+        let clears_bounds = min_tick_index == 0 && max_tick_index == 0;
+        if !clears_bounds
+            && (!Tick::check_is_usable_tick(min_tick_index, self.tick_spacing)
+                || !Tick::check_is_usable_tick(max_tick_index, self.tick_spacing)
+                || min_tick_index >= max_tick_index)
+        {
+            return Err(ErrorCode::InvalidTickIndex.into());
+        }
+        self.min_tick_index = min_tick_index;
+        self.max_tick_index = max_tick_index;
+        self.verify_sqrt_price_in_tick_bounds(self.sqrt_price)
+    }
+
+    pub fn has_tick_bounds(&self) -> bool {
+        self.min_tick_index < self.max_tick_index
+    }
+
+    /// Lowest and highest usable tick index of the pool's positions.
+    pub fn usable_tick_range(&self) -> (i32, i32) {
+        if self.has_tick_bounds() {
+            (self.min_tick_index, self.max_tick_index)
+        } else {
+            Tick::full_range_tick_indexes(self.tick_spacing)
+        }
+    }
+
+    /// Verify a position range is within the tick bounds of the pool.
+    pub fn verify_position_in_tick_bounds(
+        &self,
+        tick_lower_index: i32,
+        tick_upper_index: i32,
+    ) -> Result<()> {
+        if self.has_tick_bounds()
+            && (tick_lower_index < self.min_tick_index || tick_upper_index > self.max_tick_index)
+        {
+            return Err(ErrorCode::InvalidTickIndex.into());
+        }
+        Ok(())
+    }
+
+    fn verify_sqrt_price_in_tick_bounds(&self, sqrt_price: u128) -> Result<()> {
+        if self.has_tick_bounds()
+            && (sqrt_price < sqrt_price_from_tick_index(self.min_tick_index)
+                || sqrt_price > sqrt_price_from_tick_index(self.max_tick_index))
+        {
+            return Err(ErrorCode::SqrtPriceOutOfBounds.into());
+        }
+        Ok(())
+    }
+
+    /// Clamp the sqrt price limit of a swap to the tick bounds of the pool, so swaps stop at the
+    /// bound in their direction.
+    pub fn clamp_sqrt_price_limit(&self, sqrt_price_limit: u128, a_to_b: bool) -> u128 {
+        if !self.has_tick_bounds() {
+            return sqrt_price_limit;
+        }
+        if a_to_b {
+            sqrt_price_limit.max(sqrt_price_from_tick_index(self.min_tick_index))
+        } else {
+            sqrt_price_limit.min(sqrt_price_from_tick_index(self.max_tick_index))
+        }
+    }
+
+    /// Copy the price, fee rates, fee growth and reward configuration of `source` into this newly
+    /// initialized Whirlpool. Reward authorities are kept, and each initialized reward of the
+    /// source is set up with the vault at the same index of `reward_vaults`. Liquidity and
+    /// amounts owed are not copied.
+    pub fn clone_state_from(
+        &mut self,
+        source: &Whirlpool,
+        reward_vaults: &[Pubkey],
+        timestamp: u64,
+    ) -> Result<()> {
+        self.update_fee_rate(source.effective_fee_rate())?;
+        self.update_protocol_fee_rate(source.protocol_fee_rate)?;
+
+        self.sqrt_price = source.sqrt_price;
+        self.tick_current_index = source.tick_current_index;
+        self.fee_growth_global_a = source.fee_growth_global_a;
+        self.fee_growth_global_b = source.fee_growth_global_b;
+
+        for (index, source_reward) in source.reward_infos.iter().enumerate() {
+            if !source_reward.initialized() {
+                continue;
+            }
+            let vault = reward_vaults
+                .get(index)
+                .ok_or(ErrorCode::RemainingAccountsMissing)?;
+            let reward_info = &mut self.reward_infos[index];
+            reward_info.mint = source_reward.mint;
+            reward_info.vault = *vault;
+            reward_info.emissions_per_second_x64 = source_reward.emissions_per_second_x64;
+            reward_info.growth_global_x64 = source_reward.growth_global_x64;
+        }
+        self.reward_accrues_to_all_liquidity = source.reward_accrues_to_all_liquidity;
+        self.reward_tenure_boost_periods = source.reward_tenure_boost_periods;
+        self.reward_tenure_boost_base_rates = source.reward_tenure_boost_base_rates;
+        self.reward_emissions_liquidity_caps = source.reward_emissions_liquidity_caps;
+        self.liquidity_removal_cooldown = source.liquidity_removal_cooldown;
+        self.jit_surcharge_rate = source.jit_surcharge_rate;
+        self.jit_surcharge_slots = source.jit_surcharge_slots;
+        self.min_tick_index = source.min_tick_index;
+        self.max_tick_index = source.max_tick_index;
+        self.reward_last_updated_timestamp = timestamp;
+        self.update_reward_emissions_zero();
+
+        Ok(())
+    }
+
+    pub fn add_rounding_dust(&mut self, rounding_dust_a: u64, rounding_dust_b: u64) {
+        self.rounding_dust_a = self.rounding_dust_a.saturating_add(rounding_dust_a);
+        self.rounding_dust_b = self.rounding_dust_b.saturating_add(rounding_dust_b);
+    }
+
+    pub fn update_swap_direction_enabled(&mut self, a_to_b: bool, enabled: bool) {
+        if a_to_b {
+            self.swap_a_to_b_disabled = !enabled;
+        } else {
+            self.swap_b_to_a_disabled = !enabled;
+        }
+    }
+
+    pub fn is_swap_direction_enabled(&self, a_to_b: bool) -> bool {
+        if a_to_b {
+            !self.swap_a_to_b_disabled
+        } else {
+            !self.swap_b_to_a_disabled
+        }
+    }
+
+    pub fn update_paused(&mut self, paused: bool) {
+        self.paused = paused;
+    }
+
+    pub fn verify_not_paused(&self) -> Result<()> {
+        if self.paused {
+            return Err(ErrorCode::PoolPaused.into());
+        }
+        Ok(())
+    }
+
+    pub fn update_fees_in_token_b(&mut self, fees_in_token_b: bool) {
+        self.fees_in_token_b = fees_in_token_b;
+    }
+
+    /// True if the fees of a swap in the given direction are taken in token A.
+    pub fn is_fee_in_token_a(&self, a_to_b: bool) -> bool {
+        a_to_b && !self.fees_in_token_b
+    }
+
+    /// Set the tick cache of the Whirlpool. The cache has to be refreshed before swaps can use it.
+    pub fn update_tick_cache(&mut self, tick_cache: Pubkey) {
+        self.tick_cache = tick_cache;
+        self.tick_cache_stale = true;
+        self.tick_cache_ahead = false;
+    }
+
+    pub fn has_tick_cache(&self) -> bool {
+        self.tick_cache.ne(&Pubkey::default())
+    }
+
+    /// Tick arrays can only be read or modified once the tick cache has written back its ticks.
+    pub fn verify_tick_arrays_synced(&self) -> Result<()> {
+        if self.tick_cache_ahead {
+            return Err(ErrorCode::TickCacheNotWrittenBack.into());
+        }
+        Ok(())
+    }
+
+    /// The tick cache can only be swapped against while it holds the current ticks.
+    pub fn verify_tick_cache_fresh(&self) -> Result<()> {
+        if self.tick_cache_stale {
+            return Err(ErrorCode::TickCacheStale.into());
+        }
+        Ok(())
+    }
+
+    /// Record that ticks were modified, either in the tick cache or in the tick arrays.
+    pub fn record_tick_updates(&mut self, in_tick_cache: bool) {
+        if in_tick_cache {
+            self.tick_cache_ahead = true;
+        } else if self.has_tick_cache() {
+            self.tick_cache_stale = true;
+        }
+    }
+
+    pub fn mark_tick_cache_synced(&mut self) {
+        self.tick_cache_stale = false;
+        self.tick_cache_ahead = false;
+    }
+
+    pub fn update_trade_log(&mut self, trade_log: Pubkey) {
+        self.trade_log = trade_log;
+    }
+
+    pub fn has_trade_log(&self) -> bool {
+        self.trade_log.ne(&Pubkey::default())
+    }
+
+    pub fn update_fee_schedule(&mut self, fee_schedule: Pubkey) {
+        self.fee_schedule = fee_schedule;
+    }
+
+    pub fn has_fee_schedule(&self) -> bool {
+        self.fee_schedule.ne(&Pubkey::default())
+    }
+
+    pub fn update_last_swap_slot(&mut self, slot: u64) {
+        self.last_swap_slot = slot;
+    }
+
+    /// Verify the pool was swapped against within the last `within_slots` slots.
+    pub fn verify_recent_swap(&self, slot: u64, within_slots: u64) -> Result<()> {
+        if slot.saturating_sub(self.last_swap_slot) > within_slots {
+            return Err(ErrorCode::StalePool.into());
+        }
+        Ok(())
+    }
+
+    pub fn update_liquidity_removal_cooldown(&mut self, liquidity_removal_cooldown: u32) {
+        self.liquidity_removal_cooldown = liquidity_removal_cooldown;
+    }
+
+    /// Verify the liquidity removal cooldown since the last liquidity increase of the position has
+    /// passed.
+    pub fn verify_liquidity_removal_cooldown(
+        &self,
+        position: &Position,
+        timestamp: u64,
+    ) -> Result<()> {
+        let cooldown_end = position
+            .last_liquidity_increase_timestamp
+            .saturating_add(self.liquidity_removal_cooldown as u64);
+        if timestamp < cooldown_end {
+            return Err(ErrorCode::LiquidityRemovalCooldown.into());
+        }
+        Ok(())
+    }
+
+    pub fn update_jit_surcharge(
+        &mut self,
+        jit_surcharge_rate: u16,
+        jit_surcharge_slots: u32,
+    ) -> Result<()> {
+        if jit_surcharge_rate > MAX_JIT_SURCHARGE_RATE {
+            return Err(ErrorCode::JitSurchargeRateMaxExceeded.into());
+        }
+        self.jit_surcharge_rate = jit_surcharge_rate;
+        self.jit_surcharge_slots = jit_surcharge_slots;
+        Ok(())
+    }
+
+    /// Take the JIT surcharge out of the amounts removed from a position in `slot` and add it to
+    /// the fee growth of the in-range liquidity. Returns the surcharges of token A and B, zero if
+    /// the position's last liquidity increase is outside the surcharge window or no liquidity is
+    /// left in range to receive them.
+    pub fn take_jit_surcharge(
+        &mut self,
+        position: &Position,
+        slot: u64,
+        amount_a: u64,
+        amount_b: u64,
+    ) -> (u64, u64) {
+        let window_end = position
+            .last_liquidity_increase_slot
+            .saturating_add(self.jit_surcharge_slots as u64);
+        if self.jit_surcharge_rate == 0 || self.liquidity == 0 || slot > window_end {
+            return (0, 0);
+        }
+
+        let surcharge_rate = self.jit_surcharge_rate as u128;
+        let surcharge_a = (amount_a as u128 * surcharge_rate / PROTOCOL_FEE_RATE_MUL_VALUE) as u64;
+        let surcharge_b = (amount_b as u128 * surcharge_rate / PROTOCOL_FEE_RATE_MUL_VALUE) as u64;
+        self.fee_growth_global_a = self
+            .fee_growth_global_a
+            .wrapping_add(((surcharge_a as u128) << Q64_RESOLUTION) / self.liquidity);
+        self.fee_growth_global_b = self
+            .fee_growth_global_b
+            .wrapping_add(((surcharge_b as u128) << Q64_RESOLUTION) / self.liquidity);
+        (surcharge_a, surcharge_b)
+    }
+
+    pub fn update_liquidity_shift_alert_rate(&mut self, liquidity_shift_alert_rate: u16) {
+        self.liquidity_shift_alert_rate = liquidity_shift_alert_rate;
+    }
+
+    /// Record a change of the active liquidity from `previous_liquidity` to the current liquidity
+    /// in `slot`. Returns the active liquidity at the start of the slot if the change within the
+    /// slot exceeds the liquidity shift alert rate with this change, and did not before it.
+    pub fn record_liquidity_shift(&mut self, previous_liquidity: u128, slot: u64) -> Option<u128> {
+        if self.liquidity_shift_alert_rate == 0 {
+            return None;
+        }
+        if slot != self.liquidity_shift_slot {
+            self.liquidity_shift_slot = slot;
+            self.liquidity_shift_slot_start_liquidity = previous_liquidity;
+        }
+
+        let slot_start_liquidity = self.liquidity_shift_slot_start_liquidity;
+        let exceeds_alert_rate = |liquidity: u128| {
+            let shift = liquidity.abs_diff(slot_start_liquidity);
+            mul_u256(shift, PROTOCOL_FEE_RATE_MUL_VALUE).gt(mul_u256(
+                slot_start_liquidity,
+                self.liquidity_shift_alert_rate as u128,
+            ))
+        };
+        if !exceeds_alert_rate(previous_liquidity) && exceeds_alert_rate(self.liquidity) {
+            Some(slot_start_liquidity)
+        } else {
+            None
+        }
+    }
+
+    pub fn update_position_collection_mint(&mut self, position_collection_mint: Pubkey) {
+        self.position_collection_mint = position_collection_mint;
+    }
+
+    pub fn has_position_collection(&self) -> bool {
+        self.position_collection_mint.ne(&Pubkey::default())
+    }
+
+    /// Map one side of the pool to an exchange rate oracle so that its amounts are accounted in
+    /// underlying units. Only allowed while the pool has no active liquidity.
+    pub fn update_exchange_rate_oracle(
+        &mut self,
+        exchange_rate_oracle: Pubkey,
+        exchange_rate_on_a: bool,
+        exchange_rate_x64: u128,
+    ) -> Result<()> {
+        if self.liquidity != 0 {
+            return Err(ErrorCode::ExchangeRateModeLocked.into());
+        }
+        self.exchange_rate_oracle = exchange_rate_oracle;
+        self.exchange_rate_on_a = exchange_rate_on_a;
+        self.update_exchange_rate(exchange_rate_x64)
+    }
+
+    pub fn update_exchange_rate(&mut self, exchange_rate_x64: u128) -> Result<()> {
+        if exchange_rate_x64 == 0 {
+            return Err(ErrorCode::InvalidExchangeRateOracle.into());
+        }
+        self.exchange_rate_x64 = exchange_rate_x64;
+
+        Ok(())
+    }
+
+    /// Record token amounts the program deposited into the vaults.
+    pub fn record_vault_deposits(&mut self, amount_a: u64, amount_b: u64) {
+        self.vault_owed_a = self.vault_owed_a.saturating_add(amount_a);
+        self.vault_owed_b = self.vault_owed_b.saturating_add(amount_b);
+    }
+
+    /// Record token amounts the program paid out of the vaults. Pools created before the owed
+    /// amounts were tracked pay out more than was recorded, so the amounts do not go below zero.
+    pub fn record_vault_withdrawals(&mut self, amount_a: u64, amount_b: u64) {
+        self.vault_owed_a = self.vault_owed_a.saturating_sub(amount_a);
+        self.vault_owed_b = self.vault_owed_b.saturating_sub(amount_b);
+    }
+
+    pub fn has_vault_owed(&self) -> bool {
+        self.vault_owed_a != 0 || self.vault_owed_b != 0
+    }
+
+    pub fn has_exchange_rate_oracle(&self) -> bool {
+        self.exchange_rate_oracle.ne(&Pubkey::default())
+    }
+
+    fn is_rebasing_side(&self, is_token_a: bool) -> bool {
+        self.has_exchange_rate_oracle() && self.exchange_rate_on_a == is_token_a
+    }
+
+    /// Convert a token amount transferred in or out of the pool into the units the pool accounts in.
+    pub fn to_pool_amount(&self, amount: u64, is_token_a: bool, round_up: bool) -> Result<u64> {
+        if !self.is_rebasing_side(is_token_a) {
+            return Ok(amount);
+        }
+        Ok(token_to_underlying_amount(
+            amount,
+            self.exchange_rate_x64,
+            round_up,
+        )?)
+    }
+
+    /// Convert an amount in the units the pool accounts in into the token amount to transfer.
+    pub fn to_token_amount(&self, amount: u64, is_token_a: bool, round_up: bool) -> Result<u64> {
+        if !self.is_rebasing_side(is_token_a) {
+            return Ok(amount);
+        }
+        Ok(underlying_to_token_amount(
+            amount,
+            self.exchange_rate_x64,
+            round_up,
+        )?)
+    }
+
     pub fn reset_protocol_fees_owed(&mut self) {
         self.protocol_fee_owed_a = 0;
         self.protocol_fee_owed_b = 0;
@@ -247,7 +1075,7 @@ impl Whirlpool {
 /// and `Whirlpool.reward_last_updated_timestamp` to determine how many rewards are earned by open
 /// positions.
 #[derive(Copy, Clone, AnchorSerialize, AnchorDeserialize, Default, PartialEq)]
-#[cfg_attr(not(target_arch = "bpf"),derive(Debug))]
+#[cfg_attr(not(target_arch = "bpf"), derive(Debug))]
 pub struct WhirlpoolRewardInfo {
     /// Reward token mint.
     pub mint: Pubkey,
@@ -294,6 +1122,497 @@ pub struct WhirlpoolBumps {
     pub whirlpool_bump: u8,
 }
 
+#[test]
+fn test_whirlpool_token_amount_conversion_without_oracle() {
+    let whirlpool = Whirlpool::default();
+    assert_eq!(whirlpool.to_pool_amount(100, true, true).unwrap(), 100);
+    assert_eq!(whirlpool.to_token_amount(100, false, false).unwrap(), 100);
+}
+
+#[test]
+fn test_whirlpool_token_amount_conversion_with_oracle() {
+    let whirlpool = Whirlpool {
+        exchange_rate_oracle: Pubkey::new_unique(),
+        exchange_rate_x64: 2 << 64,
+        exchange_rate_on_a: false,
+        ..Default::default()
+    };
+    assert_eq!(whirlpool.to_pool_amount(100, true, false).unwrap(), 100);
+    assert_eq!(whirlpool.to_pool_amount(100, false, false).unwrap(), 200);
+    assert_eq!(whirlpool.to_token_amount(201, false, false).unwrap(), 100);
+    assert_eq!(whirlpool.to_token_amount(201, false, true).unwrap(), 101);
+}
+
+#[test]
+fn test_whirlpool_total_liquidity_of_legacy_pool() {
+    // Pools created before the total liquidity was tracked read it as zero and incomplete.
+    let mut whirlpool = Whirlpool::default();
+    assert_eq!(
+        whirlpool
+            .update_reward_accrues_to_all_liquidity(0, true)
+            .unwrap_err(),
+        ErrorCode::TotalLiquidityIncomplete.into()
+    );
+    whirlpool
+        .update_reward_accrues_to_all_liquidity(0, false)
+        .unwrap();
+
+    whirlpool.count_migrated_position_liquidity(1_000).unwrap();
+    whirlpool.count_migrated_position_liquidity(500).unwrap();
+    assert_eq!(whirlpool.total_liquidity, 1_500);
+    assert!(whirlpool
+        .count_migrated_position_liquidity(u128::MAX)
+        .is_err());
+
+    whirlpool.mark_total_liquidity_complete();
+    whirlpool
+        .update_reward_accrues_to_all_liquidity(0, true)
+        .unwrap();
+    assert!(whirlpool.reward_accrues_to_all_liquidity[0]);
+}
+
+#[test]
+fn test_whirlpool_vault_owed() {
+    let mut whirlpool = Whirlpool::default();
+    assert!(!whirlpool.has_vault_owed());
+
+    whirlpool.record_vault_deposits(100, 50);
+    whirlpool.record_vault_withdrawals(100, 20);
+    assert_eq!((whirlpool.vault_owed_a, whirlpool.vault_owed_b), (0, 30));
+    assert!(whirlpool.has_vault_owed());
+
+    // Payouts of pools created before the owed amounts were tracked
+    whirlpool.record_vault_withdrawals(10, 40);
+    assert_eq!((whirlpool.vault_owed_a, whirlpool.vault_owed_b), (0, 0));
+    assert!(!whirlpool.has_vault_owed());
+}
+
+#[test]
+fn test_whirlpool_reward_authority_transfer() {
+    let mut whirlpool = Whirlpool::default();
+    let authority = Pubkey::new_unique();
+
+    assert!(whirlpool.accept_reward_authority(1).is_err());
+    whirlpool.propose_reward_authority(1, authority).unwrap();
+    assert_eq!(whirlpool.reward_infos[1].authority, Pubkey::default());
+
+    whirlpool.accept_reward_authority(1).unwrap();
+    assert_eq!(whirlpool.reward_infos[1].authority, authority);
+    assert_eq!(whirlpool.pending_reward_authorities[1], Pubkey::default());
+    assert!(whirlpool.propose_reward_authority(NUM_REWARDS, authority).is_err());
+}
+
+#[test]
+fn test_whirlpool_reset_sqrt_price() {
+    let mut whirlpool = Whirlpool {
+        total_liquidity: 1,
+        ..Default::default()
+    };
+    assert!(whirlpool.reset_sqrt_price(1 << 64).is_err());
+
+    // Positions of a pool created before the total liquidity was tracked may not be counted yet.
+    whirlpool.total_liquidity = 0;
+    assert!(whirlpool.reset_sqrt_price(1 << 64).is_err());
+
+    whirlpool.mark_total_liquidity_complete();
+    assert!(whirlpool.reset_sqrt_price(MAX_SQRT_PRICE_X64 + 1).is_err());
+    whirlpool.reset_sqrt_price(1 << 64).unwrap();
+    assert_eq!(whirlpool.sqrt_price, 1 << 64);
+    assert_eq!(whirlpool.tick_current_index, 0);
+}
+
+#[test]
+fn test_whirlpool_clone_state_from() {
+    let mut source = Whirlpool {
+        fee_rate: 3000,
+        protocol_fee_rate: 300,
+        sqrt_price: 1 << 64,
+        tick_current_index: -1,
+        fee_growth_global_a: 100,
+        fee_growth_global_b: 200,
+        reward_last_updated_timestamp: 10,
+        ..Default::default()
+    };
+    source.reward_infos[0] = WhirlpoolRewardInfo {
+        mint: Pubkey::new_unique(),
+        vault: Pubkey::new_unique(),
+        authority: Pubkey::new_unique(),
+        emissions_per_second_x64: 1 << 64,
+        growth_global_x64: 500,
+    };
+    source.reward_accrues_to_all_liquidity[0] = true;
+
+    let reward_authority = Pubkey::new_unique();
+    let mut clone = Whirlpool {
+        reward_infos: [WhirlpoolRewardInfo::new(reward_authority); NUM_REWARDS],
+        ..Default::default()
+    };
+    assert_eq!(
+        clone.clone_state_from(&source, &[], 20),
+        Err(ErrorCode::RemainingAccountsMissing.into())
+    );
+
+    let reward_vault = Pubkey::new_unique();
+    clone.clone_state_from(&source, &[reward_vault], 20).unwrap();
+    assert_eq!(clone.fee_rate, 3000);
+    assert_eq!(clone.precise_fee_rate, 30_000);
+    assert_eq!(clone.protocol_fee_rate, 300);
+    assert_eq!(clone.sqrt_price, 1 << 64);
+    assert_eq!(clone.tick_current_index, -1);
+    assert_eq!(clone.fee_growth_global_a, 100);
+    assert_eq!(clone.fee_growth_global_b, 200);
+    assert_eq!(clone.reward_last_updated_timestamp, 20);
+    assert_eq!(clone.reward_infos[0].mint, source.reward_infos[0].mint);
+    assert_eq!(clone.reward_infos[0].vault, reward_vault);
+    assert_eq!(clone.reward_infos[0].authority, reward_authority);
+    assert_eq!(clone.reward_infos[0].emissions_per_second_x64, 1 << 64);
+    assert_eq!(clone.reward_infos[0].growth_global_x64, 500);
+    assert!(!clone.reward_infos[1].initialized());
+    assert!(clone.reward_accrues_to_all_liquidity[0]);
+}
+
+#[test]
+fn test_whirlpool_tick_cache_sync() {
+    let mut whirlpool = Whirlpool::default();
+    whirlpool.record_tick_updates(false);
+    assert!(!whirlpool.tick_cache_stale);
+
+    whirlpool.update_tick_cache(Pubkey::new_unique());
+    assert!(whirlpool.verify_tick_cache_fresh().is_err());
+    whirlpool.mark_tick_cache_synced();
+    assert!(whirlpool.verify_tick_cache_fresh().is_ok());
+
+    whirlpool.record_tick_updates(true);
+    assert!(whirlpool.verify_tick_arrays_synced().is_err());
+    assert!(whirlpool.verify_tick_cache_fresh().is_ok());
+
+    whirlpool.mark_tick_cache_synced();
+    whirlpool.record_tick_updates(false);
+    assert!(whirlpool.verify_tick_arrays_synced().is_ok());
+    assert!(whirlpool.verify_tick_cache_fresh().is_err());
+}
+
+#[test]
+fn test_whirlpool_swap_direction_enabled() {
+    let mut whirlpool = Whirlpool::default();
+    assert!(whirlpool.is_swap_direction_enabled(true));
+    assert!(whirlpool.is_swap_direction_enabled(false));
+
+    whirlpool.update_swap_direction_enabled(true, false);
+    assert!(!whirlpool.is_swap_direction_enabled(true));
+    assert!(whirlpool.is_swap_direction_enabled(false));
+
+    whirlpool.update_swap_direction_enabled(true, true);
+    assert!(whirlpool.is_swap_direction_enabled(true));
+}
+
+#[test]
+fn test_whirlpool_take_referral_fee() {
+    let mut whirlpool = Whirlpool {
+        protocol_fee_owed_a: 1_000,
+        protocol_fee_owed_b: 1_000,
+        ..Default::default()
+    };
+    assert_eq!(
+        whirlpool.update_referral_fee_rate(MAX_REFERRAL_FEE_RATE + 1),
+        Err(ErrorCode::ReferralFeeRateMaxExceeded.into())
+    );
+    whirlpool.update_referral_fee_rate(2_000).unwrap();
+
+    assert_eq!(whirlpool.take_referral_fee(500, false), 100);
+    assert_eq!(whirlpool.protocol_fee_owed_a, 1_000);
+    assert_eq!(whirlpool.protocol_fee_owed_b, 900);
+}
+
+#[test]
+fn test_whirlpool_reward_escrow_program() {
+    let mut whirlpool = Whirlpool::default();
+    assert!(!whirlpool.has_reward_escrow_program(1));
+
+    let escrow_program = Pubkey::new_unique();
+    whirlpool
+        .update_reward_escrow_program(1, escrow_program)
+        .unwrap();
+    assert!(whirlpool.has_reward_escrow_program(1));
+    assert!(!whirlpool.has_reward_escrow_program(0));
+
+    assert_eq!(
+        whirlpool.update_reward_escrow_program(0, crate::ID),
+        Err(ErrorCode::InvalidRewardEscrowProgram.into())
+    );
+    assert_eq!(
+        whirlpool.update_reward_escrow_program(NUM_REWARDS, escrow_program),
+        Err(ErrorCode::InvalidRewardIndex.into())
+    );
+
+    whirlpool
+        .update_reward_escrow_program(1, Pubkey::default())
+        .unwrap();
+    assert!(!whirlpool.has_reward_escrow_program(1));
+}
+
+#[test]
+fn test_whirlpool_max_positions() {
+    let mut whirlpool = Whirlpool::default();
+    whirlpool.add_open_position().unwrap();
+    whirlpool.add_open_position().unwrap();
+    whirlpool.update_max_positions(2);
+    assert_eq!(
+        whirlpool.add_open_position(),
+        Err(ErrorCode::PositionLimitReached.into())
+    );
+    whirlpool.remove_open_position();
+    whirlpool.add_open_position().unwrap();
+    assert_eq!(whirlpool.open_positions, 2);
+
+    whirlpool.open_positions = 0;
+    whirlpool.remove_open_position();
+    assert_eq!(whirlpool.open_positions, 0);
+}
+
+#[test]
+fn test_whirlpool_record_swap_volume() {
+    let mut whirlpool = Whirlpool::default();
+    whirlpool.record_swap_volume(1_000, 2_000, 3, true);
+    whirlpool.record_swap_volume(500, 1_000, 4, false);
+    assert_eq!(whirlpool.lifetime_volume_a, 1_500);
+    assert_eq!(whirlpool.lifetime_volume_b, 3_000);
+    assert_eq!(whirlpool.lifetime_fees_a, 3);
+    assert_eq!(whirlpool.lifetime_fees_b, 4);
+}
+
+#[test]
+fn test_whirlpool_record_liquidity_shift() {
+    let mut whirlpool = Whirlpool {
+        liquidity: 800,
+        ..Default::default()
+    };
+    assert_eq!(whirlpool.record_liquidity_shift(1_000, 5), None);
+
+    // Alert once the liquidity moved more than 10% from the 1_000 at the start of slot 6.
+    whirlpool.update_liquidity_shift_alert_rate(1_000);
+    whirlpool.liquidity = 950;
+    assert_eq!(whirlpool.record_liquidity_shift(1_000, 6), None);
+    whirlpool.liquidity = 850;
+    assert_eq!(whirlpool.record_liquidity_shift(950, 6), Some(1_000));
+    whirlpool.liquidity = 700;
+    assert_eq!(whirlpool.record_liquidity_shift(850, 6), None);
+
+    // The next slot measures from the liquidity before its first change.
+    whirlpool.liquidity = 750;
+    assert_eq!(whirlpool.record_liquidity_shift(700, 7), None);
+    assert_eq!(whirlpool.liquidity_shift_slot_start_liquidity, 700);
+    whirlpool.liquidity = 0;
+    assert_eq!(whirlpool.record_liquidity_shift(750, 7), Some(700));
+}
+
+#[test]
+fn test_whirlpool_reward_emissions_zero() {
+    let mut whirlpool = Whirlpool::default();
+    let reward_infos = whirlpool.reward_infos;
+    whirlpool
+        .update_emissions(1, reward_infos, 0, 1 << 64)
+        .unwrap();
+    assert!(!whirlpool.reward_emissions_zero);
+
+    let reward_infos = whirlpool.reward_infos;
+    whirlpool.update_emissions(2, reward_infos, 0, 0).unwrap();
+    assert!(!whirlpool.reward_emissions_zero);
+
+    let reward_infos = whirlpool.reward_infos;
+    whirlpool.update_emissions(1, reward_infos, 0, 0).unwrap();
+    assert!(whirlpool.reward_emissions_zero);
+}
+
+#[test]
+fn test_whirlpool_update_reward_tenure_boost() {
+    let mut whirlpool = Whirlpool::default();
+    assert!(!whirlpool.has_reward_tenure_boost());
+    assert_eq!(
+        whirlpool.update_reward_tenure_boost(NUM_REWARDS, 60, 5_000),
+        Err(ErrorCode::InvalidRewardIndex.into())
+    );
+    assert_eq!(
+        whirlpool.update_reward_tenure_boost(1, 60, REWARD_TENURE_BOOST_RATE_MUL_VALUE + 1),
+        Err(ErrorCode::InvalidRewardTenureBoost.into())
+    );
+
+    whirlpool.update_reward_tenure_boost(1, 60, 5_000).unwrap();
+    assert!(whirlpool.has_reward_tenure_boost());
+    assert_eq!(whirlpool.reward_tenure_boost_periods, [0, 60, 0]);
+    assert_eq!(whirlpool.reward_tenure_boost_base_rates, [0, 5_000, 0]);
+
+    whirlpool.update_reward_tenure_boost(1, 0, 5_000).unwrap();
+    assert!(!whirlpool.has_reward_tenure_boost());
+}
+
+#[test]
+fn test_whirlpool_update_reward_emissions_liquidity_cap() {
+    let mut whirlpool = Whirlpool::default();
+    assert_eq!(
+        whirlpool.update_reward_emissions_liquidity_cap(NUM_REWARDS, 1_000),
+        Err(ErrorCode::InvalidRewardIndex.into())
+    );
+
+    whirlpool
+        .update_reward_emissions_liquidity_cap(2, 1_000)
+        .unwrap();
+    assert_eq!(whirlpool.reward_emissions_liquidity_caps, [0, 0, 1_000]);
+
+    whirlpool.update_reward_emissions_liquidity_cap(2, 0).unwrap();
+    assert_eq!(whirlpool.reward_emissions_liquidity_caps, [0; NUM_REWARDS]);
+}
+
+#[test]
+fn test_whirlpool_verify_reward_vault_covers_emissions() {
+    let mut whirlpool = Whirlpool::default();
+    // One token per second, 86_400 tokens per day
+    whirlpool.reward_infos[0].emissions_per_second_x64 = 1 << 64;
+    assert!(whirlpool
+        .verify_reward_vault_covers_emissions(0, 86_400)
+        .is_ok());
+    assert_eq!(
+        whirlpool.verify_reward_vault_covers_emissions(0, 86_399),
+        Err(ErrorCode::RewardVaultAmountInsufficient.into())
+    );
+
+    // Per liquidity emissions have to be covered for the whole liquidity cap
+    whirlpool
+        .update_reward_emissions_liquidity_cap(0, 1_000)
+        .unwrap();
+    assert_eq!(
+        whirlpool.reward_total_emissions_per_second_x64(0),
+        Some(1_000 << 64)
+    );
+    assert_eq!(
+        whirlpool.verify_reward_vault_covers_emissions(0, 86_400),
+        Err(ErrorCode::RewardVaultAmountInsufficient.into())
+    );
+    assert!(whirlpool
+        .verify_reward_vault_covers_emissions(0, 86_400_000)
+        .is_ok());
+
+    whirlpool
+        .update_reward_emissions_liquidity_cap(0, u128::MAX)
+        .unwrap();
+    assert_eq!(whirlpool.reward_total_emissions_per_second_x64(0), None);
+    assert_eq!(
+        whirlpool.verify_reward_vault_covers_emissions(0, u64::MAX),
+        Err(ErrorCode::RewardVaultAmountInsufficient.into())
+    );
+}
+
+#[test]
+fn test_whirlpool_verify_liquidity_removal_cooldown() {
+    let mut whirlpool = Whirlpool::default();
+    let mut position = Position::default();
+    position.record_liquidity_increase(1_000, 0);
+    assert!(whirlpool
+        .verify_liquidity_removal_cooldown(&position, 1_000)
+        .is_ok());
+
+    whirlpool.update_liquidity_removal_cooldown(60);
+    assert_eq!(
+        whirlpool
+            .verify_liquidity_removal_cooldown(&position, 1_059)
+            .unwrap_err(),
+        ErrorCode::LiquidityRemovalCooldown.into()
+    );
+    assert!(whirlpool
+        .verify_liquidity_removal_cooldown(&position, 1_060)
+        .is_ok());
+}
+
+#[test]
+fn test_whirlpool_take_jit_surcharge() {
+    let mut whirlpool = Whirlpool {
+        liquidity: 1 << 10,
+        ..Default::default()
+    };
+    let mut position = Position::default();
+    position.record_liquidity_increase(0, 100);
+    assert_eq!(
+        whirlpool.take_jit_surcharge(&position, 100, 1_000, 2_000),
+        (0, 0)
+    );
+
+    whirlpool.update_jit_surcharge(500, 2).unwrap();
+    assert_eq!(
+        whirlpool.take_jit_surcharge(&position, 103, 1_000, 2_000),
+        (0, 0)
+    );
+    assert_eq!(
+        whirlpool.take_jit_surcharge(&position, 102, 1_000, 2_000),
+        (50, 100)
+    );
+    assert_eq!(whirlpool.fee_growth_global_a, 50 << 54);
+    assert_eq!(whirlpool.fee_growth_global_b, 100 << 54);
+
+    whirlpool.liquidity = 0;
+    assert_eq!(
+        whirlpool.take_jit_surcharge(&position, 100, 1_000, 2_000),
+        (0, 0)
+    );
+    assert!(whirlpool
+        .update_jit_surcharge(MAX_JIT_SURCHARGE_RATE + 1, 2)
+        .is_err());
+}
+
+#[test]
+fn test_whirlpool_tick_bounds() {
+    let mut whirlpool = Whirlpool {
+        tick_spacing: 1,
+        sqrt_price: 1 << 64,
+        ..Default::default()
+    };
+    assert_eq!(
+        whirlpool.usable_tick_range(),
+        Tick::full_range_tick_indexes(1)
+    );
+    assert!(whirlpool.update_tick_bounds(200, -200).is_err());
+    assert!(whirlpool.update_tick_bounds(100, 200).is_err());
+
+    whirlpool.update_tick_bounds(-200, 200).unwrap();
+    assert_eq!(whirlpool.usable_tick_range(), (-200, 200));
+    assert!(whirlpool.verify_position_in_tick_bounds(-200, 200).is_ok());
+    assert!(whirlpool.verify_position_in_tick_bounds(-201, 200).is_err());
+    assert!(whirlpool
+        .reset_sqrt_price(sqrt_price_from_tick_index(201))
+        .is_err());
+    assert_eq!(
+        whirlpool.clamp_sqrt_price_limit(MIN_SQRT_PRICE_X64, true),
+        sqrt_price_from_tick_index(-200)
+    );
+    assert_eq!(whirlpool.clamp_sqrt_price_limit(1 << 64, false), 1 << 64);
+
+    whirlpool.update_tick_bounds(0, 0).unwrap();
+    assert!(!whirlpool.has_tick_bounds());
+}
+
+#[test]
+fn test_whirlpool_fee_rate_precision() {
+    let mut whirlpool = Whirlpool {
+        fee_rate: 3000,
+        ..Default::default()
+    };
+    assert_eq!(whirlpool.effective_fee_rate(), 30_000);
+    whirlpool.migrate_fee_rate_precision();
+    assert_eq!(whirlpool.precise_fee_rate, 30_000);
+
+    assert_eq!(
+        whirlpool.update_fee_rate(MAX_FEE_RATE + 1),
+        Err(ErrorCode::FeeRateMaxExceeded.into())
+    );
+    whirlpool.update_fee_rate(3).unwrap();
+    assert_eq!(whirlpool.fee_rate, 0);
+    assert_eq!(whirlpool.effective_fee_rate(), 3);
+    whirlpool.migrate_fee_rate_precision();
+    assert_eq!(whirlpool.effective_fee_rate(), 3);
+
+    whirlpool.update_fee_rate(0).unwrap();
+    assert_eq!(whirlpool.effective_fee_rate(), 0);
+}
+
 #[test]
 fn test_whirlpool_reward_info_not_initialized() {
     let reward_info = WhirlpoolRewardInfo::default();
@@ -310,14 +1629,16 @@ fn test_whirlpool_reward_info_initialized() {
 #[cfg(test)]
 pub mod whirlpool_builder {
     use super::{Whirlpool, WhirlpoolRewardInfo, NUM_REWARDS};
+    use crate::math::fee_rate_to_legacy;
 
     #[derive(Default)]
     pub struct WhirlpoolBuilder {
         liquidity: u128,
+        total_liquidity: u128,
         tick_spacing: u16,
         tick_current_index: i32,
         sqrt_price: u128,
-        fee_rate: u16,
+        fee_rate: u32,
         protocol_fee_rate: u16,
         fee_growth_global_a: u128,
         fee_growth_global_b: u128,
@@ -338,6 +1659,11 @@ pub mod whirlpool_builder {
             self
         }
 
+        pub fn total_liquidity(mut self, total_liquidity: u128) -> Self {
+            self.total_liquidity = total_liquidity;
+            self
+        }
+
         pub fn reward_last_updated_timestamp(mut self, reward_last_updated_timestamp: u64) -> Self {
             self.reward_last_updated_timestamp = reward_last_updated_timestamp;
             self
@@ -378,7 +1704,7 @@ pub mod whirlpool_builder {
             self
         }
 
-        pub fn fee_rate(mut self, fee_rate: u16) -> Self {
+        pub fn fee_rate(mut self, fee_rate: u32) -> Self {
             self.fee_rate = fee_rate;
             self
         }
@@ -391,6 +1717,7 @@ pub mod whirlpool_builder {
         pub fn build(self) -> Whirlpool {
             Whirlpool {
                 liquidity: self.liquidity,
+                total_liquidity: self.total_liquidity,
                 reward_last_updated_timestamp: self.reward_last_updated_timestamp,
                 reward_infos: self.reward_infos,
                 tick_current_index: self.tick_current_index,
@@ -398,8 +1725,10 @@ pub mod whirlpool_builder {
                 tick_spacing: self.tick_spacing,
                 fee_growth_global_a: self.fee_growth_global_a,
                 fee_growth_global_b: self.fee_growth_global_b,
-                fee_rate: self.fee_rate,
+                fee_rate: fee_rate_to_legacy(self.fee_rate),
+                precise_fee_rate: self.fee_rate,
                 protocol_fee_rate: self.protocol_fee_rate,
+                total_liquidity_complete: true,
                 ..Default::default()
             }
         }
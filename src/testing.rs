@@ -0,0 +1,256 @@
+//! In-memory fixtures for integration tests of programs built on Whirlpools. The constructors
+//! build valid `Whirlpool`, `TickArray` and `Position` states directly, and `FixtureAccount`
+//! holds the serialized account to add to a `ProgramTest` or bankrun context, instead of
+//! sending the setup transactions that would create the same state.
+//!
+//! Fixtures start without fee or reward growth, so no fees or rewards are owed until swaps run
+//! against them.
+use anchor_lang::prelude::*;
+use anchor_lang::{Discriminator, ZeroCopy};
+use std::convert::TryFrom;
+
+use crate::errors::ErrorCode;
+use crate::math::{fee_rate_to_legacy, tick_index_from_sqrt_price};
+use crate::state::{Position, TickArray, TickUpdate, Whirlpool};
+
+/// A serialized program account, ready to be added at `address` in a test validator.
+pub struct FixtureAccount {
+    pub address: Pubkey,
+    pub lamports: u64,
+    pub data: Vec<u8>,
+    pub owner: Pubkey,
+}
+
+impl FixtureAccount {
+    /// Serialize a Borsh account owned by the Whirlpool program, funded to be rent exempt.
+    pub fn from_account<T: AccountSerialize>(address: Pubkey, state: &T) -> Self {
+        let mut data = Vec::new();
+        state.try_serialize(&mut data).unwrap();
+        Self::from_data(address, data)
+    }
+
+    /// Serialize a zero copy account owned by the Whirlpool program, funded to be rent exempt.
+    pub fn from_zero_copy<T: ZeroCopy + Discriminator>(address: Pubkey, state: &T) -> Self {
+        let mut data = T::discriminator().to_vec();
+        data.extend_from_slice(bytemuck::bytes_of(state));
+        Self::from_data(address, data)
+    }
+
+    fn from_data(address: Pubkey, data: Vec<u8>) -> Self {
+        FixtureAccount {
+            address,
+            lamports: Rent::default().minimum_balance(data.len()),
+            data,
+            owner: crate::ID,
+        }
+    }
+}
+
+/// Parameters of a Whirlpool fixture. Mints and vaults are only recorded, the token accounts
+/// have to be created separately.
+pub struct WhirlpoolFixture {
+    pub whirlpools_config: Pubkey,
+    pub token_mint_a: Pubkey,
+    pub token_vault_a: Pubkey,
+    pub token_mint_b: Pubkey,
+    pub token_vault_b: Pubkey,
+    pub tick_spacing: u16,
+    pub fee_rate: u32,
+    pub protocol_fee_rate: u16,
+    pub sqrt_price: u128,
+}
+
+impl WhirlpoolFixture {
+    /// Address and bump of the Whirlpool PDA.
+    pub fn find_address(&self) -> (Pubkey, u8) {
+        Pubkey::find_program_address(
+            &[
+                b"whirlpool",
+                self.whirlpools_config.as_ref(),
+                self.token_mint_a.as_ref(),
+                self.token_mint_b.as_ref(),
+                self.tick_spacing.to_le_bytes().as_ref(),
+            ],
+            &crate::ID,
+        )
+    }
+
+    pub fn build(&self) -> Whirlpool {
+        Whirlpool {
+            whirlpools_config: self.whirlpools_config,
+            whirlpool_bump: [self.find_address().1],
+            tick_spacing: self.tick_spacing,
+            tick_spacing_seed: self.tick_spacing.to_le_bytes(),
+            fee_rate: fee_rate_to_legacy(self.fee_rate),
+            precise_fee_rate: self.fee_rate,
+            protocol_fee_rate: self.protocol_fee_rate,
+            sqrt_price: self.sqrt_price,
+            tick_current_index: tick_index_from_sqrt_price(&self.sqrt_price),
+            token_mint_a: self.token_mint_a,
+            token_vault_a: self.token_vault_a,
+            token_mint_b: self.token_mint_b,
+            token_vault_b: self.token_vault_b,
+            total_liquidity_complete: true,
+            ..Default::default()
+        }
+    }
+}
+
+/// An empty tick array of a Whirlpool, with the address it has to be added at.
+pub fn tick_array(whirlpool: Pubkey, start_tick_index: i32) -> (Pubkey, TickArray) {
+    let tick_array = TickArray {
+        whirlpool,
+        start_tick_index,
+        ..Default::default()
+    };
+    (
+        TickArray::find_address(&whirlpool, start_tick_index),
+        tick_array,
+    )
+}
+
+/// An empty position, with the address it has to be added at. The position token mint and the
+/// owner's token account have to be created separately.
+pub fn position(
+    whirlpool: Pubkey,
+    position_mint: Pubkey,
+    owner: Pubkey,
+    tick_lower_index: i32,
+    tick_upper_index: i32,
+) -> (Pubkey, Position) {
+    let position = Position {
+        whirlpool,
+        position_mint,
+        owner_hint: owner,
+        tick_lower_index,
+        tick_upper_index,
+        ..Default::default()
+    };
+    let address =
+        Pubkey::find_program_address(&[b"position", position_mint.as_ref()], &crate::ID).0;
+    (address, position)
+}
+
+/// Add liquidity to a position fixture and to the liquidity of its Whirlpool. The liquidity
+/// also has to be added to both range ticks with `add_tick_liquidity`.
+pub fn add_position_liquidity(
+    whirlpool: &mut Whirlpool,
+    position: &mut Position,
+    liquidity: u128,
+) -> Result<()> {
+    position.liquidity = position
+        .liquidity
+        .checked_add(liquidity)
+        .ok_or(ErrorCode::LiquidityOverflow)?;
+    whirlpool.total_liquidity = whirlpool
+        .total_liquidity
+        .checked_add(liquidity)
+        .ok_or(ErrorCode::LiquidityOverflow)?;
+    if whirlpool.tick_current_index >= position.tick_lower_index
+        && whirlpool.tick_current_index < position.tick_upper_index
+    {
+        whirlpool.liquidity = whirlpool
+            .liquidity
+            .checked_add(liquidity)
+            .ok_or(ErrorCode::LiquidityOverflow)?;
+    }
+    Ok(())
+}
+
+/// Add the liquidity of a position to one of its range ticks, initializing the tick.
+pub fn add_tick_liquidity(
+    tick_array: &mut TickArray,
+    tick_index: i32,
+    tick_spacing: u16,
+    liquidity: u128,
+    is_upper_tick: bool,
+) -> Result<()> {
+    let liquidity_delta = i128::try_from(liquidity).map_err(|_| ErrorCode::LiquidityOverflow)?;
+    let mut update = TickUpdate::from(tick_array.get_tick(tick_index, tick_spacing)?);
+    update.initialized = true;
+    update.liquidity_gross = update
+        .liquidity_gross
+        .checked_add(liquidity)
+        .ok_or(ErrorCode::LiquidityOverflow)?;
+    update.liquidity_net = if is_upper_tick {
+        update.liquidity_net.checked_sub(liquidity_delta)
+    } else {
+        update.liquidity_net.checked_add(liquidity_delta)
+    }
+    .ok_or(ErrorCode::LiquidityNetError)?;
+    tick_array.update_tick(tick_index, tick_spacing, &update)
+}
+
+#[cfg(test)]
+mod testing_tests {
+    use super::*;
+    use crate::math::sqrt_price_from_tick_index;
+
+    fn whirlpool_fixture() -> WhirlpoolFixture {
+        WhirlpoolFixture {
+            whirlpools_config: Pubkey::new_unique(),
+            token_mint_a: Pubkey::new_from_array([1; 32]),
+            token_vault_a: Pubkey::new_unique(),
+            token_mint_b: Pubkey::new_from_array([2; 32]),
+            token_vault_b: Pubkey::new_unique(),
+            tick_spacing: 64,
+            fee_rate: 30_000,
+            protocol_fee_rate: 300,
+            sqrt_price: sqrt_price_from_tick_index(100),
+        }
+    }
+
+    #[test]
+    fn test_fixture_accounts_have_account_sizes() {
+        let fixture = whirlpool_fixture();
+        let (address, _) = fixture.find_address();
+        let whirlpool = FixtureAccount::from_account(address, &fixture.build());
+        assert_eq!(whirlpool.data.len(), Whirlpool::LEN);
+        assert_eq!(whirlpool.owner, crate::ID);
+
+        let (address, tick_array) = tick_array(address, 0);
+        let tick_array = FixtureAccount::from_zero_copy(address, &tick_array);
+        assert_eq!(tick_array.data.len(), TickArray::LEN);
+
+        let (address, position) = position(
+            whirlpool.address,
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            0,
+            128,
+        );
+        let position = FixtureAccount::from_account(address, &position);
+        assert_eq!(position.data.len(), Position::LEN);
+    }
+
+    #[test]
+    fn test_add_liquidity_in_range() {
+        let fixture = whirlpool_fixture();
+        let mut whirlpool = fixture.build();
+        assert_eq!(whirlpool.tick_current_index, 100);
+
+        let whirlpool_key = fixture.find_address().0;
+        let (_, mut tick_array) = tick_array(whirlpool_key, 0);
+        let (_, mut position) = position(
+            whirlpool_key,
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            0,
+            128,
+        );
+
+        add_position_liquidity(&mut whirlpool, &mut position, 1_000).unwrap();
+        add_tick_liquidity(&mut tick_array, 0, 64, 1_000, false).unwrap();
+        add_tick_liquidity(&mut tick_array, 128, 64, 1_000, true).unwrap();
+
+        assert_eq!(whirlpool.liquidity, 1_000);
+        assert_eq!(whirlpool.total_liquidity, 1_000);
+        assert_eq!(position.liquidity, 1_000);
+        assert_eq!({ tick_array.get_tick(0, 64).unwrap().liquidity_net }, 1_000);
+        assert_eq!(
+            { tick_array.get_tick(128, 64).unwrap().liquidity_net },
+            -1_000
+        );
+        assert_eq!({ tick_array.initialized_bitmap }, 0b101);
+    }
+}
@@ -0,0 +1,129 @@
+use anchor_lang::prelude::*;
+use solana_program::program::set_return_data;
+
+use crate::errors::ErrorCode;
+use crate::state::Whirlpool;
+use crate::util::{MAX_AFTER_SWAP_HOOK_COMPUTE_UNITS, MAX_TICK_CROSS_HOOK_COMPUTE_UNITS};
+
+// Compute units of one swap step crossing an initialized tick, the most expensive step: the swap
+// step math on U256 values (~5,000), the sqrt price of the next target (~2,000), the tick update
+// with the fee and reward growth outside for all reward slots (~4,000) and the search of the next
+// initialized tick (~2,000), with headroom for the tick cross hook bookkeeping.
+pub const SWAP_STEP_COMPUTE_UNITS: u64 = 15_000;
+
+// Compute units of the work following the swap loop, without the swap hooks: two token program
+// transfer CPIs (1,000 for the invocation plus ~5,000 for the transfer each), serializing the
+// Whirlpool account on exit (~8,000), and the trade log record, volume stats and fee event
+// (~5,000).
+pub const SWAP_POST_LOOP_COMPUTE_UNITS: u64 = 25_000;
+
+// Compute units that must be left before a swap step in a whirlpool without swap hooks: the step
+// itself and the work following the swap loop, so a swap truncated at the next step can still
+// complete.
+pub const SWAP_COMPUTE_UNITS_RESERVE: u64 = SWAP_STEP_COMPUTE_UNITS + SWAP_POST_LOOP_COMPUTE_UNITS;
+
+/// Progress of a swap truncated before running out of compute units, set as return data with the
+/// `SwapTruncated` error. Clients simulating the swap can split the trade at `amount_traded`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, PartialEq)]
+#[cfg_attr(not(target_arch = "bpf"), derive(Debug))]
+pub struct SwapProgress {
+    // Amount of the specified token traded before truncation
+    pub amount_traded: u64,
+    // Amount of the other token computed before truncation
+    pub amount_calculated: u64,
+    pub sqrt_price: u128,
+    pub tick_current_index: i32,
+}
+
+/// Compute units left to the transaction, or None where they cannot be measured, e.g. off-chain.
+pub fn remaining_compute_units() -> Option<u64> {
+    #[cfg(target_os = "solana")]
+    {
+        Some(solana_program::compute_units::sol_remaining_compute_units())
+    }
+    #[cfg(not(target_os = "solana"))]
+    {
+        None
+    }
+}
+
+/// Compute units that must be left before a swap step in the whirlpool: `SWAP_COMPUTE_UNITS_RESERVE`
+/// and the most each swap hook of the whirlpool may consume.
+pub fn swap_compute_units_reserve(whirlpool: &Whirlpool) -> u64 {
+    let mut reserve = SWAP_COMPUTE_UNITS_RESERVE;
+    if whirlpool.has_tick_cross_hook() {
+        reserve += MAX_TICK_CROSS_HOOK_COMPUTE_UNITS;
+    }
+    if whirlpool.has_after_swap_hook() {
+        reserve += MAX_AFTER_SWAP_HOOK_COMPUTE_UNITS;
+    }
+    reserve
+}
+
+fn is_compute_budget_exhausted(remaining_compute_units: Option<u64>, reserve: u64) -> bool {
+    matches!(remaining_compute_units, Some(remaining) if remaining < reserve)
+}
+
+/// Stop a swap before its next step if the compute units left would not cover the step and the
+/// work following the swap loop, including the swap hooks of the whirlpool, rather than failing
+/// with an opaque compute budget exhaustion.
+///
+/// # Errors
+/// - `SwapTruncated` - Too few compute units are left, the progress is set as return data
+pub fn verify_swap_compute_budget(
+    whirlpool: &Whirlpool,
+    progress: impl FnOnce() -> SwapProgress,
+) -> Result<()> {
+    if is_compute_budget_exhausted(
+        remaining_compute_units(),
+        swap_compute_units_reserve(whirlpool),
+    ) {
+        set_return_data(&progress().try_to_vec()?);
+        return Err(ErrorCode::SwapTruncated.into());
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod compute_budget_tests {
+    use super::*;
+
+    #[test]
+    fn test_is_compute_budget_exhausted() {
+        let reserve = SWAP_COMPUTE_UNITS_RESERVE;
+        assert!(!is_compute_budget_exhausted(None, reserve));
+        assert!(!is_compute_budget_exhausted(Some(reserve), reserve));
+        assert!(is_compute_budget_exhausted(Some(reserve - 1), reserve));
+        assert!(is_compute_budget_exhausted(Some(0), reserve));
+    }
+
+    #[test]
+    fn test_hooked_whirlpool_truncates_earlier() {
+        let mut whirlpool = Whirlpool::default();
+        assert_eq!(
+            swap_compute_units_reserve(&whirlpool),
+            SWAP_COMPUTE_UNITS_RESERVE
+        );
+
+        whirlpool.after_swap_hook = Pubkey::new_unique();
+        whirlpool.tick_cross_hook = Pubkey::new_unique();
+        let reserve = swap_compute_units_reserve(&whirlpool);
+        assert_eq!(
+            reserve,
+            SWAP_COMPUTE_UNITS_RESERVE
+                + MAX_AFTER_SWAP_HOOK_COMPUTE_UNITS
+                + MAX_TICK_CROSS_HOOK_COMPUTE_UNITS
+        );
+        // Enough for a swap step in a pool without hooks, but not for the hooks of this pool.
+        assert!(is_compute_budget_exhausted(
+            Some(SWAP_COMPUTE_UNITS_RESERVE),
+            reserve
+        ));
+        assert!(!is_compute_budget_exhausted(Some(reserve), reserve));
+    }
+
+    #[test]
+    fn test_off_chain_swaps_never_truncated() {
+        assert!(verify_swap_compute_budget(&Whirlpool::default(), SwapProgress::default).is_ok());
+    }
+}
@@ -0,0 +1,85 @@
+use anchor_lang::prelude::*;
+use std::convert::TryInto;
+
+use crate::errors::ErrorCode;
+use crate::math::{checked_mul_div, TO_Q64};
+
+pub mod spl_stake_pool_program {
+    use super::*;
+    declare_id!("SPoo1Ku8WFXoNDMHPsrGSTSG1Y47rzgn41SLUNakuHy");
+}
+
+// Offsets of `total_lamports` and `pool_token_supply` in an SPL stake pool account
+const STAKE_POOL_TOTAL_LAMPORTS_OFFSET: usize = 258;
+const STAKE_POOL_POOL_TOKEN_SUPPLY_OFFSET: usize = 266;
+
+/// Read the Q64.64 exchange rate (lamports per pool token) from an SPL stake pool account. Only
+/// the SPL stake pool layout is supported, other liquid staking programs such as Marinade or Lido
+/// keep their rate in a different account layout and are rejected.
+///
+/// # Errors
+/// - `InvalidExchangeRateOracle` - The account is not owned by the stake pool program, is too small
+///   or reports an empty pool
+pub fn read_exchange_rate_x64(oracle: &AccountInfo) -> Result<u128> {
+    if oracle.owner != &spl_stake_pool_program::ID {
+        return Err(ErrorCode::InvalidExchangeRateOracle.into());
+    }
+    let data = oracle.try_borrow_data()?;
+    Ok(stake_pool_exchange_rate_x64(&data)?)
+}
+
+fn stake_pool_exchange_rate_x64(data: &[u8]) -> std::result::Result<u128, ErrorCode> {
+    let read_u64 = |offset: usize| {
+        data.get(offset..offset + 8)
+            .map(|bytes| u64::from_le_bytes(bytes.try_into().unwrap()))
+            .ok_or(ErrorCode::InvalidExchangeRateOracle)
+    };
+    let total_lamports = read_u64(STAKE_POOL_TOTAL_LAMPORTS_OFFSET)?;
+    let pool_token_supply = read_u64(STAKE_POOL_POOL_TOKEN_SUPPLY_OFFSET)?;
+
+    if total_lamports == 0 || pool_token_supply == 0 {
+        return Err(ErrorCode::InvalidExchangeRateOracle);
+    }
+
+    checked_mul_div(total_lamports as u128, TO_Q64, pool_token_supply as u128)
+}
+
+#[cfg(test)]
+mod exchange_rate_tests {
+    use super::*;
+
+    fn stake_pool_data(total_lamports: u64, pool_token_supply: u64) -> Vec<u8> {
+        let mut data = vec![0u8; 300];
+        data[STAKE_POOL_TOTAL_LAMPORTS_OFFSET..STAKE_POOL_TOTAL_LAMPORTS_OFFSET + 8]
+            .copy_from_slice(&total_lamports.to_le_bytes());
+        data[STAKE_POOL_POOL_TOKEN_SUPPLY_OFFSET..STAKE_POOL_POOL_TOKEN_SUPPLY_OFFSET + 8]
+            .copy_from_slice(&pool_token_supply.to_le_bytes());
+        data
+    }
+
+    #[test]
+    fn test_stake_pool_exchange_rate() {
+        let data = stake_pool_data(1_100, 1_000);
+        assert_eq!(
+            stake_pool_exchange_rate_x64(&data).unwrap(),
+            (1_100u128 << 64) / 1_000
+        );
+    }
+
+    #[test]
+    fn test_stake_pool_exchange_rate_empty_pool() {
+        let data = stake_pool_data(0, 0);
+        assert_eq!(
+            stake_pool_exchange_rate_x64(&data).unwrap_err(),
+            ErrorCode::InvalidExchangeRateOracle
+        );
+    }
+
+    #[test]
+    fn test_stake_pool_exchange_rate_account_too_small() {
+        assert_eq!(
+            stake_pool_exchange_rate_x64(&[0u8; 100]).unwrap_err(),
+            ErrorCode::InvalidExchangeRateOracle
+        );
+    }
+}
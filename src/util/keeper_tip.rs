@@ -0,0 +1,158 @@
+use anchor_lang::prelude::*;
+use anchor_lang::Discriminator;
+use solana_program::sysvar::instructions::load_instruction_at_checked;
+use std::convert::TryInto;
+
+use crate::errors::ErrorCode;
+use crate::events::KeeperTipPaid;
+use crate::state::KeeperTipEscrow;
+
+pub mod compute_budget_program {
+    use super::*;
+    declare_id!("ComputeBudget111111111111111111111111111111");
+}
+
+// Compute unit limit of each instruction of a transaction that does not set its limit
+pub const DEFAULT_INSTRUCTION_COMPUTE_UNIT_LIMIT: u64 = 200_000;
+// Maximum compute unit limit of a transaction
+pub const MAX_COMPUTE_UNIT_LIMIT: u64 = 1_400_000;
+
+// Compute unit prices are set in micro-lamports
+const MICRO_LAMPORTS_PER_LAMPORT: u128 = 1_000_000;
+
+// Tags of the compute budget instructions setting the compute unit limit and price
+const SET_COMPUTE_UNIT_LIMIT_TAG: u8 = 2;
+const SET_COMPUTE_UNIT_PRICE_TAG: u8 = 3;
+
+/// Accounts of a crank paying a keeper tip, passed as its first remaining accounts.
+pub struct KeeperTipAccounts<'info> {
+    pub keeper_tip_escrow: &'info AccountInfo<'info>,
+    pub instructions_sysvar: &'info AccountInfo<'info>,
+    pub keeper: &'info AccountInfo<'info>,
+}
+
+/// Split the keeper tip accounts off the remaining accounts of a crank. A crank pays a tip when
+/// its first remaining account is a keeper tip escrow, followed by the instructions sysvar and
+/// the keeper receiving the tip.
+///
+/// # Errors
+/// - `RemainingAccountsMissing` - The keeper tip escrow is not followed by the other accounts
+pub fn split_keeper_tip_accounts<'info>(
+    remaining_accounts: &'info [AccountInfo<'info>],
+) -> Result<(
+    Option<KeeperTipAccounts<'info>>,
+    &'info [AccountInfo<'info>],
+)> {
+    match remaining_accounts.first() {
+        Some(account_info) if is_keeper_tip_escrow(account_info)? => match remaining_accounts {
+            [keeper_tip_escrow, instructions_sysvar, keeper, rest @ ..] => Ok((
+                Some(KeeperTipAccounts {
+                    keeper_tip_escrow,
+                    instructions_sysvar,
+                    keeper,
+                }),
+                rest,
+            )),
+            _ => Err(ErrorCode::RemainingAccountsMissing.into()),
+        },
+        _ => Ok((None, remaining_accounts)),
+    }
+}
+
+fn is_keeper_tip_escrow(account_info: &AccountInfo) -> Result<bool> {
+    Ok(account_info.owner == &crate::ID
+        && account_info
+            .try_borrow_data()?
+            .starts_with(&KeeperTipEscrow::discriminator()))
+}
+
+/// Pay the keeper of a crank of the whirlpool its tip from the keeper tip escrow. An escrow
+/// running low pays what it holds above its rent exemption rather than failing the crank.
+///
+/// # Errors
+/// - `InvalidKeeperTipEscrow` - The escrow tips the keepers of another whirlpool
+pub fn pay_keeper_tip(accounts: &KeeperTipAccounts, whirlpool: &Pubkey) -> Result<()> {
+    let escrow = Account::<KeeperTipEscrow>::try_from(accounts.keeper_tip_escrow)?;
+    if escrow.whirlpool != *whirlpool {
+        return Err(ErrorCode::InvalidKeeperTipEscrow.into());
+    }
+
+    let priority_fee = transaction_priority_fee(accounts.instructions_sysvar)?;
+    let available = accounts
+        .keeper_tip_escrow
+        .lamports()
+        .saturating_sub(Rent::get()?.minimum_balance(KeeperTipEscrow::LEN));
+    let tip = escrow.tip_for(priority_fee).min(available);
+
+    **accounts.keeper_tip_escrow.try_borrow_mut_lamports()? -= tip;
+    **accounts.keeper.try_borrow_mut_lamports()? += tip;
+
+    emit!(KeeperTipPaid {
+        whirlpool: *whirlpool,
+        keeper_tip_escrow: accounts.keeper_tip_escrow.key(),
+        keeper: accounts.keeper.key(),
+        tip,
+    });
+
+    Ok(())
+}
+
+/// Priority fee in lamports of the current transaction, from its compute budget instructions.
+fn transaction_priority_fee(instructions_sysvar: &AccountInfo) -> Result<u64> {
+    let mut compute_unit_limit = None;
+    let mut compute_unit_price = 0;
+    let mut instruction_count = 0;
+    let mut index = 0;
+    while let Ok(instruction) = load_instruction_at_checked(index, instructions_sysvar) {
+        index += 1;
+        if instruction.program_id != compute_budget_program::ID {
+            instruction_count += 1;
+            continue;
+        }
+        match instruction.data.split_first() {
+            Some((&SET_COMPUTE_UNIT_LIMIT_TAG, data)) if data.len() == 4 => {
+                compute_unit_limit = Some(u32::from_le_bytes(data.try_into().unwrap()));
+            }
+            Some((&SET_COMPUTE_UNIT_PRICE_TAG, data)) if data.len() == 8 => {
+                compute_unit_price = u64::from_le_bytes(data.try_into().unwrap());
+            }
+            _ => {}
+        }
+    }
+
+    Ok(priority_fee(
+        compute_unit_price,
+        compute_unit_limit,
+        instruction_count,
+    ))
+}
+
+fn priority_fee(
+    compute_unit_price: u64,
+    compute_unit_limit: Option<u32>,
+    instruction_count: u64,
+) -> u64 {
+    let compute_unit_limit = compute_unit_limit
+        .map(u64::from)
+        .unwrap_or(DEFAULT_INSTRUCTION_COMPUTE_UNIT_LIMIT * instruction_count)
+        .min(MAX_COMPUTE_UNIT_LIMIT);
+    let micro_lamports = compute_unit_price as u128 * compute_unit_limit as u128;
+    micro_lamports.div_ceil(MICRO_LAMPORTS_PER_LAMPORT) as u64
+}
+
+#[cfg(test)]
+mod keeper_tip_tests {
+    use super::*;
+
+    #[test]
+    fn test_priority_fee() {
+        assert_eq!(priority_fee(0, Some(200_000), 1), 0);
+        assert_eq!(priority_fee(1_000_000, Some(200_000), 1), 200_000);
+        // Rounded up to the lamport
+        assert_eq!(priority_fee(1, Some(1), 1), 1);
+        // Without a limit each instruction gets the default limit
+        assert_eq!(priority_fee(1_000_000, None, 2), 400_000);
+        assert_eq!(priority_fee(1_000_000, None, 10), 1_400_000);
+        assert_eq!(priority_fee(1_000_000, Some(u32::MAX), 1), 1_400_000);
+    }
+}
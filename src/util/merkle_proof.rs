@@ -0,0 +1,88 @@
+use anchor_lang::prelude::*;
+use solana_program::hash::hashv;
+
+// Domain separation between leaves and inner nodes, so an inner node can never pass as a leaf.
+const LEAF_PREFIX: &[u8] = &[0];
+const NODE_PREFIX: &[u8] = &[1];
+
+/// Leaf of a retroactive reward merkle tree, committing to the cumulative amount a claimant can
+/// claim from a distributor.
+pub fn retro_reward_leaf(
+    distributor: &Pubkey,
+    claimant: &Pubkey,
+    cumulative_amount: u64,
+) -> [u8; 32] {
+    hashv(&[
+        LEAF_PREFIX,
+        distributor.as_ref(),
+        claimant.as_ref(),
+        &cumulative_amount.to_le_bytes(),
+    ])
+    .to_bytes()
+}
+
+/// Verify a merkle proof of a leaf against a root. Pairs are hashed in sorted order, so the proof
+/// only holds the sibling hashes, from the leaf up.
+pub fn verify_merkle_proof(proof: &[[u8; 32]], root: &[u8; 32], leaf: [u8; 32]) -> bool {
+    let computed = proof.iter().fold(leaf, |node, sibling| {
+        if node <= *sibling {
+            hashv(&[NODE_PREFIX, &node, sibling]).to_bytes()
+        } else {
+            hashv(&[NODE_PREFIX, sibling, &node]).to_bytes()
+        }
+    });
+    computed == *root
+}
+
+#[cfg(test)]
+mod merkle_proof_tests {
+    use super::*;
+
+    fn node(a: [u8; 32], b: [u8; 32]) -> [u8; 32] {
+        if a <= b {
+            hashv(&[NODE_PREFIX, &a, &b]).to_bytes()
+        } else {
+            hashv(&[NODE_PREFIX, &b, &a]).to_bytes()
+        }
+    }
+
+    #[test]
+    fn test_verify_merkle_proof() {
+        let distributor = Pubkey::new_unique();
+        let claimants = [
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+        ];
+        let leaves = [
+            retro_reward_leaf(&distributor, &claimants[0], 100),
+            retro_reward_leaf(&distributor, &claimants[1], 200),
+            retro_reward_leaf(&distributor, &claimants[2], 300),
+        ];
+        let left = node(leaves[0], leaves[1]);
+        let root = node(left, leaves[2]);
+
+        assert!(verify_merkle_proof(
+            &[leaves[1], leaves[2]],
+            &root,
+            leaves[0]
+        ));
+        assert!(verify_merkle_proof(
+            &[leaves[0], leaves[2]],
+            &root,
+            leaves[1]
+        ));
+        assert!(verify_merkle_proof(&[left], &root, leaves[2]));
+
+        // Wrong amount, wrong distributor and truncated proofs are rejected
+        let inflated = retro_reward_leaf(&distributor, &claimants[0], 101);
+        assert!(!verify_merkle_proof(
+            &[leaves[1], leaves[2]],
+            &root,
+            inflated
+        ));
+        let other = retro_reward_leaf(&Pubkey::new_unique(), &claimants[0], 100);
+        assert!(!verify_merkle_proof(&[leaves[1], leaves[2]], &root, other));
+        assert!(!verify_merkle_proof(&[leaves[1]], &root, leaves[0]));
+    }
+}
@@ -1,9 +1,39 @@
+pub mod compute_budget;
+pub mod exchange_rate;
+pub mod keeper_tip;
+pub mod merkle_proof;
+pub mod paranoid_checks;
+pub mod price_limits;
+pub mod price_oracle;
+pub mod quote_hint;
+pub mod reward_escrow;
+pub mod swap_hook;
+#[cfg(any(test, feature = "sim"))]
+pub mod swap_quote;
 pub mod swap_tick_sequence;
+pub mod tick_cross_hook;
+pub mod tick_array;
 pub mod token;
+pub mod upstream_position;
 pub mod util;
 
+pub use compute_budget::*;
+pub use exchange_rate::*;
+pub use keeper_tip::*;
+pub use merkle_proof::*;
+pub use paranoid_checks::*;
+pub use price_limits::*;
+pub use price_oracle::*;
+pub use quote_hint::*;
+pub use reward_escrow::*;
+pub use swap_hook::*;
+#[cfg(any(test, feature = "sim"))]
+pub use swap_quote::*;
 pub use swap_tick_sequence::*;
+pub use tick_cross_hook::*;
+pub use tick_array::*;
 pub use token::*;
+pub use upstream_position::*;
 pub use util::*;
 
 #[cfg(test)]
@@ -0,0 +1,171 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::TokenAccount;
+
+use crate::{
+    errors::ErrorCode,
+    state::{Position, Tick, Whirlpool},
+};
+
+// Shadow accounting assertions, called at the end of handlers in builds with the
+// `paranoid-checks` feature. Handlers compute the amounts they move and the liquidity they
+// apply separately from moving and writing them; these checks re-derive the effect from the
+// accounts and fail the transaction if the two disagree.
+
+/// Vault balances of a Whirlpool captured before a handler moves tokens.
+#[derive(Clone, Copy)]
+#[cfg_attr(not(target_arch = "bpf"), derive(Debug))]
+pub struct VaultBalances {
+    pub amount_a: u64,
+    pub amount_b: u64,
+}
+
+impl VaultBalances {
+    pub fn read(token_vault_a: &AccountInfo, token_vault_b: &AccountInfo) -> Result<Self> {
+        Ok(VaultBalances {
+            amount_a: token_balance(token_vault_a)?,
+            amount_b: token_balance(token_vault_b)?,
+        })
+    }
+
+    /// Verify the vaults moved by exactly the signed amounts the handler computed, positive
+    /// amounts being deposits into the vaults.
+    pub fn verify_deltas(
+        &self,
+        token_vault_a: &AccountInfo,
+        token_vault_b: &AccountInfo,
+        delta_a: i128,
+        delta_b: i128,
+    ) -> Result<()> {
+        let after = VaultBalances::read(token_vault_a, token_vault_b)?;
+        verify_balance_delta(self.amount_a, after.amount_a, delta_a)?;
+        verify_balance_delta(self.amount_b, after.amount_b, delta_b)?;
+        Ok(())
+    }
+}
+
+// The token account data is read directly, the typed accounts held by the handler still carry
+// the balances from before the transfers.
+fn token_balance(token_account: &AccountInfo) -> Result<u64> {
+    let data = token_account.try_borrow_data()?;
+    Ok(TokenAccount::try_deserialize(&mut &data[..])?.amount)
+}
+
+pub fn verify_balance_delta(before: u64, after: u64, delta: i128) -> Result<()> {
+    if i128::from(after) - i128::from(before) != delta {
+        return Err(ErrorCode::ShadowAccountingMismatch.into());
+    }
+    Ok(())
+}
+
+/// Verify a liquidity change applied to a position is reflected in its boundary ticks and, while
+/// the position is in range, in the active liquidity of its Whirlpool.
+pub fn verify_liquidity_sums(
+    whirlpool_liquidity_before: u128,
+    position_liquidity_before: u128,
+    whirlpool: &Whirlpool,
+    position: &Position,
+    tick_lower: &Tick,
+    tick_upper: &Tick,
+) -> Result<()> {
+    if tick_lower.liquidity_gross < position.liquidity
+        || tick_upper.liquidity_gross < position.liquidity
+    {
+        return Err(ErrorCode::ShadowAccountingMismatch.into());
+    }
+
+    let in_range = whirlpool.tick_current_index >= position.tick_lower_index
+        && whirlpool.tick_current_index < position.tick_upper_index;
+    let position_change = position.liquidity as i128 - position_liquidity_before as i128;
+    let whirlpool_change = whirlpool.liquidity as i128 - whirlpool_liquidity_before as i128;
+    let expected_change = if in_range { position_change } else { 0 };
+    if whirlpool_change != expected_change {
+        return Err(ErrorCode::ShadowAccountingMismatch.into());
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod paranoid_checks_tests {
+    use super::*;
+
+    fn position_in(tick_lower_index: i32, tick_upper_index: i32, liquidity: u128) -> Position {
+        Position {
+            tick_lower_index,
+            tick_upper_index,
+            liquidity,
+            ..Default::default()
+        }
+    }
+
+    fn tick_with_gross(liquidity_gross: u128) -> Tick {
+        Tick {
+            initialized: true,
+            liquidity_gross,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_verify_balance_delta() {
+        assert!(verify_balance_delta(100, 150, 50).is_ok());
+        assert!(verify_balance_delta(100, 40, -60).is_ok());
+        assert!(verify_balance_delta(u64::MAX, 0, -(u64::MAX as i128)).is_ok());
+        assert_eq!(
+            verify_balance_delta(100, 149, 50),
+            Err(ErrorCode::ShadowAccountingMismatch.into())
+        );
+        assert_eq!(
+            verify_balance_delta(100, 160, -60),
+            Err(ErrorCode::ShadowAccountingMismatch.into())
+        );
+    }
+
+    #[test]
+    fn test_verify_liquidity_sums_in_range() {
+        let whirlpool = Whirlpool {
+            tick_current_index: 0,
+            liquidity: 1_500,
+            ..Default::default()
+        };
+        let position = position_in(-64, 64, 1_000);
+        let tick = tick_with_gross(1_000);
+        assert!(verify_liquidity_sums(1_000, 500, &whirlpool, &position, &tick, &tick).is_ok());
+        assert_eq!(
+            verify_liquidity_sums(1_200, 500, &whirlpool, &position, &tick, &tick),
+            Err(ErrorCode::ShadowAccountingMismatch.into())
+        );
+    }
+
+    #[test]
+    fn test_verify_liquidity_sums_out_of_range() {
+        let whirlpool = Whirlpool {
+            tick_current_index: 128,
+            liquidity: 1_000,
+            ..Default::default()
+        };
+        let position = position_in(-64, 64, 0);
+        let tick = tick_with_gross(0);
+        assert!(verify_liquidity_sums(1_000, 500, &whirlpool, &position, &tick, &tick).is_ok());
+        assert_eq!(
+            verify_liquidity_sums(1_500, 500, &whirlpool, &position, &tick, &tick),
+            Err(ErrorCode::ShadowAccountingMismatch.into())
+        );
+    }
+
+    #[test]
+    fn test_verify_liquidity_sums_tick_below_position() {
+        let whirlpool = Whirlpool::default();
+        let position = position_in(-64, 64, 1_000);
+        assert_eq!(
+            verify_liquidity_sums(
+                0,
+                1_000,
+                &whirlpool,
+                &position,
+                &tick_with_gross(999),
+                &tick_with_gross(1_000)
+            ),
+            Err(ErrorCode::ShadowAccountingMismatch.into())
+        );
+    }
+}
@@ -0,0 +1,89 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::ErrorCode;
+use crate::math::{MAX_SQRT_PRICE_X64, MIN_SQRT_PRICE_X64};
+
+/// Sqrt price limit that lets a swap run until the price bound in its direction.
+pub fn default_sqrt_price_limit(a_to_b: bool) -> u128 {
+    if a_to_b {
+        MIN_SQRT_PRICE_X64
+    } else {
+        MAX_SQRT_PRICE_X64
+    }
+}
+
+/// Resolve the sqrt price limit passed to a swap instruction, where 0 stands for no limit.
+pub fn resolve_sqrt_price_limit(sqrt_price_limit: u128, a_to_b: bool) -> u128 {
+    if sqrt_price_limit == 0 {
+        default_sqrt_price_limit(a_to_b)
+    } else {
+        sqrt_price_limit
+    }
+}
+
+/// Verify that a sqrt price limit is within the price bounds and on the side of the current
+/// sqrt price the swap moves towards.
+///
+/// # Errors
+/// - `SqrtPriceOutOfBounds` - The limit is outside of the min and max sqrt price
+/// - `InvalidSqrtPriceLimitDirection` - The limit is above the current price for an A to B swap,
+///   or below it for a B to A swap
+pub fn validate_sqrt_price_limit(
+    sqrt_price_limit: u128,
+    sqrt_price: u128,
+    a_to_b: bool,
+) -> Result<()> {
+    if !(MIN_SQRT_PRICE_X64..=MAX_SQRT_PRICE_X64).contains(&sqrt_price_limit) {
+        return Err(ErrorCode::SqrtPriceOutOfBounds.into());
+    }
+
+    if a_to_b && sqrt_price_limit > sqrt_price || !a_to_b && sqrt_price_limit < sqrt_price {
+        return Err(ErrorCode::InvalidSqrtPriceLimitDirection.into());
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod price_limits_tests {
+    use super::*;
+    use crate::math::sqrt_price_from_tick_index;
+
+    #[test]
+    fn test_zero_limit_resolves_to_bound_in_swap_direction() {
+        assert_eq!(resolve_sqrt_price_limit(0, true), MIN_SQRT_PRICE_X64);
+        assert_eq!(resolve_sqrt_price_limit(0, false), MAX_SQRT_PRICE_X64);
+
+        let sqrt_price_limit = sqrt_price_from_tick_index(-64);
+        assert_eq!(
+            resolve_sqrt_price_limit(sqrt_price_limit, true),
+            sqrt_price_limit
+        );
+    }
+
+    #[test]
+    fn test_default_limits_are_valid() {
+        let sqrt_price = sqrt_price_from_tick_index(0);
+        for a_to_b in [true, false] {
+            validate_sqrt_price_limit(default_sqrt_price_limit(a_to_b), sqrt_price, a_to_b)
+                .unwrap();
+        }
+    }
+
+    #[test]
+    fn test_invalid_limits() {
+        let sqrt_price = sqrt_price_from_tick_index(0);
+        assert_eq!(
+            validate_sqrt_price_limit(0, sqrt_price, true),
+            Err(ErrorCode::SqrtPriceOutOfBounds.into())
+        );
+        assert_eq!(
+            validate_sqrt_price_limit(sqrt_price_from_tick_index(64), sqrt_price, true),
+            Err(ErrorCode::InvalidSqrtPriceLimitDirection.into())
+        );
+        assert_eq!(
+            validate_sqrt_price_limit(sqrt_price_from_tick_index(-64), sqrt_price, false),
+            Err(ErrorCode::InvalidSqrtPriceLimitDirection.into())
+        );
+    }
+}
@@ -0,0 +1,437 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::Mint;
+use std::convert::TryInto;
+
+use crate::errors::ErrorCode;
+use crate::math::{MAX_SQRT_PRICE_X64, MIN_SQRT_PRICE_X64, U256};
+use crate::state::MintPriceFeed;
+
+pub mod pyth_receiver_program {
+    use super::*;
+    declare_id!("rec5EKMGg6MxZYaMdyBfgwp4d5rB9T1VQH5pJv5LtFJ");
+}
+
+// PriceUpdateV2 account of the Pyth receiver program: Anchor discriminator, write authority,
+// Borsh verification level and the price feed message
+const PRICE_UPDATE_V2_DISCRIMINATOR: [u8; 8] = [34, 241, 35, 99, 157, 126, 244, 205];
+const PRICE_UPDATE_VERIFICATION_LEVEL_OFFSET: usize = 40;
+// Partial verification (0) carries its signature count, full verification (1) nothing
+const PRICE_UPDATE_VERIFICATION_LEVEL_FULL: u8 = 1;
+const PRICE_UPDATE_FEED_ID_OFFSET: usize = 41;
+const PRICE_UPDATE_PRICE_OFFSET: usize = 73;
+const PRICE_UPDATE_CONFIDENCE_OFFSET: usize = 81;
+const PRICE_UPDATE_EXPONENT_OFFSET: usize = 89;
+const PRICE_UPDATE_PUBLISH_TIME_OFFSET: usize = 93;
+
+// Largest power of ten that fits in a U256
+const MAX_U256_POW10: u32 = 77;
+
+/// Pyth price updates an initial sqrt price is read from, with the checks the prices have to
+/// pass. The updates have to be of the feeds configured for the mints of the pool.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default)]
+pub struct OraclePriceCheck {
+    pub price_update_a: Pubkey,
+    pub price_update_b: Pubkey,
+    // Seconds the oracle prices may be old
+    pub max_age: u64,
+    // Confidence interval each oracle price may have, in basis points of the price
+    pub max_confidence_rate: u16,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+#[cfg_attr(not(target_arch = "bpf"), derive(Debug))]
+pub struct OraclePrice {
+    pub feed_id: [u8; 32],
+    pub price: u64,
+    pub confidence: u64,
+    pub exponent: i32,
+    pub publish_timestamp: i64,
+}
+
+impl OraclePrice {
+    pub fn verify(
+        &self,
+        check: &OraclePriceCheck,
+        timestamp: i64,
+    ) -> std::result::Result<(), ErrorCode> {
+        if timestamp.saturating_sub(self.publish_timestamp) > check.max_age as i64 {
+            return Err(ErrorCode::StaleOraclePrice);
+        }
+        if self.confidence as u128 * 10_000 > self.price as u128 * check.max_confidence_rate as u128
+        {
+            return Err(ErrorCode::OracleConfidenceTooWide);
+        }
+        Ok(())
+    }
+}
+
+/// Read the initial sqrt price of a Whirlpool from the Pyth price updates of `check`, passed
+/// among the remaining accounts with the mint price feeds of both mints. The price of token A is
+/// divided by the price of token B, so both feeds have to share a quote currency.
+///
+/// # Errors
+/// - `InvalidPriceOracle` - An update is missing or not a fully verified Pyth price update
+/// - `OraclePriceFeedMismatch` - A mint has no price feed or the update is of another feed
+/// - `StaleOraclePrice` - A price is older than the maximum age
+/// - `OracleConfidenceTooWide` - A confidence interval exceeds the maximum confidence rate
+/// - `SqrtPriceOutOfBounds` - The price is outside of the prices Whirlpools support
+pub fn read_oracle_sqrt_price<'info>(
+    check: &OraclePriceCheck,
+    whirlpools_config: &Pubkey,
+    token_mint_a: &Account<'info, Mint>,
+    token_mint_b: &Account<'info, Mint>,
+    remaining_accounts: &'info [AccountInfo<'info>],
+    timestamp: i64,
+) -> Result<u128> {
+    let price_a = read_mint_oracle_price(
+        &check.price_update_a,
+        whirlpools_config,
+        &token_mint_a.key(),
+        remaining_accounts,
+    )?;
+    let price_b = read_mint_oracle_price(
+        &check.price_update_b,
+        whirlpools_config,
+        &token_mint_b.key(),
+        remaining_accounts,
+    )?;
+    price_a.verify(check, timestamp)?;
+    price_b.verify(check, timestamp)?;
+    Ok(sqrt_price_from_oracle_prices(
+        &price_a,
+        &price_b,
+        token_mint_a.decimals,
+        token_mint_b.decimals,
+    )?)
+}
+
+fn read_mint_oracle_price<'info>(
+    price_update: &Pubkey,
+    whirlpools_config: &Pubkey,
+    token_mint: &Pubkey,
+    remaining_accounts: &'info [AccountInfo<'info>],
+) -> Result<OraclePrice> {
+    // Mint price feeds are PDAs of the config and the mint, so at most one account can match.
+    let mint_price_feed = remaining_accounts
+        .iter()
+        .filter(|account| account.owner == &crate::ID)
+        .filter_map(|account| Account::<MintPriceFeed>::try_from(account).ok())
+        .find(|price_feed| price_feed.is_feed_of(whirlpools_config, token_mint))
+        .ok_or(ErrorCode::OraclePriceFeedMismatch)?;
+    let price_update = remaining_accounts
+        .iter()
+        .find(|account| account.key() == *price_update)
+        .ok_or(ErrorCode::InvalidPriceOracle)?;
+    if price_update.owner != &pyth_receiver_program::ID {
+        return Err(ErrorCode::InvalidPriceOracle.into());
+    }
+    let oracle_price = parse_price_update(&price_update.try_borrow_data()?)?;
+    if oracle_price.feed_id != mint_price_feed.feed_id {
+        return Err(ErrorCode::OraclePriceFeedMismatch.into());
+    }
+    Ok(oracle_price)
+}
+
+fn parse_price_update(data: &[u8]) -> std::result::Result<OraclePrice, ErrorCode> {
+    let read = |offset: usize, len: usize| {
+        data.get(offset..offset + len)
+            .ok_or(ErrorCode::InvalidPriceOracle)
+    };
+    let read_u64 =
+        |offset: usize| read(offset, 8).map(|bytes| u64::from_le_bytes(bytes.try_into().unwrap()));
+
+    // Partially verified updates were only checked against a subset of the Wormhole guardians.
+    if read(0, 8)? != PRICE_UPDATE_V2_DISCRIMINATOR
+        || read(PRICE_UPDATE_VERIFICATION_LEVEL_OFFSET, 1)?[0]
+            != PRICE_UPDATE_VERIFICATION_LEVEL_FULL
+    {
+        return Err(ErrorCode::InvalidPriceOracle);
+    }
+    let price = read_u64(PRICE_UPDATE_PRICE_OFFSET)? as i64;
+    if price <= 0 {
+        return Err(ErrorCode::InvalidPriceOracle);
+    }
+
+    Ok(OraclePrice {
+        feed_id: read(PRICE_UPDATE_FEED_ID_OFFSET, 32)?.try_into().unwrap(),
+        price: price as u64,
+        confidence: read_u64(PRICE_UPDATE_CONFIDENCE_OFFSET)?,
+        exponent: i32::from_le_bytes(read(PRICE_UPDATE_EXPONENT_OFFSET, 4)?.try_into().unwrap()),
+        publish_timestamp: read_u64(PRICE_UPDATE_PUBLISH_TIME_OFFSET)? as i64,
+    })
+}
+
+// Converts the prices of token A and token B in whole tokens of a common quote to the Q64.64
+// sqrt of the price in token B base units per token A base unit.
+// Raw price = price_a / price_b * 10^(exponent_a - exponent_b + decimals_b - decimals_a)
+pub fn sqrt_price_from_oracle_prices(
+    oracle_price_a: &OraclePrice,
+    oracle_price_b: &OraclePrice,
+    decimals_a: u8,
+    decimals_b: u8,
+) -> std::result::Result<u128, ErrorCode> {
+    let exponent = oracle_price_a.exponent as i64 - oracle_price_b.exponent as i64
+        + decimals_b as i64
+        - decimals_a as i64;
+    let mut numerator = U256::from(oracle_price_a.price);
+    let mut denominator = U256::from(oracle_price_b.price);
+    let scale = pow10(exponent.unsigned_abs())?;
+    if exponent >= 0 {
+        numerator = numerator
+            .checked_mul(scale)
+            .ok_or(ErrorCode::SqrtPriceOutOfBounds)?;
+    } else {
+        denominator = denominator
+            .checked_mul(scale)
+            .ok_or(ErrorCode::SqrtPriceOutOfBounds)?;
+    }
+
+    let sqrt_price = sqrt_ratio_x64(numerator, denominator)?;
+    if !(MIN_SQRT_PRICE_X64..=MAX_SQRT_PRICE_X64).contains(&sqrt_price) {
+        return Err(ErrorCode::SqrtPriceOutOfBounds);
+    }
+    Ok(sqrt_price)
+}
+
+// Q64.64 sqrt of numerator / denominator. The numerator is scaled by up to 2^64 beyond the Q128
+// of the ratio before dividing, so small ratios keep their precision.
+fn sqrt_ratio_x64(numerator: U256, denominator: U256) -> std::result::Result<u128, ErrorCode> {
+    let headroom = numerator.leading_zeros();
+    if headroom < 128 {
+        return Err(ErrorCode::SqrtPriceOutOfBounds);
+    }
+    let extra_bits = ((headroom - 128) / 2).min(32) as usize;
+    let ratio_x128 = (numerator << (128 + 2 * extra_bits)) / denominator;
+    (ratio_x128.integer_sqrt() >> extra_bits)
+        .try_into_u128()
+        .map_err(|_| ErrorCode::SqrtPriceOutOfBounds)
+}
+
+fn pow10(exponent: u64) -> std::result::Result<U256, ErrorCode> {
+    if exponent > MAX_U256_POW10 as u64 {
+        return Err(ErrorCode::SqrtPriceOutOfBounds);
+    }
+    Ok(U256::from(10).pow(U256::from(exponent)))
+}
+
+#[cfg(test)]
+mod price_oracle_tests {
+    use super::*;
+    use crate::testing::FixtureAccount;
+    use crate::util::test_utils::{fixture_account_info, leak_account_infos};
+
+    fn price_update_data(
+        feed_id: [u8; 32],
+        price: i64,
+        confidence: u64,
+        exponent: i32,
+        verification_level: u8,
+    ) -> Vec<u8> {
+        let mut data = vec![0u8; 134];
+        data[..8].copy_from_slice(&PRICE_UPDATE_V2_DISCRIMINATOR);
+        data[PRICE_UPDATE_VERIFICATION_LEVEL_OFFSET] = verification_level;
+        data[PRICE_UPDATE_FEED_ID_OFFSET..PRICE_UPDATE_FEED_ID_OFFSET + 32]
+            .copy_from_slice(&feed_id);
+        data[PRICE_UPDATE_PRICE_OFFSET..PRICE_UPDATE_PRICE_OFFSET + 8]
+            .copy_from_slice(&price.to_le_bytes());
+        data[PRICE_UPDATE_CONFIDENCE_OFFSET..PRICE_UPDATE_CONFIDENCE_OFFSET + 8]
+            .copy_from_slice(&confidence.to_le_bytes());
+        data[PRICE_UPDATE_EXPONENT_OFFSET..PRICE_UPDATE_EXPONENT_OFFSET + 4]
+            .copy_from_slice(&exponent.to_le_bytes());
+        data[PRICE_UPDATE_PUBLISH_TIME_OFFSET..PRICE_UPDATE_PUBLISH_TIME_OFFSET + 8]
+            .copy_from_slice(&1_000i64.to_le_bytes());
+        data
+    }
+
+    fn oracle_price(price: u64, exponent: i32) -> OraclePrice {
+        OraclePrice {
+            feed_id: [0; 32],
+            price,
+            confidence: 0,
+            exponent,
+            publish_timestamp: 0,
+        }
+    }
+
+    #[test]
+    fn test_parse_price_update() {
+        let data = price_update_data([7; 32], 15_012_345_678, 1_000_000, -8, 1);
+        assert_eq!(
+            parse_price_update(&data).unwrap(),
+            OraclePrice {
+                feed_id: [7; 32],
+                price: 15_012_345_678,
+                confidence: 1_000_000,
+                exponent: -8,
+                publish_timestamp: 1_000,
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_price_update_rejects_invalid_accounts() {
+        let partially_verified = price_update_data([7; 32], 15_012_345_678, 0, -8, 0);
+        assert_eq!(
+            parse_price_update(&partially_verified).unwrap_err(),
+            ErrorCode::InvalidPriceOracle
+        );
+        let negative = price_update_data([7; 32], -1, 0, -8, 1);
+        assert_eq!(
+            parse_price_update(&negative).unwrap_err(),
+            ErrorCode::InvalidPriceOracle
+        );
+        let mut other_account = price_update_data([7; 32], 15_012_345_678, 0, -8, 1);
+        other_account[0] ^= 1;
+        assert_eq!(
+            parse_price_update(&other_account).unwrap_err(),
+            ErrorCode::InvalidPriceOracle
+        );
+        assert_eq!(
+            parse_price_update(&[0u8; 60]).unwrap_err(),
+            ErrorCode::InvalidPriceOracle
+        );
+    }
+
+    #[test]
+    fn test_read_mint_oracle_price_checks_feed_id() {
+        let whirlpools_config = Pubkey::new_unique();
+        let token_mint = Pubkey::new_unique();
+        let mut mint_price_feed = MintPriceFeed::default();
+        mint_price_feed.initialize(whirlpools_config, token_mint, [7; 32]);
+        let price_update = |feed_id: [u8; 32]| FixtureAccount {
+            address: Pubkey::new_unique(),
+            lamports: 1_000_000,
+            data: price_update_data(feed_id, 100, 0, 0, 1),
+            owner: pyth_receiver_program::ID,
+        };
+        let configured_update = price_update([7; 32]);
+        let configured_update_key = configured_update.address;
+        let other_update = price_update([8; 32]);
+        let other_update_key = other_update.address;
+        let remaining_accounts = leak_account_infos([
+            fixture_account_info(FixtureAccount::from_account(
+                Pubkey::new_unique(),
+                &mint_price_feed,
+            )),
+            fixture_account_info(configured_update),
+            fixture_account_info(other_update),
+        ]);
+
+        let oracle_price = read_mint_oracle_price(
+            &configured_update_key,
+            &whirlpools_config,
+            &token_mint,
+            remaining_accounts,
+        )
+        .unwrap();
+        assert_eq!(oracle_price.price, 100);
+        assert_eq!(
+            read_mint_oracle_price(
+                &other_update_key,
+                &whirlpools_config,
+                &token_mint,
+                remaining_accounts,
+            )
+            .unwrap_err(),
+            ErrorCode::OraclePriceFeedMismatch.into()
+        );
+        assert_eq!(
+            read_mint_oracle_price(
+                &configured_update_key,
+                &whirlpools_config,
+                &Pubkey::new_unique(),
+                remaining_accounts,
+            )
+            .unwrap_err(),
+            ErrorCode::OraclePriceFeedMismatch.into()
+        );
+        assert_eq!(
+            read_mint_oracle_price(
+                &Pubkey::new_unique(),
+                &whirlpools_config,
+                &token_mint,
+                remaining_accounts,
+            )
+            .unwrap_err(),
+            ErrorCode::InvalidPriceOracle.into()
+        );
+    }
+
+    #[test]
+    fn test_verify_oracle_price() {
+        let check = OraclePriceCheck {
+            max_age: 60,
+            max_confidence_rate: 100,
+            ..Default::default()
+        };
+        let price = OraclePrice {
+            feed_id: [0; 32],
+            price: 10_000,
+            confidence: 100,
+            exponent: 0,
+            publish_timestamp: 1_000,
+        };
+        assert!(price.verify(&check, 1_060).is_ok());
+        assert_eq!(
+            price.verify(&check, 1_061),
+            Err(ErrorCode::StaleOraclePrice)
+        );
+        let wide = OraclePrice {
+            confidence: 101,
+            ..price
+        };
+        assert_eq!(
+            wide.verify(&check, 1_000),
+            Err(ErrorCode::OracleConfidenceTooWide)
+        );
+    }
+
+    #[test]
+    fn test_sqrt_price_from_oracle_prices() {
+        // 1.0 at equal decimals, with different feed exponents
+        assert_eq!(
+            sqrt_price_from_oracle_prices(
+                &oracle_price(100_000_000, -8),
+                &oracle_price(1_000_000, -6),
+                6,
+                6
+            )
+            .unwrap(),
+            1 << 64
+        );
+        // 100 token B per token A, B having 2 decimals less: raw price 1
+        assert_eq!(
+            sqrt_price_from_oracle_prices(&oracle_price(100, 0), &oracle_price(1, 0), 8, 6)
+                .unwrap(),
+            1 << 64
+        );
+        // 4 token B per token A, and 1/4
+        assert_eq!(
+            sqrt_price_from_oracle_prices(&oracle_price(8, 0), &oracle_price(2, 0), 6, 6).unwrap(),
+            2 << 64
+        );
+        assert_eq!(
+            sqrt_price_from_oracle_prices(&oracle_price(2, 0), &oracle_price(8, 0), 6, 6).unwrap(),
+            1 << 63
+        );
+    }
+
+    #[test]
+    fn test_sqrt_price_from_oracle_prices_out_of_bounds() {
+        assert_eq!(
+            sqrt_price_from_oracle_prices(&oracle_price(1, 40), &oracle_price(1, 0), 0, 0)
+                .unwrap_err(),
+            ErrorCode::SqrtPriceOutOfBounds
+        );
+        assert_eq!(
+            sqrt_price_from_oracle_prices(&oracle_price(1, 0), &oracle_price(1, 40), 0, 0)
+                .unwrap_err(),
+            ErrorCode::SqrtPriceOutOfBounds
+        );
+        assert_eq!(
+            sqrt_price_from_oracle_prices(&oracle_price(1, 100), &oracle_price(1, 0), 0, 0)
+                .unwrap_err(),
+            ErrorCode::SqrtPriceOutOfBounds
+        );
+    }
+}
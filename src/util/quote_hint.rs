@@ -0,0 +1,32 @@
+use anchor_lang::prelude::*;
+use solana_program::program::set_return_data;
+
+use crate::errors::ErrorCode;
+
+/// Token amounts an instruction required or produced, set as return data when they fail the
+/// limits provided by the client. Clients simulating the transaction can retry with corrected
+/// limits instead of bisecting them.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, PartialEq)]
+#[cfg_attr(not(target_arch = "bpf"), derive(Debug))]
+pub struct QuoteHint {
+    pub amount_a: u64,
+    pub amount_b: u64,
+}
+
+/// Fail with `error`, setting the token amounts that failed the client's limits as return data.
+pub fn fail_with_quote_hint<T>(error: ErrorCode, amount_a: u64, amount_b: u64) -> Result<T> {
+    let hint = QuoteHint { amount_a, amount_b };
+    set_return_data(&hint.try_to_vec()?);
+    Err(error.into())
+}
+
+#[cfg(test)]
+mod quote_hint_tests {
+    use super::*;
+
+    #[test]
+    fn test_fail_with_quote_hint() {
+        let result: Result<()> = fail_with_quote_hint(ErrorCode::TokenMaxExceeded, 10, 20);
+        assert_eq!(result.unwrap_err(), ErrorCode::TokenMaxExceeded.into());
+    }
+}
@@ -0,0 +1,122 @@
+use anchor_lang::prelude::*;
+use solana_program::hash::hash;
+use solana_program::instruction::{AccountMeta, Instruction};
+use solana_program::program::invoke;
+
+use crate::errors::ErrorCode;
+use crate::state::Whirlpool;
+
+// Max number of remaining accounts (after the escrow program) forwarded to the escrow.
+pub const MAX_REWARD_ESCROW_ACCOUNTS: usize = 6;
+
+/// Request passed to the reward escrow program to release rewards into a reward vault.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, PartialEq)]
+#[cfg_attr(not(target_arch = "bpf"), derive(Debug))]
+pub struct ReleaseRewardsData {
+    pub whirlpool: Pubkey,
+    pub reward_index: u8,
+    pub reward_vault: Pubkey,
+    // Amount missing in the reward vault to pay out the collection
+    pub amount: u64,
+}
+
+impl ReleaseRewardsData {
+    /// Anchor style sighash of the `release_rewards` instruction expected on the escrow program.
+    pub fn discriminator() -> [u8; 8] {
+        let mut discriminator = [0u8; 8];
+        discriminator.copy_from_slice(&hash(b"global:release_rewards").to_bytes()[..8]);
+        discriminator
+    }
+
+    pub fn instruction_data(&self) -> Result<Vec<u8>> {
+        let mut data = Self::discriminator().to_vec();
+        self.serialize(&mut data)?;
+        Ok(data)
+    }
+}
+
+/// Ask the escrow program configured for a reward to release up to `amount` into the reward
+/// vault. The escrow decides how much is released, e.g. only the amount vested so far by a
+/// payment stream, and the caller reloads the vault to see the result.
+///
+/// The first remaining account must be the escrow program. Up to `MAX_REWARD_ESCROW_ACCOUNTS`
+/// further remaining accounts are forwarded to the escrow. The whirlpool is passed read-only and
+/// none of the accounts carry signer privileges, so the escrow can only deposit into the vault.
+///
+/// # Errors
+/// - `RewardEscrowMissing` - The escrow program account is missing or does not match the whirlpool
+/// - `TooManyRewardEscrowAccounts` - More accounts than allowed were provided for the escrow
+pub fn invoke_reward_escrow<'info>(
+    whirlpool: &Account<'info, Whirlpool>,
+    reward_index: usize,
+    reward_vault: &AccountInfo<'info>,
+    remaining_accounts: &[AccountInfo<'info>],
+    amount: u64,
+) -> Result<()> {
+    let (escrow_program, escrow_accounts) = match remaining_accounts.split_first() {
+        Some((escrow_program, escrow_accounts))
+            if escrow_program.key() == whirlpool.reward_escrow_programs[reward_index] =>
+        {
+            (escrow_program, escrow_accounts)
+        }
+        _ => return Err(ErrorCode::RewardEscrowMissing.into()),
+    };
+
+    if escrow_accounts.len() > MAX_REWARD_ESCROW_ACCOUNTS {
+        return Err(ErrorCode::TooManyRewardEscrowAccounts.into());
+    }
+
+    let data = ReleaseRewardsData {
+        whirlpool: whirlpool.key(),
+        reward_index: reward_index as u8,
+        reward_vault: reward_vault.key(),
+        amount,
+    };
+
+    let mut account_metas = vec![
+        AccountMeta::new_readonly(whirlpool.key(), false),
+        AccountMeta::new(reward_vault.key(), false),
+    ];
+    let mut account_infos = vec![whirlpool.to_account_info(), reward_vault.clone()];
+    for account in escrow_accounts {
+        account_metas.push(if account.is_writable {
+            AccountMeta::new(account.key(), false)
+        } else {
+            AccountMeta::new_readonly(account.key(), false)
+        });
+        account_infos.push(account.clone());
+    }
+    account_infos.push(escrow_program.clone());
+
+    invoke(
+        &Instruction {
+            program_id: escrow_program.key(),
+            accounts: account_metas,
+            data: data.instruction_data()?,
+        },
+        &account_infos,
+    )?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod reward_escrow_tests {
+    use super::ReleaseRewardsData;
+    use anchor_lang::prelude::*;
+
+    #[test]
+    fn test_instruction_data_prefixed_with_discriminator() {
+        let data = ReleaseRewardsData {
+            reward_index: 2,
+            amount: 500,
+            ..Default::default()
+        };
+        let ix_data = data.instruction_data().unwrap();
+        assert_eq!(ix_data[..8], ReleaseRewardsData::discriminator());
+        assert_eq!(
+            ReleaseRewardsData::deserialize(&mut &ix_data[8..]).unwrap(),
+            data
+        );
+    }
+}
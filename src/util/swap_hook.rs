@@ -0,0 +1,170 @@
+use anchor_lang::prelude::*;
+use solana_program::hash::hash;
+use solana_program::instruction::{AccountMeta, Instruction};
+use solana_program::program::invoke;
+
+use crate::errors::ErrorCode;
+use crate::state::Whirlpool;
+use crate::util::remaining_compute_units;
+
+// Max number of remaining accounts (after the hook program) forwarded to the hook.
+pub const MAX_AFTER_SWAP_HOOK_ACCOUNTS: usize = 4;
+
+// Max compute units the invocation of the after swap hook may consume, including the invocation
+// cost itself. Hooks observe swaps, so they are kept cheap enough not to starve the swap.
+pub const MAX_AFTER_SWAP_HOOK_COMPUTE_UNITS: u64 = 50_000;
+
+/// Summary of a completed swap passed to the after swap hook program.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, PartialEq)]
+#[cfg_attr(not(target_arch = "bpf"), derive(Debug))]
+pub struct AfterSwapHookData {
+    pub whirlpool: Pubkey,
+    pub token_authority: Pubkey,
+    pub a_to_b: bool,
+    pub amount_specified_is_input: bool,
+    pub amount_a: u64,
+    pub amount_b: u64,
+    pub sqrt_price: u128,
+    pub tick_current_index: i32,
+    pub timestamp: u64,
+}
+
+impl AfterSwapHookData {
+    /// Anchor style sighash of the `after_swap` instruction expected on the hook program.
+    pub fn discriminator() -> [u8; 8] {
+        let mut discriminator = [0u8; 8];
+        discriminator.copy_from_slice(&hash(b"global:after_swap").to_bytes()[..8]);
+        discriminator
+    }
+
+    pub fn instruction_data(&self) -> Result<Vec<u8>> {
+        let mut data = Self::discriminator().to_vec();
+        self.serialize(&mut data)?;
+        Ok(data)
+    }
+}
+
+/// Invoke the after swap hook configured on the whirlpool.
+///
+/// The first remaining account must be the hook program. Up to `MAX_AFTER_SWAP_HOOK_ACCOUNTS`
+/// further remaining accounts are forwarded to the hook. None of the forwarded accounts carry
+/// signer privileges and the whirlpool is passed read-only, so the hook can observe but never
+/// act on behalf of the swapper or the pool. The runtime rejects reentrant calls from the hook
+/// back into this program.
+///
+/// The whirlpool is written to its account before the invocation, so the hook reads the state
+/// after the swap. The runtime cannot bound the compute of an invocation, so the units consumed
+/// by the hook are measured and the swap fails if they exceed `MAX_AFTER_SWAP_HOOK_COMPUTE_UNITS`.
+///
+/// # Errors
+/// - `AfterSwapHookMissing` - The hook program account is missing or does not match the whirlpool
+/// - `TooManyAfterSwapHookAccounts` - More accounts than allowed were provided for the hook
+/// - `AfterSwapHookComputeExceeded` - The hook consumed more compute units than allowed
+pub fn invoke_after_swap_hook<'info>(
+    whirlpool: &Account<'info, Whirlpool>,
+    token_authority: &AccountInfo<'info>,
+    remaining_accounts: &[AccountInfo<'info>],
+    data: &AfterSwapHookData,
+) -> Result<()> {
+    let (hook_program, hook_accounts) = match remaining_accounts.split_first() {
+        Some((hook_program, hook_accounts)) if hook_program.key() == whirlpool.after_swap_hook => {
+            (hook_program, hook_accounts)
+        }
+        _ => return Err(ErrorCode::AfterSwapHookMissing.into()),
+    };
+
+    if hook_accounts.len() > MAX_AFTER_SWAP_HOOK_ACCOUNTS {
+        return Err(ErrorCode::TooManyAfterSwapHookAccounts.into());
+    }
+
+    let mut account_metas = vec![
+        AccountMeta::new_readonly(whirlpool.key(), false),
+        AccountMeta::new_readonly(token_authority.key(), false),
+    ];
+    let mut account_infos = vec![whirlpool.to_account_info(), token_authority.clone()];
+    for account in hook_accounts {
+        account_metas.push(if account.is_writable {
+            AccountMeta::new(account.key(), false)
+        } else {
+            AccountMeta::new_readonly(account.key(), false)
+        });
+        account_infos.push(account.clone());
+    }
+    account_infos.push(hook_program.clone());
+
+    whirlpool.exit(&crate::ID)?;
+
+    let compute_units_before = remaining_compute_units();
+    invoke(
+        &Instruction {
+            program_id: hook_program.key(),
+            accounts: account_metas,
+            data: data.instruction_data()?,
+        },
+        &account_infos,
+    )?;
+    if is_hook_compute_exceeded(
+        compute_units_before,
+        remaining_compute_units(),
+        MAX_AFTER_SWAP_HOOK_COMPUTE_UNITS,
+    ) {
+        return Err(ErrorCode::AfterSwapHookComputeExceeded.into());
+    }
+
+    Ok(())
+}
+
+/// True if a hook invocation consumed more than `max_compute_units`. Never true where the compute
+/// units cannot be measured.
+pub fn is_hook_compute_exceeded(
+    compute_units_before: Option<u64>,
+    compute_units_after: Option<u64>,
+    max_compute_units: u64,
+) -> bool {
+    matches!(
+        (compute_units_before, compute_units_after),
+        (Some(before), Some(after)) if before.saturating_sub(after) > max_compute_units
+    )
+}
+
+#[cfg(test)]
+mod swap_hook_tests {
+    use super::{is_hook_compute_exceeded, AfterSwapHookData, MAX_AFTER_SWAP_HOOK_COMPUTE_UNITS};
+    use anchor_lang::prelude::*;
+
+    #[test]
+    fn test_is_hook_compute_exceeded() {
+        let before = 200_000;
+        assert!(!is_hook_compute_exceeded(
+            Some(before),
+            Some(before - MAX_AFTER_SWAP_HOOK_COMPUTE_UNITS),
+            MAX_AFTER_SWAP_HOOK_COMPUTE_UNITS
+        ));
+        assert!(is_hook_compute_exceeded(
+            Some(before),
+            Some(before - MAX_AFTER_SWAP_HOOK_COMPUTE_UNITS - 1),
+            MAX_AFTER_SWAP_HOOK_COMPUTE_UNITS
+        ));
+        // Off-chain the compute units cannot be measured.
+        assert!(!is_hook_compute_exceeded(
+            None,
+            None,
+            MAX_AFTER_SWAP_HOOK_COMPUTE_UNITS
+        ));
+    }
+
+    #[test]
+    fn test_instruction_data_prefixed_with_discriminator() {
+        let data = AfterSwapHookData {
+            amount_a: 10,
+            amount_b: 20,
+            ..Default::default()
+        };
+        let ix_data = data.instruction_data().unwrap();
+        assert_eq!(ix_data[..8], AfterSwapHookData::discriminator());
+        assert_eq!(
+            AfterSwapHookData::deserialize(&mut &ix_data[8..]).unwrap(),
+            data
+        );
+    }
+}
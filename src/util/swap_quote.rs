@@ -0,0 +1,345 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    errors::ErrorCode,
+    manager::swap_manager::{swap_with_fee_rate, SwapParams},
+    state::Whirlpool,
+    util::{resolve_sqrt_price_limit, SwapTickSequence},
+};
+
+// Off-chain quotes for routers, computed with the swap manager the program runs on chain. Amounts
+// are converted in and out of token units with the rounding of the swap instruction, so a quote is
+// exactly what the instruction transfers against the same state.
+
+/// Token amounts a swap instruction transfers, and the price it moves the pool to.
+#[derive(Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(not(target_arch = "bpf"), derive(Debug))]
+pub struct SwapQuote {
+    pub amount_in: u64,
+    pub amount_out: u64,
+    pub next_sqrt_price: u128,
+    pub next_tick_index: i32,
+}
+
+/// A pool of a two-hop route and the direction it is swapped in.
+pub struct TwoHopLeg<'a, 'info> {
+    pub whirlpool: &'a Whirlpool,
+    pub swap_tick_sequence: &'a mut SwapTickSequence<'info>,
+    pub sqrt_price_limit: u128,
+    pub a_to_b: bool,
+}
+
+impl TwoHopLeg<'_, '_> {
+    fn input_mint(&self) -> Pubkey {
+        if self.a_to_b {
+            self.whirlpool.token_mint_a
+        } else {
+            self.whirlpool.token_mint_b
+        }
+    }
+
+    fn output_mint(&self) -> Pubkey {
+        if self.a_to_b {
+            self.whirlpool.token_mint_b
+        } else {
+            self.whirlpool.token_mint_a
+        }
+    }
+
+    fn quote(
+        &mut self,
+        amount: u64,
+        amount_specified_is_input: bool,
+        timestamp: u64,
+    ) -> Result<SwapQuote> {
+        quote_swap(
+            self.whirlpool,
+            self.swap_tick_sequence,
+            amount,
+            self.sqrt_price_limit,
+            amount_specified_is_input,
+            self.a_to_b,
+            timestamp,
+        )
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(not(target_arch = "bpf"), derive(Debug))]
+pub struct TwoHopQuote {
+    pub quote_one: SwapQuote,
+    pub quote_two: SwapQuote,
+}
+
+impl TwoHopQuote {
+    pub fn amount_in(&self) -> u64 {
+        self.quote_one.amount_in
+    }
+
+    pub fn amount_out(&self) -> u64 {
+        self.quote_two.amount_out
+    }
+}
+
+/// Quote a swap the way the swap instruction executes it, at the base fee rate of the pool.
+///
+/// # Parameters
+/// - `amount` - The token amount specified, input or output depending on
+///   `amount_specified_is_input`
+/// - `sqrt_price_limit` - The sqrt price limit of the swap, 0 for no limit
+pub fn quote_swap(
+    whirlpool: &Whirlpool,
+    swap_tick_sequence: &mut SwapTickSequence,
+    amount: u64,
+    sqrt_price_limit: u128,
+    amount_specified_is_input: bool,
+    a_to_b: bool,
+    timestamp: u64,
+) -> Result<SwapQuote> {
+    let amount = whirlpool.to_pool_amount(
+        amount,
+        a_to_b == amount_specified_is_input,
+        !amount_specified_is_input,
+    )?;
+    let sqrt_price_limit = resolve_sqrt_price_limit(sqrt_price_limit, a_to_b);
+    let sqrt_price_limit = whirlpool.clamp_sqrt_price_limit(sqrt_price_limit, a_to_b);
+
+    let swap_update = swap_with_fee_rate(
+        whirlpool,
+        whirlpool.effective_fee_rate(),
+        swap_tick_sequence,
+        SwapParams {
+            amount,
+            sqrt_price_limit,
+            amount_specified_is_input,
+            a_to_b,
+        },
+        timestamp,
+    )?;
+
+    // Deposits round up and withdrawals round down, as in the swap instruction.
+    let amount_a = whirlpool.to_token_amount(swap_update.amount_a, true, a_to_b)?;
+    let amount_b = whirlpool.to_token_amount(swap_update.amount_b, false, !a_to_b)?;
+    let (amount_in, amount_out) = if a_to_b {
+        (amount_a, amount_b)
+    } else {
+        (amount_b, amount_a)
+    };
+
+    Ok(SwapQuote {
+        amount_in,
+        amount_out,
+        next_sqrt_price: swap_update.next_sqrt_price,
+        next_tick_index: swap_update.next_tick_index,
+    })
+}
+
+/// Quote a route through two pools, executed as two swap instructions. The intermediate amount is
+/// the token amount the first swap pays out for exact input routes, and the token amount the
+/// second swap takes in for exact output routes, so neither leg is misquoted by a rounding unit.
+///
+/// # Errors
+/// - `InvalidIntermediaryMint` - The output mint of the first leg is not the input mint of the
+///   second leg
+/// - `IntermediateTokenAmountMismatch` - A leg stops at its price limit or at the end of its tick
+///   arrays, so the second leg would not take in exactly what the first leg pays out
+pub fn quote_two_hop(
+    mut leg_one: TwoHopLeg,
+    mut leg_two: TwoHopLeg,
+    amount: u64,
+    amount_specified_is_input: bool,
+    timestamp: u64,
+) -> Result<TwoHopQuote> {
+    if leg_one.output_mint() != leg_two.input_mint() {
+        return Err(ErrorCode::InvalidIntermediaryMint.into());
+    }
+
+    let (quote_one, quote_two) = if amount_specified_is_input {
+        let quote_one = leg_one.quote(amount, true, timestamp)?;
+        let quote_two = leg_two.quote(quote_one.amount_out, true, timestamp)?;
+        (quote_one, quote_two)
+    } else {
+        let quote_two = leg_two.quote(amount, false, timestamp)?;
+        let quote_one = leg_one.quote(quote_two.amount_in, false, timestamp)?;
+        (quote_one, quote_two)
+    };
+
+    if quote_one.amount_out != quote_two.amount_in {
+        return Err(ErrorCode::IntermediateTokenAmountMismatch.into());
+    }
+
+    Ok(TwoHopQuote {
+        quote_one,
+        quote_two,
+    })
+}
+
+#[cfg(test)]
+mod swap_quote_tests {
+    use super::*;
+    use crate::util::test_utils::swap_test_fixture::*;
+
+    const MINT_A: Pubkey = Pubkey::new_from_array([1; 32]);
+    const MINT_B: Pubkey = Pubkey::new_from_array([2; 32]);
+    const MINT_C: Pubkey = Pubkey::new_from_array([3; 32]);
+
+    fn pool(token_mint_a: Pubkey, token_mint_b: Pubkey, a_to_b: bool) -> SwapTestFixture {
+        let mut fixture = SwapTestFixture::new(SwapTestFixtureInfo {
+            tick_spacing: TS_128,
+            liquidity: 500_000_000,
+            curr_tick_index: 1_000,
+            start_tick_index: 0,
+            a_to_b,
+            fee_rate: 3_000,
+            ..Default::default()
+        });
+        fixture.whirlpool.token_mint_a = token_mint_a;
+        fixture.whirlpool.token_mint_b = token_mint_b;
+        fixture
+    }
+
+    fn leg<'a, 'info>(
+        fixture: &'a SwapTestFixture,
+        swap_tick_sequence: &'a mut SwapTickSequence<'info>,
+        a_to_b: bool,
+    ) -> TwoHopLeg<'a, 'info> {
+        TwoHopLeg {
+            whirlpool: &fixture.whirlpool,
+            swap_tick_sequence,
+            sqrt_price_limit: 0,
+            a_to_b,
+        }
+    }
+
+    fn tick_sequence(fixture: &SwapTestFixture) -> SwapTickSequence<'_> {
+        SwapTickSequence::new(fixture.tick_arrays[0].borrow_mut(), None, None)
+    }
+
+    #[test]
+    fn test_quote_two_hop_exact_input_chains_token_amounts() {
+        // A to B, then B to C.
+        let pool_one = pool(MINT_A, MINT_B, true);
+        let pool_two = pool(MINT_B, MINT_C, true);
+
+        let quote = {
+            let mut sequence_one = tick_sequence(&pool_one);
+            let mut sequence_two = tick_sequence(&pool_two);
+            quote_two_hop(
+                leg(&pool_one, &mut sequence_one, true),
+                leg(&pool_two, &mut sequence_two, true),
+                10_000,
+                true,
+                0,
+            )
+            .unwrap()
+        };
+
+        let quote_one = quote_swap(
+            &pool_one.whirlpool,
+            &mut tick_sequence(&pool_one),
+            10_000,
+            0,
+            true,
+            true,
+            0,
+        )
+        .unwrap();
+        let quote_two = quote_swap(
+            &pool_two.whirlpool,
+            &mut tick_sequence(&pool_two),
+            quote_one.amount_out,
+            0,
+            true,
+            true,
+            0,
+        )
+        .unwrap();
+        assert_eq!(quote.amount_in(), 10_000);
+        assert_eq!(quote.quote_one, quote_one);
+        assert_eq!(quote.quote_two, quote_two);
+        assert!(quote.amount_out() > 0);
+    }
+
+    #[test]
+    fn test_quote_two_hop_exact_output_on_rebasing_intermediate() {
+        // A to B with B rebasing in the first pool, then B to C.
+        let mut pool_one = pool(MINT_A, MINT_B, true);
+        pool_one.whirlpool.exchange_rate_oracle = Pubkey::new_unique();
+        pool_one.whirlpool.exchange_rate_x64 = (3 << 64) / 2;
+        pool_one.whirlpool.exchange_rate_on_a = false;
+        let pool_two = pool(MINT_B, MINT_C, true);
+
+        let quote = {
+            let mut sequence_one = tick_sequence(&pool_one);
+            let mut sequence_two = tick_sequence(&pool_two);
+            quote_two_hop(
+                leg(&pool_one, &mut sequence_one, true),
+                leg(&pool_two, &mut sequence_two, true),
+                10_000,
+                false,
+                0,
+            )
+            .unwrap()
+        };
+
+        // The first leg pays out exactly the token amount the second leg takes in.
+        let quote_one = quote_swap(
+            &pool_one.whirlpool,
+            &mut tick_sequence(&pool_one),
+            quote.quote_two.amount_in,
+            0,
+            false,
+            true,
+            0,
+        )
+        .unwrap();
+        assert_eq!(quote.amount_out(), 10_000);
+        assert_eq!(quote.quote_one, quote_one);
+        assert_eq!(quote_one.amount_out, quote.quote_two.amount_in);
+    }
+
+    #[test]
+    fn test_quote_two_hop_invalid_intermediary_mint() {
+        let pool_one = pool(MINT_A, MINT_B, true);
+        let pool_two = pool(MINT_C, MINT_A, true);
+
+        let mut sequence_one = tick_sequence(&pool_one);
+        let mut sequence_two = tick_sequence(&pool_two);
+        let result = quote_two_hop(
+            leg(&pool_one, &mut sequence_one, true),
+            leg(&pool_two, &mut sequence_two, true),
+            10_000,
+            true,
+            0,
+        );
+
+        assert_eq!(
+            result.unwrap_err(),
+            ErrorCode::InvalidIntermediaryMint.into()
+        );
+    }
+
+    #[test]
+    fn test_quote_two_hop_second_leg_price_limit() {
+        let pool_one = pool(MINT_A, MINT_B, true);
+        let pool_two = pool(MINT_B, MINT_C, true);
+
+        let mut sequence_one = tick_sequence(&pool_one);
+        let mut sequence_two = tick_sequence(&pool_two);
+        let mut leg_two = leg(&pool_two, &mut sequence_two, true);
+        // The second leg stops right below its current price.
+        leg_two.sqrt_price_limit = pool_two.whirlpool.sqrt_price - 1;
+        let result = quote_two_hop(
+            leg(&pool_one, &mut sequence_one, true),
+            leg_two,
+            10_000,
+            true,
+            0,
+        );
+
+        assert_eq!(
+            result.unwrap_err(),
+            ErrorCode::IntermediateTokenAmountMismatch.into()
+        );
+    }
+}
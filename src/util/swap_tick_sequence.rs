@@ -1,8 +1,60 @@
 use crate::errors::ErrorCode;
 use crate::state::*;
+use crate::util::verify_start_tick_index;
 use anchor_lang::prelude::*;
 use std::cell::RefMut;
 
+/// Verify that the first tick array passed to a swap is the tick array PDA expected from the
+/// current tick index and the swap direction. The following arrays are only checked once the swap
+/// moves into them, see `SwapTickSequence::get_next_initialized_tick_index`, so swaps staying
+/// within the first array may pass any accounts for them.
+///
+/// # Errors
+/// - `TickArrayAddressMismatch` - The tick array is not the expected PDA. The expected address is
+///   logged.
+pub fn verify_swap_tick_array_address(
+    whirlpool: &Whirlpool,
+    whirlpool_key: &Pubkey,
+    a_to_b: bool,
+    tick_array: &Pubkey,
+) -> Result<()> {
+    let tick_spacing = whirlpool.tick_spacing;
+    // b_to_a swaps can start on the last tick of the previous array, see `in_search_range`.
+    let shift = if a_to_b { 0 } else { tick_spacing as i32 };
+    let start_tick_index =
+        TickArray::start_tick_index_for(whirlpool.tick_current_index + shift, tick_spacing);
+
+    let expected = TickArray::find_address(whirlpool_key, start_tick_index);
+    if *tick_array != expected {
+        msg!(
+            "tick_array_0 should be {} (start tick index {})",
+            expected,
+            start_tick_index
+        );
+        return Err(ErrorCode::TickArrayAddressMismatch.into());
+    }
+    Ok(())
+}
+
+// Verify that the tick array the swap moves into at `array_index` follows the previous array.
+// Tick arrays are PDAs of their whirlpool and start tick index, so this verifies its address.
+fn verify_next_tick_array(
+    tick_array: &TickArray,
+    array_index: usize,
+    expected_start_tick_index: i32,
+) -> Result<()> {
+    if tick_array.start_tick_index != expected_start_tick_index {
+        msg!(
+            "tick_array_{} should be {} (start tick index {})",
+            array_index,
+            TickArray::find_address(&tick_array.whirlpool, expected_start_tick_index),
+            expected_start_tick_index
+        );
+        return Err(ErrorCode::TickArrayAddressMismatch.into());
+    }
+    Ok(())
+}
+
 pub struct SwapTickSequence<'info> {
     arrays: Vec<RefMut<'info, TickArray>>,
 }
@@ -24,6 +76,19 @@ impl<'info> SwapTickSequence<'info> {
         Self { arrays: vec }
     }
 
+    /// Verify the start tick index of every tick array in the sequence, so arrays misaligned to
+    /// the tick spacing are rejected before ticks are looked up by offset.
+    ///
+    /// # Errors
+    /// - `InvalidStartTick` - A tick array has a start tick index that is not valid for the tick
+    ///   spacing.
+    pub fn verify_start_tick_indexes(&self, tick_spacing: u16) -> Result<()> {
+        for array in self.arrays.iter() {
+            verify_start_tick_index(array.start_tick_index, tick_spacing)?;
+        }
+        Ok(())
+    }
+
     /// Get the Tick object at the given tick-index & tick-spacing
     ///
     /// # Parameters
@@ -102,6 +167,7 @@ impl<'info> SwapTickSequence<'info> {
     /// - `(usize, i32, &mut Tick)`: The array_index which the next initialized index was found, the next initialized tick-index & a mutable reference to that tick
     /// - `TickArraySequenceInvalidIndex` - The swap loop provided an invalid array index to query the next tick in.
     /// - `InvalidTickArraySequence`: - User provided tick-arrays are not in sequential order required to proceed in this trade direction.
+    /// - `TickArrayAddressMismatch` - The search moved into a tick array that does not follow the previous one.
 
     pub fn get_next_initialized_tick_index(
         &self,
@@ -156,8 +222,103 @@ impl<'info> SwapTickSequence<'info> {
                     };
 
                     array_index += 1;
+                    let expected_start_tick_index = if a_to_b {
+                        next_array.start_tick_index - ticks_in_array
+                    } else {
+                        next_array.start_tick_index + ticks_in_array
+                    };
+                    verify_next_tick_array(
+                        &self.arrays[array_index],
+                        array_index,
+                        expected_start_tick_index,
+                    )?;
                 }
             }
         }
     }
 }
+
+#[cfg(test)]
+mod verify_swap_tick_array_address_tests {
+    use super::*;
+    use std::cell::RefCell;
+
+    fn whirlpool(tick_current_index: i32) -> Whirlpool {
+        Whirlpool {
+            tick_spacing: 8,
+            tick_current_index,
+            ..Default::default()
+        }
+    }
+
+    fn tick_array(start_tick_index: i32) -> RefCell<TickArray> {
+        RefCell::new(TickArray {
+            start_tick_index,
+            ..TickArray::default()
+        })
+    }
+
+    #[test]
+    fn test_expected_address_a_to_b() {
+        let key = Pubkey::new_unique();
+        let tick_array = TickArray::find_address(&key, 0);
+        assert!(verify_swap_tick_array_address(&whirlpool(700), &key, true, &tick_array).is_ok());
+    }
+
+    #[test]
+    fn test_expected_address_b_to_a_shifted() {
+        let key = Pubkey::new_unique();
+        let tick_array = TickArray::find_address(&key, 704);
+        assert!(verify_swap_tick_array_address(&whirlpool(700), &key, false, &tick_array).is_ok());
+    }
+
+    #[test]
+    fn test_unexpected_address() {
+        let key = Pubkey::new_unique();
+        let tick_array = TickArray::find_address(&key, -704);
+        assert!(verify_swap_tick_array_address(&whirlpool(0), &key, true, &tick_array).is_err());
+    }
+
+    #[test]
+    fn test_next_tick_array_checked_when_moved_into() {
+        // The swap stays within the first array while it has an initialized tick to stop at.
+        let tick_array_0 = tick_array(0);
+        tick_array_0
+            .borrow_mut()
+            .update_tick(
+                0,
+                8,
+                &TickUpdate {
+                    initialized: true,
+                    ..Default::default()
+                },
+            )
+            .unwrap();
+        let unexpected = tick_array(704);
+        let sequence = SwapTickSequence::new(
+            tick_array_0.borrow_mut(),
+            Some(unexpected.borrow_mut()),
+            None,
+        );
+        assert_eq!(
+            sequence
+                .get_next_initialized_tick_index(700, 8, true, 0)
+                .unwrap(),
+            (0, 0)
+        );
+        drop(sequence);
+
+        let tick_array_0 = tick_array(0);
+        let sequence = SwapTickSequence::new(
+            tick_array_0.borrow_mut(),
+            Some(unexpected.borrow_mut()),
+            None,
+        );
+        assert_eq!(
+            sequence
+                .get_next_initialized_tick_index(700, 8, true, 0)
+                .unwrap_err(),
+            ErrorCode::TickArrayAddressMismatch.into()
+        );
+    }
+}
@@ -0,0 +1,276 @@
+use crate::math::sqrt_price_from_tick_index;
+use crate::testing::{FixtureAccount, WhirlpoolFixture};
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{
+    entrypoint::SUCCESS,
+    instruction::Instruction,
+    program_pack::Pack,
+    program_stubs::{set_syscall_stubs, SyscallStubs},
+};
+use anchor_lang::Bumps;
+use anchor_spl::token::spl_token::{self, instruction::TokenInstruction};
+use std::collections::BTreeSet;
+use std::convert::TryFrom;
+use std::sync::Once;
+
+pub const TEST_SLOT: u64 = 1_000;
+pub const TEST_UNIX_TIMESTAMP: i64 = 1_700_000_000;
+
+/// Syscall stubs for running instruction handlers off-chain. The clock is fixed at `TEST_SLOT`
+/// and `TEST_UNIX_TIMESTAMP`, rent is the default rent, and token program transfers move the
+/// amounts between the token accounts passed to the invocation. Other invocations succeed without
+/// effect.
+struct InstructionTestStubs;
+
+impl SyscallStubs for InstructionTestStubs {
+    fn sol_get_clock_sysvar(&self, var_addr: *mut u8) -> u64 {
+        let clock = Clock {
+            slot: TEST_SLOT,
+            unix_timestamp: TEST_UNIX_TIMESTAMP,
+            ..Clock::default()
+        };
+        unsafe { std::ptr::write(var_addr as *mut Clock, clock) };
+        SUCCESS
+    }
+
+    fn sol_get_rent_sysvar(&self, var_addr: *mut u8) -> u64 {
+        unsafe { std::ptr::write(var_addr as *mut Rent, Rent::default()) };
+        SUCCESS
+    }
+
+    fn sol_invoke_signed(
+        &self,
+        instruction: &Instruction,
+        account_infos: &[AccountInfo],
+        _signers_seeds: &[&[&[u8]]],
+    ) -> std::result::Result<(), ProgramError> {
+        if instruction.program_id != spl_token::ID {
+            return Ok(());
+        }
+        let (source, destination, amount) = match TokenInstruction::unpack(&instruction.data)? {
+            TokenInstruction::Transfer { amount } => (0, 1, amount),
+            TokenInstruction::TransferChecked { amount, .. } => (0, 2, amount),
+            _ => return Ok(()),
+        };
+        let find_account = |index: usize| {
+            account_infos
+                .iter()
+                .find(|account_info| *account_info.key == instruction.accounts[index].pubkey)
+                .ok_or(ProgramError::NotEnoughAccountKeys)
+        };
+        move_token_amount(find_account(source)?, -i128::from(amount))?;
+        move_token_amount(find_account(destination)?, i128::from(amount))
+    }
+}
+
+fn move_token_amount(
+    account_info: &AccountInfo,
+    delta: i128,
+) -> std::result::Result<(), ProgramError> {
+    let mut data = account_info.try_borrow_mut_data()?;
+    let mut token_account = spl_token::state::Account::unpack(&data)?;
+    token_account.amount = u64::try_from(i128::from(token_account.amount) + delta)
+        .map_err(|_| ProgramError::InsufficientFunds)?;
+    spl_token::state::Account::pack(token_account, &mut data)
+}
+
+/// Install the instruction test syscall stubs. Safe to call from every test.
+pub fn install_instruction_test_stubs() {
+    static INSTALL: Once = Once::new();
+    INSTALL.call_once(|| {
+        set_syscall_stubs(Box::new(InstructionTestStubs));
+    });
+}
+
+fn leak_account_info(
+    key: Pubkey,
+    is_signer: bool,
+    is_writable: bool,
+    lamports: u64,
+    data: Vec<u8>,
+    owner: Pubkey,
+    executable: bool,
+) -> AccountInfo<'static> {
+    AccountInfo::new(
+        Box::leak(Box::new(key)),
+        is_signer,
+        is_writable,
+        Box::leak(Box::new(lamports)),
+        Box::leak(data.into_boxed_slice()),
+        Box::leak(Box::new(owner)),
+        executable,
+        0,
+    )
+}
+
+/// Writable account info holding a fixture account. The account lives until the test ends.
+pub fn fixture_account_info(fixture: FixtureAccount) -> AccountInfo<'static> {
+    leak_account_info(
+        fixture.address,
+        false,
+        true,
+        fixture.lamports,
+        fixture.data,
+        fixture.owner,
+        false,
+    )
+}
+
+/// Writable account info holding the first `data_len` bytes of a fixture account, e.g. an account
+/// with a legacy layout, that can be reallocated up to the size of the fixture.
+pub fn reallocatable_account_info(
+    fixture: FixtureAccount,
+    data_len: usize,
+) -> AccountInfo<'static> {
+    // `AccountInfo::realloc` reads the original data length from the 4 bytes before the key and
+    // writes the data length to the 8 bytes before the data, as the runtime serializes them.
+    let key_words: &'static mut [u64] = Box::leak(vec![0u64; 5].into_boxed_slice());
+    let key_bytes: &mut [u8] = bytemuck::cast_slice_mut(&mut *key_words);
+    key_bytes[4..8].copy_from_slice(&(data_len as u32).to_le_bytes());
+    key_bytes[8..].copy_from_slice(fixture.address.as_ref());
+    let key_words: &'static [u64] = key_words;
+    let key: &'static Pubkey = bytemuck::from_bytes(&bytemuck::cast_slice(key_words)[8..]);
+
+    let data_words = Box::leak(vec![0u64; (fixture.data.len() + 15) / 8].into_boxed_slice());
+    let data_bytes: &'static mut [u8] = bytemuck::cast_slice_mut(data_words);
+    data_bytes[..8].copy_from_slice(&(data_len as u64).to_le_bytes());
+    data_bytes[8..8 + data_len].copy_from_slice(&fixture.data[..data_len]);
+
+    AccountInfo::new(
+        key,
+        false,
+        true,
+        Box::leak(Box::new(fixture.lamports)),
+        &mut data_bytes[8..8 + data_len],
+        Box::leak(Box::new(fixture.owner)),
+        false,
+        0,
+    )
+}
+
+/// Writable SPL token account holding `amount` of `mint`.
+pub fn token_account_info(
+    address: Pubkey,
+    mint: Pubkey,
+    owner: Pubkey,
+    amount: u64,
+) -> AccountInfo<'static> {
+    let mut data = vec![0; spl_token::state::Account::LEN];
+    spl_token::state::Account::pack(
+        spl_token::state::Account {
+            mint,
+            owner,
+            amount,
+            state: spl_token::state::AccountState::Initialized,
+            ..Default::default()
+        },
+        &mut data,
+    )
+    .unwrap();
+    leak_account_info(
+        address,
+        false,
+        true,
+        1_000_000,
+        data,
+        spl_token::ID,
+        false,
+    )
+}
+
+pub fn signer_account_info(address: Pubkey) -> AccountInfo<'static> {
+    leak_account_info(
+        address,
+        true,
+        true,
+        1_000_000_000,
+        vec![],
+        System::id(),
+        false,
+    )
+}
+
+pub fn program_account_info(program_id: Pubkey) -> AccountInfo<'static> {
+    leak_account_info(
+        program_id,
+        false,
+        false,
+        1_000_000,
+        vec![],
+        Pubkey::default(),
+        true,
+    )
+}
+
+/// Amount held by a token account info.
+pub fn token_amount(account_info: &AccountInfo) -> u64 {
+    spl_token::state::Account::unpack(&account_info.try_borrow_data().unwrap())
+        .unwrap()
+        .amount
+}
+
+/// Deserialize the program account held by an account info.
+pub fn read_account<T: AccountDeserialize>(account_info: &AccountInfo) -> T {
+    T::try_deserialize(&mut account_info.try_borrow_data().unwrap().as_ref()).unwrap()
+}
+
+/// Whirlpool fixture for instruction tests, priced at `tick_index`, without a protocol fee.
+pub fn instruction_test_whirlpool(
+    tick_spacing: u16,
+    fee_rate: u32,
+    tick_index: i32,
+) -> WhirlpoolFixture {
+    WhirlpoolFixture {
+        whirlpools_config: Pubkey::new_unique(),
+        token_mint_a: Pubkey::new_from_array([1; 32]),
+        token_vault_a: Pubkey::new_unique(),
+        token_mint_b: Pubkey::new_from_array([2; 32]),
+        token_vault_b: Pubkey::new_unique(),
+        tick_spacing,
+        fee_rate,
+        protocol_fee_rate: 0,
+        sqrt_price: sqrt_price_from_tick_index(tick_index),
+    }
+}
+
+/// Account infos living until the test ends, as instruction accounts borrow them.
+pub fn leak_account_infos<const N: usize>(
+    account_infos: [AccountInfo<'static>; N],
+) -> &'static [AccountInfo<'static>] {
+    Box::leak(Box::new(account_infos))
+}
+
+/// Validate the accounts of an instruction from account infos in the order of its accounts
+/// struct, with the instruction test syscall stubs installed.
+pub fn instruction_accounts<T>(account_infos: &'static [AccountInfo<'static>]) -> T
+where
+    T: Accounts<'static, T::Bumps> + Bumps,
+    T::Bumps: Default,
+{
+    install_instruction_test_stubs();
+    T::try_accounts(
+        &crate::ID,
+        &mut &account_infos[..],
+        &[],
+        &mut T::Bumps::default(),
+        &mut BTreeSet::new(),
+    )
+    .unwrap()
+}
+
+/// Context to call an instruction handler with.
+pub fn instruction_context<'a, T>(
+    accounts: &'a mut T,
+    remaining_accounts: &'static [AccountInfo<'static>],
+) -> Context<'static, 'a, 'static, 'static, T>
+where
+    T: Accounts<'static, T::Bumps> + Bumps,
+    T::Bumps: Default,
+{
+    Context::new(
+        &crate::ID,
+        accounts,
+        remaining_accounts,
+        T::Bumps::default(),
+    )
+}
@@ -67,6 +67,7 @@ impl LiquidityTestFixture {
         let whirlpool = WhirlpoolBuilder::new()
             .tick_current_index(curr_index)
             .liquidity(info.whirlpool_liquidity)
+            .total_liquidity(info.whirlpool_liquidity.max(info.position_liquidity))
             .reward_infos(info.reward_infos)
             .fee_growth_global_a(info.fee_growth_global_a)
             .fee_growth_global_b(info.fee_growth_global_b)
@@ -160,6 +161,7 @@ impl LiquidityTestFixture {
         assert!(reward_last_updated_timestamp >= self.whirlpool.reward_last_updated_timestamp);
         self.whirlpool.reward_last_updated_timestamp = reward_last_updated_timestamp;
         self.whirlpool.liquidity = update.whirlpool_liquidity;
+        self.whirlpool.total_liquidity = update.whirlpool_total_liquidity;
         self.whirlpool.reward_infos = update.reward_infos;
         self.tick_lower.update(&update.tick_lower_update);
         self.tick_upper.update(&update.tick_upper_update);
@@ -1,5 +1,7 @@
+pub mod instruction_test_fixture;
 pub mod liquidity_test_fixture;
 pub mod swap_test_fixture;
 
+pub use instruction_test_fixture::*;
 pub use liquidity_test_fixture::*;
 pub use swap_test_fixture::*;
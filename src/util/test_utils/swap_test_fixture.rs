@@ -1,4 +1,3 @@
-use crate::errors::ErrorCode;
 use crate::manager::swap_manager::*;
 use crate::math::tick_math::*;
 use crate::state::{
@@ -49,7 +48,7 @@ pub struct SwapTestFixtureInfo<'info> {
     pub array_1_ticks: &'info Vec<TestTickInfo>,
     pub array_2_ticks: Option<&'info Vec<TestTickInfo>>,
     pub array_3_ticks: Option<&'info Vec<TestTickInfo>>,
-    pub fee_rate: u16,
+    pub fee_rate: u32,
     pub protocol_fee_rate: u16,
 }
 
@@ -165,6 +164,7 @@ impl SwapTestFixture {
                 start_tick_index: array_start_tick_index,
                 ticks: [Tick::default(); TICK_ARRAY_SIZE_USIZE],
                 whirlpool: Pubkey::default(),
+                ..Default::default()
             };
 
             if array.is_none() {
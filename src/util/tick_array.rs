@@ -0,0 +1,125 @@
+use crate::errors::ErrorCode;
+use crate::state::*;
+use crate::util::verify_not_upstream_account;
+use anchor_lang::prelude::*;
+
+/// Nearest valid start tick index to the given tick index for the tick spacing. Tick indices out
+/// of the tick range map to the start tick index of the first or last tick array.
+pub fn nearest_valid_start_tick_index(tick_index: i32, tick_spacing: u16) -> i32 {
+    let ticks_in_array = TICK_ARRAY_SIZE * tick_spacing as i32;
+    let tick_index = Tick::bound_tick_index(tick_index);
+    let start_tick_index = TickArray::start_tick_index_for(tick_index, tick_spacing);
+    if tick_index - start_tick_index >= ticks_in_array / 2
+        && Tick::check_is_valid_start_tick(start_tick_index + ticks_in_array, tick_spacing)
+    {
+        start_tick_index + ticks_in_array
+    } else {
+        start_tick_index
+    }
+}
+
+/// Verify that a tick array start tick index is valid for the tick spacing, i.e. a multiple of
+/// the ticks in an array and within the tick range.
+///
+/// # Errors
+/// - `InvalidStartTick` - The start tick index is not valid. The nearest valid start tick index
+///   is logged.
+pub fn verify_start_tick_index(start_tick_index: i32, tick_spacing: u16) -> Result<()> {
+    if tick_spacing == 0 {
+        return Err(ErrorCode::InvalidTickSpacing.into());
+    }
+    if Tick::check_is_valid_start_tick(start_tick_index, tick_spacing) {
+        return Ok(());
+    }
+    msg!(
+        "start tick index {} is not valid for tick spacing {}, nearest valid start tick index is {}",
+        start_tick_index,
+        tick_spacing,
+        nearest_valid_start_tick_index(start_tick_index, tick_spacing)
+    );
+    Err(ErrorCode::InvalidStartTick.into())
+}
+
+/// Link a tick array into the paging list of its pool, between the neighbouring initialized tick
+/// arrays passed as up to two writable accounts.
+///
+/// # Errors
+/// - `InvalidTickArrayLink` - A neighbour is not adjacent to the tick array in the paging list,
+///   belongs to another pool or still has the legacy tick array layout.
+/// - `ForeignProgramAccount` - A neighbour is owned by the upstream Whirlpool program.
+pub fn link_tick_array_neighbours<'info>(
+    tick_array: &mut TickArray,
+    neighbours: &[&'info AccountInfo<'info>],
+) -> Result<()> {
+    let start_tick_index = tick_array.start_tick_index;
+    let (mut linked_prev, mut linked_next) = (false, false);
+    for account_info in neighbours {
+        verify_not_upstream_account(account_info)?;
+        if account_info.data_len() < TickArray::LEN {
+            return Err(ErrorCode::InvalidTickArrayLink.into());
+        }
+        let neighbour_loader = AccountLoader::<TickArray>::try_from(account_info)?;
+        let mut neighbour = neighbour_loader.load_mut()?;
+        if neighbour.start_tick_index < start_tick_index && !linked_prev {
+            tick_array.link_after(&mut neighbour)?;
+            linked_prev = true;
+        } else if neighbour.start_tick_index > start_tick_index && !linked_next {
+            tick_array.link_before(&mut neighbour)?;
+            linked_next = true;
+        } else {
+            return Err(ErrorCode::InvalidTickArrayLink.into());
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod verify_start_tick_index_tests {
+    use super::*;
+
+    const TS_64: u16 = 64;
+    const TICKS_IN_ARRAY: i32 = TICK_ARRAY_SIZE * TS_64 as i32;
+
+    #[test]
+    fn test_valid_start_tick_indexes() {
+        assert!(verify_start_tick_index(0, TS_64).is_ok());
+        assert!(verify_start_tick_index(TICKS_IN_ARRAY, TS_64).is_ok());
+        assert!(verify_start_tick_index(-TICKS_IN_ARRAY, TS_64).is_ok());
+        let min_start = TickArray::start_tick_index_for(MIN_TICK_INDEX, TS_64);
+        assert!(verify_start_tick_index(min_start, TS_64).is_ok());
+    }
+
+    #[test]
+    fn test_invalid_start_tick_indexes() {
+        assert_eq!(
+            verify_start_tick_index(64, TS_64).unwrap_err(),
+            ErrorCode::InvalidStartTick.into()
+        );
+        assert_eq!(
+            verify_start_tick_index(MAX_TICK_INDEX + TICKS_IN_ARRAY, TS_64).unwrap_err(),
+            ErrorCode::InvalidStartTick.into()
+        );
+        assert_eq!(
+            verify_start_tick_index(0, 0).unwrap_err(),
+            ErrorCode::InvalidTickSpacing.into()
+        );
+    }
+
+    #[test]
+    fn test_nearest_valid_start_tick_index() {
+        assert_eq!(nearest_valid_start_tick_index(64, TS_64), 0);
+        assert_eq!(
+            nearest_valid_start_tick_index(TICKS_IN_ARRAY - 64, TS_64),
+            TICKS_IN_ARRAY
+        );
+        assert_eq!(nearest_valid_start_tick_index(-64, TS_64), 0);
+        assert_eq!(
+            nearest_valid_start_tick_index(i32::MIN, TS_64),
+            TickArray::start_tick_index_for(MIN_TICK_INDEX, TS_64)
+        );
+        assert_eq!(
+            nearest_valid_start_tick_index(i32::MAX, TS_64),
+            TickArray::start_tick_index_for(MAX_TICK_INDEX, TS_64)
+        );
+    }
+}
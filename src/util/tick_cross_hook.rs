@@ -0,0 +1,201 @@
+use anchor_lang::prelude::*;
+use solana_program::hash::hash;
+use solana_program::instruction::{AccountMeta, Instruction};
+use solana_program::program::invoke;
+
+use crate::errors::ErrorCode;
+use crate::state::Whirlpool;
+use crate::util::{is_hook_compute_exceeded, remaining_compute_units};
+
+// Max number of remaining accounts (after the hook program) forwarded to the tick cross hook.
+pub const MAX_TICK_CROSS_HOOK_ACCOUNTS: usize = 4;
+// Max number of initialized ticks a swap can cross in a whirlpool with a tick cross hook.
+pub const MAX_TICK_CROSS_NOTIFICATIONS: usize = 8;
+// Max compute units all tick cross notifications of a swap may consume together, including the
+// invocation costs.
+pub const MAX_TICK_CROSS_HOOK_COMPUTE_UNITS: u64 = 50_000;
+
+/// Initialized tick crossed by a swap, passed to the tick cross hook program.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, PartialEq)]
+#[cfg_attr(not(target_arch = "bpf"), derive(Debug))]
+pub struct TickCrossHookData {
+    pub whirlpool: Pubkey,
+    pub tick_index: i32,
+    // True if the price moved down through the tick, i.e. the swap was A to B
+    pub a_to_b: bool,
+    pub timestamp: u64,
+}
+
+impl TickCrossHookData {
+    /// Anchor style sighash of the `on_tick_cross` instruction expected on the hook program.
+    pub fn discriminator() -> [u8; 8] {
+        let mut discriminator = [0u8; 8];
+        discriminator.copy_from_slice(&hash(b"global:on_tick_cross").to_bytes()[..8]);
+        discriminator
+    }
+
+    pub fn instruction_data(&self) -> Result<Vec<u8>> {
+        let mut data = Self::discriminator().to_vec();
+        self.serialize(&mut data)?;
+        Ok(data)
+    }
+}
+
+/// Split the swap hook accounts into the tick cross hook accounts and the accounts following
+/// them. The tick cross hook program comes first and its accounts run up to the after swap hook
+/// program, or to the end if the whirlpool has no after swap hook.
+pub fn split_tick_cross_hook_accounts<'a, 'info>(
+    whirlpool: &Whirlpool,
+    remaining_accounts: &'a [AccountInfo<'info>],
+) -> (&'a [AccountInfo<'info>], &'a [AccountInfo<'info>]) {
+    if !whirlpool.has_tick_cross_hook() {
+        return (&[], remaining_accounts);
+    }
+    let end = if whirlpool.has_after_swap_hook() {
+        remaining_accounts
+            .iter()
+            .skip(1)
+            .position(|account| account.key() == whirlpool.after_swap_hook)
+            .map_or(remaining_accounts.len(), |index| index + 1)
+    } else {
+        remaining_accounts.len()
+    };
+    remaining_accounts.split_at(end)
+}
+
+/// Notify the tick cross hook configured on the whirlpool of every initialized tick crossed by a
+/// swap, in crossing order. Swaps crossing more than `MAX_TICK_CROSS_NOTIFICATIONS` initialized
+/// ticks fail rather than skip notifications, and have to be split by the caller.
+///
+/// The first hook account must be the hook program. Up to `MAX_TICK_CROSS_HOOK_ACCOUNTS` further
+/// accounts are forwarded to the hook. As for the after swap hook, none of the forwarded accounts
+/// carry signer privileges and the whirlpool is passed read-only.
+///
+/// The whirlpool is written to its account before the first notification, so the hook reads the
+/// state after the swap. The units consumed by all notifications are measured together and the
+/// swap fails if they exceed `MAX_TICK_CROSS_HOOK_COMPUTE_UNITS`.
+///
+/// # Errors
+/// - `TickCrossHookMissing` - The hook program account is missing or does not match the whirlpool
+/// - `TooManyTickCrossHookAccounts` - More accounts than allowed were provided for the hook
+/// - `TooManyTickCrossings` - The swap crossed more initialized ticks than can be notified
+/// - `TickCrossHookComputeExceeded` - The notifications consumed more compute units than allowed
+pub fn invoke_tick_cross_hook<'info>(
+    whirlpool: &Account<'info, Whirlpool>,
+    hook_accounts: &[AccountInfo<'info>],
+    crossed_tick_indexes: &[i32],
+    a_to_b: bool,
+    timestamp: u64,
+) -> Result<()> {
+    let (hook_program, forwarded_accounts) = match hook_accounts.split_first() {
+        Some((hook_program, forwarded_accounts))
+            if hook_program.key() == whirlpool.tick_cross_hook =>
+        {
+            (hook_program, forwarded_accounts)
+        }
+        _ => return Err(ErrorCode::TickCrossHookMissing.into()),
+    };
+
+    if forwarded_accounts.len() > MAX_TICK_CROSS_HOOK_ACCOUNTS {
+        return Err(ErrorCode::TooManyTickCrossHookAccounts.into());
+    }
+    if crossed_tick_indexes.len() > MAX_TICK_CROSS_NOTIFICATIONS {
+        return Err(ErrorCode::TooManyTickCrossings.into());
+    }
+
+    let mut account_metas = vec![AccountMeta::new_readonly(whirlpool.key(), false)];
+    let mut account_infos = vec![whirlpool.to_account_info()];
+    for account in forwarded_accounts {
+        account_metas.push(if account.is_writable {
+            AccountMeta::new(account.key(), false)
+        } else {
+            AccountMeta::new_readonly(account.key(), false)
+        });
+        account_infos.push(account.clone());
+    }
+    account_infos.push(hook_program.clone());
+
+    whirlpool.exit(&crate::ID)?;
+
+    let compute_units_before = remaining_compute_units();
+    for tick_index in crossed_tick_indexes {
+        let data = TickCrossHookData {
+            whirlpool: whirlpool.key(),
+            tick_index: *tick_index,
+            a_to_b,
+            timestamp,
+        };
+        invoke(
+            &Instruction {
+                program_id: hook_program.key(),
+                accounts: account_metas.clone(),
+                data: data.instruction_data()?,
+            },
+            &account_infos,
+        )?;
+    }
+    if is_hook_compute_exceeded(
+        compute_units_before,
+        remaining_compute_units(),
+        MAX_TICK_CROSS_HOOK_COMPUTE_UNITS,
+    ) {
+        return Err(ErrorCode::TickCrossHookComputeExceeded.into());
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tick_cross_hook_tests {
+    use super::*;
+
+    #[test]
+    fn test_instruction_data_prefixed_with_discriminator() {
+        let data = TickCrossHookData {
+            tick_index: -128,
+            a_to_b: true,
+            ..Default::default()
+        };
+        let ix_data = data.instruction_data().unwrap();
+        assert_eq!(ix_data[..8], TickCrossHookData::discriminator());
+        assert_eq!(
+            TickCrossHookData::deserialize(&mut &ix_data[8..]).unwrap(),
+            data
+        );
+    }
+
+    #[test]
+    fn test_split_tick_cross_hook_accounts() {
+        let keys = [
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+        ];
+        let mut lamports = [0u64; 4];
+        let mut data = [[0u8; 0]; 4];
+        let accounts: Vec<AccountInfo> = keys
+            .iter()
+            .zip(lamports.iter_mut())
+            .zip(data.iter_mut())
+            .map(|((key, lamports), data)| {
+                AccountInfo::new(key, false, false, lamports, data, key, false, 0)
+            })
+            .collect();
+
+        let mut whirlpool = Whirlpool::default();
+        let (hook_accounts, rest) = split_tick_cross_hook_accounts(&whirlpool, &accounts);
+        assert!(hook_accounts.is_empty());
+        assert_eq!(rest.len(), 4);
+
+        whirlpool.tick_cross_hook = keys[0];
+        let (hook_accounts, rest) = split_tick_cross_hook_accounts(&whirlpool, &accounts);
+        assert_eq!(hook_accounts.len(), 4);
+        assert!(rest.is_empty());
+
+        whirlpool.after_swap_hook = keys[2];
+        let (hook_accounts, rest) = split_tick_cross_hook_accounts(&whirlpool, &accounts);
+        assert_eq!(hook_accounts.len(), 2);
+        assert_eq!(rest[0].key(), keys[2]);
+    }
+}
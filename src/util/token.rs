@@ -1,10 +1,15 @@
-use crate::state::Whirlpool;
+use crate::errors::ErrorCode;
+use crate::state::{Whirlpool, WhirlpoolsConfig};
+use crate::util::verify_transfer_not_frozen;
 use anchor_lang::prelude::*;
+use anchor_lang::system_program::{self, CreateAccount};
+use anchor_spl::associated_token::{self, AssociatedToken, Create};
 use anchor_spl::token::spl_token::instruction::{
     burn_checked, close_account, mint_to, set_authority, AuthorityType,
 };
-use anchor_spl::token::{self, Mint, Token, TokenAccount, Transfer};
-use mpl_token_metadata::instruction::create_metadata_accounts_v2;
+use anchor_spl::token::{self, InitializeMint2, Mint, Token, TokenAccount, Transfer};
+use mpl_token_metadata::instructions::{CreateMetadataAccountV3Builder, VerifyCollectionBuilder};
+use mpl_token_metadata::types::{Collection, DataV2};
 use solana_program::program::invoke_signed;
 
 pub fn transfer_from_owner_to_vault<'info>(
@@ -14,6 +19,8 @@ pub fn transfer_from_owner_to_vault<'info>(
     token_program: &Program<'info, Token>,
     amount: u64,
 ) -> Result<()> {
+    verify_transfer_not_frozen(token_vault, token_owner_account)?;
+
     token::transfer(
         CpiContext::new(
             token_program.to_account_info(),
@@ -27,6 +34,25 @@ pub fn transfer_from_owner_to_vault<'info>(
     )
 }
 
+pub fn transfer_position_token<'info>(
+    position_authority: &Signer<'info>,
+    position_token_account: &Account<'info, TokenAccount>,
+    destination_token_account: &Account<'info, TokenAccount>,
+    token_program: &Program<'info, Token>,
+) -> Result<()> {
+    token::transfer(
+        CpiContext::new(
+            token_program.to_account_info(),
+            Transfer {
+                from: position_token_account.to_account_info(),
+                to: destination_token_account.to_account_info(),
+                authority: position_authority.to_account_info(),
+            },
+        ),
+        1,
+    )
+}
+
 pub fn transfer_from_vault_to_owner<'info>(
     whirlpool: &Account<'info, Whirlpool>,
     token_vault: &Account<'info, TokenAccount>,
@@ -34,6 +60,8 @@ pub fn transfer_from_vault_to_owner<'info>(
     token_program: &Program<'info, Token>,
     amount: u64,
 ) -> Result<()> {
+    verify_transfer_not_frozen(token_vault, token_owner_account)?;
+
     token::transfer(
         CpiContext::new_with_signer(
             token_program.to_account_info(),
@@ -100,6 +128,81 @@ pub fn mint_position_token_and_remove_authority<'info>(
     position_mint: &Account<'info, Mint>,
     position_token_account: &Account<'info, TokenAccount>,
     token_program: &Program<'info, Token>,
+) -> Result<()> {
+    mint_created_position_token_and_remove_authority(
+        whirlpool,
+        &position_mint.to_account_info(),
+        &position_token_account.to_account_info(),
+        token_program,
+    )
+}
+
+/// Programs creating a position mint and the associated token account of its owner.
+pub struct PositionTokenPrograms<'a, 'info> {
+    pub token_program: &'a Program<'info, Token>,
+    pub system_program: &'a Program<'info, System>,
+    pub associated_token_program: &'a Program<'info, AssociatedToken>,
+}
+
+/// Create a position mint with the whirlpool as its authority and the associated token account
+/// of the owner for it, for handlers that open positions without account constraints. The position
+/// mint must sign. The token account is created idempotently, the owner may be off the curve.
+pub fn create_position_mint_and_token_account<'info>(
+    funder: &AccountInfo<'info>,
+    owner: &AccountInfo<'info>,
+    whirlpool: &Account<'info, Whirlpool>,
+    position_mint: &AccountInfo<'info>,
+    position_token_account: &AccountInfo<'info>,
+    programs: PositionTokenPrograms<'_, 'info>,
+) -> Result<()> {
+    let PositionTokenPrograms {
+        token_program,
+        system_program,
+        associated_token_program,
+    } = programs;
+    system_program::create_account(
+        CpiContext::new(
+            system_program.to_account_info(),
+            CreateAccount {
+                from: funder.clone(),
+                to: position_mint.clone(),
+            },
+        ),
+        Rent::get()?.minimum_balance(Mint::LEN),
+        Mint::LEN as u64,
+        &token::ID,
+    )?;
+    token::initialize_mint2(
+        CpiContext::new(
+            token_program.to_account_info(),
+            InitializeMint2 {
+                mint: position_mint.clone(),
+            },
+        ),
+        0,
+        &whirlpool.key(),
+        None,
+    )?;
+    associated_token::create_idempotent(CpiContext::new(
+        associated_token_program.to_account_info(),
+        Create {
+            payer: funder.clone(),
+            associated_token: position_token_account.clone(),
+            authority: owner.clone(),
+            mint: position_mint.clone(),
+            system_program: system_program.to_account_info(),
+            token_program: token_program.to_account_info(),
+        },
+    ))
+}
+
+/// Same as `mint_position_token_and_remove_authority`, for a position mint and token account
+/// created by the instruction handler instead of account constraints.
+pub fn mint_created_position_token_and_remove_authority<'info>(
+    whirlpool: &Account<'info, Whirlpool>,
+    position_mint: &AccountInfo<'info>,
+    position_token_account: &AccountInfo<'info>,
+    token_program: &Program<'info, Token>,
 ) -> Result<()> {
     mint_position_token(
         whirlpool,
@@ -111,47 +214,107 @@ pub fn mint_position_token_and_remove_authority<'info>(
 }
 
 const WP_METADATA_NAME: &str = "Orca Whirlpool Position";
-const WP_METADATA_SYMBOL: &str = "OWP";
-const WP_METADATA_URI: &str = "https://arweave.net/KZlsubXZyzeSYi2wJhyL7SY-DAot_OXhfWSYQGLmmOc";
+pub const WP_METADATA_SYMBOL: &str = "OWP";
+pub const WP_METADATA_URI: &str = "https://arweave.net/KZlsubXZyzeSYi2wJhyL7SY-DAot_OXhfWSYQGLmmOc";
+
+/// Collection accounts of a whirlpool that position metadata is verified against.
+pub struct PositionCollectionAccounts<'a, 'info> {
+    pub collection_mint: &'a AccountInfo<'info>,
+    pub collection_metadata: &'a AccountInfo<'info>,
+    pub collection_master_edition: &'a AccountInfo<'info>,
+}
+
+/// Metadata program accounts of a position token and the accounts creating its metadata.
+pub struct PositionMetadataAccounts<'a, 'info> {
+    pub position_metadata_account: &'a UncheckedAccount<'info>,
+    pub metadata_update_auth: &'a UncheckedAccount<'info>,
+    pub funder: &'a Signer<'info>,
+    pub metadata_program: &'a UncheckedAccount<'info>,
+    pub system_program: &'a Program<'info, System>,
+    pub rent: &'a Sysvar<'info, Rent>,
+}
+
+/// Collection accounts passed as the first three remaining accounts when the whirlpool has a
+/// position collection, None otherwise.
+///
+/// # Errors
+/// - `InvalidPositionCollection` - The collection mint is not the one of the whirlpool
+/// - `RemainingAccountsMissing` - The collection accounts are missing
+pub fn position_collection_accounts<'a, 'info>(
+    whirlpool: &Whirlpool,
+    remaining_accounts: &'a [AccountInfo<'info>],
+) -> Result<Option<PositionCollectionAccounts<'a, 'info>>> {
+    if !whirlpool.has_position_collection() {
+        return Ok(None);
+    }
+    match remaining_accounts {
+        [collection_mint, collection_metadata, collection_master_edition, ..] => {
+            if collection_mint.key() != whirlpool.position_collection_mint {
+                return Err(ErrorCode::InvalidPositionCollection.into());
+            }
+            Ok(Some(PositionCollectionAccounts {
+                collection_mint,
+                collection_metadata,
+                collection_master_edition,
+            }))
+        }
+        _ => Err(ErrorCode::RemainingAccountsMissing.into()),
+    }
+}
 
 pub fn mint_position_token_with_metadata_and_remove_authority<'info>(
+    whirlpools_config: &WhirlpoolsConfig,
     whirlpool: &Account<'info, Whirlpool>,
     position_mint: &Account<'info, Mint>,
     position_token_account: &Account<'info, TokenAccount>,
-    position_metadata_account: &UncheckedAccount<'info>,
-    metadata_update_auth: &UncheckedAccount<'info>,
-    funder: &Signer<'info>,
-    metadata_program: &UncheckedAccount<'info>,
     token_program: &Program<'info, Token>,
-    system_program: &Program<'info, System>,
-    rent: &Sysvar<'info, Rent>,
+    metadata_accounts: PositionMetadataAccounts<'_, 'info>,
+    position_collection: Option<&PositionCollectionAccounts<'_, 'info>>,
 ) -> Result<()> {
+    let PositionMetadataAccounts {
+        position_metadata_account,
+        metadata_update_auth,
+        funder,
+        metadata_program,
+        system_program,
+        rent,
+    } = metadata_accounts;
     mint_position_token(
         whirlpool,
-        position_mint,
-        position_token_account,
+        &position_mint.to_account_info(),
+        &position_token_account.to_account_info(),
         token_program,
     )?;
 
+    let collection = position_collection.map(|accounts| Collection {
+        verified: false,
+        key: accounts.collection_mint.key(),
+    });
+
+    let uri = whirlpools_config
+        .position_metadata_uri(&position_mint.key())
+        .unwrap_or_else(|| WP_METADATA_URI.to_string());
+
     let metadata_mint_auth_account = whirlpool;
     invoke_signed(
-        &create_metadata_accounts_v2(
-            metadata_program.key(),
-            position_metadata_account.key(),
-            position_mint.key(),
-            metadata_mint_auth_account.key(),
-            funder.key(),
-            metadata_update_auth.key(),
-            WP_METADATA_NAME.to_string(),
-            WP_METADATA_SYMBOL.to_string(),
-            WP_METADATA_URI.to_string(),
-            None,
-            0,
-            false,
-            true,
-            None,
-            None,
-        ),
+        &CreateMetadataAccountV3Builder::new()
+            .metadata(position_metadata_account.key())
+            .mint(position_mint.key())
+            .mint_authority(metadata_mint_auth_account.key())
+            .payer(funder.key())
+            .update_authority(metadata_update_auth.key(), false)
+            .data(DataV2 {
+                name: WP_METADATA_NAME.to_string(),
+                symbol: WP_METADATA_SYMBOL.to_string(),
+                uri,
+                seller_fee_basis_points: 0,
+                creators: None,
+                collection,
+                uses: None,
+            })
+            .is_mutable(true)
+            .rent(Some(rent.key()))
+            .instruction(),
         &[
             position_metadata_account.to_account_info(),
             position_mint.to_account_info(),
@@ -165,27 +328,55 @@ pub fn mint_position_token_with_metadata_and_remove_authority<'info>(
         &[&metadata_mint_auth_account.seeds()],
     )?;
 
-    remove_position_token_mint_authority(whirlpool, position_mint, token_program)
+    // The whirlpool is the update authority of its collection and verifies the position into it.
+    if let Some(accounts) = position_collection {
+        invoke_signed(
+            &VerifyCollectionBuilder::new()
+                .metadata(position_metadata_account.key())
+                .collection_authority(whirlpool.key())
+                .payer(funder.key())
+                .collection_mint(accounts.collection_mint.key())
+                .collection(accounts.collection_metadata.key())
+                .collection_master_edition_account(accounts.collection_master_edition.key())
+                .instruction(),
+            &[
+                position_metadata_account.to_account_info(),
+                whirlpool.to_account_info(),
+                funder.to_account_info(),
+                accounts.collection_mint.clone(),
+                accounts.collection_metadata.clone(),
+                accounts.collection_master_edition.clone(),
+                metadata_program.to_account_info(),
+            ],
+            &[&whirlpool.seeds()],
+        )?;
+    }
+
+    remove_position_token_mint_authority(
+        whirlpool,
+        &position_mint.to_account_info(),
+        token_program,
+    )
 }
 
-fn mint_position_token<'info>(
+pub fn mint_position_token<'info>(
     whirlpool: &Account<'info, Whirlpool>,
-    position_mint: &Account<'info, Mint>,
-    position_token_account: &Account<'info, TokenAccount>,
+    position_mint: &AccountInfo<'info>,
+    position_token_account: &AccountInfo<'info>,
     token_program: &Program<'info, Token>,
 ) -> Result<()> {
     invoke_signed(
         &mint_to(
             token_program.key,
-            position_mint.to_account_info().key,
-            position_token_account.to_account_info().key,
+            position_mint.key,
+            position_token_account.key,
             whirlpool.to_account_info().key,
             &[whirlpool.to_account_info().key],
             1,
         )?,
         &[
-            position_mint.to_account_info(),
-            position_token_account.to_account_info(),
+            position_mint.clone(),
+            position_token_account.clone(),
             whirlpool.to_account_info(),
             token_program.to_account_info(),
         ],
@@ -196,20 +387,20 @@ fn mint_position_token<'info>(
 
 fn remove_position_token_mint_authority<'info>(
     whirlpool: &Account<'info, Whirlpool>,
-    position_mint: &Account<'info, Mint>,
+    position_mint: &AccountInfo<'info>,
     token_program: &Program<'info, Token>,
 ) -> Result<()> {
     invoke_signed(
         &set_authority(
             token_program.key,
-            position_mint.to_account_info().key,
+            position_mint.key,
             Option::None,
             AuthorityType::MintTokens,
             whirlpool.to_account_info().key,
             &[whirlpool.to_account_info().key],
         )?,
         &[
-            position_mint.to_account_info(),
+            position_mint.clone(),
             whirlpool.to_account_info(),
             token_program.to_account_info(),
         ],
@@ -0,0 +1,199 @@
+use anchor_lang::prelude::*;
+use anchor_lang::Discriminator;
+use std::convert::TryInto;
+
+use crate::errors::ErrorCode;
+use crate::state::{Position, Whirlpool};
+
+pub mod upstream_whirlpool_program {
+    use super::*;
+    declare_id!("whirLbMiicVdio4qvUfM5KAg6Ct8VwpYzGff3uctyCc");
+}
+
+// Size of a position account of the upstream program, smaller than `Position::LEN` of the fork
+pub const UPSTREAM_POSITION_LEN: usize = 216;
+
+// Offsets of the position fields in an upstream position account
+const POSITION_WHIRLPOOL_OFFSET: usize = 8;
+const POSITION_MINT_OFFSET: usize = 40;
+const POSITION_LIQUIDITY_OFFSET: usize = 72;
+const POSITION_TICK_LOWER_INDEX_OFFSET: usize = 88;
+const POSITION_TICK_UPPER_INDEX_OFFSET: usize = 92;
+
+// Offsets of `token_mint_a` and `token_mint_b` in an upstream whirlpool account
+const WHIRLPOOL_TOKEN_MINT_A_OFFSET: usize = 101;
+const WHIRLPOOL_TOKEN_MINT_B_OFFSET: usize = 181;
+
+/// Fields of an upstream position needed to mirror it in the fork.
+#[derive(Clone, Copy, Default, PartialEq)]
+#[cfg_attr(not(target_arch = "bpf"), derive(Debug))]
+pub struct UpstreamPosition {
+    pub whirlpool: Pubkey,
+    pub position_mint: Pubkey,
+    pub liquidity: u128,
+    pub tick_lower_index: i32,
+    pub tick_upper_index: i32,
+}
+
+/// Read a position account of the upstream Whirlpool program.
+///
+/// # Errors
+/// - `InvalidUpstreamPosition` - The account is not owned by the upstream program or is not a
+///   position account of it
+pub fn read_upstream_position(position: &AccountInfo) -> Result<UpstreamPosition> {
+    if position.owner != &upstream_whirlpool_program::ID {
+        return Err(ErrorCode::InvalidUpstreamPosition.into());
+    }
+    let data = position.try_borrow_data()?;
+    Ok(upstream_position(&data)?)
+}
+
+/// Read the token mints of a whirlpool account of the upstream Whirlpool program.
+///
+/// # Errors
+/// - `UpstreamWhirlpoolMismatch` - The account is not owned by the upstream program or is not a
+///   whirlpool account of it
+pub fn read_upstream_whirlpool_mints(whirlpool: &AccountInfo) -> Result<(Pubkey, Pubkey)> {
+    if whirlpool.owner != &upstream_whirlpool_program::ID {
+        return Err(ErrorCode::UpstreamWhirlpoolMismatch.into());
+    }
+    let data = whirlpool.try_borrow_data()?;
+    Ok(upstream_whirlpool_mints(&data)?)
+}
+
+/// Verify that an account passed in place of an account of this program is not an account of the
+/// upstream program. Accounts of both programs share their discriminators, so without this check
+/// an upstream account fails later with a less obvious constraint error.
+///
+/// # Errors
+/// - `ForeignProgramAccount` - The account is owned by the upstream program
+pub fn verify_not_upstream_account(account: &AccountInfo) -> Result<()> {
+    if is_upstream_owner(account.owner, &crate::ID) {
+        msg!(
+            "account {} is owned by the upstream Whirlpool program",
+            account.key
+        );
+        return Err(ErrorCode::ForeignProgramAccount.into());
+    }
+    Ok(())
+}
+
+// Accounts of the upstream program cannot be told apart while the fork is deployed at the
+// upstream program address.
+fn is_upstream_owner(owner: &Pubkey, program_id: &Pubkey) -> bool {
+    owner == &upstream_whirlpool_program::ID && owner != program_id
+}
+
+fn upstream_position(data: &[u8]) -> std::result::Result<UpstreamPosition, ErrorCode> {
+    // Positions of the fork share the discriminator, but not the account size.
+    if data.len() != UPSTREAM_POSITION_LEN || data[..8] != Position::discriminator() {
+        return Err(ErrorCode::InvalidUpstreamPosition);
+    }
+    let read_pubkey =
+        |offset: usize| Pubkey::new_from_array(data[offset..offset + 32].try_into().unwrap());
+    let read_i32 = |offset: usize| i32::from_le_bytes(data[offset..offset + 4].try_into().unwrap());
+
+    Ok(UpstreamPosition {
+        whirlpool: read_pubkey(POSITION_WHIRLPOOL_OFFSET),
+        position_mint: read_pubkey(POSITION_MINT_OFFSET),
+        liquidity: u128::from_le_bytes(
+            data[POSITION_LIQUIDITY_OFFSET..POSITION_LIQUIDITY_OFFSET + 16]
+                .try_into()
+                .unwrap(),
+        ),
+        tick_lower_index: read_i32(POSITION_TICK_LOWER_INDEX_OFFSET),
+        tick_upper_index: read_i32(POSITION_TICK_UPPER_INDEX_OFFSET),
+    })
+}
+
+fn upstream_whirlpool_mints(data: &[u8]) -> std::result::Result<(Pubkey, Pubkey), ErrorCode> {
+    if data.len() < WHIRLPOOL_TOKEN_MINT_B_OFFSET + 32 || data[..8] != Whirlpool::discriminator() {
+        return Err(ErrorCode::UpstreamWhirlpoolMismatch);
+    }
+    let read_pubkey =
+        |offset: usize| Pubkey::new_from_array(data[offset..offset + 32].try_into().unwrap());
+
+    Ok((
+        read_pubkey(WHIRLPOOL_TOKEN_MINT_A_OFFSET),
+        read_pubkey(WHIRLPOOL_TOKEN_MINT_B_OFFSET),
+    ))
+}
+
+#[cfg(test)]
+mod upstream_position_tests {
+    use super::*;
+
+    fn upstream_position_data(position: &UpstreamPosition) -> Vec<u8> {
+        let mut data = vec![0u8; UPSTREAM_POSITION_LEN];
+        data[..8].copy_from_slice(&Position::discriminator());
+        data[8..40].copy_from_slice(position.whirlpool.as_ref());
+        data[40..72].copy_from_slice(position.position_mint.as_ref());
+        data[72..88].copy_from_slice(&position.liquidity.to_le_bytes());
+        data[88..92].copy_from_slice(&position.tick_lower_index.to_le_bytes());
+        data[92..96].copy_from_slice(&position.tick_upper_index.to_le_bytes());
+        data
+    }
+
+    #[test]
+    fn test_upstream_position() {
+        let position = UpstreamPosition {
+            whirlpool: Pubkey::new_unique(),
+            position_mint: Pubkey::new_unique(),
+            liquidity: 1_000_000,
+            tick_lower_index: -128,
+            tick_upper_index: 64,
+        };
+        let data = upstream_position_data(&position);
+        assert_eq!(upstream_position(&data).unwrap(), position);
+    }
+
+    #[test]
+    fn test_upstream_position_rejects_fork_and_other_accounts() {
+        let mut data = upstream_position_data(&UpstreamPosition::default());
+        data.resize(Position::LEN, 0);
+        assert_eq!(
+            upstream_position(&data).unwrap_err(),
+            ErrorCode::InvalidUpstreamPosition
+        );
+
+        let mut data = upstream_position_data(&UpstreamPosition::default());
+        data[..8].copy_from_slice(&Whirlpool::discriminator());
+        assert_eq!(
+            upstream_position(&data).unwrap_err(),
+            ErrorCode::InvalidUpstreamPosition
+        );
+    }
+
+    #[test]
+    fn test_upstream_whirlpool_mints() {
+        let token_mint_a = Pubkey::new_unique();
+        let token_mint_b = Pubkey::new_unique();
+        let mut data = vec![0u8; 653];
+        data[..8].copy_from_slice(&Whirlpool::discriminator());
+        data[101..133].copy_from_slice(token_mint_a.as_ref());
+        data[181..213].copy_from_slice(token_mint_b.as_ref());
+        assert_eq!(
+            upstream_whirlpool_mints(&data).unwrap(),
+            (token_mint_a, token_mint_b)
+        );
+        assert_eq!(
+            upstream_whirlpool_mints(&data[..200]).unwrap_err(),
+            ErrorCode::UpstreamWhirlpoolMismatch
+        );
+    }
+
+    #[test]
+    fn test_is_upstream_owner() {
+        let fork_program_id = Pubkey::new_unique();
+        assert!(is_upstream_owner(
+            &upstream_whirlpool_program::ID,
+            &fork_program_id
+        ));
+        assert!(!is_upstream_owner(&fork_program_id, &fork_program_id));
+        assert!(!is_upstream_owner(&Pubkey::new_unique(), &fork_program_id));
+        assert!(!is_upstream_owner(
+            &upstream_whirlpool_program::ID,
+            &upstream_whirlpool_program::ID
+        ));
+    }
+}
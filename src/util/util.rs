@@ -1,13 +1,19 @@
 use anchor_lang::prelude::*;
+use anchor_lang::system_program::{self, Transfer};
 use anchor_lang::{
-    prelude::{AccountInfo, ProgramError, Pubkey, Signer},
+    prelude::{AccountInfo, Pubkey, Signer},
     ToAccountInfo,
 };
-use anchor_spl::token::TokenAccount;
+use anchor_spl::token::{Mint, TokenAccount};
 use solana_program::program_option::COption;
 use std::convert::TryFrom;
 
 use crate::errors::ErrorCode;
+use crate::events::LiquidityShifted;
+use crate::state::{
+    CollectRouting, LiquidityTimeAccumulator, Position, RentSponsor, SessionAuthority, TokenBadge,
+    Whirlpool,
+};
 
 pub fn verify_position_authority<'info>(
     position_token_account: &TokenAccount,
@@ -29,6 +35,151 @@ pub fn verify_position_authority<'info>(
     Ok(())
 }
 
+/// Verify the position authority, also accepting a session key authorized by the owner of the
+/// position token for the given scope. The session authority account is expected as the first
+/// remaining account.
+///
+/// # Returns
+/// - `Ok(true)` if the position authority acts through a session, `Ok(false)` if it owns or is
+///   the delegate of the position token.
+pub fn verify_position_or_session_authority<'info>(
+    position_token_account: &TokenAccount,
+    position_authority: &Signer<'info>,
+    remaining_accounts: &'info [AccountInfo<'info>],
+    scope: u8,
+) -> Result<bool> {
+    let authority_error =
+        match verify_position_authority(position_token_account, position_authority) {
+            Ok(()) => return Ok(false),
+            Err(error) => error,
+        };
+    let session_account_info = match remaining_accounts.first() {
+        Some(account_info) => account_info,
+        None => return Err(authority_error),
+    };
+
+    let session = Account::<SessionAuthority>::try_from(session_account_info)?;
+    let timestamp = to_timestamp_u64(Clock::get()?.unix_timestamp)?;
+    if !session.is_valid_for(
+        &position_token_account.mint,
+        &position_token_account.owner,
+        position_authority.key,
+        scope,
+        timestamp,
+    ) {
+        return Err(ErrorCode::InvalidSessionAuthority.into());
+    }
+
+    Ok(true)
+}
+
+/// Load the collect routing of `owner` if it is the given remaining account. Any other account,
+/// e.g. the next optional remaining account of the handler, yields `None`, so the routing only
+/// binds collects it is passed to.
+pub fn load_collect_routing<'info>(
+    owner: &Pubkey,
+    account_info: Option<&'info AccountInfo<'info>>,
+) -> Result<Option<Account<'info, CollectRouting>>> {
+    match account_info {
+        Some(account_info) if account_info.key() == CollectRouting::address(owner) => {
+            Ok(Some(Account::try_from(account_info)?))
+        }
+        _ => Ok(None),
+    }
+}
+
+/// Verify a destination token account of a collect. A mint routed by the collect routing must be
+/// collected into its routed destination. Otherwise a session may only collect into token accounts
+/// of the owed destination of the position.
+pub fn verify_collect_destination(
+    token_account: &Account<TokenAccount>,
+    owed_destination: &Pubkey,
+    collect_routing: Option<&Account<CollectRouting>>,
+    is_session: bool,
+) -> Result<()> {
+    let routed_destination =
+        collect_routing.and_then(|routing| routing.destination_for(&token_account.mint));
+    match routed_destination {
+        Some(destination) if token_account.key() != destination => {
+            Err(ErrorCode::CollectRouteMismatch.into())
+        }
+        Some(_) => Ok(()),
+        None if is_session && token_account.owner != *owed_destination => {
+            Err(ErrorCode::InvalidSessionDestination.into())
+        }
+        None => Ok(()),
+    }
+}
+
+/// Verify the position authority, also accepting the withdraw authority of the position.
+///
+/// # Returns
+/// - `Ok(true)` if the position authority is the withdraw authority of the position, `Ok(false)`
+///   if it owns or is the delegate of the position token.
+pub fn verify_position_or_withdraw_authority<'info>(
+    position_token_account: &TokenAccount,
+    position_authority: &Signer<'info>,
+    position: &Position,
+) -> Result<bool> {
+    match verify_position_authority(position_token_account, position_authority) {
+        Ok(()) => Ok(false),
+        Err(_) if position.is_withdraw_authority(position_authority.key) => Ok(true),
+        Err(error) => Err(error),
+    }
+}
+
+/// Accrue the liquidity time of a position to the accumulator of its owner in the Whirlpool. If
+/// no accumulator is passed, the time since the last accrual is forfeited instead, so liquidity
+/// changes cannot be attributed retroactively.
+pub fn accrue_liquidity_time<'info>(
+    accumulator_info: Option<&'info AccountInfo<'info>>,
+    whirlpool: &Account<Whirlpool>,
+    position: &mut Position,
+    position_owner: &Pubkey,
+    timestamp: u64,
+) -> Result<()> {
+    let accumulator_info = match accumulator_info {
+        Some(accumulator_info) => accumulator_info,
+        None => {
+            position.checkpoint_liquidity_time(timestamp);
+            return Ok(());
+        }
+    };
+
+    let mut accumulator = Account::<LiquidityTimeAccumulator>::try_from(accumulator_info)?;
+    if accumulator.whirlpool != whirlpool.key() || accumulator.owner != *position_owner {
+        return Err(ErrorCode::InvalidLiquidityTimeAccumulator.into());
+    }
+    accumulator.accrue(position, whirlpool.tick_current_index, timestamp);
+    accumulator.exit(&crate::ID)
+}
+
+/// Fail with `DestinationFrozen` before any owed amount is touched if the destination cannot
+/// receive tokens, so the amount stays owed and the collection can be retried elsewhere.
+pub fn verify_destination_not_frozen(destination: &TokenAccount) -> Result<()> {
+    if destination.is_frozen() {
+        return Err(ErrorCode::DestinationFrozen.into());
+    }
+
+    Ok(())
+}
+
+/// Fail with `VaultFrozen` or `UserAccountFrozen` instead of the generic token program error if
+/// either side of a transfer between a Whirlpool vault and a user token account is frozen.
+pub fn verify_transfer_not_frozen(
+    token_vault: &TokenAccount,
+    token_user_account: &TokenAccount,
+) -> Result<()> {
+    if token_vault.is_frozen() {
+        return Err(ErrorCode::VaultFrozen.into());
+    }
+    if token_user_account.is_frozen() {
+        return Err(ErrorCode::UserAccountFrozen.into());
+    }
+
+    Ok(())
+}
+
 fn validate_owner(expected_owner: &Pubkey, owner_account_info: &AccountInfo) -> Result<()> {
     if expected_owner != owner_account_info.key || !owner_account_info.is_signer {
         return Err(ErrorCode::MissingOrInvalidDelegate.into());
@@ -37,6 +188,130 @@ fn validate_owner(expected_owner: &Pubkey, owner_account_info: &AccountInfo) ->
     Ok(())
 }
 
+/// Move lamports out of a rent sponsor, keeping the sponsor rent exempt.
+pub fn draw_rent_sponsor<'info>(
+    rent_sponsor: &AccountInfo<'info>,
+    destination: &AccountInfo<'info>,
+    lamports: u64,
+) -> Result<()> {
+    let available = rent_sponsor
+        .lamports()
+        .saturating_sub(Rent::get()?.minimum_balance(RentSponsor::LEN));
+    if lamports > available {
+        return Err(ErrorCode::RentSponsorInsufficientFunds.into());
+    }
+
+    **rent_sponsor.try_borrow_mut_lamports()? -= lamports;
+    **destination.try_borrow_mut_lamports()? += lamports;
+    Ok(())
+}
+
+/// Grow a program account to `new_len` bytes, zero-filling the added bytes. The funder pays the
+/// rent of the added bytes. A no-op for accounts that are already `new_len` bytes or longer.
+pub fn realloc_account<'info>(
+    account_info: &AccountInfo<'info>,
+    new_len: usize,
+    funder: &Signer<'info>,
+    system_program: &Program<'info, System>,
+) -> Result<()> {
+    if account_info.data_len() >= new_len {
+        return Ok(());
+    }
+
+    let rent_shortfall = Rent::get()?
+        .minimum_balance(new_len)
+        .saturating_sub(account_info.lamports());
+    if rent_shortfall > 0 {
+        system_program::transfer(
+            CpiContext::new(
+                system_program.to_account_info(),
+                Transfer {
+                    from: funder.to_account_info(),
+                    to: account_info.clone(),
+                },
+            ),
+            rent_shortfall,
+        )?;
+    }
+    account_info.realloc(new_len, true)?;
+    Ok(())
+}
+
+/// Verify a pool may be created for a token mint. Mints with a freeze authority could freeze the
+/// token vaults of the pool, so they need a token badge of the config allowing it among the
+/// remaining accounts.
+pub fn verify_freeze_authority_allowed<'info>(
+    whirlpools_config: &Pubkey,
+    token_mint: &Account<'info, Mint>,
+    remaining_accounts: &'info [AccountInfo<'info>],
+) -> Result<()> {
+    if token_mint.freeze_authority.is_none() {
+        return Ok(());
+    }
+    // Badges are PDAs of the config and the mint, so at most one account can match.
+    let allowed = remaining_accounts
+        .iter()
+        .filter(|account| account.owner == &crate::ID)
+        .filter_map(|account| Account::<TokenBadge>::try_from(account).ok())
+        .any(|badge| badge.permits_freeze_authority(whirlpools_config, &token_mint.key()));
+    if !allowed {
+        return Err(ErrorCode::FreezeAuthorityNotAllowed.into());
+    }
+    Ok(())
+}
+
+/// Record a change of the active liquidity of a whirlpool from `previous_liquidity`, emitting a
+/// `LiquidityShifted` event if the change within the slot crossed the liquidity shift alert rate.
+pub fn record_liquidity_shift(
+    whirlpool: &mut Account<Whirlpool>,
+    previous_liquidity: u128,
+    slot: u64,
+) {
+    if let Some(slot_start_liquidity) = whirlpool.record_liquidity_shift(previous_liquidity, slot)
+    {
+        emit!(LiquidityShifted {
+            whirlpool: whirlpool.key(),
+            slot,
+            slot_start_liquidity,
+            liquidity: whirlpool.liquidity,
+        });
+    }
+}
+
 pub fn to_timestamp_u64(t: i64) -> Result<u64> {
     u64::try_from(t).or(Err(ErrorCode::InvalidTimestampConversion.into()))
 }
+
+#[cfg(test)]
+mod util_tests {
+    use super::*;
+    use anchor_lang::AccountDeserialize;
+    use anchor_spl::token::spl_token::state::{Account as SplTokenAccount, AccountState};
+    use solana_program::program_pack::Pack;
+
+    fn token_account(state: AccountState) -> TokenAccount {
+        let mut data = vec![0u8; SplTokenAccount::LEN];
+        SplTokenAccount {
+            state,
+            ..Default::default()
+        }
+        .pack_into_slice(&mut data);
+        TokenAccount::try_deserialize_unchecked(&mut data.as_slice()).unwrap()
+    }
+
+    #[test]
+    fn test_verify_transfer_not_frozen() {
+        let initialized = token_account(AccountState::Initialized);
+        let frozen = token_account(AccountState::Frozen);
+
+        assert!(verify_transfer_not_frozen(&initialized, &initialized).is_ok());
+        assert_eq!(
+            verify_transfer_not_frozen(&frozen, &initialized).unwrap_err(),
+            ErrorCode::VaultFrozen.into()
+        );
+        assert_eq!(
+            verify_transfer_not_frozen(&initialized, &frozen).unwrap_err(),
+            ErrorCode::UserAccountFrozen.into()
+        );
+    }
+}